@@ -7,6 +7,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/common.proto",
                 "proto/wallet.proto",
                 "proto/transfer.proto",
+                "proto/token.proto",
             ],
             &["proto"],
         )?;
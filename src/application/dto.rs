@@ -11,6 +11,15 @@ pub struct CreateWalletRequest {
     pub owner_id: Option<String>,
 }
 
+/// DTO для регистрации watch-only адреса (без приватного ключа)
+#[derive(Debug, Deserialize)]
+pub struct RegisterWatchOnlyWalletRequest {
+    /// Внешний TRON адрес для отслеживания депозитов
+    pub address: String,
+    /// Идентификатор владельца кошелька (опционально)
+    pub owner_id: Option<String>,
+}
+
 /// DTO для создания трансфера (TransferRequestDto)
 #[derive(Debug, Deserialize)]
 pub struct CreateTransferRequest {
@@ -20,10 +29,39 @@ pub struct CreateTransferRequest {
     pub order_amount: Decimal,
     /// Референс для связи с внешней системой
     pub reference_id: Option<String>,
+    /// Memo для биржевых депозитов (передается в webhook/export метаданных)
+    pub destination_tag: Option<String>,
+    /// Произвольные метаданные клиента (JSON объект, лимит по размеру)
+    pub metadata: Option<serde_json::Value>,
+    /// Кто оплачивает комиссии: customer (поверх суммы, по умолчанию)
+    /// или merchant (вычитаются из суммы заказа)
+    pub fee_payer: Option<crate::application::services::FeePayer>,
     /// Если true, показать только preview без создания трансфера
     pub preview_only: Option<bool>,
 }
 
+/// DTO для создания вывода на произвольный адрес (withdrawal)
+#[derive(Debug, Deserialize)]
+pub struct CreateWithdrawalRequest {
+    /// ID кошелька отправителя
+    pub from_wallet_id: i64,
+    /// Адрес назначения вывода. Валидируется; при непустом белом
+    /// списке в конфиге должен входить в него
+    pub to_address: String,
+    /// Сумма вывода в USDT (без комиссии)
+    pub amount: Decimal,
+    /// Референс для связи с внешней системой
+    pub reference_id: Option<String>,
+    /// Memo для биржевых депозитов (передается в webhook/export метаданных)
+    pub destination_tag: Option<String>,
+    /// Произвольные метаданные клиента (JSON объект, лимит по размеру)
+    pub metadata: Option<serde_json::Value>,
+    /// Кто оплачивает комиссии (по умолчанию customer)
+    pub fee_payer: Option<crate::application::services::FeePayer>,
+    /// Символ TRC-20 токена вывода (по умолчанию USDT)
+    pub token_symbol: Option<String>,
+}
+
 /// DTO для ответа с информацией о кошельке
 #[derive(Debug, Serialize)]
 pub struct WalletResponse {
@@ -31,7 +69,58 @@ pub struct WalletResponse {
     pub address: String,
     pub owner_id: Option<String>,
     pub created_at: DateTime<Utc>,
+    #[serde(serialize_with = "crate::utils::serialize_optional_amount")]
     pub balance: Option<Decimal>, // Баланс может быть недоступен сразу
+    /// Кошелек помечен комплаенсом как "на проверке"
+    pub under_review: bool,
+    /// Watch-only адрес: депозиты отслеживаются, трансферы запрещены
+    pub watch_only: bool,
+}
+
+/// DTO для запроса передачи владения кошельком
+#[derive(Debug, Deserialize)]
+pub struct TransferWalletOwnershipRequest {
+    /// Новый owner_id (None снимает привязку владельца)
+    pub new_owner_id: Option<String>,
+}
+
+/// DTO для запроса изменения комплаенс-статуса кошелька
+#[derive(Debug, Deserialize)]
+pub struct UpdateComplianceRequest {
+    pub under_review: bool,
+    /// Свободный комментарий проверяющего
+    pub notes: Option<String>,
+    /// Идентификатор проверяющего
+    pub reviewer_id: Option<String>,
+}
+
+/// DTO с комплаенс-статусом кошелька
+#[derive(Debug, Serialize)]
+pub struct ComplianceStatusResponse {
+    pub wallet_id: i64,
+    pub under_review: bool,
+    pub notes: Option<String>,
+    pub reviewer_id: Option<String>,
+    pub flagged_at: Option<DateTime<Utc>>,
+}
+
+/// DTO для запроса выпуска wallet-scoped API токена
+#[derive(Debug, Deserialize)]
+pub struct IssueWalletTokenRequest {
+    /// Человекочитаемая метка токена (например "customer-portal")
+    pub label: Option<String>,
+}
+
+/// DTO для ответа с wallet-scoped API токеном
+#[derive(Debug, Serialize)]
+pub struct WalletTokenResponse {
+    pub id: i64,
+    pub wallet_id: i64,
+    /// Сам токен возвращается только при выпуске, дальше хранится лишь хеш
+    pub token: Option<String>,
+    pub label: Option<String>,
+    pub revoked: bool,
+    pub created_at: DateTime<Utc>,
 }
 
 /// DTO для запроса трансфера
@@ -43,31 +132,56 @@ pub struct TransferRequest {
     pub order_amount: Decimal,
     /// Референс для связи с внешней системой
     pub reference_id: Option<String>,
+    /// Кто оплачивает комиссии (по умолчанию customer)
+    pub fee_payer: Option<crate::application::services::FeePayer>,
+}
+
+/// Строка структурированной детализации расчета превью
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakdownItem {
+    /// Человекочитаемая подпись ("Order", "Gas fee", ...)
+    pub label: String,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub amount: Decimal,
+    pub currency: String,
+    /// Вид строки: order, gas_fee, service_fee, total или payout
+    pub kind: String,
 }
 
 /// DTO для превью трансфера (TransferPreviewDto)
 #[derive(Debug, Serialize)]
 pub struct TransferPreview {
     /// Сумма заказа
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub order_amount: Decimal,
     /// Общая комиссия
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub commission: Decimal,
     /// Газовые расходы в USDT эквиваленте
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub gas_cost_in_usdt: Decimal,
     /// Процентная комиссия
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub percentage_commission: Decimal,
     /// Общая сумма к списанию
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub total_amount: Decimal,
     /// Сумма получаемая master wallet
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub master_wallet_receives: Decimal,
-    /// Детальное описание расчета
+    /// Детальное описание расчета (legacy-строка, сохранена для
+    /// обратной совместимости - фронтенды рендерят breakdown_items)
     pub breakdown: String,
+    /// Структурированная детализация расчета
+    pub breakdown_items: Vec<BreakdownItem>,
     /// Текущий курс TRX/USDT
     pub trx_to_usdt_rate: Decimal,
     /// ID кошелька отправителя
     pub from_wallet_id: i64,
     /// Референс ID
     pub reference_id: Option<String>,
+    /// Кто оплачивает комиссии
+    pub fee_payer: crate::application::services::FeePayer,
 }
 
 /// DTO для ответа по трансферу
@@ -76,13 +190,108 @@ pub struct TransferResponse {
     pub id: i64,
     pub from_wallet_id: i64,
     pub to_address: String,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub amount: Decimal,
     pub status: TransactionStatus,
     pub tx_hash: Option<String>,
     pub reference_id: Option<String>,
+    pub destination_tag: Option<String>,
     pub error_message: Option<String>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// Оценка риска адреса назначения (0-100), если скрининг включен
+    pub risk_score: Option<i32>,
+    /// Метаданные клиента, переданные при создании
+    pub metadata: Option<serde_json::Value>,
+    /// Кто оплачивает комиссии трансфера
+    pub fee_payer: crate::application::services::FeePayer,
+    /// Итоговая комиссия (газ + сервисная) на момент создания
+    #[serde(serialize_with = "crate::utils::serialize_optional_amount")]
+    pub fee_amount: Option<Decimal>,
+    /// Символ TRC-20 токена трансфера
+    pub token_symbol: String,
+    /// Курс TRX/USDT, примененный при расчете комиссий (снимок)
+    pub fee_trx_usdt_rate: Option<Decimal>,
+    /// Источник курса: имя оракула живого фида или `config`
+    pub fee_rate_source: Option<String>,
+    /// Момент снятия снимка курса
+    pub fee_rate_captured_at: Option<DateTime<Utc>>,
+}
+
+/// DTO для in-flight трансфера: что процессор обрабатывает прямо сейчас
+#[derive(Debug, Serialize)]
+pub struct InFlightTransferResponse {
+    pub id: i64,
+    pub from_wallet_id: i64,
+    pub to_address: String,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub amount: Decimal,
+    /// Когда трансфер забран в работу
+    pub claimed_at: Option<DateTime<Utc>>,
+    /// Инстанс процессора, забравший трансфер
+    pub claimed_by: Option<String>,
+    /// Сколько секунд трансфер уже в обработке
+    pub elapsed_seconds: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO для записи TRX отправки (активация, спонсирование газа, faucet)
+#[derive(Debug, Serialize)]
+pub struct TrxTransferResponse {
+    pub id: i64,
+    pub from_address: String,
+    pub to_address: String,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub amount: Decimal,
+    /// Назначение отправки: activation, gas_sponsorship, faucet, admin_send
+    pub purpose: String,
+    pub tx_hash: String,
+    pub related_wallet_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO для запроса создания платежного намерения
+#[derive(Debug, Deserialize)]
+pub struct CreatePaymentIntentRequest {
+    /// ID кошелька-получателя платежа
+    pub wallet_id: i64,
+    /// Ожидаемая сумма в USDT
+    pub expected_amount: Decimal,
+    /// Референс для связи с внешней системой
+    pub reference_id: Option<String>,
+    /// Refund адрес клиента для возвратных флоу (валидируется как TRON адрес)
+    pub refund_address: Option<String>,
+    /// Время жизни намерения в минутах (опционально)
+    pub expires_in_minutes: Option<i64>,
+    /// Кодировать сумму уникальным центовым суффиксом для
+    /// детерминированной атрибуции депозита (общий кошелек мерчанта)
+    pub encode_amount: Option<bool>,
+    /// Произвольные метаданные клиента (JSON объект, лимит по размеру)
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// DTO для ответа по платежному намерению
+#[derive(Debug, Serialize)]
+pub struct PaymentIntentResponse {
+    pub id: i64,
+    pub wallet_id: i64,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub expected_amount: Decimal,
+    pub reference_id: Option<String>,
+    pub refund_address: Option<String>,
+    pub status: String,
+    pub matched_tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Недоплата, прощенная при матчинге с допуском
+    #[serde(serialize_with = "crate::utils::serialize_optional_amount")]
+    pub shortfall: Option<Decimal>,
+    /// Сумма закодирована уникальным суффиксом - депозит должен
+    /// совпасть с expected_amount точно
+    pub amount_encoded: bool,
+    /// Метаданные клиента, переданные при создании
+    pub metadata: Option<serde_json::Value>,
 }
 
 /// DTO для ответа с информацией о входящей транзакции
@@ -94,8 +303,87 @@ pub struct IncomingTransactionResponse {
     pub block_number: Option<i64>,
     pub from_address: String,
     pub to_address: String,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
     pub amount: Decimal,
     pub status: TransactionStatus,
     pub detected_at: DateTime<Utc>,
     pub confirmed_at: Option<DateTime<Utc>>,
+    /// Метка источника депозита (exchange, mixer, internal, private_wallet)
+    pub source_label: Option<String>,
+    /// Символ токена депозита
+    pub token_symbol: String,
+    /// Адрес TRC-20 контракта токена (None - для исторических строк)
+    pub contract_address: Option<String>,
+}
+
+/// DTO с описанием возможностей шлюза для feature-detection клиентских SDK
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayCapabilities {
+    pub version: String,
+    /// Активная сеть TRON: mainnet, shasta или nile
+    pub network: String,
+    /// Зарегистрированные блокчейн-бэкенды (пока только tron)
+    pub chains: Vec<String>,
+    pub grpc_enabled: bool,
+    pub payment_intents_enabled: bool,
+    pub refund_addresses_supported: bool,
+    pub netting_enabled: bool,
+    pub gas_sponsorship_enabled: bool,
+    pub webhook_events_version: String,
+    pub amounts: AmountLimits,
+    pub confirmation_policy: ConfirmationPolicy,
+    pub api_versioning: ApiVersioningPolicy,
+}
+
+/// Политика версионирования HTTP API.
+/// Версия задается префиксом пути (/api/v1), неверсионированный
+/// алиас /api отдает Deprecation/Sunset заголовки до отключения
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiVersioningPolicy {
+    /// Актуальная версия API
+    pub current: String,
+    /// Поддерживаемые версии
+    pub supported: Vec<String>,
+    /// Неверсионированные пути /api/* помечены как deprecated
+    pub unversioned_alias_deprecated: bool,
+    /// Дата отключения неверсионированного алиаса (HTTP-date из Sunset)
+    pub unversioned_alias_sunset: String,
+}
+
+/// Лимиты сумм, принимаемых валидацией шлюза
+#[derive(Debug, Clone, Serialize)]
+pub struct AmountLimits {
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub min_amount: Decimal,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub max_amount: Decimal,
+    pub max_decimals: u32,
+}
+
+/// Политика подтверждений входящих транзакций
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfirmationPolicy {
+    pub processing_confirmations: i32,
+    pub completed_confirmations: i32,
+}
+
+/// DTO для ответа faucet о выдаче тестовых средств
+#[derive(Debug, Serialize)]
+pub struct FaucetFundingResponse {
+    pub address: String,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub trx_amount: Decimal,
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub usdt_amount: Decimal,
+    pub trx_tx_hash: Option<String>,
+    pub usdt_tx_hash: Option<String>,
+}
+
+/// DTO для запроса изменения настроек пайплайна обработки трансферов
+#[derive(Debug, Deserialize)]
+pub struct UpdateProcessingTuningRequest {
+    /// Максимум трансферов за одну итерацию обработки
+    pub max_batch_size: Option<i64>,
+    /// Сколько батчей обрабатывать параллельно
+    pub parallelism: Option<usize>,
 }
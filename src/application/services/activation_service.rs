@@ -1,19 +1,17 @@
 //! # Сервис активации кошельков
-//! 
+//!
 //! Автоматическая активация новых кошельков отправкой TRX
 
 use anyhow::Result;
 use rust_decimal::Decimal;
+use std::sync::Arc;
 
-
-
-use super::TrxTransferService;
+use super::{MasterWalletPool, TrxTransferPurpose, TrxTransferService};
 
 /// Сервис автоматической активации кошельков (отправка TRX для активации в сети TRON)
 pub struct WalletActivationService {
     trx_transfer_service: TrxTransferService,
-    master_wallet_address: String,
-    master_wallet_private_key: String,
+    master_wallet_pool: Arc<MasterWalletPool>,
     activation_amount: Decimal,
     auto_activation_enabled: bool,
 }
@@ -22,15 +20,13 @@ impl WalletActivationService {
     /// Создает новый экземпляр сервиса
     pub fn new(
         trx_transfer_service: TrxTransferService,
-        master_wallet_address: String,
-        master_wallet_private_key: String,
+        master_wallet_pool: Arc<MasterWalletPool>,
         activation_amount: Decimal,
         auto_activation_enabled: bool,
     ) -> Self {
         Self {
             trx_transfer_service,
-            master_wallet_address,
-            master_wallet_private_key,
+            master_wallet_pool,
             activation_amount,
             auto_activation_enabled,
         }
@@ -46,20 +42,23 @@ impl WalletActivationService {
             return Ok(false);
         }
 
+        let master_wallet = self.master_wallet_pool.select().await;
+
         tracing::info!(
             "Активация кошелька {} отправкой {} TRX с мастер-кошелька {}",
             wallet_address,
             self.activation_amount,
-            self.master_wallet_address
+            master_wallet.address
         );
 
         match self
             .trx_transfer_service
-            .send_trx(
-                &self.master_wallet_address,
-                &self.master_wallet_private_key,
+            .send_trx_with_purpose(
+                &master_wallet.address,
+                &master_wallet.private_key,
                 wallet_address,
                 self.activation_amount,
+                TrxTransferPurpose::Activation,
             )
             .await
         {
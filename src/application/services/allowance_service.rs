@@ -0,0 +1,238 @@
+//! # Сервис TRC-20 разрешений (pull-платежи)
+//!
+//! Для approve/transferFrom флоу: внешние кошельки выдают шлюзу
+//! разрешение на списание USDT. Сервис читает текущие allowance
+//! on-chain, хранит историю снимков для аудита, готовит неподписанные
+//! транзакции отзыва и поднимает алерт при неожиданном уменьшении
+//! разрешения (владелец отозвал или сторонний transferFrom)
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domain::validation::TronValidator;
+use crate::infrastructure::database::models::{AllowanceSnapshotModel, NewAllowanceSnapshot};
+use crate::infrastructure::database::{schema, DbPool};
+use crate::infrastructure::{NotificationDispatcher, NotificationSeverity, TronGridClient};
+use crate::utils::conversions::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+/// Decimals USDT контракта
+const USDT_DECIMALS: u32 = 6;
+
+/// Результат проверки разрешения внешнего кошелька
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AllowanceCheck {
+    pub owner_address: String,
+    pub spender_address: String,
+    pub token_contract: String,
+    /// Текущее on-chain разрешение в USDT
+    pub allowance: Decimal,
+    /// Разрешение из предыдущего снимка (None - первая проверка)
+    pub previous_allowance: Option<Decimal>,
+    /// Разрешение уменьшилось с прошлого снимка
+    pub reduced: bool,
+}
+
+/// Сервис управления TRC-20 разрешениями
+pub struct AllowanceService {
+    db: DbPool,
+    tron_client: TronGridClient,
+    /// Контракт токена (USDT)
+    token_contract: String,
+    /// Кому выданы разрешения - мастер-кошелек шлюза
+    spender_address: String,
+    notifications: Option<Arc<NotificationDispatcher>>,
+}
+
+impl AllowanceService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(
+        db: DbPool,
+        tron_client: TronGridClient,
+        token_contract: String,
+        spender_address: String,
+    ) -> Self {
+        Self {
+            db,
+            tron_client,
+            token_contract,
+            spender_address,
+            notifications: None,
+        }
+    }
+
+    /// Подключает канал операционных уведомлений
+    pub fn with_notifications(mut self, notifications: Arc<NotificationDispatcher>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Читает текущее on-chain разрешение владельца, пишет снимок
+    /// в историю и алертит при неожиданном уменьшении
+    pub async fn check_allowance(&self, owner_address: &str) -> Result<AllowanceCheck> {
+        TronValidator::validate_address(owner_address)
+            .map_err(|e| anyhow::anyhow!("Невалидный адрес владельца: {}", e))?;
+
+        let allowance = self
+            .tron_client
+            .get_trc20_allowance(
+                owner_address,
+                &self.spender_address,
+                &self.token_contract,
+                USDT_DECIMALS,
+            )
+            .await?;
+
+        let mut conn = self.db.get().await?;
+
+        let previous: Option<AllowanceSnapshotModel> = schema::allowance_snapshots::table
+            .filter(schema::allowance_snapshots::owner_address.eq(owner_address))
+            .filter(schema::allowance_snapshots::spender_address.eq(&self.spender_address))
+            .order(schema::allowance_snapshots::captured_at.desc())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        let previous_allowance = previous.map(|snapshot| bigdecimal_to_decimal(snapshot.allowance));
+        let reduced = previous_allowance
+            .map(|prev| allowance < prev)
+            .unwrap_or(false);
+
+        diesel::insert_into(schema::allowance_snapshots::table)
+            .values(&NewAllowanceSnapshot {
+                owner_address: owner_address.to_string(),
+                spender_address: self.spender_address.clone(),
+                token_contract: self.token_contract.clone(),
+                allowance: decimal_to_bigdecimal(allowance),
+            })
+            .execute(&mut conn)
+            .await?;
+
+        if reduced {
+            self.alert_reduction(owner_address, previous_allowance.unwrap(), allowance)
+                .await;
+        }
+
+        Ok(AllowanceCheck {
+            owner_address: owner_address.to_string(),
+            spender_address: self.spender_address.clone(),
+            token_contract: self.token_contract.clone(),
+            allowance,
+            previous_allowance,
+            reduced,
+        })
+    }
+
+    /// Последний снимок разрешения каждого владельца (новые первыми)
+    pub async fn list_allowances(&self) -> Result<Vec<AllowanceSnapshotModel>> {
+        let mut conn = self.db.get().await?;
+
+        let snapshots: Vec<AllowanceSnapshotModel> = schema::allowance_snapshots::table
+            .filter(schema::allowance_snapshots::spender_address.eq(&self.spender_address))
+            .order(schema::allowance_snapshots::captured_at.desc())
+            .load(&mut conn)
+            .await?;
+
+        Ok(latest_per_owner(snapshots))
+    }
+
+    /// История снимков разрешения одного владельца (новые первыми)
+    pub async fn allowance_history(
+        &self,
+        owner_address: &str,
+        limit: i64,
+    ) -> Result<Vec<AllowanceSnapshotModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::allowance_snapshots::table
+            .filter(schema::allowance_snapshots::owner_address.eq(owner_address))
+            .filter(schema::allowance_snapshots::spender_address.eq(&self.spender_address))
+            .order(schema::allowance_snapshots::captured_at.desc())
+            .limit(limit.clamp(1, 500))
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Готовит неподписанную approve(spender, 0) транзакцию отзыва.
+    /// Подписать и отправить ее может только владелец внешнего кошелька
+    pub async fn build_revocation(&self, owner_address: &str) -> Result<serde_json::Value> {
+        TronValidator::validate_address(owner_address)
+            .map_err(|e| anyhow::anyhow!("Невалидный адрес владельца: {}", e))?;
+
+        info!(
+            "🔐 Подготовка транзакции отзыва разрешения {} -> {}",
+            owner_address, self.spender_address
+        );
+
+        self.tron_client
+            .create_trc20_approve_transaction(owner_address, &self.spender_address, 0)
+            .await
+    }
+
+    /// Алертит о неожиданном уменьшении разрешения
+    async fn alert_reduction(&self, owner_address: &str, previous: Decimal, current: Decimal) {
+        warn!(
+            "⚠️ Разрешение кошелька {} уменьшилось: {} -> {} USDT",
+            owner_address, previous, current
+        );
+
+        if let Some(notifications) = &self.notifications {
+            notifications
+                .alert(
+                    NotificationSeverity::Warning,
+                    "Уменьшение TRC-20 разрешения",
+                    format!(
+                        "Разрешение {} для {} снизилось с {} до {} USDT",
+                        owner_address, self.spender_address, previous, current
+                    ),
+                )
+                .await;
+        }
+    }
+}
+
+/// Оставляет последний снимок каждого владельца.
+/// Вход отсортирован по captured_at по убыванию
+fn latest_per_owner(snapshots: Vec<AllowanceSnapshotModel>) -> Vec<AllowanceSnapshotModel> {
+    let mut seen = HashSet::new();
+    snapshots
+        .into_iter()
+        .filter(|snapshot| seen.insert(snapshot.owner_address.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+
+    fn snapshot(id: i64, owner: &str, allowance: i64) -> AllowanceSnapshotModel {
+        AllowanceSnapshotModel {
+            id,
+            owner_address: owner.to_string(),
+            spender_address: "spender".to_string(),
+            token_contract: "usdt".to_string(),
+            allowance: BigDecimal::from(allowance),
+            captured_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_latest_per_owner_keeps_first_occurrence() {
+        // Вход новые первыми - для каждого владельца остается свежий снимок
+        let result = latest_per_owner(vec![
+            snapshot(3, "TAlice", 500),
+            snapshot(2, "TBob", 100),
+            snapshot(1, "TAlice", 900),
+        ]);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, 3);
+        assert_eq!(result[1].id, 2);
+    }
+}
@@ -0,0 +1,423 @@
+//! # Сервис пороговых подписок на балансы
+//!
+//! Клиент регистрирует алерт вида "USDT кошелька X >= 500" или
+//! "TRX мастера < 1000"; задача планировщика периодически сверяет
+//! пороги с балансами и шлет webhook/уведомление при пересечении.
+//! Гистерезис защищает от дребезга: сработавший алерт не стреляет
+//! повторно, пока значение не откатится за порог на hysteresis
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::infrastructure::database::models::{BalanceAlertModel, NewBalanceAlert, WalletModel};
+use crate::infrastructure::database::{schema, DbPool};
+use crate::infrastructure::{NotificationDispatcher, NotificationSeverity, TronGridClient};
+use crate::utils::conversions::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+use super::webhook_service::BalanceThresholdEvent;
+use super::WebhookService;
+
+/// Параметры новой пороговой подписки
+#[derive(Debug, Clone)]
+pub struct CreateBalanceAlert {
+    /// Кошелек подписки; None - мастер-кошелек
+    pub wallet_id: Option<i64>,
+    /// USDT или TRX
+    pub asset: String,
+    /// above (значение >= порога) или below (значение <= порога)
+    pub direction: String,
+    pub threshold: Decimal,
+    /// Мертвая зона перевзвода (по умолчанию 0)
+    pub hysteresis: Option<Decimal>,
+}
+
+/// Итог одного прохода сверки порогов
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BalanceAlertSummary {
+    pub alerts_checked: i64,
+    pub alerts_fired: i64,
+}
+
+/// Сервис пороговых подписок на балансы
+pub struct BalanceAlertService {
+    db: DbPool,
+    tron_client: TronGridClient,
+    /// Адрес мастер-кошелька для алертов без wallet_id
+    master_wallet_address: String,
+    webhook_service: Option<Arc<WebhookService>>,
+    notifications: Option<Arc<NotificationDispatcher>>,
+}
+
+impl BalanceAlertService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool, tron_client: TronGridClient, master_wallet_address: String) -> Self {
+        Self {
+            db,
+            tron_client,
+            master_wallet_address,
+            webhook_service: None,
+            notifications: None,
+        }
+    }
+
+    /// Подключает доставку webhook'ов о пересечениях порогов
+    pub fn with_webhooks(mut self, webhook_service: Arc<WebhookService>) -> Self {
+        self.webhook_service = Some(webhook_service);
+        self
+    }
+
+    /// Подключает канал операционных уведомлений
+    pub fn with_notifications(mut self, notifications: Arc<NotificationDispatcher>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Регистрирует пороговую подписку
+    pub async fn create_alert(&self, request: CreateBalanceAlert) -> Result<BalanceAlertModel> {
+        let asset = request.asset.to_ascii_uppercase();
+        if !matches!(asset.as_str(), "USDT" | "TRX") {
+            anyhow::bail!(
+                "Неизвестный актив алерта: {} (ожидается USDT или TRX)",
+                asset
+            );
+        }
+
+        let direction = request.direction.to_ascii_lowercase();
+        if !matches!(direction.as_str(), "above" | "below") {
+            anyhow::bail!(
+                "Неизвестное направление алерта: {} (ожидается above или below)",
+                direction
+            );
+        }
+
+        if request.threshold <= Decimal::ZERO {
+            anyhow::bail!("Порог алерта должен быть положительным");
+        }
+
+        let hysteresis = request.hysteresis.unwrap_or(Decimal::ZERO);
+        if hysteresis < Decimal::ZERO {
+            anyhow::bail!("Гистерезис не может быть отрицательным");
+        }
+
+        let mut conn = self.db.get().await?;
+
+        // Подписка на несуществующий кошелек - ошибка клиента
+        if let Some(wallet_id) = request.wallet_id {
+            let exists = schema::wallets::table
+                .find(wallet_id)
+                .select(schema::wallets::id)
+                .first::<i64>(&mut conn)
+                .await
+                .optional()?;
+            if exists.is_none() {
+                anyhow::bail!("Кошелек {} не найден", wallet_id);
+            }
+        }
+
+        let alert = diesel::insert_into(schema::balance_alerts::table)
+            .values(&NewBalanceAlert {
+                wallet_id: request.wallet_id,
+                asset,
+                direction,
+                threshold: decimal_to_bigdecimal(request.threshold),
+                hysteresis: decimal_to_bigdecimal(hysteresis),
+            })
+            .returning(BalanceAlertModel::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        info!(
+            "🔔 Зарегистрирован алерт #{}: {} {} {} {}",
+            alert.id,
+            alert
+                .wallet_id
+                .map(|id| format!("кошелек {}", id))
+                .unwrap_or_else(|| "мастер".to_string()),
+            alert.asset,
+            alert.direction,
+            alert.threshold
+        );
+
+        Ok(alert)
+    }
+
+    /// Список зарегистрированных подписок
+    pub async fn list_alerts(&self) -> Result<Vec<BalanceAlertModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::balance_alerts::table
+            .order(schema::balance_alerts::id.asc())
+            .select(BalanceAlertModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Удаляет подписку; false - алерт не найден
+    pub async fn delete_alert(&self, alert_id: i64) -> Result<bool> {
+        let mut conn = self.db.get().await?;
+
+        let deleted = diesel::delete(schema::balance_alerts::table.find(alert_id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Один проход сверки: для каждого включенного алерта берется
+    /// текущее значение, применяется гистерезис, при пересечении
+    /// порога уходят webhook и уведомление
+    pub async fn evaluate_once(&self) -> Result<BalanceAlertSummary> {
+        let alerts = {
+            let mut conn = self.db.get().await?;
+            schema::balance_alerts::table
+                .filter(schema::balance_alerts::enabled.eq(true))
+                .order(schema::balance_alerts::id.asc())
+                .select(BalanceAlertModel::as_select())
+                .load(&mut conn)
+                .await?
+        };
+
+        let mut summary = BalanceAlertSummary {
+            alerts_checked: 0,
+            alerts_fired: 0,
+        };
+
+        for alert in alerts {
+            let (wallet_address, value) = match self.resolve_value(&alert).await {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    warn!("⚠️  Алерт #{}: не удалось получить баланс: {}", alert.id, e);
+                    continue;
+                }
+            };
+
+            summary.alerts_checked += 1;
+
+            let threshold = bigdecimal_to_decimal(alert.threshold.clone());
+            let hysteresis = bigdecimal_to_decimal(alert.hysteresis.clone());
+            let (fire, rearm) = evaluate_threshold(
+                &alert.direction,
+                alert.triggered,
+                value,
+                threshold,
+                hysteresis,
+            );
+
+            if fire {
+                summary.alerts_fired += 1;
+                self.fire_alert(&alert, &wallet_address, value, threshold)
+                    .await;
+            }
+
+            let triggered_now = if fire {
+                true
+            } else if rearm {
+                false
+            } else {
+                alert.triggered
+            };
+
+            // Состояние сверки пишется best-effort: следующая итерация
+            // перечитает балансы заново
+            let updated = async {
+                let mut conn = self.db.get().await?;
+                diesel::update(schema::balance_alerts::table.find(alert.id))
+                    .set((
+                        schema::balance_alerts::triggered.eq(triggered_now),
+                        schema::balance_alerts::last_value.eq(Some(decimal_to_bigdecimal(value))),
+                        schema::balance_alerts::last_triggered_at.eq(if fire {
+                            Some(chrono::Utc::now())
+                        } else {
+                            alert.last_triggered_at
+                        }),
+                    ))
+                    .execute(&mut conn)
+                    .await?;
+                anyhow::Ok(())
+            }
+            .await;
+
+            if let Err(e) = updated {
+                warn!(
+                    "⚠️  Не удалось сохранить состояние алерта #{}: {}",
+                    alert.id, e
+                );
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Разрешает адрес и текущее значение баланса для алерта.
+    /// USDT кошельков берется из read model балансов (wallet_balances),
+    /// TRX и балансы мастера - напрямую из сети
+    async fn resolve_value(&self, alert: &BalanceAlertModel) -> Result<(String, Decimal)> {
+        let address = match alert.wallet_id {
+            Some(wallet_id) => {
+                let mut conn = self.db.get().await?;
+                let wallet = schema::wallets::table
+                    .find(wallet_id)
+                    .select(WalletModel::as_select())
+                    .first(&mut conn)
+                    .await
+                    .optional()?
+                    .ok_or_else(|| anyhow::anyhow!("Кошелек {} не найден", wallet_id))?;
+                wallet.address
+            }
+            None => self.master_wallet_address.clone(),
+        };
+
+        let value = match (alert.asset.as_str(), alert.wallet_id) {
+            ("USDT", Some(wallet_id)) => {
+                let mut conn = self.db.get().await?;
+                let projected = schema::wallet_balances::table
+                    .find(wallet_id)
+                    .select(schema::wallet_balances::usdt_balance)
+                    .first::<bigdecimal::BigDecimal>(&mut conn)
+                    .await
+                    .optional()?;
+                match projected {
+                    Some(balance) => bigdecimal_to_decimal(balance),
+                    // Проекция еще не построена - читаем сеть
+                    None => self.tron_client.get_usdt_balance(&address).await?,
+                }
+            }
+            ("USDT", None) => self.tron_client.get_usdt_balance(&address).await?,
+            ("TRX", _) => self.tron_client.get_trx_balance(&address).await?,
+            (other, _) => anyhow::bail!("Неизвестный актив алерта: {}", other),
+        };
+
+        Ok((address, value))
+    }
+
+    /// Доставляет событие пересечения порога (webhook + уведомление)
+    async fn fire_alert(
+        &self,
+        alert: &BalanceAlertModel,
+        wallet_address: &str,
+        value: Decimal,
+        threshold: Decimal,
+    ) {
+        info!(
+            "🔔 Алерт #{} сработал: {} {} баланс {} (порог {} {})",
+            alert.id, wallet_address, alert.asset, value, alert.direction, threshold
+        );
+
+        if let Some(webhook_service) = &self.webhook_service {
+            let event = BalanceThresholdEvent {
+                alert_id: alert.id,
+                wallet_id: alert.wallet_id,
+                wallet_address: wallet_address.to_string(),
+                asset: alert.asset.clone(),
+                direction: alert.direction.clone(),
+                threshold: threshold.to_string(),
+                current_value: value.to_string(),
+            };
+            if let Err(e) = webhook_service.notify_balance_threshold(event).await {
+                warn!(
+                    "⚠️  Не удалось отправить webhook алерта #{}: {}",
+                    alert.id, e
+                );
+            }
+        }
+
+        if let Some(notifications) = &self.notifications {
+            notifications
+                .alert(
+                    NotificationSeverity::Warning,
+                    "Пересечение порога баланса",
+                    format!(
+                        "Баланс {} {} достиг {} (алерт #{}: {} {})",
+                        wallet_address, alert.asset, value, alert.id, alert.direction, threshold
+                    ),
+                )
+                .await;
+        }
+    }
+}
+
+/// Решение по алерту: (стрелять, перевзвести).
+/// Для above: выстрел при value >= threshold, перевзвод при
+/// value < threshold - hysteresis; для below зеркально
+fn evaluate_threshold(
+    direction: &str,
+    triggered: bool,
+    value: Decimal,
+    threshold: Decimal,
+    hysteresis: Decimal,
+) -> (bool, bool) {
+    match direction {
+        "above" => {
+            let crossed = value >= threshold;
+            let rearmed = value < threshold - hysteresis;
+            (crossed && !triggered, triggered && rearmed)
+        }
+        "below" => {
+            let crossed = value <= threshold;
+            let rearmed = value > threshold + hysteresis;
+            (crossed && !triggered, triggered && rearmed)
+        }
+        _ => (false, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: i64) -> Decimal {
+        Decimal::new(value, 0)
+    }
+
+    #[test]
+    fn test_above_fires_once_until_rearmed() {
+        // Первое пересечение - выстрел
+        assert_eq!(
+            evaluate_threshold("above", false, dec(500), dec(500), dec(50)),
+            (true, false)
+        );
+        // Сработавший алерт не стреляет повторно
+        assert_eq!(
+            evaluate_threshold("above", true, dec(600), dec(500), dec(50)),
+            (false, false)
+        );
+        // Откат в мертвую зону не перевзводит
+        assert_eq!(
+            evaluate_threshold("above", true, dec(470), dec(500), dec(50)),
+            (false, false)
+        );
+        // Откат за порог минус гистерезис - перевзвод
+        assert_eq!(
+            evaluate_threshold("above", true, dec(449), dec(500), dec(50)),
+            (false, true)
+        );
+    }
+
+    #[test]
+    fn test_below_mirrors_above() {
+        assert_eq!(
+            evaluate_threshold("below", false, dec(900), dec(1000), dec(100)),
+            (true, false)
+        );
+        assert_eq!(
+            evaluate_threshold("below", true, dec(1050), dec(1000), dec(100)),
+            (false, false)
+        );
+        assert_eq!(
+            evaluate_threshold("below", true, dec(1101), dec(1000), dec(100)),
+            (false, true)
+        );
+    }
+
+    #[test]
+    fn test_zero_hysteresis_rearms_on_any_retreat() {
+        assert_eq!(
+            evaluate_threshold("above", true, dec(499), dec(500), Decimal::ZERO),
+            (false, true)
+        );
+    }
+}
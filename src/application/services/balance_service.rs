@@ -0,0 +1,251 @@
+//! # Сервис проекции балансов
+//!
+//! Поддерживает локальную проекцию балансов кошельков, обновляемую из
+//! подтвержденных депозитов и завершенных трансферов, вместо живых
+//! запросов к TronGrid на каждый API вызов
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::infrastructure::database::{models::*, schema, DbPool};
+use crate::infrastructure::TronGridClient;
+use crate::utils::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+/// Источник данных для чтения баланса
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceSource {
+    /// Локальная проекция (по умолчанию)
+    Projected,
+    /// Живой запрос к TronGrid (escape hatch `?source=chain`)
+    Chain,
+}
+
+impl BalanceSource {
+    /// Парсит источник из query параметра (`chain` или `projected`)
+    pub fn from_query(source: Option<&str>) -> Self {
+        match source {
+            Some("chain") => Self::Chain,
+            _ => Self::Projected,
+        }
+    }
+}
+
+/// Снимок баланса кошелька на момент времени в прошлом
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HistoricalBalance {
+    pub wallet_id: i64,
+    /// Момент, на который восстановлен баланс
+    pub at: DateTime<Utc>,
+    /// Подтвержденные депозиты минус завершенные трансферы на момент `at`
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub usdt_balance: Decimal,
+    /// Депозиты, обнаруженные до `at`, но подтвержденные позже (или так и не подтвержденные)
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub pending_incoming: Decimal,
+    /// Трансферы, созданные до `at`, но завершенные позже (или еще в обработке)
+    #[serde(serialize_with = "crate::utils::serialize_amount")]
+    pub pending_outgoing: Decimal,
+}
+
+/// Сервис проекции балансов кошельков
+#[derive(Clone)]
+pub struct BalanceService {
+    db: DbPool,
+    tron_client: TronGridClient,
+}
+
+impl BalanceService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool, tron_client: TronGridClient) -> Self {
+        Self { db, tron_client }
+    }
+
+    /// Получение проецированного USDT баланса кошелька
+    /// Если записи еще нет - возвращает ноль (кошелек без движений)
+    pub async fn get_projected_balance(&self, wallet_id: i64) -> Result<Decimal> {
+        let mut conn = self.db.get().await?;
+
+        let balance_result: Result<WalletBalanceModel, diesel::result::Error> =
+            schema::wallet_balances::table
+                .find(wallet_id)
+                .first(&mut conn)
+                .await;
+
+        match balance_result {
+            Ok(balance) => Ok(bigdecimal_to_decimal(balance.usdt_balance)),
+            Err(diesel::result::Error::NotFound) => Ok(Decimal::ZERO),
+            Err(e) => Err(anyhow::Error::from(e)),
+        }
+    }
+
+    /// Восстанавливает баланс кошелька на момент `at` из записанных
+    /// транзакций (time-travel запрос для финансовой сверки).
+    ///
+    /// Семантика pending: учитывается, каким был статус на момент `at` -
+    /// депозит, подтвержденный позже `at`, на тот момент был pending
+    /// и в баланс не входит (аналогично для исходящих трансферов).
+    /// FAILED записи денег не двигали и не учитываются
+    pub async fn get_balance_at(
+        &self,
+        wallet_id: i64,
+        at: DateTime<Utc>,
+    ) -> Result<HistoricalBalance> {
+        use schema::{incoming_transactions as inc, outgoing_transfers as out};
+
+        let mut conn = self.db.get().await?;
+
+        // Депозиты, подтвержденные к моменту `at`
+        let confirmed_in: Option<bigdecimal::BigDecimal> = inc::table
+            .filter(inc::wallet_id.eq(wallet_id))
+            .filter(inc::status.eq(crate::domain::TransactionStatus::Completed.as_db_str()))
+            .filter(
+                inc::confirmed_at
+                    .le(at)
+                    .or(inc::confirmed_at.is_null().and(inc::detected_at.le(at))),
+            )
+            .select(diesel::dsl::sum(inc::amount))
+            .first(&mut conn)
+            .await?;
+
+        // Депозиты, обнаруженные до `at`, но на тот момент еще не подтвержденные
+        let pending_in: Option<bigdecimal::BigDecimal> = inc::table
+            .filter(inc::wallet_id.eq(wallet_id))
+            .filter(inc::status.ne(crate::domain::TransactionStatus::Failed.as_db_str()))
+            .filter(inc::detected_at.le(at))
+            .filter(inc::confirmed_at.is_null().or(inc::confirmed_at.gt(at)))
+            .filter(
+                inc::status
+                    .ne(crate::domain::TransactionStatus::Completed.as_db_str())
+                    .or(inc::confirmed_at.is_not_null()),
+            )
+            .select(diesel::dsl::sum(inc::amount))
+            .first(&mut conn)
+            .await?;
+
+        // Трансферы, завершенные к моменту `at`
+        let completed_out: Option<bigdecimal::BigDecimal> = out::table
+            .filter(out::from_wallet_id.eq(wallet_id))
+            .filter(out::status.eq(crate::domain::TransactionStatus::Completed.as_db_str()))
+            .filter(
+                out::completed_at
+                    .le(at)
+                    .or(out::completed_at.is_null().and(out::created_at.le(at))),
+            )
+            .select(diesel::dsl::sum(out::amount))
+            .first(&mut conn)
+            .await?;
+
+        // Трансферы, созданные до `at`, но на тот момент еще не завершенные
+        let pending_out: Option<bigdecimal::BigDecimal> = out::table
+            .filter(out::from_wallet_id.eq(wallet_id))
+            .filter(out::status.ne(crate::domain::TransactionStatus::Failed.as_db_str()))
+            .filter(out::created_at.le(at))
+            .filter(out::completed_at.is_null().or(out::completed_at.gt(at)))
+            .filter(
+                out::status
+                    .ne(crate::domain::TransactionStatus::Completed.as_db_str())
+                    .or(out::completed_at.is_not_null()),
+            )
+            .select(diesel::dsl::sum(out::amount))
+            .first(&mut conn)
+            .await?;
+
+        let to_decimal = |value: Option<bigdecimal::BigDecimal>| {
+            value.map(bigdecimal_to_decimal).unwrap_or_default()
+        };
+
+        Ok(HistoricalBalance {
+            wallet_id,
+            at,
+            usdt_balance: to_decimal(confirmed_in) - to_decimal(completed_out),
+            pending_incoming: to_decimal(pending_in),
+            pending_outgoing: to_decimal(pending_out),
+        })
+    }
+
+    /// Применяет подтвержденный депозит к проекции (upsert с инкрементом)
+    pub async fn apply_confirmed_deposit(&self, wallet_id: i64, amount: Decimal) -> Result<()> {
+        self.apply_delta(wallet_id, amount).await
+    }
+
+    /// Применяет завершенный исходящий трансфер к проекции (декремент)
+    pub async fn apply_completed_transfer(&self, wallet_id: i64, amount: Decimal) -> Result<()> {
+        self.apply_delta(wallet_id, -amount).await
+    }
+
+    /// Сверяет проекцию с реальным балансом из сети TRON
+    pub async fn reconcile_with_chain(&self, wallet_id: i64, address: &str) -> Result<Decimal> {
+        let chain_balance = self.tron_client.get_usdt_balance(address).await?;
+
+        let mut conn = self.db.get().await?;
+        let new_balance = NewWalletBalance {
+            wallet_id,
+            usdt_balance: decimal_to_bigdecimal(chain_balance),
+        };
+
+        diesel::insert_into(schema::wallet_balances::table)
+            .values(&new_balance)
+            .on_conflict(schema::wallet_balances::wallet_id)
+            .do_update()
+            .set((
+                schema::wallet_balances::usdt_balance.eq(decimal_to_bigdecimal(chain_balance)),
+                schema::wallet_balances::last_reconciled_at.eq(diesel::dsl::now),
+                schema::wallet_balances::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        info!(
+            "Баланс кошелька {} сверен с сетью: {} USDT",
+            wallet_id, chain_balance
+        );
+
+        Ok(chain_balance)
+    }
+
+    /// Сверяет проекции всех кошельков с сетью (для периодической задачи)
+    pub async fn reconcile_all(&self) -> Result<()> {
+        let mut conn = self.db.get().await?;
+        let wallets: Vec<WalletModel> = schema::wallets::table
+            .select(WalletModel::as_select())
+            .load(&mut conn)
+            .await?;
+
+        for wallet in wallets {
+            if let Err(e) = self.reconcile_with_chain(wallet.id, &wallet.address).await {
+                warn!("Ошибка сверки баланса кошелька {}: {}", wallet.id, e);
+                // Продолжаем с другими кошельками
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Применяет дельту к проекции баланса (upsert)
+    async fn apply_delta(&self, wallet_id: i64, delta: Decimal) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        let new_balance = NewWalletBalance {
+            wallet_id,
+            usdt_balance: decimal_to_bigdecimal(delta),
+        };
+
+        diesel::insert_into(schema::wallet_balances::table)
+            .values(&new_balance)
+            .on_conflict(schema::wallet_balances::wallet_id)
+            .do_update()
+            .set((
+                schema::wallet_balances::usdt_balance
+                    .eq(schema::wallet_balances::usdt_balance + decimal_to_bigdecimal(delta)),
+                schema::wallet_balances::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
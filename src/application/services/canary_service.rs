@@ -0,0 +1,245 @@
+//! # Канарейка продакшн-пайплайна
+//!
+//! Health-пробы проверяют компоненты по отдельности, но зеленые пробы
+//! не гарантируют, что конвейер работает целиком. Канарейка периодически
+//! гоняет крошечный реальный USDT трансфер между двумя выделенными
+//! canary-кошельками через обычный пайплайн (спонсирование газа,
+//! подписание, broadcast, подтверждение, webhook), меряет полную
+//! латентность и алертит при провале
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use crate::application::dto::CreateWithdrawalRequest;
+use crate::config::CanaryConfig;
+use crate::domain::TransactionStatus;
+use crate::infrastructure::{Notification, NotificationDispatcher, NotificationSeverity};
+
+use super::TransferService;
+
+/// Интервал опроса статуса канареечного трансфера
+const CANARY_POLL_INTERVAL_SECONDS: u64 = 5;
+
+/// Итог одного прогона канарейки
+#[derive(Debug, Clone)]
+pub struct CanaryResult {
+    /// Начало прогона
+    pub started_at: DateTime<Utc>,
+    /// Полная латентность пайплайна (создание -> COMPLETED), мс
+    pub latency_ms: Option<i64>,
+    /// Исход: completed / failed / timeout
+    pub outcome: String,
+    /// ID канареечного трансфера (если он был создан)
+    pub transfer_id: Option<i64>,
+    /// Текст ошибки при провале
+    pub error: Option<String>,
+}
+
+/// Сервис канареечных трансферов
+pub struct CanaryService {
+    config: CanaryConfig,
+    transfer_service: Arc<TransferService>,
+    /// Канал операционных уведомлений (алерт при провале канарейки)
+    notifications: Option<Arc<NotificationDispatcher>>,
+    /// Итог последнего прогона (для отладки и ручных проверок)
+    last_result: Mutex<Option<CanaryResult>>,
+}
+
+impl CanaryService {
+    /// Создает сервис канарейки
+    pub fn new(config: CanaryConfig, transfer_service: Arc<TransferService>) -> Self {
+        Self {
+            config,
+            transfer_service,
+            notifications: None,
+            last_result: Mutex::new(None),
+        }
+    }
+
+    /// Подключает канал операционных уведомлений
+    pub fn with_notifications(mut self, notifications: Arc<NotificationDispatcher>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Включена ли канарейка (и полностью ли сконфигурирована)
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+            && self.config.from_wallet_id.is_some()
+            && self.config.to_address.is_some()
+    }
+
+    /// Интервал прогонов в минутах (для задачи планировщика)
+    pub fn interval_minutes(&self) -> u64 {
+        self.config.interval_minutes
+    }
+
+    /// Итог последнего прогона
+    pub fn last_result(&self) -> Option<CanaryResult> {
+        self.last_result.lock().unwrap().clone()
+    }
+
+    /// Один прогон канарейки: создает канареечный вывод, ждет его
+    /// прохождения через весь пайплайн и алертит при провале.
+    /// Возвращает итог прогона; ошибкой завершается только
+    /// неполная конфигурация
+    pub async fn run_canary(&self) -> Result<CanaryResult> {
+        let from_wallet_id = self
+            .config
+            .from_wallet_id
+            .ok_or_else(|| anyhow::anyhow!("Канарейка: не задан from_wallet_id"))?;
+        let to_address = self
+            .config
+            .to_address
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Канарейка: не задан to_address"))?;
+
+        let started_at = Utc::now();
+        let started = std::time::Instant::now();
+
+        info!(
+            "🐤 Канарейка: запуск трансфера {} USDT (кошелек {} -> {})",
+            self.config.amount, from_wallet_id, to_address
+        );
+
+        // Канареечный трансфер идет обычным пайплайном выводов -
+        // именно это и проверяется
+        let request = CreateWithdrawalRequest {
+            from_wallet_id,
+            to_address,
+            amount: self.config.amount,
+            reference_id: Some(format!("canary-{}", started_at.timestamp())),
+            destination_tag: None,
+            metadata: Some(serde_json::json!({ "canary": true })),
+            fee_payer: None,
+            token_symbol: None,
+        };
+
+        let transfer = match self.transfer_service.create_withdrawal(request).await {
+            Ok(transfer) => transfer,
+            Err(e) => {
+                let result = CanaryResult {
+                    started_at,
+                    latency_ms: None,
+                    outcome: "failed".to_string(),
+                    transfer_id: None,
+                    error: Some(format!("Создание канареечного трансфера: {}", e)),
+                };
+                self.finish_run(result.clone()).await;
+                return Ok(result);
+            }
+        };
+
+        // Ждем прохождения пайплайна: обработку делают обычные фоновые
+        // задачи, канарейка только опрашивает статус
+        let timeout = std::time::Duration::from_secs(self.config.completion_timeout_minutes * 60);
+        let poll_interval = std::time::Duration::from_secs(CANARY_POLL_INTERVAL_SECONDS);
+
+        let result = loop {
+            if started.elapsed() >= timeout {
+                break CanaryResult {
+                    started_at,
+                    latency_ms: None,
+                    outcome: "timeout".to_string(),
+                    transfer_id: Some(transfer.id),
+                    error: Some(format!(
+                        "Канареечный трансфер {} не завершился за {} минут",
+                        transfer.id, self.config.completion_timeout_minutes
+                    )),
+                };
+            }
+
+            tokio::time::sleep(poll_interval).await;
+
+            let current = match self.transfer_service.get_transfer(transfer.id).await {
+                Ok(Some(current)) => current,
+                Ok(None) => {
+                    break CanaryResult {
+                        started_at,
+                        latency_ms: None,
+                        outcome: "failed".to_string(),
+                        transfer_id: Some(transfer.id),
+                        error: Some(format!("Канареечный трансфер {} пропал из БД", transfer.id)),
+                    };
+                }
+                Err(e) => {
+                    warn!("⚠️ Канарейка: ошибка опроса статуса: {}", e);
+                    continue;
+                }
+            };
+
+            match current.status {
+                TransactionStatus::Completed => {
+                    break CanaryResult {
+                        started_at,
+                        latency_ms: Some(started.elapsed().as_millis() as i64),
+                        outcome: "completed".to_string(),
+                        transfer_id: Some(transfer.id),
+                        error: None,
+                    };
+                }
+                TransactionStatus::Failed | TransactionStatus::Cancelled => {
+                    break CanaryResult {
+                        started_at,
+                        latency_ms: None,
+                        outcome: "failed".to_string(),
+                        transfer_id: Some(transfer.id),
+                        error: current.error_message.clone().or_else(|| {
+                            Some(format!(
+                                "Канареечный трансфер {} завершился статусом {:?}",
+                                transfer.id, current.status
+                            ))
+                        }),
+                    };
+                }
+                TransactionStatus::Pending | TransactionStatus::Processing => continue,
+            }
+        };
+
+        self.finish_run(result.clone()).await;
+        Ok(result)
+    }
+
+    /// Фиксирует итог прогона: лог, снимок для отладки и алерт при провале
+    async fn finish_run(&self, result: CanaryResult) {
+        match result.outcome.as_str() {
+            "completed" => {
+                info!(
+                    "🐤 Канарейка: пайплайн прошел за {} мс (трансфер {})",
+                    result.latency_ms.unwrap_or(0),
+                    result.transfer_id.unwrap_or(0)
+                );
+            }
+            _ => {
+                warn!(
+                    "❌ Канарейка провалилась ({}): {}",
+                    result.outcome,
+                    result.error.as_deref().unwrap_or("без деталей")
+                );
+
+                if let Some(notifications) = &self.notifications {
+                    notifications
+                        .dispatch(Notification::new(
+                            NotificationSeverity::Critical,
+                            "Канарейка пайплайна провалилась",
+                            format!(
+                                "Исход: {}\nТрансфер: {}\nОшибка: {}",
+                                result.outcome,
+                                result
+                                    .transfer_id
+                                    .map(|id| id.to_string())
+                                    .unwrap_or_else(|| "-".to_string()),
+                                result.error.as_deref().unwrap_or("-")
+                            ),
+                        ))
+                        .await;
+                }
+            }
+        }
+
+        *self.last_result.lock().unwrap() = Some(result);
+    }
+}
@@ -0,0 +1,208 @@
+//! # Пост-обработка депозитов
+//!
+//! Реестр подключаемых хуков, выполняемых после записи нового депозита:
+//! авто-форвардинг, уведомление CRM, начисление баллов лояльности.
+//! Хуки регистрируются кодом деплоймента через builder и выполняются
+//! по порядку регистрации. Каждый хук изолирован - его ошибка
+//! логируется и не мешает остальным хукам и самой обработке депозита
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::domain::TransactionStatus;
+
+/// Количество попыток выполнения одного хука
+const HOOK_MAX_ATTEMPTS: u32 = 3;
+
+/// Пауза между попытками выполнения хука
+const HOOK_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Контекст депозита, передаваемый хукам
+#[derive(Debug, Clone)]
+pub struct DepositHookContext {
+    pub wallet_id: i64,
+    pub wallet_address: String,
+    pub owner_id: Option<String>,
+    pub tx_hash: String,
+    pub from_address: String,
+    pub amount: Decimal,
+    pub status: TransactionStatus,
+    /// Метка источника депозита (exchange, mixer, internal, private_wallet)
+    pub source_label: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Подключаемое действие после обработки депозита
+#[tonic::async_trait]
+pub trait DepositHook: Send + Sync {
+    /// Имя хука для логирования
+    fn name(&self) -> &str;
+
+    /// Выполняет действие над депозитом
+    async fn on_deposit(&self, ctx: &DepositHookContext) -> Result<()>;
+}
+
+/// Реестр хуков пост-обработки депозитов
+pub struct DepositHookRegistry {
+    hooks: Vec<Arc<dyn DepositHook>>,
+    max_attempts: u32,
+    retry_delay: std::time::Duration,
+}
+
+impl Default for DepositHookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DepositHookRegistry {
+    /// Создает пустой реестр
+    pub fn new() -> Self {
+        Self {
+            hooks: Vec::new(),
+            max_attempts: HOOK_MAX_ATTEMPTS,
+            retry_delay: HOOK_RETRY_DELAY,
+        }
+    }
+
+    /// Регистрирует хук. Хуки выполняются в порядке регистрации
+    pub fn register(mut self, hook: Arc<dyn DepositHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Переопределяет количество попыток на хук
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Переопределяет паузу между попытками
+    pub fn with_retry_delay(mut self, retry_delay: std::time::Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Зарегистрированы ли хуки
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Выполняет все хуки по порядку. Упавший после всех попыток хук
+    /// логируется и не прерывает цепочку
+    pub async fn run(&self, ctx: &DepositHookContext) {
+        for hook in &self.hooks {
+            self.run_hook(hook.as_ref(), ctx).await;
+        }
+    }
+
+    /// Выполняет один хук с повторами
+    async fn run_hook(&self, hook: &dyn DepositHook, ctx: &DepositHookContext) {
+        for attempt in 1..=self.max_attempts {
+            match hook.on_deposit(ctx).await {
+                Ok(()) => return,
+                Err(e) if attempt < self.max_attempts => {
+                    warn!(
+                        "⚠️ Хук {} упал на депозите {} (попытка {}/{}): {}",
+                        hook.name(),
+                        ctx.tx_hash,
+                        attempt,
+                        self.max_attempts,
+                        e
+                    );
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+                Err(e) => {
+                    warn!(
+                        "❌ Хук {} не выполнен для депозита {} после {} попыток: {}",
+                        hook.name(),
+                        ctx.tx_hash,
+                        self.max_attempts,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Тестовый хук: падает заданное число раз, считает вызовы
+    struct FlakyHook {
+        calls: AtomicU32,
+        failures: u32,
+    }
+
+    #[tonic::async_trait]
+    impl DepositHook for FlakyHook {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn on_deposit(&self, _ctx: &DepositHookContext) -> Result<()> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                anyhow::bail!("временная ошибка");
+            }
+            Ok(())
+        }
+    }
+
+    fn test_context() -> DepositHookContext {
+        DepositHookContext {
+            wallet_id: 1,
+            wallet_address: "TWd4WrZ9wn84f5x1hZhL4DHvk738ns5jwb".to_string(),
+            owner_id: None,
+            tx_hash: "abc".to_string(),
+            from_address: "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".to_string(),
+            amount: Decimal::new(100, 0),
+            status: TransactionStatus::Completed,
+            source_label: None,
+            detected_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hook_retries_until_success() {
+        let hook = Arc::new(FlakyHook {
+            calls: AtomicU32::new(0),
+            failures: 2,
+        });
+        let registry = DepositHookRegistry::new()
+            .with_retry_delay(std::time::Duration::ZERO)
+            .register(hook.clone());
+
+        registry.run(&test_context()).await;
+        assert_eq!(hook.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_failed_hook_does_not_block_chain() {
+        let failing = Arc::new(FlakyHook {
+            calls: AtomicU32::new(0),
+            failures: 100, // Падает всегда
+        });
+        let succeeding = Arc::new(FlakyHook {
+            calls: AtomicU32::new(0),
+            failures: 0,
+        });
+        let registry = DepositHookRegistry::new()
+            .with_retry_delay(std::time::Duration::ZERO)
+            .register(failing.clone())
+            .register(succeeding.clone());
+
+        registry.run(&test_context()).await;
+
+        // Упавший хук исчерпал попытки, следующий все равно выполнился
+        assert_eq!(failing.calls.load(Ordering::SeqCst), HOOK_MAX_ATTEMPTS);
+        assert_eq!(succeeding.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,457 @@
+//! # Маршрутизация депозитов по правилам разложения
+//!
+//! Декларативная таблица правил на кошелек: каждый подтвержденный
+//! депозит раскладывается по ногам (например 95% на мастер-кошелек
+//! и 5% партнеру). Проценты набора в сумме дают ровно 100, трансферы
+//! всех ног создаются атомарно в одной транзакции БД, по каждой ноге
+//! остается учетная запись. Запускается хуком пост-обработки депозитов
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domain::{TransactionStatus, TronValidator};
+use crate::infrastructure::database::models::{
+    DepositSplitLegModel, DepositSplitRuleModel, NewDepositSplitLeg, NewDepositSplitRule,
+    NewOutgoingTransfer, OutgoingTransferModel,
+};
+use crate::infrastructure::database::{schema, DbPool};
+use crate::utils::conversions::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+use super::deposit_hooks::{DepositHook, DepositHookContext};
+use super::fee_service::FeePayer;
+use super::transfer_service::TransferKind;
+
+/// Максимальное количество ног в наборе правил
+const MAX_SPLIT_LEGS: usize = 10;
+
+/// Точность сумм ног (десятичные знаки USDT в сети TRON)
+const LEG_AMOUNT_SCALE: u32 = 6;
+
+/// Ключевое слово назначения "мастер-кошелек"
+pub const SPLIT_DESTINATION_MASTER: &str = "master";
+
+/// Нога набора правил разложения (входные данные клиента)
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct SplitLegSpec {
+    /// 'master' или конкретный TRON адрес
+    pub destination: String,
+    pub percent: Decimal,
+    /// Человекочитаемая метка ноги (partner, treasury и т.п.)
+    pub label: Option<String>,
+}
+
+/// Сервис маршрутизации депозитов
+pub struct DepositSplitService {
+    db: DbPool,
+    /// Адрес мастер-кошелька для назначения 'master'
+    master_wallet_address: String,
+}
+
+impl DepositSplitService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool, master_wallet_address: String) -> Self {
+        Self {
+            db,
+            master_wallet_address,
+        }
+    }
+
+    /// Заменяет набор правил кошелька целиком (пустой список запрещен -
+    /// для отключения разложения есть clear_rules). Старые и новые
+    /// правила меняются в одной транзакции БД
+    pub async fn set_rules(
+        &self,
+        wallet_id: i64,
+        legs: Vec<SplitLegSpec>,
+    ) -> Result<Vec<DepositSplitRuleModel>> {
+        validate_legs(&legs)?;
+
+        let mut conn = self.db.get().await?;
+
+        // Правила на несуществующий кошелек - ошибка клиента
+        let exists = schema::wallets::table
+            .find(wallet_id)
+            .select(schema::wallets::id)
+            .first::<i64>(&mut conn)
+            .await
+            .optional()?;
+        if exists.is_none() {
+            anyhow::bail!("Кошелек {} не найден", wallet_id);
+        }
+
+        let new_rules: Vec<NewDepositSplitRule> = legs
+            .into_iter()
+            .enumerate()
+            .map(|(index, leg)| NewDepositSplitRule {
+                wallet_id,
+                position: index as i32 + 1,
+                destination: leg.destination,
+                percent: decimal_to_bigdecimal(leg.percent),
+                label: leg.label,
+            })
+            .collect();
+
+        let rules = conn
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    diesel::delete(
+                        schema::deposit_split_rules::table
+                            .filter(schema::deposit_split_rules::wallet_id.eq(wallet_id)),
+                    )
+                    .execute(conn)
+                    .await?;
+
+                    Ok(diesel::insert_into(schema::deposit_split_rules::table)
+                        .values(&new_rules)
+                        .returning(DepositSplitRuleModel::as_returning())
+                        .get_results(conn)
+                        .await?)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        info!(
+            "📋 Кошелек {}: установлен набор из {} ног разложения депозитов",
+            wallet_id,
+            rules.len()
+        );
+
+        Ok(rules)
+    }
+
+    /// Набор правил кошелька в порядке ног
+    pub async fn get_rules(&self, wallet_id: i64) -> Result<Vec<DepositSplitRuleModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::deposit_split_rules::table
+            .filter(schema::deposit_split_rules::wallet_id.eq(wallet_id))
+            .order(schema::deposit_split_rules::position.asc())
+            .select(DepositSplitRuleModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Удаляет набор правил кошелька; false - правил не было
+    pub async fn clear_rules(&self, wallet_id: i64) -> Result<bool> {
+        let mut conn = self.db.get().await?;
+
+        let deleted = diesel::delete(
+            schema::deposit_split_rules::table
+                .filter(schema::deposit_split_rules::wallet_id.eq(wallet_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Учетные записи по ногам депозита
+    pub async fn get_legs(&self, deposit_tx_hash: &str) -> Result<Vec<DepositSplitLegModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::deposit_split_legs::table
+            .filter(schema::deposit_split_legs::deposit_tx_hash.eq(deposit_tx_hash))
+            .order(schema::deposit_split_legs::id.asc())
+            .select(DepositSplitLegModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Раскладывает подтвержденный депозит по правилам кошелька:
+    /// PENDING-трансферы всех ног и учетные записи создаются в одной
+    /// транзакции БД, отправку выполняет обычный пайплайн обработки.
+    /// Повторный вызов по тому же tx_hash - no-op (идемпотентность)
+    pub async fn apply_split(
+        &self,
+        wallet_id: i64,
+        deposit_tx_hash: &str,
+        amount: Decimal,
+    ) -> Result<usize> {
+        let rules = self.get_rules(wallet_id).await?;
+        if rules.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.db.get().await?;
+
+        // Депозит уже разложен - ноги записаны предыдущим вызовом
+        let already_split = schema::deposit_split_legs::table
+            .filter(schema::deposit_split_legs::deposit_tx_hash.eq(deposit_tx_hash))
+            .select(schema::deposit_split_legs::id)
+            .first::<i64>(&mut conn)
+            .await
+            .optional()?;
+        if already_split.is_some() {
+            info!(
+                "Депозит {} уже разложен по правилам, пропускаем",
+                deposit_tx_hash
+            );
+            return Ok(0);
+        }
+
+        let percents: Vec<Decimal> = rules
+            .iter()
+            .map(|rule| bigdecimal_to_decimal(rule.percent.clone()))
+            .collect();
+        let amounts = compute_leg_amounts(amount, &percents);
+
+        let master_wallet_address = self.master_wallet_address.clone();
+        let tx_hash = deposit_tx_hash.to_string();
+        let legs_created = conn
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    let mut legs_created = 0usize;
+                    for (rule, leg_amount) in rules.iter().zip(amounts) {
+                        // Ноги с нулевой суммой (микродепозиты) остаются
+                        // в учете, но трансфер по ним не создается
+                        let transfer_id = if leg_amount > Decimal::ZERO {
+                            let to_address = if rule.destination == SPLIT_DESTINATION_MASTER {
+                                master_wallet_address.clone()
+                            } else {
+                                rule.destination.clone()
+                            };
+
+                            let transfer: OutgoingTransferModel =
+                                diesel::insert_into(schema::outgoing_transfers::table)
+                                    .values(&NewOutgoingTransfer {
+                                        from_wallet_id: wallet_id,
+                                        to_address,
+                                        amount: decimal_to_bigdecimal(leg_amount),
+                                        status: TransactionStatus::Pending.as_db_str().to_string(),
+                                        reference_id: Some(format!(
+                                            "split-{}-{}",
+                                            tx_hash, rule.position
+                                        )),
+                                        destination_tag: None,
+                                        risk_score: None,
+                                        risk_provider: None,
+                                        screened_at: None,
+                                        metadata: None,
+                                        fee_payer: FeePayer::Merchant.as_db_str().to_string(),
+                                        fee_amount: None,
+                                        transfer_kind: TransferKind::Split.as_db_str().to_string(),
+                                        token_symbol: "USDT".to_string(),
+                                        fee_trx_usdt_rate: None,
+                                        fee_rate_source: None,
+                                        fee_rate_captured_at: None,
+                                    })
+                                    .get_result(conn)
+                                    .await?;
+                            Some(transfer.id)
+                        } else {
+                            None
+                        };
+
+                        diesel::insert_into(schema::deposit_split_legs::table)
+                            .values(&NewDepositSplitLeg {
+                                wallet_id,
+                                deposit_tx_hash: tx_hash.clone(),
+                                destination: rule.destination.clone(),
+                                percent: rule.percent.clone(),
+                                amount: decimal_to_bigdecimal(leg_amount),
+                                transfer_id,
+                                label: rule.label.clone(),
+                            })
+                            .execute(conn)
+                            .await?;
+
+                        if transfer_id.is_some() {
+                            legs_created += 1;
+                        }
+                    }
+                    Ok(legs_created)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        info!(
+            "🔀 Депозит {} ({} USDT) разложен на {} ног по правилам кошелька {}",
+            deposit_tx_hash, amount, legs_created, wallet_id
+        );
+
+        Ok(legs_created)
+    }
+}
+
+/// Хук пост-обработки депозитов: раскладывает подтвержденные
+/// депозиты по правилам кошелька
+pub struct DepositSplitHook {
+    service: Arc<DepositSplitService>,
+}
+
+impl DepositSplitHook {
+    /// Создает хук поверх сервиса маршрутизации
+    pub fn new(service: Arc<DepositSplitService>) -> Self {
+        Self { service }
+    }
+}
+
+#[tonic::async_trait]
+impl DepositHook for DepositSplitHook {
+    fn name(&self) -> &str {
+        "deposit_split"
+    }
+
+    async fn on_deposit(&self, ctx: &DepositHookContext) -> Result<()> {
+        // Раскладываются только подтвержденные депозиты: незрелый
+        // депозит еще может не собрать подтверждения
+        if ctx.status != TransactionStatus::Completed {
+            return Ok(());
+        }
+
+        self.service
+            .apply_split(ctx.wallet_id, &ctx.tx_hash, ctx.amount)
+            .await
+            .map(|_| ())
+            .inspect_err(|e| {
+                warn!("⚠️ Не удалось разложить депозит {}: {}", ctx.tx_hash, e);
+            })
+    }
+}
+
+/// Проверяет набор ног: непустой, в пределах лимита, без дублей
+/// назначений, каждый процент положителен, сумма - ровно 100
+fn validate_legs(legs: &[SplitLegSpec]) -> Result<()> {
+    if legs.is_empty() {
+        anyhow::bail!("Набор правил не может быть пустым");
+    }
+    if legs.len() > MAX_SPLIT_LEGS {
+        anyhow::bail!(
+            "Слишком много ног: {} (максимум {})",
+            legs.len(),
+            MAX_SPLIT_LEGS
+        );
+    }
+
+    let mut total = Decimal::ZERO;
+    for leg in legs {
+        if leg.percent <= Decimal::ZERO {
+            anyhow::bail!("Процент ноги {} должен быть положительным", leg.destination);
+        }
+        total += leg.percent;
+
+        if leg.destination != SPLIT_DESTINATION_MASTER {
+            TronValidator::validate_address(&leg.destination)
+                .map_err(|e| anyhow::anyhow!("Назначение {}: {}", leg.destination, e))?;
+        }
+
+        let duplicates = legs
+            .iter()
+            .filter(|other| other.destination == leg.destination)
+            .count();
+        if duplicates > 1 {
+            anyhow::bail!("Назначение {} указано более одного раза", leg.destination);
+        }
+    }
+
+    if total != Decimal::new(100, 0) {
+        anyhow::bail!("Проценты ног в сумме дают {}, ожидается ровно 100", total);
+    }
+
+    Ok(())
+}
+
+/// Суммы ног: каждая нога получает свой процент, округленный вниз
+/// до точности USDT, остаток округления уходит последней ноге -
+/// сумма ног всегда равна сумме депозита
+fn compute_leg_amounts(total: Decimal, percents: &[Decimal]) -> Vec<Decimal> {
+    let mut amounts = Vec::with_capacity(percents.len());
+    let mut distributed = Decimal::ZERO;
+
+    for (index, percent) in percents.iter().enumerate() {
+        let amount = if index + 1 == percents.len() {
+            total - distributed
+        } else {
+            (total * percent / Decimal::new(100, 0))
+                .round_dp_with_strategy(LEG_AMOUNT_SCALE, RoundingStrategy::ToZero)
+        };
+        distributed += amount;
+        amounts.push(amount);
+    }
+
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(destination: &str, percent: Decimal) -> SplitLegSpec {
+        SplitLegSpec {
+            destination: destination.to_string(),
+            percent,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_legs_requires_exact_hundred() {
+        let valid = vec![
+            leg(SPLIT_DESTINATION_MASTER, Decimal::new(95, 0)),
+            leg("TWd4WrZ9wn84f5x1hZhL4DHvk738ns5jwb", Decimal::new(5, 0)),
+        ];
+        assert!(validate_legs(&valid).is_ok());
+
+        let short = vec![leg(SPLIT_DESTINATION_MASTER, Decimal::new(99, 0))];
+        assert!(validate_legs(&short).is_err());
+
+        let over = vec![
+            leg(SPLIT_DESTINATION_MASTER, Decimal::new(95, 0)),
+            leg("TWd4WrZ9wn84f5x1hZhL4DHvk738ns5jwb", Decimal::new(10, 0)),
+        ];
+        assert!(validate_legs(&over).is_err());
+    }
+
+    #[test]
+    fn test_validate_legs_rejects_bad_input() {
+        assert!(validate_legs(&[]).is_err());
+
+        let bad_address = vec![
+            leg(SPLIT_DESTINATION_MASTER, Decimal::new(50, 0)),
+            leg("not-an-address", Decimal::new(50, 0)),
+        ];
+        assert!(validate_legs(&bad_address).is_err());
+
+        let duplicate = vec![
+            leg(SPLIT_DESTINATION_MASTER, Decimal::new(50, 0)),
+            leg(SPLIT_DESTINATION_MASTER, Decimal::new(50, 0)),
+        ];
+        assert!(validate_legs(&duplicate).is_err());
+
+        let negative = vec![
+            leg(SPLIT_DESTINATION_MASTER, Decimal::new(150, 0)),
+            leg("TWd4WrZ9wn84f5x1hZhL4DHvk738ns5jwb", Decimal::new(-50, 0)),
+        ];
+        assert!(validate_legs(&negative).is_err());
+    }
+
+    #[test]
+    fn test_compute_leg_amounts_preserves_total() {
+        // 0.01 USDT не делится на 95/5 без остатка: 0.0095 + 0.0005
+        let amounts = compute_leg_amounts(
+            Decimal::new(1, 2),
+            &[Decimal::new(95, 0), Decimal::new(5, 0)],
+        );
+        assert_eq!(amounts.iter().sum::<Decimal>(), Decimal::new(1, 2));
+
+        // Остаток округления уходит последней ноге
+        let amounts = compute_leg_amounts(
+            Decimal::new(100, 0),
+            &[
+                Decimal::new(3333, 2),
+                Decimal::new(3333, 2),
+                Decimal::new(3334, 2),
+            ],
+        );
+        assert_eq!(amounts.iter().sum::<Decimal>(), Decimal::new(100, 0));
+        assert_eq!(amounts[0], Decimal::new(33_330_000, 6));
+        assert_eq!(amounts[1], Decimal::new(33_330_000, 6));
+    }
+}
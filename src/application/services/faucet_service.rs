@@ -0,0 +1,127 @@
+//! # Faucet сервис для sandbox окружений
+//!
+//! Выдает тестовые TRX/USDT на новые кошельки из тестового резерва
+//! (мастер-кошельки Shasta), чтобы интеграторы гоняли end-to-end флоу
+//! без ручного пополнения. На mainnet сервис всегда отключен.
+
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::application::dto::FaucetFundingResponse;
+use crate::config::FaucetConfig;
+use crate::domain::TronValidator;
+use crate::infrastructure::{LocalSigningBackend, SigningBackend, TronGridClient};
+
+use super::{MasterWalletPool, TrxTransferPurpose, TrxTransferService};
+
+/// Сервис выдачи тестовых средств в sandbox
+pub struct FaucetService {
+    tron_client: TronGridClient,
+    trx_transfer_service: TrxTransferService,
+    master_wallet_pool: Arc<MasterWalletPool>,
+    signing_backend: Arc<dyn SigningBackend>,
+    config: FaucetConfig,
+    /// true только для Shasta (определяется по base_url)
+    is_sandbox: bool,
+}
+
+impl FaucetService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(
+        tron_client: TronGridClient,
+        trx_transfer_service: TrxTransferService,
+        master_wallet_pool: Arc<MasterWalletPool>,
+        config: FaucetConfig,
+        is_sandbox: bool,
+    ) -> Self {
+        Self {
+            tron_client,
+            trx_transfer_service,
+            master_wallet_pool,
+            signing_backend: Arc::new(LocalSigningBackend::new()),
+            config,
+            is_sandbox,
+        }
+    }
+
+    /// Заменяет бэкенд подписания (по умолчанию - локальный)
+    pub fn with_signing_backend(mut self, signing_backend: Arc<dyn SigningBackend>) -> Self {
+        self.signing_backend = signing_backend;
+        self
+    }
+
+    /// Выдает тестовые TRX и USDT на указанный адрес из тестового резерва
+    pub async fn fund_wallet(&self, address: &str) -> Result<FaucetFundingResponse> {
+        if !self.config.enabled {
+            return Err(anyhow::anyhow!("Faucet отключен в конфигурации"));
+        }
+
+        // Защита от случайной выдачи реальных средств
+        if !self.is_sandbox {
+            return Err(anyhow::anyhow!(
+                "Faucet доступен только в sandbox окружении (Shasta)"
+            ));
+        }
+
+        TronValidator::validate_address(address)
+            .map_err(|e| anyhow::anyhow!("Валидация адреса: {}", e))?;
+
+        let master_wallet = self.master_wallet_pool.select().await;
+
+        tracing::info!(
+            "🚰 Faucet: выдаем {} TRX и {} USDT на {} с резерва {}",
+            self.config.trx_amount,
+            self.config.usdt_amount,
+            address,
+            master_wallet.address
+        );
+
+        // 1. Тестовые TRX (также активируют новый кошелек)
+        let trx_tx_hash = if self.config.trx_amount > rust_decimal::Decimal::ZERO {
+            Some(
+                self.trx_transfer_service
+                    .send_trx_with_purpose(
+                        &master_wallet.address,
+                        &master_wallet.private_key,
+                        address,
+                        self.config.trx_amount,
+                        TrxTransferPurpose::Faucet,
+                    )
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        // 2. Тестовые USDT
+        let usdt_tx_hash = if self.config.usdt_amount > rust_decimal::Decimal::ZERO {
+            let create_result = self
+                .tron_client
+                .create_trc20_transaction(&master_wallet.address, address, self.config.usdt_amount)
+                .await?;
+
+            let signed_transaction = self
+                .signing_backend
+                .sign_transaction(&create_result, &master_wallet.private_key)
+                .await?;
+
+            Some(
+                self.tron_client
+                    .broadcast_transaction(&signed_transaction)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        tracing::info!("🚰 Faucet: кошелек {} пополнен", address);
+
+        Ok(FaucetFundingResponse {
+            address: address.to_string(),
+            trx_amount: self.config.trx_amount,
+            usdt_amount: self.config.usdt_amount,
+            trx_tx_hash,
+            usdt_tx_hash,
+        })
+    }
+}
@@ -3,13 +3,19 @@
 //! Объединяет статические и динамические комиссии для USDT трансферов
 
 use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::interval;
 use tracing::{info, warn};
 
+use crate::domain::TransactionStatus;
+use crate::infrastructure::database::{schema, DbPool};
 use crate::infrastructure::tron::TronGridClient;
+use crate::utils::bigdecimal_to_decimal;
 
 /// Конфигурация расчета комиссий
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +27,11 @@ pub struct FeeConfig {
     pub min_commission_usdt: Decimal,
     pub max_commission_usdt: Decimal,
 
+    // Объемные тиры: сниженные ставки для владельцев с большим
+    // 30-дневным объемом завершенных трансферов
+    #[serde(default)]
+    pub volume_tiers: Vec<CommissionTier>,
+
     // Динамические комиссии
     pub dynamic_fees_enabled: bool,
     pub dynamic_min_fee: Decimal,
@@ -39,6 +50,8 @@ impl Default for FeeConfig {
             min_commission_usdt: Decimal::new(1, 0),       // 1 USDT
             max_commission_usdt: Decimal::new(10, 0),      // 10 USDT
 
+            volume_tiers: Vec::new(),
+
             // Динамические настройки
             dynamic_fees_enabled: true,
             dynamic_min_fee: Decimal::new(10, 0), // 10 TRX минимум
@@ -49,6 +62,27 @@ impl Default for FeeConfig {
     }
 }
 
+/// Объемный тир комиссии: ставка для владельцев, чей rolling 30-дневный
+/// завершенный объем достиг порога. Применяется тир с наибольшим
+/// достигнутым порогом; без тиров действует базовая ставка
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommissionTier {
+    /// Порог 30-дневного объема в USDT
+    pub min_volume_usdt: Decimal,
+    /// Процентная ставка комиссии для тира
+    pub commission_percentage: Decimal,
+}
+
+impl CommissionTier {
+    /// Выбирает подходящий тир: наибольший порог, не превышающий объем
+    pub fn pick(tiers: &[CommissionTier], volume: Decimal) -> Option<&CommissionTier> {
+        tiers
+            .iter()
+            .filter(|tier| tier.min_volume_usdt <= volume)
+            .max_by(|a, b| a.min_volume_usdt.cmp(&b.min_volume_usdt))
+    }
+}
+
 /// Состояние сети TRON
 #[derive(Debug, Clone, Serialize)]
 pub struct NetworkState {
@@ -67,6 +101,36 @@ pub enum CongestionLevel {
     High,   // Высокая загрузка
 }
 
+/// Кто оплачивает комиссии трансфера
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeePayer {
+    /// Комиссии добавляются поверх order_amount (платит клиент)
+    #[default]
+    Customer,
+    /// Комиссии вычитаются из order_amount (поглощает мерчант)
+    Merchant,
+}
+
+impl FeePayer {
+    /// Каноническое представление в БД
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::Customer => "customer",
+            Self::Merchant => "merchant",
+        }
+    }
+
+    /// Парсит значение из БД
+    pub fn from_db_str(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "customer" => Some(Self::Customer),
+            "merchant" => Some(Self::Merchant),
+            _ => None,
+        }
+    }
+}
+
 /// Результат расчета комиссий
 #[derive(Debug, Clone, Serialize)]
 pub struct FeeCalculationResult {
@@ -85,13 +149,46 @@ pub enum FeeSource {
     Fallback, // Резервный (при ошибке API)
 }
 
+/// Снимок курса TRX/USDT, примененного при расчете комиссий.
+/// Сохраняется на трансфере, чтобы аудит мог воспроизвести расчет
+/// после изменения курса
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeRateSnapshot {
+    /// Курс TRX/USDT на момент расчета
+    pub rate: Decimal,
+    /// Источник курса: имя оракула живого фида или `config`
+    pub source: String,
+    /// Момент снятия снимка
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Результат теневого расчета комиссий (dry run альтернативного конфига)
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowFeeComparison {
+    pub active_total: Decimal,
+    pub shadow_total: Decimal,
+    pub active_commission: Decimal,
+    pub shadow_commission: Decimal,
+    pub delta_total: Decimal,
+}
+
 /// Единый сервис расчета комиссий
 #[derive(Clone)]
 pub struct UnifiedFeeService {
     config: FeeConfig,
+    shadow_config: Option<FeeConfig>,
     tron_client: TronGridClient,
     master_wallet_address: String,
-    network_state: Option<NetworkState>,
+    /// Состояние сети, разделяемое всеми клонами сервиса: TransferService
+    /// клонирует сервис на каждый запрос, и без общего состояния фоновые
+    /// обновления не доходили бы до этих клонов
+    network_state: Arc<RwLock<Option<NetworkState>>>,
+    /// Пул БД для расчета 30-дневного объема владельца (объемные тиры)
+    db: Option<DbPool>,
+    /// Живой курс TRX/USDT (None - действует конфигурированный)
+    price_feed: Option<Arc<crate::infrastructure::PriceFeed>>,
+    /// Тир, примененный последним расчетом (для аудита)
+    last_applied_tier: Option<CommissionTier>,
 }
 
 impl UnifiedFeeService {
@@ -103,12 +200,74 @@ impl UnifiedFeeService {
     ) -> Self {
         Self {
             config,
+            shadow_config: None,
             tron_client,
             master_wallet_address,
-            network_state: None,
+            network_state: Arc::new(RwLock::new(None)),
+            db: None,
+            price_feed: None,
+            last_applied_tier: None,
+        }
+    }
+
+    /// Подключает БД для расчета объемных тиров комиссий
+    pub fn with_db(mut self, db: DbPool) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Подключает живой курс TRX/USDT; при его протухании конвертация
+    /// откатывается на конфигурированный trx_to_usdt_rate
+    pub fn with_price_feed(
+        mut self,
+        price_feed: Option<Arc<crate::infrastructure::PriceFeed>>,
+    ) -> Self {
+        self.price_feed = price_feed;
+        self
+    }
+
+    /// Актуальный курс TRX/USDT: живой из фида, пока он свеж,
+    /// иначе - конфигурированное значение
+    pub fn trx_to_usdt_rate(&self) -> Decimal {
+        self.price_feed
+            .as_ref()
+            .and_then(|feed| feed.current_rate())
+            .unwrap_or(self.config.trx_to_usdt_rate)
+    }
+
+    /// Снимок курса, которым `trx_to_usdt_rate` конвертирует газ
+    /// прямо сейчас (курс, источник и момент снятия)
+    pub fn rate_snapshot(&self) -> FeeRateSnapshot {
+        let live_rate = self
+            .price_feed
+            .as_ref()
+            .and_then(|feed| feed.current_rate().map(|rate| (rate, feed.source_name())));
+
+        let (rate, source) = match live_rate {
+            Some((rate, source)) => (rate, source.to_string()),
+            None => (self.config.trx_to_usdt_rate, "config".to_string()),
+        };
+
+        FeeRateSnapshot {
+            rate,
+            source,
+            captured_at: chrono::Utc::now(),
         }
     }
 
+    /// Тир, примененный последним вызовом calculate_total_amount.
+    /// None - действовала базовая ставка
+    pub fn applied_commission_tier(&self) -> Option<&CommissionTier> {
+        self.last_applied_tier.as_ref()
+    }
+
+    /// Включает теневой режим: альтернативный конфиг комиссий считается
+    /// для каждого реального трансфера, но не влияет на списания
+    pub fn with_shadow_config(mut self, shadow_config: Option<FeeConfig>) -> Self {
+        self.shadow_config = shadow_config;
+        self
+    }
+
     /// Создает из старой конфигурации (для обратной совместимости)
     pub fn from_legacy_config(
         tron_client: TronGridClient,
@@ -132,7 +291,7 @@ impl UnifiedFeeService {
     }
 
     /// Запускает фоновое обновление состояния сети (если включены динамические комиссии)
-    pub async fn start_background_updates(&mut self) -> Result<()> {
+    pub async fn start_background_updates(&self) -> Result<()> {
         if !self.config.dynamic_fees_enabled {
             info!("💰 Динамические комиссии отключены, используется статический расчет");
             return Ok(());
@@ -159,7 +318,7 @@ impl UnifiedFeeService {
 
     /// Основной метод расчета комиссии за газ в USDT
     pub async fn calculate_gas_fee(
-        &mut self,
+        &self,
         from: &str,
         _to: &str,
         amount: Decimal,
@@ -185,43 +344,56 @@ impl UnifiedFeeService {
     }
 
     /// Динамический расчет комиссии за газ
-    async fn get_dynamic_gas_fee(&mut self) -> Result<Decimal> {
-        // Обновляем состояние сети если нужно
-        if self.network_state.is_none() {
-            self.update_network_state().await?;
-        }
-
-        // Проверяем свежесть данных (не старше 10 минут)
-        if let Some(state) = &self.network_state {
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-            if now - state.timestamp > 600 {
-                // 10 минут
-                self.update_network_state().await?;
-            }
-        }
-
+    async fn get_dynamic_gas_fee(&self) -> Result<Decimal> {
         let fee_trx = self
-            .network_state
-            .as_ref()
-            .map(|s| s.recommended_fee_trx)
+            .fresh_network_state()
+            .await?
+            .map(|state| state.recommended_fee_trx)
             .unwrap_or(self.config.base_trx_per_transaction);
 
         // Конвертируем TRX в USDT
-        Ok(fee_trx * self.config.trx_to_usdt_rate)
+        Ok(fee_trx * self.trx_to_usdt_rate())
+    }
+
+    /// Текущий уровень загрузки сети (обновляет состояние при необходимости).
+    /// Используется планировщиком для откладывания несрочной работы
+    pub async fn current_congestion_level(&self) -> Result<CongestionLevel> {
+        Ok(self
+            .fresh_network_state()
+            .await?
+            .map(|state| state.congestion_level)
+            .unwrap_or(CongestionLevel::Medium))
+    }
+
+    /// Снимок состояния сети, обновленный при отсутствии или протухании
+    /// (старше 10 минут). Лок не держится через await - читается снимок,
+    /// при необходимости состояние пересчитывается и перечитывается
+    async fn fresh_network_state(&self) -> Result<Option<NetworkState>> {
+        let snapshot = self.network_state.read().unwrap().clone();
+
+        let stale = match &snapshot {
+            None => true,
+            Some(state) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                now - state.timestamp > 600
+            }
+        };
+
+        if stale {
+            self.update_network_state().await?;
+            return Ok(self.network_state.read().unwrap().clone());
+        }
+
+        Ok(snapshot)
     }
 
-    /// Статический расчет комиссии за газ
+    /// Статический расчет комиссии за газ: реальная стоимость трансфера
+    /// из данных сети, при ее недоступности - конфигурированная база
     async fn calculate_static_gas_fee(&self, from: &str, amount: Decimal) -> Result<Decimal> {
-        // Пытаемся получить реальную оценку энергии
-        match self
-            .tron_client
-            .estimate_energy(from, &self.master_wallet_address, amount)
-            .await
-        {
-            Ok(energy_cost) => {
-                tracing::info!("💰 Реальная оценка энергии: {} TRX", energy_cost);
-                let gas_cost_usdt = Decimal::from(energy_cost) * self.config.trx_to_usdt_rate;
-                Ok(gas_cost_usdt)
+        match self.estimate_transfer_cost_trx(from, amount).await {
+            Ok(cost_trx) => {
+                tracing::info!("💰 Реальная стоимость трансфера: {} TRX", cost_trx);
+                Ok(cost_trx * self.trx_to_usdt_rate())
             }
             Err(e) => {
                 tracing::warn!(
@@ -229,15 +401,56 @@ impl UnifiedFeeService {
                     e
                 );
                 // Используем конфигурированное базовое значение
-                let base_cost = self.config.base_trx_per_transaction * self.config.trx_to_usdt_rate;
+                let base_cost = self.config.base_trx_per_transaction * self.trx_to_usdt_rate();
                 Ok(base_cost)
             }
         }
     }
 
-    /// Расчет процентной комиссии
+    /// Реальная стоимость TRC20 трансфера в TRX: нода оценивает энергию
+    /// вызова, ресурсы отправителя покрывают часть, недостающее
+    /// оплачивается TRX по актуальным ценам сети
+    async fn estimate_transfer_cost_trx(&self, from: &str, amount: Decimal) -> Result<Decimal> {
+        let energy_needed = self
+            .tron_client
+            .estimate_energy(from, &self.master_wallet_address, amount)
+            .await?;
+        let params = self.tron_client.get_chain_parameters().await?;
+        let resources = self.tron_client.get_account_resources(from).await?;
+
+        let get_u64 = |path: &[&str]| -> u64 {
+            let mut value = &resources;
+            for key in path {
+                value = value.get(key).unwrap_or(&serde_json::Value::Null);
+            }
+            value.as_u64().unwrap_or(0)
+        };
+
+        let energy_available =
+            get_u64(&["energy", "limit"]).saturating_sub(get_u64(&["energy", "used"]));
+        let bandwidth_available = get_u64(&["bandwidth", "free_limit"])
+            .saturating_sub(get_u64(&["bandwidth", "free_used"]))
+            + get_u64(&["bandwidth", "limit"]).saturating_sub(get_u64(&["bandwidth", "used"]));
+
+        let cost_sun = transfer_cost_sun(
+            energy_needed,
+            energy_available,
+            params.energy_price_sun,
+            bandwidth_available,
+            params.bandwidth_price_sun,
+        );
+
+        Ok(Decimal::from(cost_sun) / Decimal::new(1_000_000, 0))
+    }
+
+    /// Расчет процентной комиссии по базовой ставке
     pub fn calculate_percentage_commission(&self, amount: Decimal) -> Decimal {
-        let commission = amount * self.config.commission_percentage / Decimal::new(100, 0);
+        self.commission_with_rate(amount, self.config.commission_percentage)
+    }
+
+    /// Процентная комиссия по указанной ставке с ограничениями min/max
+    fn commission_with_rate(&self, amount: Decimal, percentage: Decimal) -> Decimal {
+        let commission = amount * percentage / Decimal::new(100, 0);
 
         // Применяем ограничения
         commission
@@ -245,11 +458,80 @@ impl UnifiedFeeService {
             .min(self.config.max_commission_usdt)
     }
 
-    /// Полный расчет всех комиссий и итоговой суммы
+    /// Процентная комиссия с учетом объемного тира владельца.
+    /// Недоступность объема не блокирует расчет - применяется базовая ставка
+    async fn calculate_tiered_commission(
+        &self,
+        amount: Decimal,
+        owner_id: Option<&str>,
+    ) -> (Decimal, Option<CommissionTier>) {
+        let base = self.calculate_percentage_commission(amount);
+
+        if self.config.volume_tiers.is_empty() {
+            return (base, None);
+        }
+
+        let Some(owner_id) = owner_id else {
+            return (base, None);
+        };
+
+        let volume = match self.rolling_settled_volume(owner_id).await {
+            Ok(volume) => volume,
+            Err(e) => {
+                warn!(
+                    "⚠️  30-дневный объем владельца {} недоступен, базовая ставка: {}",
+                    owner_id, e
+                );
+                return (base, None);
+            }
+        };
+
+        match CommissionTier::pick(&self.config.volume_tiers, volume) {
+            Some(tier) => {
+                info!(
+                    "💰 Объемный тир для {}: объем {} USDT >= {} - ставка {}%",
+                    owner_id, volume, tier.min_volume_usdt, tier.commission_percentage
+                );
+                (
+                    self.commission_with_rate(amount, tier.commission_percentage),
+                    Some(tier.clone()),
+                )
+            }
+            None => (base, None),
+        }
+    }
+
+    /// Rolling 30-дневный объем завершенных трансферов владельца в USDT
+    async fn rolling_settled_volume(&self, owner_id: &str) -> Result<Decimal> {
+        let Some(db) = &self.db else {
+            return Ok(Decimal::ZERO);
+        };
+
+        let mut conn = db.get().await?;
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(30);
+
+        let total: Option<bigdecimal::BigDecimal> = schema::outgoing_transfers::table
+            .inner_join(schema::wallets::table)
+            .filter(schema::wallets::owner_id.eq(owner_id))
+            .filter(schema::outgoing_transfers::status.eq(TransactionStatus::Completed.as_db_str()))
+            .filter(schema::outgoing_transfers::created_at.ge(cutoff))
+            .select(diesel::dsl::sum(schema::outgoing_transfers::amount))
+            .first(&mut conn)
+            .await?;
+
+        Ok(total.map(bigdecimal_to_decimal).unwrap_or(Decimal::ZERO))
+    }
+
+    /// Полный расчет всех комиссий и итоговой суммы.
+    /// При `FeePayer::Customer` комиссии добавляются поверх order_amount,
+    /// при `FeePayer::Merchant` - поглощаются мерчантом (списывается
+    /// ровно order_amount, мастер-кошелек получает меньше)
     pub async fn calculate_total_amount(
         &mut self,
         order_amount: Decimal,
         from_wallet_address: &str,
+        fee_payer: FeePayer,
+        owner_id: Option<&str>,
     ) -> Result<(Decimal, Decimal, Decimal, Decimal)> {
         // 1. Газовая комиссия (клонируем мастер адрес)
         let master_wallet_address = self.master_wallet_address.clone();
@@ -257,14 +539,35 @@ impl UnifiedFeeService {
             .calculate_gas_fee(from_wallet_address, &master_wallet_address, order_amount)
             .await?;
 
-        // 2. Процентная комиссия
-        let percentage_commission = self.calculate_percentage_commission(order_amount);
+        // 2. Процентная комиссия (с учетом объемного тира владельца)
+        let (percentage_commission, applied_tier) = self
+            .calculate_tiered_commission(order_amount, owner_id)
+            .await;
+        self.last_applied_tier = applied_tier;
 
         // 3. Итоговая комиссия (пока равна процентной, можно добавить другие)
         let final_commission = percentage_commission;
 
         // 4. Общая сумма для списания
-        let total_amount = order_amount + gas_cost_usdt + final_commission;
+        let total_amount = match fee_payer {
+            FeePayer::Customer => order_amount + gas_cost_usdt + final_commission,
+            FeePayer::Merchant => {
+                // Комиссии вычитаются из суммы заказа - заказ должен их покрывать
+                if gas_cost_usdt + final_commission >= order_amount {
+                    return Err(anyhow::anyhow!(
+                        "Комиссии ({} USDT) превышают сумму заказа {} USDT",
+                        gas_cost_usdt + final_commission,
+                        order_amount
+                    ));
+                }
+                order_amount
+            }
+        };
+
+        // 5. Теневой расчет (dry run альтернативного конфига, не влияет на списания)
+        if self.shadow_config.is_some() {
+            self.evaluate_shadow_fees(order_amount, final_commission, total_amount);
+        }
 
         Ok((
             gas_cost_usdt,
@@ -281,10 +584,11 @@ impl UnifiedFeeService {
         from_wallet_address: &str,
     ) -> Result<FeeCalculationResult> {
         let (gas_cost_usdt, percentage_commission, final_commission, total_amount) = self
-            .calculate_total_amount(order_amount, from_wallet_address)
+            .calculate_total_amount(order_amount, from_wallet_address, FeePayer::default(), None)
             .await?;
 
-        let fee_source = if self.config.dynamic_fees_enabled && self.network_state.is_some() {
+        let has_network_state = self.network_state.read().unwrap().is_some();
+        let fee_source = if self.config.dynamic_fees_enabled && has_network_state {
             FeeSource::Dynamic
         } else {
             FeeSource::Static
@@ -299,8 +603,57 @@ impl UnifiedFeeService {
         })
     }
 
-    /// Обновляет состояние сети
-    async fn update_network_state(&mut self) -> Result<()> {
+    /// Считает комиссии по теневому конфигу и логирует расхождения с активным.
+    /// Газ оценивается по тем же данным сети (без повторных запросов к TronGrid),
+    /// чтобы dry run не удваивал нагрузку на API
+    fn evaluate_shadow_fees(
+        &self,
+        order_amount: Decimal,
+        active_commission: Decimal,
+        active_total: Decimal,
+    ) -> Option<ShadowFeeComparison> {
+        let shadow = self.shadow_config.as_ref()?;
+
+        // Газ: recommended_fee сети если есть, иначе базовое значение теневого конфига
+        let shadow_fee_trx = self
+            .network_state
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|s| s.recommended_fee_trx)
+            .unwrap_or(shadow.base_trx_per_transaction);
+        let shadow_gas_usdt = shadow_fee_trx * shadow.trx_to_usdt_rate;
+
+        // Процентная комиссия с ограничениями теневого конфига
+        let shadow_commission = (order_amount * shadow.commission_percentage
+            / Decimal::new(100, 0))
+        .max(shadow.min_commission_usdt)
+        .min(shadow.max_commission_usdt);
+
+        let shadow_total = order_amount + shadow_gas_usdt + shadow_commission;
+        let comparison = ShadowFeeComparison {
+            active_total,
+            shadow_total,
+            active_commission,
+            shadow_commission,
+            delta_total: shadow_total - active_total,
+        };
+
+        info!(
+            active_total = %comparison.active_total,
+            shadow_total = %comparison.shadow_total,
+            delta_total = %comparison.delta_total,
+            "📊 Теневой расчет комиссий: активная {} USDT, теневая {} USDT (дельта {})",
+            comparison.active_total,
+            comparison.shadow_total,
+            comparison.delta_total
+        );
+
+        Some(comparison)
+    }
+
+    /// Обновляет разделяемое состояние сети (видно всем клонам сервиса)
+    async fn update_network_state(&self) -> Result<()> {
         info!("📊 Обновление состояния TRON сети...");
 
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
@@ -325,24 +678,22 @@ impl UnifiedFeeService {
             new_state.congestion_level, new_state.recommended_fee_trx
         );
 
-        self.network_state = Some(new_state);
+        *self.network_state.write().unwrap() = Some(new_state);
         Ok(())
     }
 
-    /// Получает метрики сети (упрощенная реализация)
+    /// Получает метрики сети из /wallet/getchainparameters.
+    /// Уровень загрузки выводится из цены энергии: у базовых 420 sun -
+    /// Low, умеренный рост - Medium, выше 600 sun - High
     async fn fetch_network_metrics(&self) -> Result<(Decimal, Decimal, CongestionLevel)> {
-        // Симуляция: генерируем данные на основе времени
-        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-        let cycle = (now % 300) as f64 / 300.0; // 5-минутный цикл
+        let params = self.tron_client.get_chain_parameters().await?;
 
-        let energy_price = Decimal::new(420, 0) + // базовая цена 420 sun
-            Decimal::new((cycle * 200.0) as i64, 0); // +0-200 sun вариация
+        let energy_price = Decimal::from(params.energy_price_sun);
+        let bandwidth_price = Decimal::from(params.bandwidth_price_sun);
 
-        let bandwidth_price = Decimal::new(1000, 0); // 1000 sun за bandwidth
-
-        let congestion_level = match cycle {
-            x if x < 0.3 => CongestionLevel::Low,
-            x if x < 0.7 => CongestionLevel::Medium,
+        let congestion_level = match params.energy_price_sun {
+            price if price <= 420 => CongestionLevel::Low,
+            price if price <= 600 => CongestionLevel::Medium,
             _ => CongestionLevel::High,
         };
 
@@ -384,21 +735,48 @@ impl UnifiedFeeService {
         &self.config
     }
 
-    /// Получает текущее состояние сети
-    pub fn get_network_state(&self) -> Option<&NetworkState> {
-        self.network_state.as_ref()
+    /// Получает снимок текущего состояния сети
+    pub fn get_network_state(&self) -> Option<NetworkState> {
+        self.network_state.read().unwrap().clone()
     }
 
     /// Получает статистику по комиссиям
     pub fn get_fee_stats(&self) -> FeeStats {
+        let network_state = self.get_network_state();
         FeeStats {
             config: self.config.clone(),
-            network_state: self.network_state.clone(),
-            dynamic_fees_active: self.config.dynamic_fees_enabled && self.network_state.is_some(),
+            dynamic_fees_active: self.config.dynamic_fees_enabled && network_state.is_some(),
+            network_state,
         }
     }
 }
 
+/// Примерный размер TRC20 трансфера в байтах (bandwidth points)
+const TRC20_TRANSFER_BANDWIDTH: u64 = 345;
+
+/// Стоимость трансфера в sun: оплачивается только дефицит ресурсов -
+/// энергия и bandwidth сверх доступных отправителю сжигаются в TRX
+fn transfer_cost_sun(
+    energy_needed: u64,
+    energy_available: u64,
+    energy_price_sun: i64,
+    bandwidth_available: u64,
+    bandwidth_price_sun: i64,
+) -> u64 {
+    let energy_deficit = energy_needed.saturating_sub(energy_available);
+
+    // Bandwidth не тратится частично: при нехватке весь размер
+    // транзакции оплачивается TRX
+    let bandwidth_deficit = if bandwidth_available >= TRC20_TRANSFER_BANDWIDTH {
+        0
+    } else {
+        TRC20_TRANSFER_BANDWIDTH
+    };
+
+    energy_deficit * energy_price_sun.max(0) as u64
+        + bandwidth_deficit * bandwidth_price_sun.max(0) as u64
+}
+
 /// Статистика комиссий
 #[derive(Debug, Clone, Serialize)]
 pub struct FeeStats {
@@ -406,3 +784,51 @@ pub struct FeeStats {
     pub network_state: Option<NetworkState>,
     pub dynamic_fees_active: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commission_tier_pick() {
+        let tiers = vec![
+            CommissionTier {
+                min_volume_usdt: Decimal::new(10_000, 0),
+                commission_percentage: Decimal::new(4, 1), // 0.4%
+            },
+            CommissionTier {
+                min_volume_usdt: Decimal::new(100_000, 0),
+                commission_percentage: Decimal::new(25, 2), // 0.25%
+            },
+        ];
+
+        // Объем ниже всех порогов - тир не применяется
+        assert!(CommissionTier::pick(&tiers, Decimal::new(5_000, 0)).is_none());
+
+        // Первый порог достигнут
+        let tier = CommissionTier::pick(&tiers, Decimal::new(50_000, 0)).unwrap();
+        assert_eq!(tier.commission_percentage, Decimal::new(4, 1));
+
+        // Применяется наибольший достигнутый порог
+        let tier = CommissionTier::pick(&tiers, Decimal::new(250_000, 0)).unwrap();
+        assert_eq!(tier.commission_percentage, Decimal::new(25, 2));
+    }
+
+    #[test]
+    fn test_transfer_cost_covers_only_deficit() {
+        // Ресурсов отправителя хватает - трансфер бесплатный
+        assert_eq!(transfer_cost_sun(31_895, 65_000, 420, 1_500, 1_000), 0);
+
+        // Ресурсов нет - вся энергия и bandwidth оплачиваются TRX
+        assert_eq!(
+            transfer_cost_sun(31_895, 0, 420, 0, 1_000),
+            31_895 * 420 + TRC20_TRANSFER_BANDWIDTH * 1_000
+        );
+
+        // Частичное покрытие энергии: оплачивается только дефицит
+        assert_eq!(
+            transfer_cost_sun(31_895, 30_000, 420, 1_500, 1_000),
+            1_895 * 420
+        );
+    }
+}
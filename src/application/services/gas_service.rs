@@ -4,10 +4,11 @@
 
 use anyhow::Result;
 use rust_decimal::Decimal;
+use std::sync::Arc;
 
 use crate::infrastructure::tron::TronGridClient;
 
-use super::TrxTransferService;
+use super::{MasterWalletPool, TrxTransferPurpose, TrxTransferService};
 
 /// Сервис спонсорства газа для пользовательских кошельков
 /// Автоматически отправляет TRX с master wallet на пользовательские кошельки при необходимости
@@ -16,8 +17,7 @@ pub struct SponsorGasService {
     trx_transfer_service: TrxTransferService,
     min_trx_amount: Decimal,
     sponsor_enabled: bool,
-    master_wallet_address: String,
-    master_wallet_private_key: String,
+    master_wallet_pool: Arc<MasterWalletPool>,
 }
 
 impl SponsorGasService {
@@ -27,16 +27,14 @@ impl SponsorGasService {
         trx_transfer_service: TrxTransferService,
         min_trx_amount: Decimal,
         sponsor_enabled: bool,
-        master_wallet_address: String,
-        master_wallet_private_key: String,
+        master_wallet_pool: Arc<MasterWalletPool>,
     ) -> Self {
         Self {
             tron_client,
             trx_transfer_service,
             min_trx_amount,
             sponsor_enabled,
-            master_wallet_address,
-            master_wallet_private_key,
+            master_wallet_pool,
         }
     }
 
@@ -66,19 +64,23 @@ impl SponsorGasService {
         );
 
         // ВСЕГДА спонсируем газ с master wallet независимо от текущего баланса
+        let master_wallet = self.master_wallet_pool.select().await;
+
         tracing::info!(
-            "Спонсируем {} TRX для кошелька {} (политика: всегда спонсировать с master wallet)",
+            "Спонсируем {} TRX для кошелька {} с мастер-кошелька {} (политика: всегда спонсировать)",
             self.min_trx_amount,
-            wallet_address
+            wallet_address,
+            master_wallet.address
         );
 
         match self
             .trx_transfer_service
-            .send_trx(
-                &self.master_wallet_address,
-                &self.master_wallet_private_key,
+            .send_trx_with_purpose(
+                &master_wallet.address,
+                &master_wallet.private_key,
                 wallet_address,
                 self.min_trx_amount,
+                TrxTransferPurpose::GasSponsorship,
             )
             .await
         {
@@ -0,0 +1,293 @@
+//! # Сервис массовой загрузки трансферов (NDJSON ingestion)
+//!
+//! Мерчанты с ночными расчетами шлют тысячи трансферов одним NDJSON
+//! стримом. Строки валидируются по мере поступления: принятые становятся
+//! queued-строками job'а, отвергнутые сразу получают причину. Фоновый
+//! воркер конвертирует queued-строки в трансферы через обычный
+//! `TransferService::create_transfer` - HTTP запрос не ждет создания
+//! каждого трансфера, прогресс виден по status endpoint'у
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::application::dto::CreateTransferRequest;
+use crate::domain::TronValidator;
+use crate::infrastructure::database::models::{
+    IngestionJobModel, IngestionRowModel, NewIngestionRow,
+};
+use crate::infrastructure::database::{schema, DbPool};
+
+use super::TransferService;
+
+/// Максимум строк в одном job'е
+pub const INGESTION_MAX_ROWS: i64 = 100_000;
+/// Максимальная длина одной NDJSON строки в байтах
+pub const INGESTION_MAX_LINE_BYTES: usize = 16 * 1024;
+
+/// Прогресс job'а: счетчики загрузки + судьба строк у воркера
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestionJobStatus {
+    pub job: IngestionJobModel,
+    /// Строк в очереди воркера
+    pub rows_queued: i64,
+    /// Строк, ставших трансферами
+    pub rows_created: i64,
+    /// Строк, упавших при создании трансфера
+    pub rows_failed: i64,
+}
+
+/// Сервис массовой загрузки трансферов
+pub struct TransferIngestionService {
+    db: DbPool,
+    transfer_service: Arc<TransferService>,
+}
+
+impl TransferIngestionService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool, transfer_service: Arc<TransferService>) -> Self {
+        Self {
+            db,
+            transfer_service,
+        }
+    }
+
+    /// Синтаксическая валидация одной NDJSON строки.
+    /// Доменные проверки (баланс, риск) выполнит create_transfer у воркера
+    pub fn validate_line(line: &str) -> std::result::Result<CreateTransferRequest, String> {
+        if line.len() > INGESTION_MAX_LINE_BYTES {
+            return Err(format!("Строка длиннее {} байт", INGESTION_MAX_LINE_BYTES));
+        }
+
+        let request: CreateTransferRequest =
+            serde_json::from_str(line).map_err(|e| format!("Невалидный JSON: {}", e))?;
+
+        if request.from_wallet_id <= 0 {
+            return Err("from_wallet_id должен быть положительным".to_string());
+        }
+
+        TronValidator::validate_amount(request.order_amount).map_err(|e| e.to_string())?;
+
+        if request.preview_only.unwrap_or(false) {
+            return Err("preview_only не поддерживается при массовой загрузке".to_string());
+        }
+
+        Ok(request)
+    }
+
+    /// Создает пустой job и возвращает его id
+    pub async fn create_job(&self) -> Result<i64> {
+        let mut conn = self.db.get().await?;
+
+        let job_id = diesel::insert_into(schema::ingestion_jobs::table)
+            .default_values()
+            .returning(schema::ingestion_jobs::id)
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(job_id)
+    }
+
+    /// Дописывает пачку строк к job'у (вызывается по мере чтения стрима)
+    pub async fn append_rows(&self, rows: Vec<NewIngestionRow>) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.db.get().await?;
+        diesel::insert_into(schema::ingestion_rows::table)
+            .values(&rows)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Фиксирует итог загрузки. Job без принятых строк
+    /// завершается сразу - воркеру нечего делать
+    pub async fn finalize_job(
+        &self,
+        job_id: i64,
+        rows_total: i64,
+        rows_accepted: i64,
+        rows_rejected: i64,
+    ) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        diesel::update(schema::ingestion_jobs::table.find(job_id))
+            .set((
+                schema::ingestion_jobs::rows_total.eq(rows_total),
+                schema::ingestion_jobs::rows_accepted.eq(rows_accepted),
+                schema::ingestion_jobs::rows_rejected.eq(rows_rejected),
+                schema::ingestion_jobs::status.eq(if rows_accepted == 0 {
+                    "completed"
+                } else {
+                    "queued"
+                }),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        info!(
+            "📥 Ingestion job {}: принято {}, отвергнуто {} из {} строк",
+            job_id, rows_accepted, rows_rejected, rows_total
+        );
+
+        Ok(())
+    }
+
+    /// Обрабатывает очередную пачку queued-строк: каждая становится
+    /// трансфером или помечается failed. Возвращает число обработанных
+    pub async fn process_queued_rows(&self, limit: i64) -> Result<usize> {
+        let rows = {
+            let mut conn = self.db.get().await?;
+            schema::ingestion_rows::table
+                .filter(schema::ingestion_rows::status.eq("queued"))
+                .order(schema::ingestion_rows::id.asc())
+                .limit(limit.clamp(1, 1000))
+                .select(IngestionRowModel::as_select())
+                .load(&mut conn)
+                .await?
+        };
+
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let mut touched_jobs = Vec::new();
+        let processed = rows.len();
+
+        for row in rows {
+            if !touched_jobs.contains(&row.job_id) {
+                touched_jobs.push(row.job_id);
+            }
+
+            // Строка прошла синтаксическую валидацию при загрузке,
+            // но payload все равно перечитывается - формат мог устареть
+            let outcome = match Self::validate_line(&row.payload) {
+                Ok(request) => self
+                    .transfer_service
+                    .create_transfer(request)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e),
+            };
+
+            let (status, transfer_id, error_message) = match outcome {
+                Ok(transfer) => ("created", Some(transfer.id), None),
+                Err(e) => {
+                    warn!(
+                        "⚠️  Ingestion: строка {} job'а {} не стала трансфером: {}",
+                        row.row_number, row.job_id, e
+                    );
+                    ("failed", None, Some(e))
+                }
+            };
+
+            let mut conn = self.db.get().await?;
+            diesel::update(schema::ingestion_rows::table.find(row.id))
+                .set((
+                    schema::ingestion_rows::status.eq(status),
+                    schema::ingestion_rows::transfer_id.eq(transfer_id),
+                    schema::ingestion_rows::error_message.eq(error_message),
+                    schema::ingestion_rows::processed_at.eq(chrono::Utc::now()),
+                ))
+                .execute(&mut conn)
+                .await?;
+        }
+
+        self.complete_drained_jobs(&touched_jobs).await?;
+
+        Ok(processed)
+    }
+
+    /// Прогресс job'а со счетчиками по статусам строк
+    pub async fn job_status(&self, job_id: i64) -> Result<Option<IngestionJobStatus>> {
+        let mut conn = self.db.get().await?;
+
+        let job = schema::ingestion_jobs::table
+            .find(job_id)
+            .select(IngestionJobModel::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        let Some(job) = job else {
+            return Ok(None);
+        };
+
+        let counts: Vec<(String, i64)> = schema::ingestion_rows::table
+            .filter(schema::ingestion_rows::job_id.eq(job_id))
+            .group_by(schema::ingestion_rows::status)
+            .select((schema::ingestion_rows::status, diesel::dsl::count_star()))
+            .load(&mut conn)
+            .await?;
+
+        let count_for = |status: &str| {
+            counts
+                .iter()
+                .find(|(s, _)| s == status)
+                .map(|(_, c)| *c)
+                .unwrap_or(0)
+        };
+
+        Ok(Some(IngestionJobStatus {
+            rows_queued: count_for("queued"),
+            rows_created: count_for("created"),
+            rows_failed: count_for("failed"),
+            job,
+        }))
+    }
+
+    /// Построчные исходы job'а от cursor'а (id последней строки)
+    pub async fn list_rows(
+        &self,
+        job_id: i64,
+        cursor: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<IngestionRowModel>> {
+        let mut conn = self.db.get().await?;
+
+        let mut query = schema::ingestion_rows::table
+            .filter(schema::ingestion_rows::job_id.eq(job_id))
+            .order(schema::ingestion_rows::row_number.asc())
+            .limit(limit.clamp(1, 1000))
+            .into_boxed();
+
+        if let Some(cursor) = cursor {
+            query = query.filter(schema::ingestion_rows::id.gt(cursor));
+        }
+
+        Ok(query
+            .select(IngestionRowModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Переводит job'ы без queued-строк в completed
+    async fn complete_drained_jobs(&self, job_ids: &[i64]) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        for &job_id in job_ids {
+            let queued_left: i64 = schema::ingestion_rows::table
+                .filter(schema::ingestion_rows::job_id.eq(job_id))
+                .filter(schema::ingestion_rows::status.eq("queued"))
+                .count()
+                .get_result(&mut conn)
+                .await?;
+
+            if queued_left == 0 {
+                diesel::update(schema::ingestion_jobs::table.find(job_id))
+                    .set(schema::ingestion_jobs::status.eq("completed"))
+                    .execute(&mut conn)
+                    .await?;
+                info!("📥 Ingestion job {} обработан полностью", job_id);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+//! # Сервис инвойсов (payment requests)
+//!
+//! Интегратор создает инвойс "ожидаю 125 USDT на кошелек X за 30 минут,
+//! reference order-42"; подтвержденные депозиты накапливаются в
+//! paid_amount через хук пост-обработки. Инвойс поддерживает частичную
+//! оплату и переплату, истечение обрабатывает задача планировщика,
+//! о закрытии и истечении уходят webhook'и invoice_paid / invoice_expired
+
+use anyhow::Result;
+use chrono::Duration;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domain::TransactionStatus;
+use crate::infrastructure::database::models::{InvoiceModel, NewInvoice};
+use crate::infrastructure::database::{schema, DbPool};
+use crate::utils::conversions::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+use super::deposit_hooks::{DepositHook, DepositHookContext};
+use super::webhook_service::WebhookEventType;
+use super::WebhookService;
+
+/// Срок жизни инвойса по умолчанию
+pub const INVOICE_DEFAULT_TTL_MINUTES: i64 = 30;
+
+/// Максимальный срок жизни инвойса (7 дней)
+pub const INVOICE_MAX_TTL_MINUTES: i64 = 7 * 24 * 60;
+
+/// Статус инвойса
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceStatus {
+    /// Создан, оплаты еще нет
+    Open,
+    /// Получена часть ожидаемой суммы
+    Partial,
+    /// Оплачен полностью
+    Paid,
+    /// Получено больше ожидаемого
+    Overpaid,
+    /// Срок истек до полной оплаты
+    Expired,
+}
+
+impl InvoiceStatus {
+    /// Каноническое представление в БД
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::Open => "OPEN",
+            Self::Partial => "PARTIAL",
+            Self::Paid => "PAID",
+            Self::Overpaid => "OVERPAID",
+            Self::Expired => "EXPIRED",
+        }
+    }
+
+    /// Разбор из представления БД
+    pub fn from_db_str(value: &str) -> Option<Self> {
+        match value {
+            "OPEN" => Some(Self::Open),
+            "PARTIAL" => Some(Self::Partial),
+            "PAID" => Some(Self::Paid),
+            "OVERPAID" => Some(Self::Overpaid),
+            "EXPIRED" => Some(Self::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// Параметры нового инвойса
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct CreateInvoice {
+    pub wallet_id: i64,
+    /// Ссылка интегратора (номер заказа)
+    pub reference: String,
+    /// Ожидаемая сумма в USDT
+    pub expected_amount: Decimal,
+    /// Срок жизни в минутах (по умолчанию 30, максимум 7 дней)
+    pub ttl_minutes: Option<i64>,
+}
+
+/// Сервис инвойсов
+pub struct InvoiceService {
+    db: DbPool,
+    webhook_service: Option<Arc<WebhookService>>,
+    /// Часы для дедлайнов истечения (в тестах подменяются)
+    clock: Arc<dyn crate::utils::Clock>,
+}
+
+impl InvoiceService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool) -> Self {
+        Self {
+            db,
+            webhook_service: None,
+            clock: Arc::new(crate::utils::SystemClock),
+        }
+    }
+
+    /// Подключает доставку webhook'ов invoice_paid / invoice_expired
+    pub fn with_webhooks(mut self, webhook_service: Arc<WebhookService>) -> Self {
+        self.webhook_service = Some(webhook_service);
+        self
+    }
+
+    /// Подменяет часы (контроль времени в тестах)
+    pub fn with_clock(mut self, clock: Arc<dyn crate::utils::Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Создает инвойс
+    pub async fn create_invoice(&self, request: CreateInvoice) -> Result<InvoiceModel> {
+        if request.expected_amount <= Decimal::ZERO {
+            anyhow::bail!("Ожидаемая сумма инвойса должна быть положительной");
+        }
+
+        let reference = request.reference.trim();
+        if reference.is_empty() || reference.len() > 128 {
+            anyhow::bail!("Reference инвойса должен быть непустым и не длиннее 128 символов");
+        }
+
+        let ttl_minutes = request
+            .ttl_minutes
+            .unwrap_or(INVOICE_DEFAULT_TTL_MINUTES)
+            .clamp(1, INVOICE_MAX_TTL_MINUTES);
+
+        let mut conn = self.db.get().await?;
+
+        // Инвойс на несуществующий кошелек - ошибка клиента
+        let wallet_exists = schema::wallets::table
+            .find(request.wallet_id)
+            .select(schema::wallets::id)
+            .first::<i64>(&mut conn)
+            .await
+            .optional()?;
+        if wallet_exists.is_none() {
+            anyhow::bail!("Кошелек {} не найден", request.wallet_id);
+        }
+
+        let invoice: InvoiceModel = diesel::insert_into(schema::invoices::table)
+            .values(&NewInvoice {
+                wallet_id: request.wallet_id,
+                reference: reference.to_string(),
+                expected_amount: decimal_to_bigdecimal(request.expected_amount),
+                expires_at: self.clock.now() + Duration::minutes(ttl_minutes),
+            })
+            .get_result(&mut conn)
+            .await
+            .map_err(|e| match e {
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::UniqueViolation,
+                    _,
+                ) => anyhow::anyhow!(
+                    "Инвойс с reference '{}' для кошелька {} уже существует",
+                    reference,
+                    request.wallet_id
+                ),
+                other => anyhow::Error::from(other),
+            })?;
+
+        info!(
+            "🧾 Инвойс #{} создан: {} USDT на кошелек {} ({}), истекает {}",
+            invoice.id,
+            request.expected_amount,
+            request.wallet_id,
+            invoice.reference,
+            invoice.expires_at
+        );
+
+        Ok(invoice)
+    }
+
+    /// Засчитывает подтвержденный депозит в открытый инвойс кошелька.
+    ///
+    /// Инвойсы просматриваются от старых к новым; депозит целиком
+    /// уходит в первый открытый. Переход в PAID/OVERPAID шлет
+    /// webhook invoice_paid. Возвращает обновленный инвойс
+    pub async fn apply_deposit(
+        &self,
+        wallet_id: i64,
+        tx_hash: &str,
+        amount: Decimal,
+    ) -> Result<Option<InvoiceModel>> {
+        let now = self.clock.now();
+        let mut conn = self.db.get().await?;
+
+        let open_invoice: Option<InvoiceModel> = schema::invoices::table
+            .filter(schema::invoices::wallet_id.eq(wallet_id))
+            .filter(schema::invoices::status.eq_any(vec![
+                InvoiceStatus::Open.as_db_str(),
+                InvoiceStatus::Partial.as_db_str(),
+            ]))
+            .filter(schema::invoices::expires_at.gt(now))
+            .order(schema::invoices::created_at.asc())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        let Some(invoice) = open_invoice else {
+            return Ok(None);
+        };
+
+        let expected = bigdecimal_to_decimal(invoice.expected_amount.clone());
+        let new_paid = bigdecimal_to_decimal(invoice.paid_amount.clone()) + amount;
+        let new_status = resolve_status(new_paid, expected);
+        let fully_paid = matches!(new_status, InvoiceStatus::Paid | InvoiceStatus::Overpaid);
+
+        let updated: InvoiceModel = diesel::update(schema::invoices::table.find(invoice.id))
+            .set((
+                schema::invoices::paid_amount.eq(decimal_to_bigdecimal(new_paid)),
+                schema::invoices::status.eq(new_status.as_db_str()),
+                schema::invoices::paid_at.eq(fully_paid.then_some(now)),
+            ))
+            .get_result(&mut conn)
+            .await?;
+
+        info!(
+            "🧾 Депозит {} засчитан в инвойс #{} ({}): {}/{} USDT, статус {}",
+            tx_hash, updated.id, updated.reference, new_paid, expected, updated.status
+        );
+
+        if fully_paid {
+            self.notify(WebhookEventType::InvoicePaid, &updated).await;
+        }
+
+        Ok(Some(updated))
+    }
+
+    /// Истекает инвойсы с прошедшим сроком (задача планировщика).
+    /// Возвращает количество истекших
+    pub async fn expire_due_invoices(&self) -> Result<usize> {
+        let now = self.clock.now();
+        let mut conn = self.db.get().await?;
+
+        let expired: Vec<InvoiceModel> = diesel::update(
+            schema::invoices::table
+                .filter(schema::invoices::status.eq_any(vec![
+                    InvoiceStatus::Open.as_db_str(),
+                    InvoiceStatus::Partial.as_db_str(),
+                ]))
+                .filter(schema::invoices::expires_at.le(now)),
+        )
+        .set(schema::invoices::status.eq(InvoiceStatus::Expired.as_db_str()))
+        .get_results(&mut conn)
+        .await?;
+
+        for invoice in &expired {
+            info!(
+                "🧾 Инвойс #{} ({}) истек: оплачено {} из {} USDT",
+                invoice.id, invoice.reference, invoice.paid_amount, invoice.expected_amount
+            );
+            self.notify(WebhookEventType::InvoiceExpired, invoice).await;
+        }
+
+        Ok(expired.len())
+    }
+
+    /// Инвойс по ID
+    pub async fn get_invoice(&self, invoice_id: i64) -> Result<Option<InvoiceModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::invoices::table
+            .find(invoice_id)
+            .first(&mut conn)
+            .await
+            .optional()?)
+    }
+
+    /// Инвойсы кошелька, опционально фильтруя по статусу (новые первыми)
+    pub async fn list_invoices(
+        &self,
+        wallet_id: i64,
+        status: Option<InvoiceStatus>,
+    ) -> Result<Vec<InvoiceModel>> {
+        let mut conn = self.db.get().await?;
+
+        let mut query = schema::invoices::table
+            .filter(schema::invoices::wallet_id.eq(wallet_id))
+            .into_boxed();
+
+        if let Some(status) = status {
+            query = query.filter(schema::invoices::status.eq(status.as_db_str()));
+        }
+
+        Ok(query
+            .order(schema::invoices::created_at.desc())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Доставляет событие инвойса (ошибка не роняет обработку)
+    async fn notify(&self, event_type: WebhookEventType, invoice: &InvoiceModel) {
+        if let Some(webhook_service) = &self.webhook_service {
+            if let Err(e) = webhook_service
+                .notify_invoice_event(event_type, invoice)
+                .await
+            {
+                warn!(
+                    "⚠️ Не удалось отправить webhook инвойса #{}: {}",
+                    invoice.id, e
+                );
+            }
+        }
+    }
+}
+
+/// Статус инвойса по накопленной оплате
+fn resolve_status(paid: Decimal, expected: Decimal) -> InvoiceStatus {
+    if paid > expected {
+        InvoiceStatus::Overpaid
+    } else if paid == expected {
+        InvoiceStatus::Paid
+    } else if paid > Decimal::ZERO {
+        InvoiceStatus::Partial
+    } else {
+        InvoiceStatus::Open
+    }
+}
+
+/// Хук пост-обработки депозитов: засчитывает подтвержденные депозиты
+/// в открытые инвойсы кошелька
+pub struct InvoicePaymentHook {
+    service: Arc<InvoiceService>,
+}
+
+impl InvoicePaymentHook {
+    /// Создает хук поверх сервиса инвойсов
+    pub fn new(service: Arc<InvoiceService>) -> Self {
+        Self { service }
+    }
+}
+
+#[tonic::async_trait]
+impl DepositHook for InvoicePaymentHook {
+    fn name(&self) -> &str {
+        "invoice_payment"
+    }
+
+    async fn on_deposit(&self, ctx: &DepositHookContext) -> Result<()> {
+        // Засчитываются только подтвержденные депозиты
+        if ctx.status != TransactionStatus::Completed {
+            return Ok(());
+        }
+
+        self.service
+            .apply_deposit(ctx.wallet_id, &ctx.tx_hash, ctx.amount)
+            .await
+            .map(|_| ())
+            .inspect_err(|e| {
+                warn!(
+                    "⚠️ Не удалось засчитать депозит {} в инвойс: {}",
+                    ctx.tx_hash, e
+                );
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: i64, scale: u32) -> Decimal {
+        Decimal::new(value, scale)
+    }
+
+    #[test]
+    fn test_resolve_status_transitions() {
+        let expected = dec(125, 0);
+
+        assert_eq!(resolve_status(Decimal::ZERO, expected), InvoiceStatus::Open);
+        assert_eq!(resolve_status(dec(50, 0), expected), InvoiceStatus::Partial);
+        assert_eq!(resolve_status(dec(125, 0), expected), InvoiceStatus::Paid);
+        assert_eq!(
+            resolve_status(dec(1251, 1), expected),
+            InvoiceStatus::Overpaid
+        );
+    }
+
+    #[test]
+    fn test_invoice_status_db_roundtrip() {
+        for status in [
+            InvoiceStatus::Open,
+            InvoiceStatus::Partial,
+            InvoiceStatus::Paid,
+            InvoiceStatus::Overpaid,
+            InvoiceStatus::Expired,
+        ] {
+            assert_eq!(InvoiceStatus::from_db_str(status.as_db_str()), Some(status));
+        }
+        assert_eq!(InvoiceStatus::from_db_str("UNKNOWN"), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! # Пул мастер-кошельков
+//!
+//! Высокие объемы через один мастер-кошелек упираются в лимиты
+//! bandwidth/energy. Пул распределяет активации, спонсорство газа
+//! и sweep-назначения между несколькими мастер-кошельками по
+//! настраиваемой стратегии выбора.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rust_decimal::Decimal;
+
+use crate::config::TronConfig;
+use crate::infrastructure::TronGridClient;
+
+/// Мастер-кошелек в пуле
+#[derive(Debug, Clone)]
+pub struct MasterWallet {
+    pub address: String,
+    pub private_key: String,
+}
+
+/// Стратегия выбора мастер-кошелька из пула
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasterWalletStrategy {
+    /// По кругу (по умолчанию)
+    RoundRobin,
+    /// Наиболее давно использованный
+    LeastRecentlyUsed,
+    /// С наибольшим запасом энергии и bandwidth
+    HighestResources,
+}
+
+impl MasterWalletStrategy {
+    /// Парсит стратегию из конфигурации, по умолчанию round robin
+    pub fn from_config(value: Option<&str>) -> Self {
+        match value {
+            Some("least_recently_used") => Self::LeastRecentlyUsed,
+            Some("highest_resources") => Self::HighestResources,
+            Some("round_robin") | None => Self::RoundRobin,
+            Some(other) => {
+                tracing::warn!(
+                    "⚠️ Неизвестная стратегия мастер-кошельков '{}', используем round_robin",
+                    other
+                );
+                Self::RoundRobin
+            }
+        }
+    }
+}
+
+/// Минимальный TRX баланс, с которым кошелек может фондировать
+/// активацию или спонсорство газа (~15 TRX операция + запас)
+fn min_funding_trx() -> Decimal {
+    Decimal::new(20, 0)
+}
+
+/// Пул мастер-кошельков с выбором по стратегии
+pub struct MasterWalletPool {
+    wallets: Vec<MasterWallet>,
+    strategy: MasterWalletStrategy,
+    tron_client: TronGridClient,
+    cursor: AtomicUsize,
+    last_used: Mutex<HashMap<usize, Instant>>,
+    /// Кулдаун после использования: недавно использованные кошельки
+    /// не выбираются, пока есть отдохнувшие (равномерный расход bandwidth)
+    cooldown: Duration,
+}
+
+impl MasterWalletPool {
+    /// Создает пул из конфигурации TRON (основной кошелек + дополнительные)
+    pub fn from_config(config: &TronConfig, tron_client: TronGridClient) -> Self {
+        let mut wallets = vec![MasterWallet {
+            address: config.master_wallet_address.clone(),
+            private_key: config.master_wallet_private_key.clone(),
+        }];
+
+        for entry in &config.additional_master_wallets {
+            wallets.push(MasterWallet {
+                address: entry.address.clone(),
+                private_key: entry.private_key.clone(),
+            });
+        }
+
+        let strategy = MasterWalletStrategy::from_config(config.master_wallet_strategy.as_deref());
+
+        let cooldown = Duration::from_secs(config.master_wallet_cooldown_seconds);
+
+        if wallets.len() > 1 {
+            tracing::info!(
+                "📊 Пул мастер-кошельков: {} кошельков, стратегия {:?}, кулдаун {} сек",
+                wallets.len(),
+                strategy,
+                cooldown.as_secs()
+            );
+        }
+
+        Self {
+            wallets,
+            strategy,
+            tron_client,
+            cursor: AtomicUsize::new(0),
+            last_used: Mutex::new(HashMap::new()),
+            cooldown,
+        }
+    }
+
+    /// Количество кошельков в пуле
+    pub fn len(&self) -> usize {
+        self.wallets.len()
+    }
+
+    /// Пуст ли пул (не бывает при корректной конфигурации)
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+    }
+
+    /// Основной мастер-кошелек (первый в пуле)
+    pub fn primary(&self) -> &MasterWallet {
+        &self.wallets[0]
+    }
+
+    /// Адреса всех мастер-кошельков пула
+    pub fn addresses(&self) -> Vec<String> {
+        self.wallets.iter().map(|w| w.address.clone()).collect()
+    }
+
+    /// Выбирает мастер-кошелек для следующей операции
+    pub async fn select(&self) -> MasterWallet {
+        if self.wallets.len() == 1 {
+            return self.wallets[0].clone();
+        }
+
+        let eligible = self.eligible_indices();
+
+        let index = match self.strategy {
+            MasterWalletStrategy::RoundRobin => self.select_round_robin(&eligible),
+            MasterWalletStrategy::LeastRecentlyUsed => self.select_least_recently_used(&eligible),
+            MasterWalletStrategy::HighestResources => {
+                self.select_highest_resources(&eligible).await
+            }
+        };
+
+        self.last_used.lock().unwrap().insert(index, Instant::now());
+
+        let wallet = self.wallets[index].clone();
+        tracing::debug!(
+            "Выбран мастер-кошелек {} (стратегия {:?})",
+            wallet.address,
+            self.strategy
+        );
+        wallet
+    }
+
+    /// Индексы кошельков вне кулдауна. Если отдохнувших нет,
+    /// кулдаун игнорируется - операция важнее равномерности
+    fn eligible_indices(&self) -> Vec<usize> {
+        if self.cooldown.is_zero() {
+            return (0..self.wallets.len()).collect();
+        }
+
+        let last_used = self.last_used.lock().unwrap();
+        let now = Instant::now();
+
+        let eligible: Vec<usize> = (0..self.wallets.len())
+            .filter(|index| {
+                last_used
+                    .get(index)
+                    .is_none_or(|used| now.duration_since(*used) >= self.cooldown)
+            })
+            .collect();
+
+        if eligible.is_empty() {
+            tracing::debug!("Все мастер-кошельки в кулдауне - кулдаун игнорируется");
+            return (0..self.wallets.len()).collect();
+        }
+
+        eligible
+    }
+
+    fn select_round_robin(&self, eligible: &[usize]) -> usize {
+        eligible[self.cursor.fetch_add(1, Ordering::Relaxed) % eligible.len()]
+    }
+
+    fn select_least_recently_used(&self, eligible: &[usize]) -> usize {
+        let last_used = self.last_used.lock().unwrap();
+
+        eligible
+            .iter()
+            .copied()
+            .min_by_key(|index| last_used.get(index).copied())
+            .unwrap_or(0)
+    }
+
+    /// Выбирает кошелек с наибольшим запасом энергии + bandwidth.
+    /// Кошельки с TRX балансом ниже минимума фондирования пропускаются -
+    /// выигранный по ресурсам кошелек без TRX все равно не оплатит операцию.
+    ///
+    /// При ошибках TronGrid откатывается на round robin
+    async fn select_highest_resources(&self, eligible: &[usize]) -> usize {
+        let mut best: Option<(usize, u64)> = None;
+
+        for &index in eligible {
+            let wallet = &self.wallets[index];
+
+            match self.tron_client.get_trx_balance(&wallet.address).await {
+                Ok(balance) if balance < min_funding_trx() => {
+                    tracing::debug!(
+                        "Мастер-кошелек {} пропущен: {} TRX ниже минимума фондирования",
+                        wallet.address,
+                        balance
+                    );
+                    continue;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠️ Не удалось получить TRX баланс мастер-кошелька {}: {}",
+                        wallet.address,
+                        e
+                    );
+                    continue;
+                }
+            }
+
+            let resources = match self
+                .tron_client
+                .get_account_resources(&wallet.address)
+                .await
+            {
+                Ok(resources) => resources,
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠️ Не удалось получить ресурсы мастер-кошелька {}: {}",
+                        wallet.address,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let get = |path: &[&str]| -> u64 {
+                let mut value = &resources;
+                for key in path {
+                    value = match value.get(key) {
+                        Some(v) => v,
+                        None => return 0,
+                    };
+                }
+                value.as_u64().unwrap_or(0)
+            };
+
+            let available_energy =
+                get(&["energy", "limit"]).saturating_sub(get(&["energy", "used"]));
+            let available_bandwidth = get(&["bandwidth", "free_limit"])
+                .saturating_sub(get(&["bandwidth", "free_used"]))
+                + get(&["bandwidth", "limit"]).saturating_sub(get(&["bandwidth", "used"]));
+            let score = available_energy + available_bandwidth;
+
+            if best
+                .map(|(_, best_score)| score > best_score)
+                .unwrap_or(true)
+            {
+                best = Some((index, score));
+            }
+        }
+
+        match best {
+            Some((index, _)) => index,
+            None => self.select_round_robin(eligible),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_from_config() {
+        assert_eq!(
+            MasterWalletStrategy::from_config(None),
+            MasterWalletStrategy::RoundRobin
+        );
+        assert_eq!(
+            MasterWalletStrategy::from_config(Some("least_recently_used")),
+            MasterWalletStrategy::LeastRecentlyUsed
+        );
+        assert_eq!(
+            MasterWalletStrategy::from_config(Some("highest_resources")),
+            MasterWalletStrategy::HighestResources
+        );
+        // Неизвестное значение откатывается на round robin
+        assert_eq!(
+            MasterWalletStrategy::from_config(Some("random")),
+            MasterWalletStrategy::RoundRobin
+        );
+    }
+
+    #[test]
+    fn test_cooldown_filters_recently_used() {
+        let mut config = crate::config::Settings::default().tron;
+        config.master_wallet_cooldown_seconds = 60;
+        config.additional_master_wallets = vec![
+            crate::config::MasterWalletEntry {
+                address: "TWallet2".to_string(),
+                private_key: "key2".to_string(),
+            },
+            crate::config::MasterWalletEntry {
+                address: "TWallet3".to_string(),
+                private_key: "key3".to_string(),
+            },
+        ];
+
+        let pool = MasterWalletPool::from_config(&config, TronGridClient::new(config.clone()));
+
+        // Без истории использования отдохнули все
+        assert_eq!(pool.eligible_indices(), vec![0, 1, 2]);
+
+        // Недавно использованный кошелек выпадает из кандидатов
+        pool.last_used.lock().unwrap().insert(0, Instant::now());
+        assert_eq!(pool.eligible_indices(), vec![1, 2]);
+
+        // Все в кулдауне - кулдаун игнорируется
+        let now = Instant::now();
+        for index in 1..3 {
+            pool.last_used.lock().unwrap().insert(index, now);
+        }
+        assert_eq!(pool.eligible_indices(), vec![0, 1, 2]);
+
+        // Отдохнувший кошелек возвращается в кандидаты
+        pool.last_used
+            .lock()
+            .unwrap()
+            .insert(1, now - Duration::from_secs(120));
+        assert_eq!(pool.eligible_indices(), vec![1]);
+    }
+}
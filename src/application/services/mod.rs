@@ -3,34 +3,96 @@
 //! Бизнес-логика разбита по отдельным сервисам:
 //!
 //! - `WalletService` - управление кошельками
+//! - `BalanceService` - проекция балансов кошельков
 //! - `TransferService` - обработка переводов
 //! - `FeeCalculationService` - расчет комиссий
 //! - `WalletActivationService` - активация кошельков
 //! - `SponsorGasService` - спонсорство газа
 //! - `TrxTransferService` - TRX переводы
 //! - `TransactionMonitoringService` - мониторинг входящих транзакций
+//! - `SweepService` - консолидация средств на мастер-кошелек
+//! - `DepositSplitService` - маршрутизация депозитов по правилам разложения
+//! - `TransferIngestionService` - массовая загрузка трансферов (NDJSON)
+//! - `BalanceAlertService` - пороговые подписки на балансы
+//! - `WebhookReplayService` - реплей исторических событий в staging мерчанта
+//! - `AllowanceService` - TRC-20 разрешения для pull-платежей
+//! - `InvoiceService` - платежные запросы с истечением и webhook'ами
+//! - `QrCodeService` - QR коды платежных URI кошельков
+//! - `FaucetService` - выдача тестовых средств в sandbox
+//! - `CanaryService` - канареечный трансфер через весь пайплайн
 
 mod activation_service;
+mod allowance_service;
+mod balance_alert_service;
+mod balance_service;
+mod canary_service;
+mod deposit_hooks;
+mod deposit_split_service;
+mod faucet_service;
 mod fee_service;
 mod gas_service;
+mod ingestion_service;
+mod invoice_service;
+mod master_wallet_service;
 mod monitoring_service;
+mod payment_intent_service;
+mod qr_service;
+mod recovery_service;
 mod scheduler_service;
+mod sweep_service;
+mod transfer_events;
 mod transfer_service;
 mod wallet_service;
+mod wallet_token_service;
+mod webhook_event_service;
+mod webhook_replay_service;
 mod webhook_service;
 
 pub use activation_service::WalletActivationService;
+pub use allowance_service::{AllowanceCheck, AllowanceService};
+pub use balance_alert_service::{BalanceAlertService, BalanceAlertSummary, CreateBalanceAlert};
+pub use balance_service::{BalanceService, BalanceSource, HistoricalBalance};
+pub use canary_service::{CanaryResult, CanaryService};
+pub use deposit_hooks::{DepositHook, DepositHookContext, DepositHookRegistry};
+pub use deposit_split_service::{
+    DepositSplitHook, DepositSplitService, SplitLegSpec, SPLIT_DESTINATION_MASTER,
+};
+pub use faucet_service::FaucetService;
 pub use fee_service::{
-    CongestionLevel, FeeCalculationResult, FeeConfig, FeeSource, FeeStats, NetworkState,
-    UnifiedFeeService,
+    CommissionTier, CongestionLevel, FeeCalculationResult, FeeConfig, FeePayer, FeeRateSnapshot,
+    FeeSource, FeeStats, NetworkState, ShadowFeeComparison, UnifiedFeeService,
 };
 pub use gas_service::SponsorGasService;
-pub use monitoring_service::{MonitoringStats, TransactionMonitoringService};
-pub use scheduler_service::{SchedulerConfig, SchedulerStats, TaskScheduler};
-pub use transfer_service::{TransferService, TrxTransferService};
-pub use wallet_service::WalletService;
+pub use ingestion_service::{
+    IngestionJobStatus, TransferIngestionService, INGESTION_MAX_LINE_BYTES, INGESTION_MAX_ROWS,
+};
+pub use invoice_service::{
+    CreateInvoice, InvoicePaymentHook, InvoiceService, InvoiceStatus, INVOICE_DEFAULT_TTL_MINUTES,
+};
+pub use master_wallet_service::{MasterWallet, MasterWalletPool, MasterWalletStrategy};
+pub use monitoring_service::{
+    parse_stats_window, BatchTuning, MonitoringStats, TransactionMonitoringService,
+};
+pub use payment_intent_service::PaymentIntentService;
+pub use qr_service::{QrCodeService, QrFormat};
+pub use recovery_service::{OrphanCandidate, OrphanRecoveryService, OrphanedTransferReport};
+pub use scheduler_service::{SchedulerConfig, SchedulerRunLog, SchedulerStats, TaskScheduler};
+pub use sweep_service::{SweepRunSummary, SweepService};
+pub use transfer_events::{TransferEventBus, TransferEventKind, TransferStatusChange};
+pub use transfer_service::{
+    ProcessingStats, ProcessingTuning, TransferKind, TransferService, TrxTransferPurpose,
+    TrxTransferService,
+};
+pub use wallet_service::{WalletListFilter, WalletService, WALLET_LIST_MAX_PAGE_SIZE};
+pub use wallet_token_service::WalletTokenService;
+pub use webhook_event_service::{WebhookEventService, EXPORT_MAX_PAGE_SIZE};
+pub use webhook_replay_service::{
+    ReplayJobStatus, ReplayRequest, WebhookReplayService, REPLAY_MAX_EVENTS,
+};
 pub use webhook_service::{
-    WebhookConfig, WebhookData, WebhookEventType, WebhookPayload, WebhookService,
+    BalanceThresholdEvent, WalletLifecycleChange, WebhookConfig, WebhookData,
+    WebhookDeliveryMetrics, WebhookEventType, WebhookOverflowPolicy, WebhookPayload,
+    WebhookService,
 };
 
 // Обратная совместимость - alias для старого названия
@@ -7,16 +7,124 @@ use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use rust_decimal::Decimal;
 use std::collections::HashSet;
+use std::sync::Arc;
 use tokio::time::{interval, Duration};
 use tracing::{error, info, warn};
 
-use crate::domain::{BlockchainTransaction, TransactionStatus};
+use crate::application::dto::IncomingTransactionResponse;
+use crate::domain::{BlockchainTransaction, IncomingTransaction, TransactionStatus};
 use crate::infrastructure::database::{
-    models::{IncomingTransactionModel, NewIncomingTransaction, WalletModel},
+    models::{
+        IncomingTransactionModel, MonitoringDeadLetterModel, NewIncomingTransaction,
+        NewMonitoringDeadLetter, WalletModel,
+    },
     schema, DbPool,
 };
 use crate::infrastructure::tron::TronGridClient;
-use crate::utils::conversions::decimal_to_bigdecimal;
+use crate::infrastructure::DepositSourceLabeler;
+use crate::utils::conversions::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+use super::deposit_hooks::{DepositHookContext, DepositHookRegistry};
+use super::{
+    BalanceService, PaymentIntentService, TransferEventBus, TransferEventKind, TransferStatusChange,
+};
+
+/// Максимальное количество попыток переигрывания dead-letter записи
+const MAX_REPLAY_ATTEMPTS: i32 = 10;
+
+/// Быстрый интервал сканирования - для недавно активных кошельков
+const FAST_SCAN_INTERVAL_SECS: u64 = 30;
+
+/// Медленный потолок: дальше спящие кошельки не замедляются
+const SLOW_SCAN_INTERVAL_SECS: u64 = 600;
+
+/// Границы адаптивной пачки кошельков за тик сканирования
+const WALLET_BATCH_MIN: usize = 5;
+const WALLET_BATCH_MAX: usize = 100;
+
+/// Границы лимита транзакций в одном запросе к TronGrid
+const TX_LIMIT_MIN: u32 = 10;
+const TX_LIMIT_MAX: u32 = 50;
+
+/// Шаги аддитивного расширения при здоровом TronGrid
+const WALLET_BATCH_GROW_STEP: usize = 10;
+const TX_LIMIT_GROW_STEP: u32 = 10;
+
+/// Латентность скана кошелька, при которой TronGrid считается здоровым
+const SCAN_LATENCY_HEALTHY_MS: u64 = 500;
+
+/// Латентность, при которой пачка сжимается
+const SCAN_LATENCY_DEGRADED_MS: u64 = 2000;
+
+/// Доля ошибок сканов, при которой пачка сжимается
+const SCAN_ERROR_RATE_THRESHOLD: f64 = 0.2;
+
+/// Целевая длительность цикла сканирования - пачка кошельков
+/// дополнительно ограничивается так, чтобы цикл укладывался в нее
+const TARGET_CYCLE_MS: u64 = 20_000;
+
+/// Адаптивные размеры пачки: сколько кошельков сканировать за тик
+/// и сколько транзакций запрашивать у TronGrid на кошелек
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct BatchTuning {
+    pub wallet_batch: usize,
+    pub tx_limit: u32,
+}
+
+impl Default for BatchTuning {
+    fn default() -> Self {
+        Self {
+            wallet_batch: WALLET_BATCH_MAX,
+            tx_limit: TX_LIMIT_MAX,
+        }
+    }
+}
+
+/// Пересчитывает пачку по итогам цикла (AIMD): рост латентности или
+/// ошибок мультипликативно сжимает пачку и лимит запроса, здоровый
+/// TronGrid аддитивно возвращает их к максимуму. Пачка кошельков
+/// дополнительно ограничивается целевой длительностью цикла
+fn adjust_batch_tuning(current: BatchTuning, avg_scan_ms: u64, error_rate: f64) -> BatchTuning {
+    let mut next =
+        if error_rate >= SCAN_ERROR_RATE_THRESHOLD || avg_scan_ms >= SCAN_LATENCY_DEGRADED_MS {
+            BatchTuning {
+                wallet_batch: (current.wallet_batch / 2).max(WALLET_BATCH_MIN),
+                tx_limit: (current.tx_limit / 2).max(TX_LIMIT_MIN),
+            }
+        } else if avg_scan_ms <= SCAN_LATENCY_HEALTHY_MS && error_rate == 0.0 {
+            BatchTuning {
+                wallet_batch: (current.wallet_batch + WALLET_BATCH_GROW_STEP).min(WALLET_BATCH_MAX),
+                tx_limit: (current.tx_limit + TX_LIMIT_GROW_STEP).min(TX_LIMIT_MAX),
+            }
+        } else {
+            current
+        };
+
+    if let Some(fits_in_cycle) = TARGET_CYCLE_MS.checked_div(avg_scan_ms) {
+        next.wallet_batch = next
+            .wallet_batch
+            .min((fits_in_cycle as usize).max(WALLET_BATCH_MIN));
+    }
+
+    next
+}
+
+/// Следующий интервал сканирования кошелька: активность возвращает
+/// на быстрый интервал, тишина экспоненциально замедляет до потолка
+fn next_scan_interval(current_secs: u64, found_activity: bool) -> u64 {
+    if found_activity {
+        FAST_SCAN_INTERVAL_SECS
+    } else {
+        (current_secs * 2).min(SLOW_SCAN_INTERVAL_SECS)
+    }
+}
+
+/// График сканирования одного кошелька
+#[derive(Debug, Clone)]
+struct WalletScanState {
+    interval_secs: u64,
+    next_scan_at: chrono::DateTime<chrono::Utc>,
+}
 
 /// Сервис для мониторинга входящих транзакций
 #[derive(Clone)]
@@ -25,6 +133,15 @@ pub struct TransactionMonitoringService {
     tron_client: TronGridClient,
     usdt_contract: String,
     monitoring_enabled: bool,
+    payment_intent_service: Option<Arc<PaymentIntentService>>,
+    source_labeler: Option<Arc<DepositSourceLabeler>>,
+    deposit_hooks: Option<Arc<DepositHookRegistry>>,
+    /// Шина событий статусов для стриминговых подписчиков (опционально)
+    event_bus: Option<Arc<TransferEventBus>>,
+    /// График сканирования по кошелькам (тиры частоты по активности)
+    scan_schedule: Arc<std::sync::Mutex<std::collections::HashMap<i64, WalletScanState>>>,
+    /// Адаптивные размеры пачки, пересчитываемые по латентности TronGrid
+    batch_tuning: Arc<std::sync::Mutex<BatchTuning>>,
 }
 
 impl TransactionMonitoringService {
@@ -40,9 +157,42 @@ impl TransactionMonitoringService {
             tron_client,
             usdt_contract,
             monitoring_enabled,
+            payment_intent_service: None,
+            source_labeler: None,
+            deposit_hooks: None,
+            event_bus: None,
+            scan_schedule: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            batch_tuning: Arc::new(std::sync::Mutex::new(BatchTuning::default())),
         }
     }
 
+    /// Подключает матчинг депозитов с открытыми платежными намерениями
+    pub fn with_payment_intents(
+        mut self,
+        payment_intent_service: Arc<PaymentIntentService>,
+    ) -> Self {
+        self.payment_intent_service = Some(payment_intent_service);
+        self
+    }
+
+    /// Подключает маркировку источников депозитов
+    pub fn with_source_labeler(mut self, source_labeler: Arc<DepositSourceLabeler>) -> Self {
+        self.source_labeler = Some(source_labeler);
+        self
+    }
+
+    /// Подключает реестр хуков пост-обработки депозитов
+    pub fn with_deposit_hooks(mut self, deposit_hooks: Arc<DepositHookRegistry>) -> Self {
+        self.deposit_hooks = Some(deposit_hooks);
+        self
+    }
+
+    /// Подключает шину событий статусов (gRPC стримы WatchWalletEvents)
+    pub fn with_event_bus(mut self, event_bus: Arc<TransferEventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// Запускает фоновый мониторинг входящих транзакций
     pub async fn start_monitoring(&self) -> Result<()> {
         if !self.monitoring_enabled {
@@ -61,10 +211,18 @@ impl TransactionMonitoringService {
                 error!("Ошибка сканирования входящих транзакций: {}", e);
                 // Продолжаем работу, не падаем
             }
+
+            if let Err(e) = self.replay_dead_letters().await {
+                error!("Ошибка переигрывания dead-letter записей: {}", e);
+            }
         }
     }
 
-    /// Сканирует входящие транзакции для всех кошельков (публичный метод)
+    /// Сканирует входящие транзакции для всех кошельков (публичный метод).
+    ///
+    /// Кошельки сканируются по тирам активности: недавно активные - на
+    /// быстром интервале, спящие экспоненциально замедляются до потолка.
+    /// Новый депозит возвращает кошелек на быстрый интервал
     pub async fn scan_for_incoming_transactions(&self) -> Result<()> {
         info!("🔍 Сканирование входящих транзакций...");
 
@@ -75,25 +233,153 @@ impl TransactionMonitoringService {
             .load(&mut conn)
             .await?;
 
-        info!("Найдено {} кошельков для мониторинга", wallets.len());
+        // 2. Отбираем кошельки, у которых подошло время сканирования.
+        // Самые просроченные идут первыми - при урезанной пачке никто
+        // не голодает, остаток дождется следующего тика
+        let now = chrono::Utc::now();
+        let tuning = *self.batch_tuning.lock().unwrap();
+        let mut due_wallets: Vec<(WalletModel, chrono::DateTime<chrono::Utc>)> = {
+            let schedule = self.scan_schedule.lock().unwrap();
+            wallets
+                .into_iter()
+                .filter_map(|wallet| match schedule.get(&wallet.id) {
+                    Some(state) if state.next_scan_at <= now => Some((wallet, state.next_scan_at)),
+                    Some(_) => None,
+                    // Новые кошельки сканируются сразу
+                    None => Some((wallet, chrono::DateTime::<chrono::Utc>::MIN_UTC)),
+                })
+                .collect()
+        };
+        due_wallets.sort_by_key(|(_, due_at)| *due_at);
+
+        let deferred = due_wallets.len().saturating_sub(tuning.wallet_batch);
+        due_wallets.truncate(tuning.wallet_batch);
+
+        info!(
+            "Кошельков к сканированию: {} (отложено {}, лимит запроса {})",
+            due_wallets.len(),
+            deferred,
+            tuning.tx_limit
+        );
 
-        // 2. Для каждого кошелька проверяем входящие транзакции
-        for wallet in wallets {
-            if let Err(e) = self.scan_wallet_transactions(&wallet).await {
-                warn!("Ошибка сканирования кошелька {}: {}", wallet.address, e);
-                // Продолжаем с другими кошельками
+        // 3. Для каждого кошелька проверяем входящие транзакции,
+        // замеряя латентность TronGrid для адаптации пачки
+        let mut scanned = 0u64;
+        let mut failed = 0u64;
+        let mut total_scan_ms = 0u64;
+
+        for (wallet, _) in due_wallets {
+            let started = std::time::Instant::now();
+            let result = self
+                .scan_wallet_transactions(&wallet, tuning.tx_limit)
+                .await;
+            total_scan_ms += started.elapsed().as_millis() as u64;
+            scanned += 1;
+
+            match result {
+                Ok(new_transactions) => {
+                    self.reschedule_wallet(wallet.id, new_transactions > 0);
+                }
+                Err(e) => {
+                    warn!("Ошибка сканирования кошелька {}: {}", wallet.address, e);
+                    failed += 1;
+                    // Ошибка не меняет тир - следующий тик попробует снова
+                    self.reschedule_wallet_after_error(wallet.id);
+                }
             }
         }
 
+        // 4. Пересчитываем пачку по итогам цикла
+        if let Some(avg_scan_ms) = total_scan_ms.checked_div(scanned) {
+            let error_rate = failed as f64 / scanned as f64;
+            let next = adjust_batch_tuning(tuning, avg_scan_ms, error_rate);
+            if next != tuning {
+                info!(
+                    "📊 Адаптация пачки мониторинга: {} -> {} кошельков, лимит {} -> {} \
+                     (латентность {}мс, ошибки {:.0}%)",
+                    tuning.wallet_batch,
+                    next.wallet_batch,
+                    tuning.tx_limit,
+                    next.tx_limit,
+                    avg_scan_ms,
+                    error_rate * 100.0
+                );
+            }
+            *self.batch_tuning.lock().unwrap() = next;
+        }
+
         Ok(())
     }
 
-    /// Сканирует транзакции для конкретного кошелька
-    async fn scan_wallet_transactions(&self, wallet: &WalletModel) -> Result<()> {
-        // Получаем последние 50 транзакций для кошелька
+    /// Текущие адаптивные размеры пачки сканирования
+    pub fn batch_tuning(&self) -> BatchTuning {
+        *self.batch_tuning.lock().unwrap()
+    }
+
+    /// Принудительно сканирует кошелек вне графика (например, при показе
+    /// инвойса) и возвращает его на быстрый интервал.
+    /// Возвращает количество новых обработанных транзакций
+    pub async fn scan_wallet_now(&self, wallet_id: i64) -> Result<usize> {
+        let mut conn = self.db.get().await?;
+        let wallet: WalletModel = schema::wallets::table
+            .find(wallet_id)
+            .select(WalletModel::as_select())
+            .first(&mut conn)
+            .await?;
+
+        info!("🔍 Принудительное сканирование кошелька {}", wallet.address);
+
+        let tx_limit = self.batch_tuning.lock().unwrap().tx_limit;
+        let new_transactions = self.scan_wallet_transactions(&wallet, tx_limit).await?;
+        self.reschedule_wallet(wallet.id, true);
+
+        Ok(new_transactions)
+    }
+
+    /// Обновляет график сканирования кошелька по результату скана
+    fn reschedule_wallet(&self, wallet_id: i64, found_activity: bool) {
+        let mut schedule = self.scan_schedule.lock().unwrap();
+        let current_secs = schedule
+            .get(&wallet_id)
+            .map(|state| state.interval_secs)
+            .unwrap_or(FAST_SCAN_INTERVAL_SECS);
+
+        let interval_secs = next_scan_interval(current_secs, found_activity);
+        schedule.insert(
+            wallet_id,
+            WalletScanState {
+                interval_secs,
+                next_scan_at: chrono::Utc::now() + chrono::Duration::seconds(interval_secs as i64),
+            },
+        );
+    }
+
+    /// Перепланирует кошелек после ошибки сканирования. Тир сохраняется:
+    /// транзиентный сбой TronGrid не должен загонять активные кошельки
+    /// на медленный потолок (от перегрузки защищает AIMD-сжатие пачки)
+    fn reschedule_wallet_after_error(&self, wallet_id: i64) {
+        let mut schedule = self.scan_schedule.lock().unwrap();
+        let current_secs = schedule
+            .get(&wallet_id)
+            .map(|state| state.interval_secs)
+            .unwrap_or(FAST_SCAN_INTERVAL_SECS);
+
+        schedule.insert(
+            wallet_id,
+            WalletScanState {
+                interval_secs: current_secs,
+                next_scan_at: chrono::Utc::now() + chrono::Duration::seconds(current_secs as i64),
+            },
+        );
+    }
+
+    /// Сканирует транзакции для конкретного кошелька с адаптивным
+    /// лимитом запроса. Возвращает количество новых входящих транзакций
+    async fn scan_wallet_transactions(&self, wallet: &WalletModel, tx_limit: u32) -> Result<usize> {
+        // Получаем последние транзакции для кошелька
         let transactions = self
             .tron_client
-            .get_trc20_transactions(&wallet.address, &self.usdt_contract, 50)
+            .get_trc20_transactions(&wallet.address, &self.usdt_contract, tx_limit)
             .await?;
 
         info!(
@@ -113,18 +399,28 @@ impl TransactionMonitoringService {
         let known_hashes: HashSet<String> = known_hashes.into_iter().collect();
 
         // Обрабатываем новые транзакции
+        let mut new_transactions = 0;
         for tx in transactions {
             if !known_hashes.contains(&tx.tx_hash) {
                 // Проверяем, что это входящая транзакция (to = наш кошелек)
                 if tx.to_address.eq_ignore_ascii_case(&wallet.address) {
+                    new_transactions += 1;
                     if let Err(e) = self.process_new_incoming_transaction(wallet, &tx).await {
                         error!("Ошибка обработки новой транзакции {}: {}", tx.tx_hash, e);
+
+                        // Сохраняем в dead-letter таблицу для переигрывания с backoff
+                        if let Err(dlq_err) = self.record_dead_letter(wallet, &tx, &e).await {
+                            error!(
+                                "Ошибка записи dead-letter для транзакции {}: {}",
+                                tx.tx_hash, dlq_err
+                            );
+                        }
                     }
                 }
             }
         }
 
-        Ok(())
+        Ok(new_transactions)
     }
 
     /// Обрабатывает новую входящую транзакцию
@@ -133,9 +429,16 @@ impl TransactionMonitoringService {
         wallet: &WalletModel,
         tx: &BlockchainTransaction,
     ) -> Result<()> {
+        // Токен депозита: из token_info TronGrid, для старых источников
+        // без него - USDT (единственный токен до мультитокенного учета)
+        let token_symbol = tx
+            .token_symbol
+            .clone()
+            .unwrap_or_else(|| "USDT".to_string());
+
         info!(
-            "📥 Новая входящая транзакция: {} USDT на кошелек {} (tx: {})",
-            tx.amount, wallet.address, tx.tx_hash
+            "📥 Новая входящая транзакция: {} {} на кошелек {} (tx: {})",
+            tx.amount, token_symbol, wallet.address, tx.tx_hash
         );
 
         let mut conn = self.db.get().await?;
@@ -152,23 +455,63 @@ impl TransactionMonitoringService {
             TransactionStatus::Pending
         };
 
-        // Сохраняем в БД
+        // Классифицируем источник депозита (биржа, миксер, свой кошелек)
+        let source_label = self
+            .source_labeler
+            .as_ref()
+            .map(|labeler| labeler.label_for(&tx.from_address));
+
+        if let Some(label) = &source_label {
+            info!("🔍 Источник депозита {}: {}", tx.tx_hash, label);
+        }
+
+        let source_label_for_hooks = source_label.clone();
+
+        // Сохраняем в БД. Доменные инварианты (сумма, хэш, согласованность
+        // статуса с номером блока) проверяются builder'ом сущности
+        let entity = IncomingTransaction::builder(
+            wallet.id,
+            tx.tx_hash.clone(),
+            tx.from_address.clone(),
+            tx.to_address.clone(),
+            tx.amount,
+        )
+        .with_block_number(tx.block_number)
+        .with_status(status.clone())
+        .build()?;
+
         let new_transaction = NewIncomingTransaction {
-            wallet_id: wallet.id,
-            tx_hash: tx.tx_hash.clone(),
-            block_number: tx.block_number,
-            from_address: tx.from_address.clone(),
-            to_address: tx.to_address.clone(),
-            amount: decimal_to_bigdecimal(tx.amount),
-            status: format!("{:?}", status),
-            error_message: None,
+            wallet_id: entity.wallet_id,
+            tx_hash: entity.tx_hash,
+            block_number: entity.block_number,
+            from_address: entity.from_address,
+            to_address: entity.to_address,
+            amount: decimal_to_bigdecimal(entity.amount),
+            status: entity.status.as_db_str().to_string(),
+            error_message: entity.error_message,
+            source_label,
+            token_symbol,
+            contract_address: tx.contract_address.clone(),
         };
 
-        diesel::insert_into(schema::incoming_transactions::table)
+        let record_id: i64 = diesel::insert_into(schema::incoming_transactions::table)
             .values(&new_transaction)
-            .execute(&mut conn)
+            .returning(schema::incoming_transactions::id)
+            .get_result(&mut conn)
             .await?;
 
+        // Депозит виден стриминговым подписчикам кошелька
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.publish(TransferStatusChange {
+                kind: TransferEventKind::IncomingDeposit,
+                record_id,
+                wallet_id: wallet.id,
+                status: status.clone(),
+                tx_hash: Some(tx.tx_hash.clone()),
+                occurred_at: tx.timestamp,
+            });
+        }
+
         // Если транзакция подтверждена, обновляем баланс кошелька
         if status == TransactionStatus::Completed {
             self.update_wallet_balance(wallet.id, tx.amount).await?;
@@ -179,16 +522,207 @@ impl TransactionMonitoringService {
             );
         }
 
+        // Пытаемся закрыть открытое платежное намерение этим депозитом.
+        // Ошибка матчинга не должна ронять обработку транзакции
+        if let Some(intents) = &self.payment_intent_service {
+            if let Err(e) = intents
+                .match_deposit(wallet.id, &tx.tx_hash, tx.amount)
+                .await
+            {
+                warn!(
+                    "⚠️ Не удалось сматчить депозит {} с намерениями: {}",
+                    tx.tx_hash, e
+                );
+            }
+        }
+
+        // Запускаем хуки пост-обработки (авто-форвардинг, CRM и т.п.).
+        // Депозит уже записан - хуки работают поверх и не могут его уронить
+        if let Some(hooks) = &self.deposit_hooks {
+            if !hooks.is_empty() {
+                let ctx = DepositHookContext {
+                    wallet_id: wallet.id,
+                    wallet_address: wallet.address.clone(),
+                    owner_id: wallet.owner_id.clone(),
+                    tx_hash: tx.tx_hash.clone(),
+                    from_address: tx.from_address.clone(),
+                    amount: tx.amount,
+                    status,
+                    source_label: source_label_for_hooks,
+                    detected_at: tx.timestamp,
+                };
+                hooks.run(&ctx).await;
+            }
+        }
+
         Ok(())
     }
 
-    /// Обновляет баланс кошелька (заглушка - баланс вычисляется динамически)
-    async fn update_wallet_balance(&self, _wallet_id: i64, _amount: Decimal) -> Result<()> {
-        // В текущей реализации баланс вычисляется динамически через TronGrid API
-        // Здесь можно добавить кэширование баланса в БД если нужно
+    /// Сохраняет упавшую транзакцию в dead-letter таблицу.
+    /// Повторная ошибка по тому же tx_hash не плодит дубликаты
+    async fn record_dead_letter(
+        &self,
+        wallet: &WalletModel,
+        tx: &BlockchainTransaction,
+        error: &anyhow::Error,
+    ) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        let dead_letter = NewMonitoringDeadLetter {
+            wallet_id: wallet.id,
+            tx_hash: tx.tx_hash.clone(),
+            block_number: tx.block_number,
+            from_address: tx.from_address.clone(),
+            to_address: tx.to_address.clone(),
+            amount: decimal_to_bigdecimal(tx.amount),
+            confirmations: tx.confirmations as i32,
+            error_message: error.to_string(),
+        };
+
+        diesel::insert_into(schema::monitoring_dead_letters::table)
+            .values(&dead_letter)
+            .on_conflict(schema::monitoring_dead_letters::tx_hash)
+            .do_nothing()
+            .execute(&mut conn)
+            .await?;
+
+        warn!(
+            "📮 Транзакция {} помещена в dead-letter очередь",
+            tx.tx_hash
+        );
+
         Ok(())
     }
 
+    /// Переигрывает dead-letter записи, у которых подошло время повтора.
+    /// Возвращает количество успешно обработанных записей
+    pub async fn replay_dead_letters(&self) -> Result<usize> {
+        let mut conn = self.db.get().await?;
+
+        let entries: Vec<MonitoringDeadLetterModel> = schema::monitoring_dead_letters::table
+            .filter(schema::monitoring_dead_letters::resolved.eq(false))
+            .filter(schema::monitoring_dead_letters::attempts.lt(MAX_REPLAY_ATTEMPTS))
+            .filter(schema::monitoring_dead_letters::next_retry_at.le(chrono::Utc::now()))
+            .order(schema::monitoring_dead_letters::next_retry_at.asc())
+            .limit(50)
+            .load(&mut conn)
+            .await?;
+
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        info!(
+            "📮 Переигрывание {} dead-letter записей мониторинга",
+            entries.len()
+        );
+
+        let mut replayed = 0;
+        for entry in entries {
+            match self.replay_dead_letter(&entry).await {
+                Ok(()) => {
+                    self.mark_dead_letter_resolved(entry.id).await?;
+                    replayed += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Повторная ошибка обработки транзакции {} (попытка {}): {}",
+                        entry.tx_hash,
+                        entry.attempts + 1,
+                        e
+                    );
+                    self.schedule_dead_letter_retry(&entry, &e).await?;
+                }
+            }
+        }
+
+        Ok(replayed)
+    }
+
+    /// Переигрывает одну dead-letter запись
+    async fn replay_dead_letter(&self, entry: &MonitoringDeadLetterModel) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        // Транзакция могла быть обработана следующим сканом - тогда просто закрываем запись
+        let already_processed: i64 = schema::incoming_transactions::table
+            .filter(schema::incoming_transactions::tx_hash.eq(&entry.tx_hash))
+            .count()
+            .get_result(&mut conn)
+            .await?;
+
+        if already_processed > 0 {
+            return Ok(());
+        }
+
+        let wallet: WalletModel = schema::wallets::table
+            .find(entry.wallet_id)
+            .first(&mut conn)
+            .await?;
+
+        let tx = BlockchainTransaction {
+            tx_hash: entry.tx_hash.clone(),
+            block_number: entry.block_number,
+            from_address: entry.from_address.clone(),
+            to_address: entry.to_address.clone(),
+            amount: bigdecimal_to_decimal(entry.amount.clone()),
+            timestamp: entry.created_at,
+            confirmations: entry.confirmations as u32,
+            // Dead-letter записи токен не хранят - депозит запишется
+            // с умолчанием USDT
+            token_symbol: None,
+            contract_address: None,
+        };
+
+        self.process_new_incoming_transaction(&wallet, &tx).await
+    }
+
+    /// Помечает dead-letter запись как обработанную
+    async fn mark_dead_letter_resolved(&self, entry_id: i64) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        diesel::update(schema::monitoring_dead_letters::table.find(entry_id))
+            .set((
+                schema::monitoring_dead_letters::resolved.eq(true),
+                schema::monitoring_dead_letters::last_attempt_at.eq(chrono::Utc::now()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Планирует следующую попытку с экспоненциальным backoff (60с * 2^попытки, максимум час)
+    async fn schedule_dead_letter_retry(
+        &self,
+        entry: &MonitoringDeadLetterModel,
+        error: &anyhow::Error,
+    ) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        let backoff_seconds = (60u64 << entry.attempts.min(6) as u64).min(3600);
+        let next_retry_at = chrono::Utc::now() + chrono::Duration::seconds(backoff_seconds as i64);
+
+        diesel::update(schema::monitoring_dead_letters::table.find(entry.id))
+            .set((
+                schema::monitoring_dead_letters::attempts.eq(entry.attempts + 1),
+                schema::monitoring_dead_letters::error_message.eq(error.to_string()),
+                schema::monitoring_dead_letters::next_retry_at.eq(next_retry_at),
+                schema::monitoring_dead_letters::last_attempt_at.eq(chrono::Utc::now()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Обновляет проекцию баланса кошелька после подтвержденного депозита
+    async fn update_wallet_balance(&self, wallet_id: i64, amount: Decimal) -> Result<()> {
+        let balance_service = BalanceService::new(self.db.clone(), self.tron_client.clone());
+        balance_service
+            .apply_confirmed_deposit(wallet_id, amount)
+            .await
+    }
+
     /// Получает все входящие транзакции для кошелька
     pub async fn get_wallet_incoming_transactions(
         &self,
@@ -205,42 +739,109 @@ impl TransactionMonitoringService {
         Ok(transactions)
     }
 
-    /// Получает сводку по входящим транзакциям
-    pub async fn get_monitoring_stats(&self) -> Result<MonitoringStats> {
+    /// Получает депозиты кошелька, опционально фильтруя по метке
+    /// источника (exchange, mixer, internal, private_wallet)
+    pub async fn get_wallet_deposits(
+        &self,
+        wallet_id: i64,
+        source_label: Option<&str>,
+    ) -> Result<Vec<IncomingTransactionResponse>> {
         let mut conn = self.db.get().await?;
 
-        // Общее количество обработанных транзакций
-        let total_transactions: i64 = schema::incoming_transactions::table
-            .count()
-            .get_result(&mut conn)
-            .await?;
+        let mut query = schema::incoming_transactions::table
+            .filter(schema::incoming_transactions::wallet_id.eq(wallet_id))
+            .into_boxed();
 
-        // Количество транзакций по статусам
-        let pending_count: i64 = schema::incoming_transactions::table
-            .filter(schema::incoming_transactions::status.eq("Pending"))
-            .count()
-            .get_result(&mut conn)
-            .await?;
+        if let Some(label) = source_label {
+            query = query.filter(schema::incoming_transactions::source_label.eq(label.to_string()));
+        }
 
-        let processing_count: i64 = schema::incoming_transactions::table
-            .filter(schema::incoming_transactions::status.eq("Processing"))
-            .count()
-            .get_result(&mut conn)
+        let transactions: Vec<IncomingTransactionModel> = query
+            .select(IncomingTransactionModel::as_select())
+            .order(schema::incoming_transactions::detected_at.desc())
+            .load(&mut conn)
             .await?;
 
-        let completed_count: i64 = schema::incoming_transactions::table
-            .filter(schema::incoming_transactions::status.eq("Completed"))
-            .count()
-            .get_result(&mut conn)
-            .await?;
+        Ok(transactions
+            .into_iter()
+            .map(Self::model_to_response)
+            .collect())
+    }
+
+    /// Конвертирует модель входящей транзакции в DTO
+    fn model_to_response(tx: IncomingTransactionModel) -> IncomingTransactionResponse {
+        IncomingTransactionResponse {
+            id: tx.id,
+            wallet_id: tx.wallet_id,
+            tx_hash: tx.tx_hash,
+            block_number: tx.block_number,
+            from_address: tx.from_address,
+            to_address: tx.to_address,
+            amount: bigdecimal_to_decimal(tx.amount),
+            status: TransactionStatus::from_db_str(&tx.status)
+                .unwrap_or(TransactionStatus::Pending), // для неизвестных статусов
+            detected_at: tx.detected_at,
+            confirmed_at: tx.confirmed_at,
+            source_label: tx.source_label,
+            token_symbol: tx.token_symbol,
+            contract_address: tx.contract_address,
+        }
+    }
+
+    /// Получает сводку по входящим транзакциям за все время
+    pub async fn get_monitoring_stats(&self) -> Result<MonitoringStats> {
+        self.get_monitoring_stats_since(None).await
+    }
+
+    /// Получает сводку по входящим транзакциям за временное окно.
+    ///
+    /// Счетчики собираются одним группированным запросом - под конкурентной
+    /// записью они согласованы между собой (один снимок данных),
+    /// в отличие от серии отдельных count'ов
+    pub async fn get_monitoring_stats_since(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<MonitoringStats> {
+        let mut conn = self.db.get().await?;
+
+        let counts_by_status: Vec<(String, i64)> = match since {
+            Some(since) => {
+                schema::incoming_transactions::table
+                    .filter(schema::incoming_transactions::detected_at.ge(since))
+                    .group_by(schema::incoming_transactions::status)
+                    .select((
+                        schema::incoming_transactions::status,
+                        diesel::dsl::count_star(),
+                    ))
+                    .load(&mut conn)
+                    .await?
+            }
+            None => {
+                schema::incoming_transactions::table
+                    .group_by(schema::incoming_transactions::status)
+                    .select((
+                        schema::incoming_transactions::status,
+                        diesel::dsl::count_star(),
+                    ))
+                    .load(&mut conn)
+                    .await?
+            }
+        };
+
+        Ok(MonitoringStats::from_status_counts(
+            counts_by_status,
+            self.monitoring_enabled,
+        ))
+    }
+}
 
-        Ok(MonitoringStats {
-            total_transactions,
-            pending_count,
-            processing_count,
-            completed_count,
-            monitoring_enabled: self.monitoring_enabled,
-        })
+/// Разбирает окно статистики ("1h", "24h", "7d") в длительность
+pub fn parse_stats_window(window: &str) -> Option<chrono::Duration> {
+    match window {
+        "1h" => Some(chrono::Duration::hours(1)),
+        "24h" => Some(chrono::Duration::hours(24)),
+        "7d" => Some(chrono::Duration::days(7)),
+        _ => None,
     }
 }
 
@@ -253,3 +854,144 @@ pub struct MonitoringStats {
     pub completed_count: i64,
     pub monitoring_enabled: bool,
 }
+
+impl MonitoringStats {
+    /// Раскладывает результат группированного запроса по счетчикам
+    fn from_status_counts(counts: Vec<(String, i64)>, monitoring_enabled: bool) -> Self {
+        let mut stats = Self {
+            total_transactions: 0,
+            pending_count: 0,
+            processing_count: 0,
+            completed_count: 0,
+            monitoring_enabled,
+        };
+
+        for (status, count) in counts {
+            stats.total_transactions += count;
+            match TransactionStatus::from_db_str(&status) {
+                Some(TransactionStatus::Pending) => stats.pending_count = count,
+                Some(TransactionStatus::Processing) => stats.processing_count = count,
+                Some(TransactionStatus::Completed) => stats.completed_count = count,
+                // FAILED и неизвестные статусы входят только в total
+                _ => {}
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_status_counts() {
+        let counts = vec![
+            ("PENDING".to_string(), 3),
+            ("PROCESSING".to_string(), 2),
+            ("COMPLETED".to_string(), 10),
+            ("FAILED".to_string(), 1),
+        ];
+
+        let stats = MonitoringStats::from_status_counts(counts, true);
+        assert_eq!(stats.total_transactions, 16);
+        assert_eq!(stats.pending_count, 3);
+        assert_eq!(stats.processing_count, 2);
+        assert_eq!(stats.completed_count, 10);
+        assert!(stats.monitoring_enabled);
+    }
+
+    #[test]
+    fn test_stats_from_empty_counts() {
+        let stats = MonitoringStats::from_status_counts(Vec::new(), false);
+        assert_eq!(stats.total_transactions, 0);
+        assert_eq!(stats.pending_count, 0);
+        assert!(!stats.monitoring_enabled);
+    }
+
+    #[test]
+    fn test_next_scan_interval_backoff() {
+        // Тишина удваивает интервал до потолка
+        assert_eq!(next_scan_interval(30, false), 60);
+        assert_eq!(next_scan_interval(60, false), 120);
+        assert_eq!(next_scan_interval(400, false), SLOW_SCAN_INTERVAL_SECS);
+        assert_eq!(
+            next_scan_interval(SLOW_SCAN_INTERVAL_SECS, false),
+            SLOW_SCAN_INTERVAL_SECS
+        );
+
+        // Активность возвращает на быстрый интервал
+        assert_eq!(
+            next_scan_interval(SLOW_SCAN_INTERVAL_SECS, true),
+            FAST_SCAN_INTERVAL_SECS
+        );
+    }
+
+    #[test]
+    fn test_batch_tuning_shrinks_on_degradation() {
+        let healthy = BatchTuning::default();
+
+        // Высокая латентность мультипликативно сжимает пачку
+        let degraded = adjust_batch_tuning(healthy, SCAN_LATENCY_DEGRADED_MS, 0.0);
+        assert!(degraded.wallet_batch < healthy.wallet_batch);
+        assert!(degraded.tx_limit < healthy.tx_limit);
+
+        // Высокая доля ошибок - тоже
+        let erroring = adjust_batch_tuning(healthy, 100, 0.5);
+        assert!(erroring.wallet_batch < healthy.wallet_batch);
+
+        // Сжатие не пробивает минимумы
+        let floor = BatchTuning {
+            wallet_batch: WALLET_BATCH_MIN,
+            tx_limit: TX_LIMIT_MIN,
+        };
+        assert_eq!(
+            adjust_batch_tuning(floor, 10_000, 1.0).wallet_batch,
+            WALLET_BATCH_MIN
+        );
+        assert_eq!(
+            adjust_batch_tuning(floor, 10_000, 1.0).tx_limit,
+            TX_LIMIT_MIN
+        );
+    }
+
+    #[test]
+    fn test_batch_tuning_grows_back_when_healthy() {
+        let shrunk = BatchTuning {
+            wallet_batch: WALLET_BATCH_MIN,
+            tx_limit: TX_LIMIT_MIN,
+        };
+
+        // Здоровый TronGrid аддитивно расширяет пачку
+        let grown = adjust_batch_tuning(shrunk, 100, 0.0);
+        assert_eq!(
+            grown.wallet_batch,
+            WALLET_BATCH_MIN + WALLET_BATCH_GROW_STEP
+        );
+        assert_eq!(grown.tx_limit, TX_LIMIT_MIN + TX_LIMIT_GROW_STEP);
+
+        // Рост упирается в максимумы
+        let maxed = adjust_batch_tuning(BatchTuning::default(), 100, 0.0);
+        assert_eq!(maxed, BatchTuning::default());
+
+        // Умеренная латентность - пачка держится
+        let held = adjust_batch_tuning(shrunk, 1000, 0.1);
+        assert_eq!(held, shrunk);
+    }
+
+    #[test]
+    fn test_batch_tuning_bounded_by_target_cycle() {
+        // При латентности 1с в целевой цикл влезает 20 кошельков
+        let tuned = adjust_batch_tuning(BatchTuning::default(), 1000, 0.1);
+        assert_eq!(tuned.wallet_batch, (TARGET_CYCLE_MS / 1000) as usize);
+    }
+
+    #[test]
+    fn test_parse_stats_window() {
+        assert_eq!(parse_stats_window("1h"), Some(chrono::Duration::hours(1)));
+        assert_eq!(parse_stats_window("24h"), Some(chrono::Duration::hours(24)));
+        assert_eq!(parse_stats_window("7d"), Some(chrono::Duration::days(7)));
+        assert_eq!(parse_stats_window("30m"), None);
+    }
+}
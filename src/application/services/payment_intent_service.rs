@@ -0,0 +1,394 @@
+//! # Сервис платежных намерений
+//!
+//! Создание и отслеживание payment intents - ожидаемых входящих платежей
+//! с опциональным refund адресом клиента для возвратных флоу
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+
+use crate::application::dto::{CreatePaymentIntentRequest, PaymentIntentResponse};
+use crate::domain::{DomainError, TransactionStatus, TronValidator};
+use crate::infrastructure::database::{models::*, schema, DbPool};
+use crate::utils::{bigdecimal_to_decimal, decimal_to_bigdecimal, parse_stored_metadata};
+
+/// Сервис для работы с платежными намерениями
+pub struct PaymentIntentService {
+    db: DbPool,
+    /// Абсолютный допуск недоплаты при матчинге депозита (USDT)
+    tolerance_absolute: Decimal,
+    /// Процентный допуск недоплаты от ожидаемой суммы
+    tolerance_percent: Decimal,
+}
+
+impl PaymentIntentService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool) -> Self {
+        Self {
+            db,
+            tolerance_absolute: Decimal::new(1, 2), // 0.01 USDT
+            tolerance_percent: Decimal::ZERO,
+        }
+    }
+
+    /// Задает допуски недоплаты для матчинга депозитов.
+    /// Прощает sub-cent dust от комиссий бирж (99.995 закрывает инвойс на 100)
+    pub fn with_amount_tolerance(mut self, absolute: Decimal, percent: Decimal) -> Self {
+        self.tolerance_absolute = absolute;
+        self.tolerance_percent = percent;
+        self
+    }
+
+    /// Удовлетворяет ли полученная сумма ожидаемую с учетом допуска
+    fn satisfies_expected(&self, received: Decimal, expected: Decimal) -> bool {
+        received
+            >= expected - tolerance_for(self.tolerance_absolute, self.tolerance_percent, expected)
+    }
+
+    /// Пытается закрыть открытое платежное намерение кошелька депозитом.
+    ///
+    /// Намерения просматриваются от старых к новым; подходит первое,
+    /// чья ожидаемая сумма покрыта с учетом допуска. Прощенная недоплата
+    /// записывается в shortfall
+    pub async fn match_deposit(
+        &self,
+        wallet_id: i64,
+        tx_hash: &str,
+        amount: Decimal,
+    ) -> Result<Option<PaymentIntentResponse>> {
+        let mut conn = self.db.get().await?;
+
+        let now = Utc::now();
+        let open_intents: Vec<PaymentIntentModel> = schema::payment_intents::table
+            .filter(schema::payment_intents::wallet_id.eq(wallet_id))
+            .filter(schema::payment_intents::status.eq(TransactionStatus::Pending.as_db_str()))
+            .filter(
+                schema::payment_intents::expires_at
+                    .is_null()
+                    .or(schema::payment_intents::expires_at.gt(now)),
+            )
+            .order(schema::payment_intents::created_at.asc())
+            .load(&mut conn)
+            .await?;
+
+        // Кодированные суммы матчатся только точно: суффикс уникален
+        // в пределах кошелька, поэтому атрибуция детерминированна
+        for intent in &open_intents {
+            if intent.amount_encoded
+                && bigdecimal_to_decimal(intent.expected_amount.clone()) == amount
+            {
+                let matched: PaymentIntentModel =
+                    diesel::update(schema::payment_intents::table.find(intent.id))
+                        .set((
+                            schema::payment_intents::status
+                                .eq(TransactionStatus::Completed.as_db_str()),
+                            schema::payment_intents::matched_tx_hash.eq(tx_hash),
+                            schema::payment_intents::completed_at.eq(now),
+                        ))
+                        .get_result(&mut conn)
+                        .await?;
+
+                tracing::info!(
+                    "✅ Намерение ID {} закрыто депозитом {} по кодированной сумме {} USDT",
+                    matched.id,
+                    tx_hash,
+                    amount
+                );
+
+                return Ok(Some(Self::model_to_response(matched)));
+            }
+        }
+
+        for intent in open_intents {
+            // Кодированные намерения не участвуют в FIFO-матчинге с допуском,
+            // иначе чужой депозит может забрать их суффикс
+            if intent.amount_encoded {
+                continue;
+            }
+
+            let expected = bigdecimal_to_decimal(intent.expected_amount.clone());
+            if !self.satisfies_expected(amount, expected) {
+                continue;
+            }
+
+            let shortfall = (expected - amount).max(Decimal::ZERO);
+            let shortfall_db =
+                (shortfall > Decimal::ZERO).then(|| decimal_to_bigdecimal(shortfall));
+
+            let matched: PaymentIntentModel =
+                diesel::update(schema::payment_intents::table.find(intent.id))
+                    .set((
+                        schema::payment_intents::status
+                            .eq(TransactionStatus::Completed.as_db_str()),
+                        schema::payment_intents::matched_tx_hash.eq(tx_hash),
+                        schema::payment_intents::completed_at.eq(now),
+                        schema::payment_intents::shortfall.eq(shortfall_db),
+                    ))
+                    .get_result(&mut conn)
+                    .await?;
+
+            if shortfall > Decimal::ZERO {
+                tracing::info!(
+                    "✅ Намерение ID {} закрыто депозитом {} с допуском: получено {}, ожидалось {}, недоплата {}",
+                    matched.id,
+                    tx_hash,
+                    amount,
+                    expected,
+                    shortfall
+                );
+            } else {
+                tracing::info!(
+                    "✅ Намерение ID {} закрыто депозитом {} на {} USDT",
+                    matched.id,
+                    tx_hash,
+                    amount
+                );
+            }
+
+            return Ok(Some(Self::model_to_response(matched)));
+        }
+
+        Ok(None)
+    }
+
+    /// Создание нового платежного намерения
+    /// Refund адрес (если указан) валидируется как TRON адрес, чтобы
+    /// возвраты не зависели от from_address депозита (может быть горячим
+    /// кошельком биржи)
+    pub async fn create_intent(
+        &self,
+        request: CreatePaymentIntentRequest,
+    ) -> Result<PaymentIntentResponse> {
+        // 1. Валидация входных данных
+        TronValidator::validate_amount(request.expected_amount)
+            .map_err(|e| anyhow::anyhow!("Валидация суммы: {}", e))?;
+
+        if let Some(ref_id) = &request.reference_id {
+            TronValidator::validate_reference_id(ref_id)
+                .map_err(|e| anyhow::anyhow!("Валидация reference_id: {}", e))?;
+        }
+
+        if let Some(refund_address) = &request.refund_address {
+            TronValidator::validate_address(refund_address)
+                .map_err(|e| anyhow::anyhow!("Валидация refund адреса: {}", e))?;
+        }
+
+        // Метаданные хранятся и возвращаются как есть - валидируем
+        // только форму (объект) и размер
+        if let Some(metadata) = &request.metadata {
+            TronValidator::validate_metadata(metadata)
+                .map_err(|e| anyhow::anyhow!("Валидация metadata: {}", e))?;
+        }
+
+        // 2. Проверяем существование кошелька
+        let mut conn = self.db.get().await?;
+        schema::wallets::table
+            .find(request.wallet_id)
+            .first::<WalletModel>(&mut conn)
+            .await
+            .map_err(|_| anyhow::anyhow!("Кошелек с ID {} не найден", request.wallet_id))?;
+
+        // 3. Кодирование суммы: уникальный центовый суффикс на кошелек,
+        // чтобы депозит атрибутировался детерминированно по точной сумме
+        let amount_encoded = request.encode_amount.unwrap_or(false);
+        let expected_amount = if amount_encoded {
+            let taken: Vec<PaymentIntentModel> = schema::payment_intents::table
+                .filter(schema::payment_intents::wallet_id.eq(request.wallet_id))
+                .filter(schema::payment_intents::status.eq(TransactionStatus::Pending.as_db_str()))
+                .load(&mut conn)
+                .await?;
+            let taken: Vec<Decimal> = taken
+                .into_iter()
+                .map(|intent| bigdecimal_to_decimal(intent.expected_amount))
+                .collect();
+
+            allocate_encoded_amount(request.expected_amount, &taken).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Все центовые суффиксы для кошелька {} заняты открытыми намерениями",
+                    request.wallet_id
+                )
+            })?
+        } else {
+            request.expected_amount
+        };
+
+        // 4. Создаем запись в БД
+        let expires_at = request
+            .expires_in_minutes
+            .map(|minutes| Utc::now() + Duration::minutes(minutes));
+
+        let new_intent = NewPaymentIntent {
+            wallet_id: request.wallet_id,
+            expected_amount: decimal_to_bigdecimal(expected_amount),
+            reference_id: request.reference_id.clone(),
+            refund_address: request.refund_address.clone(),
+            status: TransactionStatus::Pending.as_db_str().to_string(),
+            expires_at,
+            amount_encoded,
+            metadata: request.metadata.as_ref().map(|value| value.to_string()),
+        };
+
+        let intent: PaymentIntentModel = diesel::insert_into(schema::payment_intents::table)
+            .values(&new_intent)
+            .get_result(&mut conn)
+            .await?;
+
+        if amount_encoded {
+            tracing::info!(
+                "Создано платежное намерение ID: {} с кодированной суммой {} USDT (запрошено {}) для кошелька {}",
+                intent.id,
+                expected_amount,
+                request.expected_amount,
+                request.wallet_id
+            );
+        } else {
+            tracing::info!(
+                "Создано платежное намерение ID: {} на {} USDT для кошелька {}",
+                intent.id,
+                request.expected_amount,
+                request.wallet_id
+            );
+        }
+
+        Ok(Self::model_to_response(intent))
+    }
+
+    /// Получение платежного намерения по ID
+    pub async fn get_intent(
+        &self,
+        intent_id: i64,
+    ) -> Result<Option<PaymentIntentResponse>, DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        let intent_result = schema::payment_intents::table
+            .find(intent_id)
+            .first::<PaymentIntentModel>(&mut conn)
+            .await;
+
+        match intent_result {
+            Ok(intent) => Ok(Some(Self::model_to_response(intent))),
+            Err(diesel::result::Error::NotFound) => Ok(None),
+            Err(_) => Err(DomainError::ConfigurationError {
+                message: "Ошибка БД".to_string(),
+            }),
+        }
+    }
+
+    /// Конвертирует модель в ответ
+    fn model_to_response(intent: PaymentIntentModel) -> PaymentIntentResponse {
+        PaymentIntentResponse {
+            id: intent.id,
+            wallet_id: intent.wallet_id,
+            expected_amount: bigdecimal_to_decimal(intent.expected_amount),
+            reference_id: intent.reference_id,
+            refund_address: intent.refund_address,
+            status: intent.status,
+            matched_tx_hash: intent.matched_tx_hash,
+            created_at: intent.created_at,
+            expires_at: intent.expires_at,
+            completed_at: intent.completed_at,
+            shortfall: intent.shortfall.map(bigdecimal_to_decimal),
+            amount_encoded: intent.amount_encoded,
+            metadata: parse_stored_metadata(intent.metadata),
+        }
+    }
+}
+
+/// Допуск недоплаты для конкретной ожидаемой суммы:
+/// больший из абсолютного и процентного
+fn tolerance_for(absolute: Decimal, percent: Decimal, expected: Decimal) -> Decimal {
+    let percent_tolerance = expected * percent / Decimal::new(100, 0);
+    absolute.max(percent_tolerance)
+}
+
+/// Подбирает уникальную кодированную сумму: базовая сумма (округленная
+/// вниз до цента) плюс центовый суффикс 0.00-0.99, не занятый другими
+/// открытыми намерениями кошелька. Старт суффикса псевдослучайный,
+/// дальше линейный перебор - коллизии исключаются за один проход.
+/// `None`, если все 100 суффиксов заняты
+fn allocate_encoded_amount(base: Decimal, taken: &[Decimal]) -> Option<Decimal> {
+    let floor = base.trunc_with_scale(2);
+    let start = (rand::random::<u8>() % 100) as i64;
+
+    for offset in 0..100 {
+        let cents = (start + offset) % 100;
+        let candidate = floor + Decimal::new(cents, 2);
+
+        // Кодированная сумма не должна быть меньше запрошенной
+        if candidate < base {
+            continue;
+        }
+
+        if !taken.contains(&candidate) {
+            return Some(candidate);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absolute_tolerance_forgives_dust() {
+        let tolerance = tolerance_for(Decimal::new(1, 2), Decimal::ZERO, Decimal::new(100, 0));
+
+        // 99.995 закрывает инвойс на 100 при допуске 0.01
+        assert!(Decimal::new(99_995, 3) >= Decimal::new(100, 0) - tolerance);
+        // 99.98 - уже нет
+        assert!(Decimal::new(99_98, 2) < Decimal::new(100, 0) - tolerance);
+    }
+
+    #[test]
+    fn test_encoded_amount_is_unique_and_covers_base() {
+        let base = Decimal::new(10_000, 2); // 100.00
+        let mut taken = Vec::new();
+
+        // Последовательное выделение не дает коллизий
+        for _ in 0..50 {
+            let allocated = allocate_encoded_amount(base, &taken).unwrap();
+            assert!(allocated >= base);
+            assert!(allocated < base + Decimal::new(1, 0));
+            assert!(!taken.contains(&allocated));
+            taken.push(allocated);
+        }
+    }
+
+    #[test]
+    fn test_encoded_amount_exhaustion() {
+        let base = Decimal::new(50, 0);
+        let taken: Vec<Decimal> = (0..100)
+            .map(|cents| base.trunc_with_scale(2) + Decimal::new(cents, 2))
+            .collect();
+
+        assert_eq!(allocate_encoded_amount(base, &taken), None);
+    }
+
+    #[test]
+    fn test_percent_tolerance_scales_with_amount() {
+        let absolute = Decimal::new(1, 2);
+        let percent = Decimal::new(1, 1);
+
+        // 0.1% от 10000 = 10 USDT больше абсолютных 0.01
+        assert_eq!(
+            tolerance_for(absolute, percent, Decimal::new(10_000, 0)),
+            Decimal::new(10, 0)
+        );
+
+        // Для мелкого инвойса действует абсолютный допуск
+        assert_eq!(
+            tolerance_for(absolute, percent, Decimal::new(1, 0)),
+            Decimal::new(1, 2)
+        );
+    }
+}
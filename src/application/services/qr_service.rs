@@ -0,0 +1,197 @@
+//! # Сервис QR кодов платежных URI
+//!
+//! Рендерит QR код платежного URI `tron:<address>` (опционально с суммой
+//! и контрактом токена) в SVG или PNG, чтобы фронтендам мерчантов не
+//! требовался собственный стек генерации QR. Отрендеренные изображения
+//! кэшируются in-memory: адрес кошелька неизменен, а набор запрашиваемых
+//! размеров и сумм на практике невелик.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use qrcode::QrCode;
+use rust_decimal::Decimal;
+
+use crate::config::QrConfig;
+
+/// Минимальный размер изображения (px) - мельче QR нечитаем камерой
+const QR_MIN_SIZE_PX: u32 = 64;
+
+/// Формат изображения QR кода
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QrFormat {
+    Svg,
+    Png,
+}
+
+impl QrFormat {
+    /// Парсит значение query параметра `format`
+    pub fn from_query_str(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "svg" => Some(Self::Svg),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+
+    /// Content-Type ответа для формата
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Png => "image/png",
+        }
+    }
+}
+
+/// Ключ кэша: все параметры, влияющие на итоговые байты изображения
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QrCacheKey {
+    address: String,
+    format: QrFormat,
+    amount: Option<String>,
+    size: u32,
+}
+
+/// Генератор QR кодов платежных URI кошельков
+pub struct QrCodeService {
+    config: QrConfig,
+    /// Контракт USDT: попадает в URI как `token=`, когда задана сумма
+    usdt_contract: String,
+    cache: Mutex<HashMap<QrCacheKey, Arc<Vec<u8>>>>,
+}
+
+impl QrCodeService {
+    /// Создает сервис с пустым кэшем
+    pub fn new(config: QrConfig, usdt_contract: String) -> Self {
+        Self {
+            config,
+            usdt_contract,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Платежный URI адреса: `tron:<address>`, с суммой - плюс
+    /// `token=<контракт USDT>` и `amount=` для кошельков плательщиков
+    pub fn payment_uri(&self, address: &str, amount: Option<Decimal>) -> String {
+        match amount {
+            Some(amount) => format!(
+                "tron:{}?token={}&amount={}",
+                address,
+                self.usdt_contract,
+                amount.normalize()
+            ),
+            None => format!("tron:{}", address),
+        }
+    }
+
+    /// Рендерит QR код платежного URI в запрошенном формате.
+    /// Размер ограничивается конфигом, результат кэшируется
+    pub fn render(
+        &self,
+        address: &str,
+        format: QrFormat,
+        amount: Option<Decimal>,
+        size: Option<u32>,
+    ) -> Result<Arc<Vec<u8>>> {
+        let size = size
+            .unwrap_or(self.config.default_size_px)
+            .clamp(QR_MIN_SIZE_PX, self.config.max_size_px.max(QR_MIN_SIZE_PX));
+
+        let key = QrCacheKey {
+            address: address.to_string(),
+            format,
+            amount: amount.map(|amount| amount.normalize().to_string()),
+            size,
+        };
+
+        if let Some(bytes) = self.cache.lock().unwrap().get(&key) {
+            return Ok(bytes.clone());
+        }
+
+        let uri = self.payment_uri(address, amount);
+        let code = QrCode::new(uri.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Не удалось построить QR код: {:?}", e))?;
+
+        let bytes = match format {
+            QrFormat::Svg => code
+                .render::<qrcode::render::svg::Color>()
+                .min_dimensions(size, size)
+                .build()
+                .into_bytes(),
+            QrFormat::Png => {
+                let rendered = code
+                    .render::<image::Luma<u8>>()
+                    .min_dimensions(size, size)
+                    .build();
+
+                let mut bytes = Vec::new();
+                rendered.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)?;
+                bytes
+            }
+        };
+
+        let bytes = Arc::new(bytes);
+        let mut cache = self.cache.lock().unwrap();
+        // Простая защита от распухания: при переполнении кэш сбрасывается
+        // целиком - переrendering дешев, а LRU здесь был бы оверкиллом
+        if cache.len() >= self.config.cache_capacity.max(1) {
+            cache.clear();
+        }
+        cache.insert(key, bytes.clone());
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_address;
+
+    fn test_service() -> QrCodeService {
+        QrCodeService::new(
+            QrConfig::default(),
+            "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_payment_uri_with_and_without_amount() {
+        let service = test_service();
+        let address = test_address(1);
+
+        assert_eq!(
+            service.payment_uri(&address, None),
+            format!("tron:{}", address)
+        );
+
+        let uri = service.payment_uri(&address, Some(Decimal::new(12_500_000, 6)));
+        assert!(uri.starts_with(&format!("tron:{}?token=", address)));
+        assert!(uri.ends_with("&amount=12.5"));
+    }
+
+    #[test]
+    fn test_render_formats_and_cache() {
+        let service = test_service();
+        let address = test_address(2);
+
+        let svg = service.render(&address, QrFormat::Svg, None, None).unwrap();
+        assert!(String::from_utf8_lossy(&svg).contains("<svg"));
+
+        // Повторный запрос отдается из кэша (тот же буфер)
+        let cached = service.render(&address, QrFormat::Svg, None, None).unwrap();
+        assert!(Arc::ptr_eq(&svg, &cached));
+
+        let png = service
+            .render(
+                &address,
+                QrFormat::Png,
+                Some(Decimal::new(10, 0)),
+                Some(128),
+            )
+            .unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
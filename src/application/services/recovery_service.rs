@@ -0,0 +1,283 @@
+//! # Восстановление потерянных связей с on-chain транзакциями
+//!
+//! Иногда broadcast проходит, но хэш теряется до записи в БД (рестарт
+//! процесса, обрыв соединения с Postgres). Трансфер остается без tx_hash,
+//! хотя средства ушли. Сервис сканирует свежую on-chain историю
+//! мастер-кошельков и кошельков отправителей, сопоставляет транзакции
+//! с "осиротевшими" трансферами по адресу назначения, сумме и временному
+//! окну и позволяет оператору явно привязать найденный хэш.
+//!
+//! Привязка - ручное решение оператора (CLI или admin endpoint):
+//! автоматическая привязка опасна при нескольких одинаковых переводах
+//! на один адрес
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+use crate::domain::TransactionStatus;
+use crate::infrastructure::database::{models::OutgoingTransferModel, schema, DbPool};
+use crate::infrastructure::TronGridClient;
+use crate::utils::bigdecimal_to_decimal;
+
+/// Сколько последних TRC20 транзакций запрашивать на адрес
+const SCAN_PAGE_SIZE: u32 = 100;
+
+/// Допустимое опережение: on-chain транзакция не может быть старше
+/// создания трансфера больше чем на это значение (минуты)
+const MATCH_LOOKBEHIND_MINUTES: i64 = 10;
+
+/// Кандидат на привязку: on-chain транзакция, похожая на потерянную
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanCandidate {
+    pub tx_hash: String,
+    pub from_address: String,
+    pub amount: Decimal,
+    pub timestamp: DateTime<Utc>,
+    /// Сумма совпала с трансфером точно (false - за вычетом комиссии)
+    pub exact_amount_match: bool,
+}
+
+/// Осиротевший трансфер с кандидатами на привязку
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedTransferReport {
+    pub transfer_id: i64,
+    pub from_wallet_address: String,
+    pub to_address: String,
+    pub amount: Decimal,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    /// Найденные on-chain кандидаты (может быть пусто)
+    pub candidates: Vec<OrphanCandidate>,
+}
+
+/// Сервис восстановления связей трансфер - on-chain транзакция
+pub struct OrphanRecoveryService {
+    db: DbPool,
+    tron_client: TronGridClient,
+    /// Контракт USDT - осиротевшие трансферы ищутся в его истории
+    usdt_contract: String,
+    /// Адреса мастер-кошельков: их история сканируется всегда,
+    /// потому что свипы и переводы уходят с них
+    master_addresses: Vec<String>,
+}
+
+impl OrphanRecoveryService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(
+        db: DbPool,
+        tron_client: TronGridClient,
+        usdt_contract: String,
+        master_addresses: Vec<String>,
+    ) -> Self {
+        Self {
+            db,
+            tron_client,
+            usdt_contract,
+            master_addresses,
+        }
+    }
+
+    /// Сканирует трансферы без tx_hash за окно и подбирает каждому
+    /// on-chain кандидатов по адресу назначения, сумме и времени.
+    ///
+    /// Кандидаты - только транзакции, еще не привязанные к другим
+    /// трансферам. Сумма сравнивается точно и за вычетом комиссии
+    /// (fee_payer = recipient уменьшает фактический on-chain перевод)
+    pub async fn scan_orphaned_transfers(
+        &self,
+        window_hours: i64,
+    ) -> Result<Vec<OrphanedTransferReport>> {
+        let cutoff = Utc::now() - chrono::Duration::hours(window_hours.clamp(1, 24 * 30));
+
+        let (orphans, linked_hashes) = {
+            let mut conn = self.db.get().await?;
+
+            // Осиротевшие: без хэша в нетерминальном или FAILED статусе -
+            // PENDING еще не broadcast'ился, его не трогаем
+            let orphans: Vec<(OutgoingTransferModel, String)> = schema::outgoing_transfers::table
+                .inner_join(schema::wallets::table)
+                .filter(schema::outgoing_transfers::tx_hash.is_null())
+                .filter(schema::outgoing_transfers::status.eq_any(vec![
+                    TransactionStatus::Processing.as_db_str(),
+                    TransactionStatus::Failed.as_db_str(),
+                ]))
+                .filter(schema::outgoing_transfers::created_at.ge(cutoff))
+                .select((OutgoingTransferModel::as_select(), schema::wallets::address))
+                .order(schema::outgoing_transfers::created_at.asc())
+                .load(&mut conn)
+                .await?;
+
+            // Уже привязанные хэши не предлагаем повторно
+            let linked_hashes: Vec<Option<String>> = schema::outgoing_transfers::table
+                .filter(schema::outgoing_transfers::tx_hash.is_not_null())
+                .filter(schema::outgoing_transfers::created_at.ge(cutoff))
+                .select(schema::outgoing_transfers::tx_hash)
+                .load(&mut conn)
+                .await?;
+
+            (orphans, linked_hashes)
+        };
+
+        if orphans.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let linked_hashes: HashSet<String> = linked_hashes.into_iter().flatten().collect();
+
+        // История сканируется по отправителям: мастер-кошельки плюс
+        // кошельки осиротевших трансферов
+        let mut scan_addresses: Vec<String> = self.master_addresses.clone();
+        for (_, wallet_address) in &orphans {
+            if !scan_addresses.contains(wallet_address) {
+                scan_addresses.push(wallet_address.clone());
+            }
+        }
+
+        let mut chain_transactions = Vec::new();
+        let mut seen_hashes = HashSet::new();
+
+        for address in &scan_addresses {
+            match self
+                .tron_client
+                .get_trc20_transactions(address, &self.usdt_contract, SCAN_PAGE_SIZE)
+                .await
+            {
+                Ok(transactions) => {
+                    for tx in transactions {
+                        if seen_hashes.insert(tx.tx_hash.clone()) {
+                            chain_transactions.push(tx);
+                        }
+                    }
+                }
+                Err(e) => {
+                    // Частичный скан лучше пустого - продолжаем
+                    warn!("⚠️  Не удалось получить историю {}: {}", address, e);
+                }
+            }
+        }
+
+        let reports = orphans
+            .into_iter()
+            .map(|(transfer, wallet_address)| {
+                let amount = bigdecimal_to_decimal(transfer.amount.clone());
+                let net_amount = transfer
+                    .fee_amount
+                    .clone()
+                    .map(|fee| amount - bigdecimal_to_decimal(fee));
+
+                let earliest =
+                    transfer.created_at - chrono::Duration::minutes(MATCH_LOOKBEHIND_MINUTES);
+
+                let mut candidates: Vec<OrphanCandidate> = chain_transactions
+                    .iter()
+                    .filter(|tx| tx.to_address == transfer.to_address)
+                    .filter(|tx| !linked_hashes.contains(&tx.tx_hash))
+                    .filter(|tx| tx.timestamp >= earliest)
+                    .filter_map(|tx| {
+                        let exact = tx.amount == amount;
+                        let net = net_amount.is_some_and(|net| tx.amount == net);
+                        (exact || net).then(|| OrphanCandidate {
+                            tx_hash: tx.tx_hash.clone(),
+                            from_address: tx.from_address.clone(),
+                            amount: tx.amount,
+                            timestamp: tx.timestamp,
+                            exact_amount_match: exact,
+                        })
+                    })
+                    .collect();
+
+                // Ближайшие по времени к созданию трансфера - первыми
+                candidates.sort_by_key(|candidate| {
+                    (candidate.timestamp - transfer.created_at)
+                        .num_seconds()
+                        .abs()
+                });
+
+                OrphanedTransferReport {
+                    transfer_id: transfer.id,
+                    from_wallet_address: wallet_address,
+                    to_address: transfer.to_address,
+                    amount,
+                    status: transfer.status,
+                    created_at: transfer.created_at,
+                    candidates,
+                }
+            })
+            .collect();
+
+        Ok(reports)
+    }
+
+    /// Привязывает on-chain транзакцию к осиротевшему трансферу.
+    ///
+    /// Хэш проверяется на цепочке (транзакция должна существовать)
+    /// и на уникальность среди уже привязанных. Трансфер переводится
+    /// в COMPLETED - средства фактически ушли
+    pub async fn link_transfer(
+        &self,
+        transfer_id: i64,
+        tx_hash: &str,
+    ) -> Result<OutgoingTransferModel> {
+        let mut conn = self.db.get().await?;
+
+        let transfer: OutgoingTransferModel = schema::outgoing_transfers::table
+            .find(transfer_id)
+            .select(OutgoingTransferModel::as_select())
+            .first(&mut conn)
+            .await
+            .map_err(|_| anyhow::anyhow!("Трансфер {} не найден", transfer_id))?;
+
+        if let Some(existing) = &transfer.tx_hash {
+            return Err(anyhow::anyhow!(
+                "Трансфер {} уже привязан к {}",
+                transfer_id,
+                existing
+            ));
+        }
+
+        let already_linked: i64 = schema::outgoing_transfers::table
+            .filter(schema::outgoing_transfers::tx_hash.eq(tx_hash))
+            .count()
+            .get_result(&mut conn)
+            .await?;
+
+        if already_linked > 0 {
+            return Err(anyhow::anyhow!(
+                "Транзакция {} уже привязана к другому трансферу",
+                tx_hash
+            ));
+        }
+
+        // Хэш должен существовать на цепочке - защита от опечаток оператора
+        let on_chain = self
+            .tron_client
+            .get_transaction_info(tx_hash)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Транзакция {} не найдена на цепочке", tx_hash))?;
+
+        let updated: OutgoingTransferModel =
+            diesel::update(schema::outgoing_transfers::table.find(transfer_id))
+                .set((
+                    schema::outgoing_transfers::tx_hash.eq(tx_hash),
+                    schema::outgoing_transfers::status.eq(TransactionStatus::Completed.as_db_str()),
+                    schema::outgoing_transfers::completed_at.eq(Utc::now()),
+                    schema::outgoing_transfers::error_message.eq(None::<String>),
+                ))
+                .returning(OutgoingTransferModel::as_returning())
+                .get_result(&mut conn)
+                .await?;
+
+        info!(
+            "✅ Трансфер {} привязан к on-chain транзакции {} (блок {:?})",
+            transfer_id, tx_hash, on_chain.block_number
+        );
+
+        Ok(updated)
+    }
+}
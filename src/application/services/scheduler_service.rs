@@ -3,11 +3,116 @@
 //! Выполняет периодические задачи (monitoring, cleanup, etc.)
 
 use anyhow::Result;
+use chrono::Timelike;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Text, Timestamptz};
+use diesel_async::RunQueryDsl;
 use std::sync::Arc;
 use tokio::time::{interval, Duration};
 use tracing::{error, info, warn};
 
-use super::{TransactionMonitoringService, TransferService, WebhookService};
+use crate::infrastructure::database::models::{NewSchedulerRun, SchedulerRunModel};
+use crate::infrastructure::database::{schema, DbPool};
+use crate::infrastructure::{Notification, NotificationDispatcher, NotificationSeverity};
+
+use super::{
+    BalanceAlertService, CanaryService, InvoiceService, SweepService, TransactionMonitoringService,
+    TransferIngestionService, TransferService, WebhookService,
+};
+
+/// Сколько дней хранить историю запусков планировщика
+const RUN_HISTORY_RETENTION_DAYS: i64 = 14;
+
+/// Журнал запусков задач планировщика в scheduler_runs.
+/// Запись best-effort: недоступность БД не валит задачу
+#[derive(Clone)]
+pub struct SchedulerRunLog {
+    db: DbPool,
+}
+
+impl SchedulerRunLog {
+    /// Создает журнал поверх пула БД
+    pub fn new(db: DbPool) -> Self {
+        Self { db }
+    }
+
+    /// Записывает выполнение задачи (best-effort)
+    pub async fn record(
+        &self,
+        task_name: &str,
+        started_at: chrono::DateTime<chrono::Utc>,
+        duration_ms: i64,
+        items_processed: i64,
+        error_message: Option<String>,
+    ) {
+        let run = NewSchedulerRun {
+            task_name: task_name.to_string(),
+            started_at,
+            duration_ms,
+            items_processed,
+            outcome: if error_message.is_none() {
+                "success".to_string()
+            } else {
+                "error".to_string()
+            },
+            error_message,
+        };
+
+        let result = async {
+            let mut conn = self.db.get().await?;
+            diesel::insert_into(schema::scheduler_runs::table)
+                .values(&run)
+                .execute(&mut conn)
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!(
+                "⚠️  Не удалось записать запуск {} в историю: {}",
+                task_name, e
+            );
+        }
+    }
+
+    /// Возвращает последние запуски, опционально по одной задаче
+    pub async fn list_runs(
+        &self,
+        task_name: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<SchedulerRunModel>> {
+        let mut conn = self.db.get().await?;
+
+        let mut query = schema::scheduler_runs::table
+            .order(schema::scheduler_runs::id.desc())
+            .limit(limit.clamp(1, 500))
+            .into_boxed();
+
+        if let Some(task_name) = task_name {
+            query = query.filter(schema::scheduler_runs::task_name.eq(task_name));
+        }
+
+        Ok(query
+            .select(SchedulerRunModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Удаляет записи старше ретеншна, возвращает число удаленных
+    pub async fn prune(&self, retention_days: i64) -> Result<usize> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days);
+        let mut conn = self.db.get().await?;
+
+        let deleted = diesel::delete(
+            schema::scheduler_runs::table.filter(schema::scheduler_runs::started_at.lt(cutoff)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted)
+    }
+}
 
 /// Конфигурация планировщика
 #[derive(Debug, Clone)]
@@ -16,6 +121,35 @@ pub struct SchedulerConfig {
     pub transfer_processing_interval_seconds: u64,
     pub cleanup_interval_hours: u64,
     pub health_check_interval_minutes: u64,
+    pub daily_summary_interval_hours: u64,
+    /// Интервал отчета о распухании таблиц
+    pub maintenance_interval_hours: u64,
+    /// Доля мертвых кортежей (0.0-1.0), с которой таблица считается распухшей
+    pub maintenance_dead_tuple_warn_ratio: f64,
+    /// Запускать ли ANALYZE для распухших таблиц в тихом окне
+    pub maintenance_analyze_enabled: bool,
+    /// Начало тихого окна для ANALYZE (час UTC)
+    pub maintenance_quiet_window_start_hour: u32,
+    /// Конец тихого окна для ANALYZE (час UTC)
+    pub maintenance_quiet_window_end_hour: u32,
+    /// Включено ли окно обслуживания (пауза обработки трансферов)
+    pub maintenance_window_enabled: bool,
+    /// Начало окна обслуживания (час UTC)
+    pub maintenance_window_start_hour: u32,
+    /// Конец окна обслуживания (час UTC)
+    pub maintenance_window_end_hour: u32,
+    /// Интервал дотягивания зависших webhook доставок из outbox'а
+    pub webhook_redelivery_interval_seconds: u64,
+    /// Интервал проверки подтверждений broadcast-транзакций
+    pub confirmation_interval_seconds: u64,
+    /// Интервал консолидации средств кошельков (sweep)
+    pub sweep_interval_seconds: u64,
+    /// Интервал обработки очереди массовой загрузки трансферов
+    pub ingestion_interval_seconds: u64,
+    /// Интервал сверки пороговых подписок на балансы
+    pub balance_alerts_interval_seconds: u64,
+    /// Интервал истечения просроченных инвойсов
+    pub invoice_expiry_interval_seconds: u64,
 }
 
 impl Default for SchedulerConfig {
@@ -25,16 +159,87 @@ impl Default for SchedulerConfig {
             transfer_processing_interval_seconds: 60, // Обработка pending каждую минуту
             cleanup_interval_hours: 24,               // Очистка каждые 24 часа
             health_check_interval_minutes: 5,         // Health check каждые 5 минут
+            daily_summary_interval_hours: 24,         // Сводка по расчетам раз в сутки
+            maintenance_interval_hours: 6,            // Отчет о распухании каждые 6 часов
+            maintenance_dead_tuple_warn_ratio: 0.2,   // 20% мертвых кортежей
+            maintenance_analyze_enabled: false,       // ANALYZE только при явном включении
+            maintenance_quiet_window_start_hour: 2,   // Тихое окно 02:00-05:00 UTC
+            maintenance_quiet_window_end_hour: 5,
+            maintenance_window_enabled: false, // Окно обслуживания выключено
+            maintenance_window_start_hour: 0,
+            maintenance_window_end_hour: 0,
+            webhook_redelivery_interval_seconds: 60, // Outbox доставок каждую минуту
+            confirmation_interval_seconds: 30,       // Подтверждения каждые 30 сек
+            sweep_interval_seconds: 3600,            // Консолидация каждый час
+            ingestion_interval_seconds: 10,          // Очередь загрузки каждые 10 сек
+            balance_alerts_interval_seconds: 60,     // Сверка порогов каждую минуту
+            invoice_expiry_interval_seconds: 60,     // Истечение инвойсов каждую минуту
         }
     }
 }
 
+/// Доля мертвых кортежей в таблице (0.0 - 1.0)
+fn dead_tuple_ratio(live_tuples: i64, dead_tuples: i64) -> f64 {
+    let total = live_tuples + dead_tuples;
+    if total <= 0 {
+        return 0.0;
+    }
+    dead_tuples as f64 / total as f64
+}
+
+/// Попадает ли час (UTC) в тихое окно.
+/// Окно может переходить через полночь (например 22-4);
+/// окно нулевой длины (start == end) считается закрытым
+fn in_quiet_window(hour: u32, start_hour: u32, end_hour: u32) -> bool {
+    if start_hour == end_hour {
+        return false;
+    }
+
+    if start_hour < end_hour {
+        hour >= start_hour && hour < end_hour
+    } else {
+        hour >= start_hour || hour < end_hour
+    }
+}
+
+/// Строка статистики распухания из pg_stat_user_tables
+#[derive(QueryableByName, Debug)]
+struct TableBloatRow {
+    #[diesel(sql_type = Text)]
+    table_name: String,
+    #[diesel(sql_type = BigInt)]
+    live_tuples: i64,
+    #[diesel(sql_type = BigInt)]
+    dead_tuples: i64,
+    #[diesel(sql_type = Nullable<Timestamptz>)]
+    last_autovacuum: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 /// Планировщик задач
 pub struct TaskScheduler {
     config: SchedulerConfig,
     monitoring_service: Arc<TransactionMonitoringService>,
     transfer_service: Arc<TransferService>,
     webhook_service: Option<Arc<WebhookService>>,
+    /// Канал операционных уведомлений (алерты и ежедневные сводки)
+    notifications: Option<Arc<NotificationDispatcher>>,
+    /// Пул БД для задачи обслуживания (отчет о распухании, ANALYZE)
+    db: Option<DbPool>,
+    /// Журнал запусков задач (появляется вместе с пулом БД)
+    run_log: Option<SchedulerRunLog>,
+    /// Сервис консолидации средств (без него задача sweep не запускается)
+    sweep_service: Option<Arc<SweepService>>,
+    /// Сервис массовой загрузки (без него задача ingestion не запускается)
+    ingestion_service: Option<Arc<TransferIngestionService>>,
+    /// Сервис пороговых подписок (без него задача алертов не запускается)
+    balance_alert_service: Option<Arc<BalanceAlertService>>,
+    /// Сервис инвойсов (без него задача истечения не запускается)
+    invoice_service: Option<Arc<InvoiceService>>,
+    /// Сервис канарейки (без него задача канарейки не запускается)
+    canary_service: Option<Arc<CanaryService>>,
+    /// Координатор graceful shutdown: по сигналу все интервальные
+    /// циклы останавливаются разом
+    shutdown: Option<Arc<crate::infrastructure::ShutdownCoordinator>>,
 }
 
 impl TaskScheduler {
@@ -50,22 +255,437 @@ impl TaskScheduler {
             monitoring_service,
             transfer_service,
             webhook_service,
+            notifications: None,
+            db: None,
+            run_log: None,
+            sweep_service: None,
+            ingestion_service: None,
+            balance_alert_service: None,
+            invoice_service: None,
+            canary_service: None,
+            shutdown: None,
         }
     }
 
-    /// Запускает все фоновые задачи
+    /// Подключает канал операционных уведомлений
+    pub fn with_notifications(mut self, notifications: Arc<NotificationDispatcher>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Подключает пул БД: задача обслуживания и журнал запусков
+    pub fn with_database(mut self, db: DbPool) -> Self {
+        self.run_log = Some(SchedulerRunLog::new(db.clone()));
+        self.db = Some(db);
+        self
+    }
+
+    /// Подключает сервис консолидации средств (задача sweep)
+    pub fn with_sweep_service(mut self, sweep_service: Arc<SweepService>) -> Self {
+        self.sweep_service = Some(sweep_service);
+        self
+    }
+
+    /// Подключает сервис массовой загрузки (задача ingestion)
+    pub fn with_ingestion_service(
+        mut self,
+        ingestion_service: Arc<TransferIngestionService>,
+    ) -> Self {
+        self.ingestion_service = Some(ingestion_service);
+        self
+    }
+
+    /// Подключает сервис пороговых подписок (задача balance_alerts)
+    pub fn with_balance_alert_service(
+        mut self,
+        balance_alert_service: Arc<BalanceAlertService>,
+    ) -> Self {
+        self.balance_alert_service = Some(balance_alert_service);
+        self
+    }
+
+    /// Подключает сервис инвойсов (задача invoice_expiry)
+    pub fn with_invoice_service(mut self, invoice_service: Arc<InvoiceService>) -> Self {
+        self.invoice_service = Some(invoice_service);
+        self
+    }
+
+    /// Подключает сервис канарейки (задача canary)
+    pub fn with_canary_service(mut self, canary_service: Arc<CanaryService>) -> Self {
+        self.canary_service = Some(canary_service);
+        self
+    }
+
+    /// Подключает координатор graceful shutdown
+    pub fn with_shutdown(
+        mut self,
+        shutdown: Arc<crate::infrastructure::ShutdownCoordinator>,
+    ) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Записывает выполнение задачи в историю запусков (no-op без БД)
+    async fn record_run(
+        &self,
+        task_name: &str,
+        started_at: chrono::DateTime<chrono::Utc>,
+        started: std::time::Instant,
+        items_processed: i64,
+        error_message: Option<String>,
+    ) {
+        if let Some(run_log) = &self.run_log {
+            run_log
+                .record(
+                    task_name,
+                    started_at,
+                    started.elapsed().as_millis() as i64,
+                    items_processed,
+                    error_message,
+                )
+                .await;
+        }
+    }
+
+    /// Запускает все фоновые задачи. При подключенном координаторе
+    /// graceful shutdown завершается по сигналу - интервальные циклы
+    /// останавливаются и новые итерации не начинаются
     pub async fn start(&self) -> Result<()> {
         info!("🕒 Запуск планировщика задач...");
 
         // Запускаем все задачи параллельно
-        tokio::try_join!(
-            self.start_monitoring_task(),
-            self.start_transfer_processing_task(),
-            self.start_cleanup_task(),
-            self.start_health_check_task()
-        )?;
+        let tasks = async {
+            tokio::try_join!(
+                self.start_monitoring_task(),
+                self.start_transfer_processing_task(),
+                self.start_cleanup_task(),
+                self.start_health_check_task(),
+                self.start_daily_summary_task(),
+                self.start_maintenance_task(),
+                self.start_webhook_redelivery_task(),
+                self.start_confirmation_tracking_task(),
+                self.start_sweep_task(),
+                self.start_ingestion_task(),
+                self.start_balance_alerts_task(),
+                self.start_invoice_expiry_task(),
+                self.start_canary_task()
+            )?;
 
-        Ok(())
+            Ok(())
+        };
+
+        match &self.shutdown {
+            Some(shutdown) => tokio::select! {
+                result = tasks => result,
+                _ = shutdown.cancelled() => {
+                    info!("🛑 Планировщик остановлен по сигналу завершения");
+                    Ok(())
+                }
+            },
+            None => tasks.await,
+        }
+    }
+
+    /// Задача трекинга подтверждений: переводит broadcast-транзакции
+    /// в COMPLETED после 19 блоков или в FAILED по on-chain receipt'у
+    async fn start_confirmation_tracking_task(&self) -> Result<()> {
+        info!(
+            "🕒 Запуск трекинга подтверждений (интервал: {} сек)",
+            self.config.confirmation_interval_seconds
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.confirmation_interval_seconds,
+        ));
+        let transfer_service = self.transfer_service.clone();
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            let (settled, error) = match transfer_service.confirm_broadcast_transfers().await {
+                Ok(settled) => {
+                    if settled > 0 {
+                        info!("🕒 Трансферов закрыто по подтверждениям: {}", settled);
+                    }
+                    (settled as i64, None)
+                }
+                Err(e) => {
+                    error!("❌ Ошибка трекинга подтверждений: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            self.record_run("confirmation_tracking", started_at, started, settled, error)
+                .await;
+        }
+    }
+
+    /// Задача дотягивания webhook доставок из outbox'а: подбирает
+    /// PENDING записи с наступившим next_retry_at (inline-доставка
+    /// не справилась или процесс был перезапущен) и доставляет их
+    /// с экспоненциальным backoff
+    async fn start_webhook_redelivery_task(&self) -> Result<()> {
+        let Some(webhook_service) = self.webhook_service.clone() else {
+            info!("📮 Webhook сервис не подключен - задача redelivery не запускается");
+            return Ok(());
+        };
+
+        info!(
+            "📮 Запуск redelivery webhook доставок (интервал: {} сек)",
+            self.config.webhook_redelivery_interval_seconds
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.webhook_redelivery_interval_seconds,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            let (processed, error) = match webhook_service.process_due_deliveries(50).await {
+                Ok(processed) => {
+                    if processed > 0 {
+                        info!("📮 Дотянуто webhook доставок из outbox'а: {}", processed);
+                    }
+                    (processed as i64, None)
+                }
+                Err(e) => {
+                    error!("❌ Ошибка обработки outbox'а webhook доставок: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            self.record_run("webhook_redelivery", started_at, started, processed, error)
+                .await;
+        }
+    }
+
+    /// Задача консолидации средств: кошельки с балансом выше порога
+    /// выметаются sweep-трансфером на мастер-кошелек
+    async fn start_sweep_task(&self) -> Result<()> {
+        let Some(sweep_service) = self.sweep_service.clone() else {
+            info!("🧹 Sweep сервис не подключен - задача консолидации не запускается");
+            return Ok(());
+        };
+
+        if !sweep_service.is_enabled() {
+            info!("🧹 Консолидация выключена в конфигурации - задача не запускается");
+            return Ok(());
+        }
+
+        info!(
+            "🧹 Запуск консолидации средств (интервал: {} сек)",
+            self.config.sweep_interval_seconds
+        );
+
+        let mut interval = interval(Duration::from_secs(self.config.sweep_interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            // Итог запуска сам пишется в sweep_runs внутри sweep_once -
+            // в журнал планировщика уходит только факт выполнения
+            let (swept, error) = match sweep_service.sweep_once().await {
+                Ok(summary) => {
+                    if summary.wallets_swept > 0 {
+                        info!(
+                            "🧹 Консолидация: выметено {} кошельков на {} USDT",
+                            summary.wallets_swept, summary.total_amount
+                        );
+                    }
+                    (summary.wallets_swept, None)
+                }
+                Err(e) => {
+                    error!("❌ Ошибка консолидации средств: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            self.record_run("sweep", started_at, started, swept, error)
+                .await;
+        }
+    }
+
+    /// Задача обработки очереди массовой загрузки: queued-строки
+    /// ingestion job'ов конвертируются в трансферы пачками
+    async fn start_ingestion_task(&self) -> Result<()> {
+        let Some(ingestion_service) = self.ingestion_service.clone() else {
+            info!("📥 Ingestion сервис не подключен - задача загрузки не запускается");
+            return Ok(());
+        };
+
+        info!(
+            "📥 Запуск обработки очереди массовой загрузки (интервал: {} сек)",
+            self.config.ingestion_interval_seconds
+        );
+
+        let mut interval = interval(Duration::from_secs(self.config.ingestion_interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            let (processed, error) = match ingestion_service.process_queued_rows(200).await {
+                Ok(processed) => {
+                    if processed > 0 {
+                        info!("📥 Обработано строк массовой загрузки: {}", processed);
+                    }
+                    (processed as i64, None)
+                }
+                Err(e) => {
+                    error!("❌ Ошибка обработки очереди массовой загрузки: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            // Пустые итерации не засоряют историю запусков
+            if processed > 0 || error.is_some() {
+                self.record_run("ingestion", started_at, started, processed, error)
+                    .await;
+            }
+        }
+    }
+
+    /// Задача сверки пороговых подписок на балансы: при пересечении
+    /// порога (с гистерезисом) уходят webhook и уведомление
+    async fn start_balance_alerts_task(&self) -> Result<()> {
+        let Some(balance_alert_service) = self.balance_alert_service.clone() else {
+            info!("🔔 Сервис алертов не подключен - задача сверки порогов не запускается");
+            return Ok(());
+        };
+
+        info!(
+            "🔔 Запуск сверки пороговых подписок (интервал: {} сек)",
+            self.config.balance_alerts_interval_seconds
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.balance_alerts_interval_seconds,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            let (fired, error) = match balance_alert_service.evaluate_once().await {
+                Ok(summary) => {
+                    if summary.alerts_fired > 0 {
+                        info!(
+                            "🔔 Сработало алертов: {} (проверено {})",
+                            summary.alerts_fired, summary.alerts_checked
+                        );
+                    }
+                    (summary.alerts_fired, None)
+                }
+                Err(e) => {
+                    error!("❌ Ошибка сверки пороговых подписок: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            self.record_run("balance_alerts", started_at, started, fired, error)
+                .await;
+        }
+    }
+
+    /// Задача истечения инвойсов: OPEN/PARTIAL инвойсы с прошедшим
+    /// сроком переводятся в EXPIRED с webhook invoice_expired
+    async fn start_invoice_expiry_task(&self) -> Result<()> {
+        let Some(invoice_service) = self.invoice_service.clone() else {
+            info!("🧾 Сервис инвойсов не подключен - задача истечения не запускается");
+            return Ok(());
+        };
+
+        info!(
+            "🧾 Запуск истечения инвойсов (интервал: {} сек)",
+            self.config.invoice_expiry_interval_seconds
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.invoice_expiry_interval_seconds,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            let (expired, error) = match invoice_service.expire_due_invoices().await {
+                Ok(expired) => {
+                    if expired > 0 {
+                        info!("🧾 Инвойсов истекло: {}", expired);
+                    }
+                    (expired as i64, None)
+                }
+                Err(e) => {
+                    error!("❌ Ошибка истечения инвойсов: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            // Пустые итерации не засоряют историю запусков
+            if expired > 0 || error.is_some() {
+                self.record_run("invoice_expiry", started_at, started, expired, error)
+                    .await;
+            }
+        }
+    }
+
+    /// Задача канарейки: периодический крошечный трансфер через весь
+    /// пайплайн с замером латентности и алертом при провале
+    async fn start_canary_task(&self) -> Result<()> {
+        let Some(canary_service) = self.canary_service.clone() else {
+            info!("🐤 Канарейка не подключена - задача не запускается");
+            return Ok(());
+        };
+
+        if !canary_service.is_enabled() {
+            info!("🐤 Канарейка выключена или не сконфигурирована - задача не запускается");
+            return Ok(());
+        }
+
+        info!(
+            "🐤 Запуск канарейки пайплайна (интервал: {} мин)",
+            canary_service.interval_minutes()
+        );
+
+        let mut interval = interval(Duration::from_secs(canary_service.interval_minutes() * 60));
+
+        loop {
+            interval.tick().await;
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+
+            let (completed, error) = match canary_service.run_canary().await {
+                Ok(result) if result.outcome == "completed" => (1, None),
+                Ok(result) => (
+                    0,
+                    result.error.or(Some(format!("Исход: {}", result.outcome))),
+                ),
+                Err(e) => {
+                    error!("❌ Ошибка запуска канарейки: {}", e);
+                    (0, Some(e.to_string()))
+                }
+            };
+
+            self.record_run("canary", started_at, started, completed, error)
+                .await;
+        }
     }
 
     /// Задача мониторинга входящих транзакций
@@ -81,14 +701,30 @@ impl TaskScheduler {
         loop {
             interval.tick().await;
 
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+            let mut error: Option<String> = None;
+
             if let Err(e) = monitoring_service.scan_for_incoming_transactions().await {
                 error!("❌ Ошибка мониторинга транзакций: {}", e);
                 // Продолжаем работу
+                error = Some(e.to_string());
+            }
+
+            if let Err(e) = monitoring_service.replay_dead_letters().await {
+                error!("❌ Ошибка переигрывания dead-letter записей: {}", e);
+                error.get_or_insert(e.to_string());
             }
+
+            self.record_run("monitoring", started_at, started, 0, error)
+                .await;
         }
     }
 
-    /// Задача обработки pending трансферов
+    /// Задача обработки pending трансферов.
+    /// В окне обслуживания (апгрейд сети, работы на бирже) обработка
+    /// и свипы приостанавливаются: новые трансферы копятся в очереди
+    /// PENDING, мониторинг депозитов продолжает работать
     async fn start_transfer_processing_task(&self) -> Result<()> {
         info!(
             "⚙️  Запуск обработки pending трансферов (интервал: {} сек)",
@@ -99,14 +735,79 @@ impl TaskScheduler {
             self.config.transfer_processing_interval_seconds,
         ));
         let transfer_service = self.transfer_service.clone();
+        let mut was_in_window = false;
 
         loop {
             interval.tick().await;
 
+            let in_window = self.config.maintenance_window_enabled
+                && in_quiet_window(
+                    chrono::Utc::now().hour(),
+                    self.config.maintenance_window_start_hour,
+                    self.config.maintenance_window_end_hour,
+                );
+
+            // События начала/конца окна - дежурные видят, что пауза плановая
+            if in_window != was_in_window {
+                was_in_window = in_window;
+
+                if in_window {
+                    info!(
+                        "🚧 Окно обслуживания началось ({}:00-{}:00 UTC): обработка трансферов приостановлена",
+                        self.config.maintenance_window_start_hour,
+                        self.config.maintenance_window_end_hour
+                    );
+                } else {
+                    info!("✅ Окно обслуживания закончилось: обработка трансферов возобновлена");
+                }
+
+                if let Some(notifications) = &self.notifications {
+                    let (subject, body) = if in_window {
+                        (
+                            "Окно обслуживания началось",
+                            format!(
+                                "Обработка трансферов приостановлена до {}:00 UTC, новые трансферы копятся в очереди",
+                                self.config.maintenance_window_end_hour
+                            ),
+                        )
+                    } else {
+                        (
+                            "Окно обслуживания закончилось",
+                            "Обработка накопленных трансферов возобновлена".to_string(),
+                        )
+                    };
+
+                    notifications
+                        .alert(NotificationSeverity::Info, subject, body)
+                        .await;
+                }
+            }
+
+            if in_window {
+                continue;
+            }
+
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+            let mut error: Option<String> = None;
+
             if let Err(e) = transfer_service.process_pending_transfers().await {
+                error = Some(e.to_string());
                 error!("❌ Ошибка обработки pending трансферов: {}", e);
-                // Продолжаем работу
+                // Продолжаем работу, но дежурные узнают об этом из алерта
+                if let Some(notifications) = &self.notifications {
+                    notifications
+                        .alert(
+                            NotificationSeverity::Critical,
+                            "Ошибка обработки pending трансферов",
+                            format!("Итерация обработки завершилась ошибкой: {}", e),
+                        )
+                        .await;
+                }
             }
+
+            self.record_run("transfer_processing", started_at, started, 0, error)
+                .await;
         }
     }
 
@@ -124,9 +825,33 @@ impl TaskScheduler {
         loop {
             interval.tick().await;
 
+            let started_at = chrono::Utc::now();
+            let started = std::time::Instant::now();
+            let mut pruned = 0i64;
+            let mut error: Option<String> = None;
+
             if let Err(e) = self.perform_cleanup().await {
                 error!("❌ Ошибка очистки: {}", e);
+                error = Some(e.to_string());
             }
+
+            // Ретеншн истории запусков - часть общей очистки
+            if let Some(run_log) = &self.run_log {
+                match run_log.prune(RUN_HISTORY_RETENTION_DAYS).await {
+                    Ok(0) => {}
+                    Ok(deleted) => {
+                        pruned = deleted as i64;
+                        info!("🧹 Удалено записей истории запусков: {}", deleted);
+                    }
+                    Err(e) => {
+                        error!("❌ Ошибка ретеншна истории запусков: {}", e);
+                        error.get_or_insert(e.to_string());
+                    }
+                }
+            }
+
+            self.record_run("cleanup", started_at, started, pruned, error)
+                .await;
         }
     }
 
@@ -150,6 +875,148 @@ impl TaskScheduler {
         }
     }
 
+    /// Задача ежедневной сводки по расчетам (no-op без канала уведомлений)
+    async fn start_daily_summary_task(&self) -> Result<()> {
+        let Some(notifications) = self.notifications.clone() else {
+            return Ok(());
+        };
+
+        info!(
+            "📧 Запуск ежедневной сводки по расчетам (интервал: {} часов)",
+            self.config.daily_summary_interval_hours
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.daily_summary_interval_hours * 3600,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.send_settlement_summary(&notifications).await {
+                warn!("⚠️  Не удалось отправить сводку по расчетам: {}", e);
+            }
+        }
+    }
+
+    /// Задача обслуживания БД: отчет о распухании таблиц и ANALYZE
+    /// в тихом окне (no-op без пула БД)
+    async fn start_maintenance_task(&self) -> Result<()> {
+        let Some(db) = self.db.clone() else {
+            return Ok(());
+        };
+
+        info!(
+            "🗄️ Запуск задачи обслуживания БД (интервал: {} часов)",
+            self.config.maintenance_interval_hours
+        );
+
+        let mut interval = interval(Duration::from_secs(
+            self.config.maintenance_interval_hours * 3600,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.perform_maintenance(&db).await {
+                warn!("⚠️  Ошибка задачи обслуживания БД: {}", e);
+            }
+        }
+    }
+
+    /// Собирает статистику распухания из pg_stat_user_tables,
+    /// предупреждает о распухших таблицах и опционально запускает
+    /// ANALYZE, если текущий час попадает в тихое окно
+    async fn perform_maintenance(&self, db: &DbPool) -> Result<()> {
+        let mut conn = db.get().await?;
+
+        let rows: Vec<TableBloatRow> = diesel::sql_query(
+            "SELECT relname::text AS table_name, \
+             n_live_tup AS live_tuples, n_dead_tup AS dead_tuples, last_autovacuum \
+             FROM pg_stat_user_tables ORDER BY n_dead_tup DESC",
+        )
+        .load(&mut conn)
+        .await?;
+
+        let analyze_allowed = self.config.maintenance_analyze_enabled
+            && in_quiet_window(
+                chrono::Utc::now().hour(),
+                self.config.maintenance_quiet_window_start_hour,
+                self.config.maintenance_quiet_window_end_hour,
+            );
+
+        for row in rows {
+            let ratio = dead_tuple_ratio(row.live_tuples, row.dead_tuples);
+
+            info!(
+                "📊 Таблица {}: {} живых / {} мертвых кортежей ({:.1}%), autovacuum: {}",
+                row.table_name,
+                row.live_tuples,
+                row.dead_tuples,
+                ratio * 100.0,
+                row.last_autovacuum
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_else(|| "никогда".to_string())
+            );
+
+            if ratio < self.config.maintenance_dead_tuple_warn_ratio {
+                continue;
+            }
+
+            warn!(
+                "⚠️  Таблица {} распухла: {:.1}% мертвых кортежей (порог {:.1}%)",
+                row.table_name,
+                ratio * 100.0,
+                self.config.maintenance_dead_tuple_warn_ratio * 100.0
+            );
+
+            if analyze_allowed {
+                info!("🧹 ANALYZE {} (тихое окно)", row.table_name);
+                // Имя таблицы берется из каталога pg_stat_user_tables
+                // (не из пользовательского ввода) и экранируется кавычками
+                diesel::sql_query(format!("ANALYZE \"{}\"", row.table_name))
+                    .execute(&mut conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Собирает и отправляет сводку по расчетам из статистики сервисов
+    async fn send_settlement_summary(&self, notifications: &NotificationDispatcher) -> Result<()> {
+        let monitoring_stats = self.monitoring_service.get_monitoring_stats().await?;
+        let processing_stats = self.transfer_service.get_processing_stats().await?;
+
+        let mut values = std::collections::HashMap::new();
+        values.insert(
+            "total_transactions",
+            monitoring_stats.total_transactions.to_string(),
+        );
+        values.insert("completed", monitoring_stats.completed_count.to_string());
+        values.insert("pending", monitoring_stats.pending_count.to_string());
+        values.insert("queue_depth", processing_stats.queue_depth.to_string());
+        values.insert(
+            "avg_wait",
+            processing_stats.avg_pending_wait_seconds.to_string(),
+        );
+
+        notifications
+            .dispatch(Notification::from_template(
+                NotificationSeverity::Info,
+                "Ежедневная сводка по расчетам",
+                "Входящих транзакций всего: {total_transactions}\n\
+                 Завершено: {completed}\n\
+                 Ожидают подтверждений: {pending}\n\
+                 Очередь исходящих трансферов: {queue_depth}\n\
+                 Средний возраст очереди: {avg_wait} сек",
+                &values,
+            ))
+            .await;
+
+        Ok(())
+    }
+
     /// Выполняет очистку старых данных
     async fn perform_cleanup(&self) -> Result<()> {
         info!("🧹 Выполнение очистки старых данных...");
@@ -289,3 +1156,40 @@ impl serde::Serialize for SchedulerConfig {
         state.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_tuple_ratio() {
+        assert_eq!(dead_tuple_ratio(0, 0), 0.0);
+        assert_eq!(dead_tuple_ratio(100, 0), 0.0);
+        assert_eq!(dead_tuple_ratio(0, 100), 1.0);
+        assert_eq!(dead_tuple_ratio(75, 25), 0.25);
+    }
+
+    #[test]
+    fn test_quiet_window_same_day() {
+        // Окно 02:00-05:00
+        assert!(!in_quiet_window(1, 2, 5));
+        assert!(in_quiet_window(2, 2, 5));
+        assert!(in_quiet_window(4, 2, 5));
+        assert!(!in_quiet_window(5, 2, 5));
+    }
+
+    #[test]
+    fn test_quiet_window_wraps_midnight() {
+        // Окно 22:00-04:00 переходит через полночь
+        assert!(in_quiet_window(23, 22, 4));
+        assert!(in_quiet_window(0, 22, 4));
+        assert!(in_quiet_window(3, 22, 4));
+        assert!(!in_quiet_window(4, 22, 4));
+        assert!(!in_quiet_window(12, 22, 4));
+    }
+
+    #[test]
+    fn test_quiet_window_zero_length_is_closed() {
+        assert!(!in_quiet_window(3, 3, 3));
+    }
+}
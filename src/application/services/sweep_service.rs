@@ -0,0 +1,274 @@
+//! # Сервис консолидации средств (sweep)
+//!
+//! Периодически выметает USDT с кастодиальных кошельков на мастер-кошелек:
+//! кошельки с балансом выше порога получают sweep-трансфер через обычный
+//! пайплайн `TransferService` (claim, подпись, broadcast, подтверждения).
+//! Политика настраивается per-wallet: sweep можно выключить для кошелька
+//! или поднять его порог относительно глобального. Каждый запуск
+//! фиксируется в sweep_runs
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::application::dto::CreateTransferRequest;
+use crate::config::SweepConfig;
+use crate::infrastructure::database::models::{NewSweepRun, SweepRunModel, WalletModel};
+use crate::infrastructure::database::{schema, DbPool};
+use crate::infrastructure::TronGridClient;
+use crate::utils::conversions::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+
+use super::{FeePayer, SponsorGasService, TransferService};
+
+/// Итог одного запуска sweep'а
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SweepRunSummary {
+    /// Сколько кошельков-кандидатов просканировано
+    pub wallets_scanned: i64,
+    /// Сколько sweep-трансферов создано
+    pub wallets_swept: i64,
+    /// Суммарный выметенный объем USDT
+    pub total_amount: Decimal,
+}
+
+/// Сервис консолидации средств кошельков на мастер-кошелек
+pub struct SweepService {
+    db: DbPool,
+    tron_client: TronGridClient,
+    transfer_service: Arc<TransferService>,
+    /// Газ дотягивается до broadcast'а, чтобы пайплайн не стоял на TRX
+    sponsor_gas_service: SponsorGasService,
+    config: SweepConfig,
+}
+
+impl SweepService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(
+        db: DbPool,
+        tron_client: TronGridClient,
+        transfer_service: Arc<TransferService>,
+        sponsor_gas_service: SponsorGasService,
+        config: SweepConfig,
+    ) -> Self {
+        Self {
+            db,
+            tron_client,
+            transfer_service,
+            sponsor_gas_service,
+            config,
+        }
+    }
+
+    /// Включена ли консолидация в конфигурации
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Один проход sweep'а: балансы кандидатов сверяются с порогом,
+    /// превышение уходит sweep-трансфером. Запуск фиксируется в sweep_runs
+    pub async fn sweep_once(&self) -> Result<SweepRunSummary> {
+        let started_at = chrono::Utc::now();
+        let started = std::time::Instant::now();
+
+        let result = self.sweep_candidates().await;
+
+        let (summary, error_message) = match &result {
+            Ok(summary) => (summary.clone(), None),
+            Err(e) => (SweepRunSummary::default(), Some(e.to_string())),
+        };
+
+        self.record_run(
+            started_at,
+            started.elapsed().as_millis() as i64,
+            &summary,
+            error_message,
+        )
+        .await;
+
+        result
+    }
+
+    /// Обновляет политику sweep'а кошелька.
+    /// None в min_threshold возвращает кошелек на глобальный порог;
+    /// None в результате - кошелек не найден
+    pub async fn set_wallet_policy(
+        &self,
+        wallet_id: i64,
+        sweep_enabled: bool,
+        min_threshold: Option<Decimal>,
+    ) -> Result<Option<WalletModel>> {
+        let mut conn = self.db.get().await?;
+
+        let wallet = diesel::update(schema::wallets::table.find(wallet_id))
+            .set((
+                schema::wallets::sweep_enabled.eq(sweep_enabled),
+                schema::wallets::sweep_min_threshold.eq(min_threshold.map(decimal_to_bigdecimal)),
+            ))
+            .returning(WalletModel::as_returning())
+            .get_result(&mut conn)
+            .await
+            .optional()?;
+
+        if wallet.is_some() {
+            info!(
+                "⚙️  Политика sweep'а кошелька {}: enabled={}, порог={}",
+                wallet_id,
+                sweep_enabled,
+                min_threshold
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| "глобальный".to_string())
+            );
+        }
+
+        Ok(wallet)
+    }
+
+    /// Последние запуски sweep'а (свежие первыми)
+    pub async fn list_runs(&self, limit: i64) -> Result<Vec<SweepRunModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::sweep_runs::table
+            .order(schema::sweep_runs::id.desc())
+            .limit(limit.clamp(1, 500))
+            .select(SweepRunModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Проходит по кандидатам и создает sweep-трансферы
+    async fn sweep_candidates(&self) -> Result<SweepRunSummary> {
+        let wallets = self.load_candidates().await?;
+        let mut summary = SweepRunSummary {
+            wallets_scanned: wallets.len() as i64,
+            ..Default::default()
+        };
+
+        for wallet in wallets {
+            let balance = match self.tron_client.get_usdt_balance(&wallet.address).await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    warn!(
+                        "⚠️  Sweep: не удалось получить баланс кошелька {}: {}",
+                        wallet.id, e
+                    );
+                    continue;
+                }
+            };
+
+            let threshold = wallet
+                .sweep_min_threshold
+                .clone()
+                .map(bigdecimal_to_decimal)
+                .unwrap_or(self.config.min_amount);
+
+            if balance < threshold {
+                debug!(
+                    "Sweep: кошелек {} ниже порога ({} < {})",
+                    wallet.id, balance, threshold
+                );
+                continue;
+            }
+
+            // Дотягиваем TRX заранее: broadcast sweep-трансфера не должен
+            // ждать спонсорства газа внутри пайплайна
+            if let Err(e) = self
+                .sponsor_gas_service
+                .ensure_gas_for_transfer(&wallet.address, balance)
+                .await
+            {
+                warn!(
+                    "⚠️  Sweep: спонсорство газа для кошелька {} не удалось: {}",
+                    wallet.id, e
+                );
+            }
+
+            // Выметаем весь баланс; комиссии вычитаются из суммы (merchant),
+            // поэтому списание не превышает баланс кошелька
+            let request = CreateTransferRequest {
+                from_wallet_id: wallet.id,
+                order_amount: balance,
+                reference_id: None,
+                destination_tag: None,
+                metadata: None,
+                fee_payer: Some(FeePayer::Merchant),
+                preview_only: None,
+            };
+
+            match self.transfer_service.create_transfer(request).await {
+                Ok(transfer) => {
+                    info!(
+                        "🧹 Sweep: кошелек {} -> трансфер {} на {} USDT",
+                        wallet.id, transfer.id, balance
+                    );
+                    summary.wallets_swept += 1;
+                    summary.total_amount += balance;
+                }
+                Err(e) => {
+                    warn!(
+                        "⚠️  Sweep: не удалось создать трансфер для кошелька {}: {}",
+                        wallet.id, e
+                    );
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Кандидаты на sweep: кастодиальные активные кошельки
+    /// с включенной политикой
+    async fn load_candidates(&self) -> Result<Vec<WalletModel>> {
+        let mut conn = self.db.get().await?;
+
+        Ok(schema::wallets::table
+            .filter(schema::wallets::watch_only.eq(false))
+            .filter(schema::wallets::under_review.eq(false))
+            .filter(schema::wallets::archived_at.is_null())
+            .filter(schema::wallets::sweep_enabled.eq(true))
+            .order(schema::wallets::id.asc())
+            .select(WalletModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Записывает запуск в sweep_runs (best-effort, как SchedulerRunLog)
+    async fn record_run(
+        &self,
+        started_at: chrono::DateTime<chrono::Utc>,
+        duration_ms: i64,
+        summary: &SweepRunSummary,
+        error_message: Option<String>,
+    ) {
+        let run = NewSweepRun {
+            started_at,
+            duration_ms,
+            wallets_scanned: summary.wallets_scanned,
+            wallets_swept: summary.wallets_swept,
+            total_amount: decimal_to_bigdecimal(summary.total_amount),
+            outcome: if error_message.is_none() {
+                "success".to_string()
+            } else {
+                "error".to_string()
+            },
+            error_message,
+        };
+
+        let result = async {
+            let mut conn = self.db.get().await?;
+            diesel::insert_into(schema::sweep_runs::table)
+                .values(&run)
+                .execute(&mut conn)
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!("⚠️  Не удалось записать запуск sweep'а в историю: {}", e);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! # Шина событий статусов трансферов
+//!
+//! Внутренний broadcast-канал переходов статусов: TransferService
+//! публикует изменения исходящих трансферов, TransactionMonitoringService -
+//! обнаруженные входящие депозиты. Подписчики (стриминговые gRPC методы
+//! WatchTransfer/WatchWalletEvents) получают переходы сразу, без поллинга.
+//!
+//! Шина best-effort: отсутствие подписчиков не ошибка, отставший
+//! подписчик теряет старые события (broadcast с ограниченной емкостью)
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+use crate::domain::TransactionStatus;
+
+/// Емкость broadcast-канала: отстающий подписчик теряет старые события
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Вид события на шине
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferEventKind {
+    /// Переход статуса исходящего трансфера
+    Outgoing,
+    /// Обнаруженный входящий депозит
+    IncomingDeposit,
+}
+
+impl TransferEventKind {
+    /// Строковое представление для API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Outgoing => "outgoing",
+            Self::IncomingDeposit => "incoming_deposit",
+        }
+    }
+}
+
+/// Событие изменения статуса трансфера или депозита
+#[derive(Debug, Clone)]
+pub struct TransferStatusChange {
+    pub kind: TransferEventKind,
+    /// id записи: outgoing_transfers для Outgoing,
+    /// incoming_transactions для IncomingDeposit
+    pub record_id: i64,
+    pub wallet_id: i64,
+    pub status: TransactionStatus,
+    pub tx_hash: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Шина событий статусов (дешевый Clone - общий канал)
+#[derive(Clone)]
+pub struct TransferEventBus {
+    sender: broadcast::Sender<TransferStatusChange>,
+}
+
+impl TransferEventBus {
+    /// Создает новую шину
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    /// Публикует событие. Отсутствие подписчиков - не ошибка
+    pub fn publish(&self, change: TransferStatusChange) {
+        let _ = self.sender.send(change);
+    }
+
+    /// Подписывается на события с момента вызова
+    pub fn subscribe(&self) -> broadcast::Receiver<TransferStatusChange> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for TransferEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_reaches_subscriber() {
+        let bus = TransferEventBus::new();
+        let mut receiver = bus.subscribe();
+
+        bus.publish(TransferStatusChange {
+            kind: TransferEventKind::Outgoing,
+            record_id: 42,
+            wallet_id: 7,
+            status: TransactionStatus::Processing,
+            tx_hash: None,
+            occurred_at: Utc::now(),
+        });
+
+        let change = receiver.recv().await.unwrap();
+        assert_eq!(change.record_id, 42);
+        assert_eq!(change.status, TransactionStatus::Processing);
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_noop() {
+        let bus = TransferEventBus::new();
+
+        // Не паникует и не возвращает ошибку
+        bus.publish(TransferStatusChange {
+            kind: TransferEventKind::IncomingDeposit,
+            record_id: 1,
+            wallet_id: 1,
+            status: TransactionStatus::Completed,
+            tx_hash: Some("abc".to_string()),
+            occurred_at: Utc::now(),
+        });
+    }
+}
@@ -6,22 +6,104 @@ use anyhow::Result;
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 
 use crate::application::dto::*;
-use crate::domain::{DomainError, TransactionStatus, TronValidator};
+use crate::domain::{DomainError, OutgoingTransfer, TransactionStatus, TronValidator};
 use crate::infrastructure::{
     database::{models::*, schema, DbPool},
-    TronGridClient, TronTransactionSigner,
+    AuditShipper, CircuitBreaker, LocalSigningBackend, RiskScreeningProvider, SigningBackend,
+    TronGridClient,
 };
-use crate::utils::{bigdecimal_to_decimal, decimal_to_bigdecimal};
+use crate::utils::{bigdecimal_to_decimal, decimal_to_bigdecimal, parse_stored_metadata};
 
-use super::{SponsorGasService, UnifiedFeeService};
+use super::{
+    BalanceService, CongestionLevel, FeePayer, MasterWalletPool, SponsorGasService,
+    TransferEventBus, TransferEventKind, TransferStatusChange, UnifiedFeeService,
+};
+use std::sync::{Arc, Mutex};
+
+/// Трансфер считается подтвержденным после этого числа блоков
+/// (соответствует ConfirmationPolicy::completed_confirmations)
+const COMPLETED_CONFIRMATIONS: i64 = 19;
+
+/// Сколько минут ждать попадания broadcast-транзакции в блок,
+/// прежде чем считать ее выпавшей из мемпула
+const BROADCAST_DROP_TIMEOUT_MINUTES: i64 = 30;
+
+/// Рантайм-настройки пайплайна обработки pending трансферов.
+/// Меняются через admin API без редеплоя - реакция на нагрузку
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessingTuning {
+    /// Максимум трансферов, забираемых из очереди за одну итерацию
+    pub max_batch_size: i64,
+    /// Сколько батчей обрабатывается параллельно внутри итерации
+    pub parallelism: usize,
+}
+
+impl Default for ProcessingTuning {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 50,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Статистика пайплайна обработки трансферов
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessingStats {
+    /// Текущая глубина очереди PENDING
+    pub queue_depth: i64,
+    /// Средний возраст PENDING трансфера в секундах
+    pub avg_pending_wait_seconds: i64,
+    /// Размеры батчей последней итерации обработки
+    pub last_batch_sizes: Vec<usize>,
+    /// Когда завершилась последняя итерация
+    pub last_iteration_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Действующие настройки пайплайна
+    pub tuning: ProcessingTuning,
+}
+
+/// Снимок последней итерации обработки
+#[derive(Debug, Default)]
+struct ProcessingIterationSnapshot {
+    batch_sizes: Vec<usize>,
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Назначение TRX отправки для записи в trx_transfers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrxTransferPurpose {
+    /// Активация нового кошелька отправкой TRX
+    Activation,
+    /// Спонсирование газа перед USDT трансфером
+    GasSponsorship,
+    /// Выдача тестовых средств из faucet
+    Faucet,
+    /// Ручная отправка администратором
+    AdminSend,
+}
+
+impl TrxTransferPurpose {
+    /// Строковое представление для колонки purpose
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            TrxTransferPurpose::Activation => "activation",
+            TrxTransferPurpose::GasSponsorship => "gas_sponsorship",
+            TrxTransferPurpose::Faucet => "faucet",
+            TrxTransferPurpose::AdminSend => "admin_send",
+        }
+    }
+}
 
 /// Сервис для TRX трансферов (отправка TRX монет)
 #[derive(Clone)]
 pub struct TrxTransferService {
     tron_client: TronGridClient,
-    transaction_signer: TronTransactionSigner,
+    signing_backend: Arc<dyn SigningBackend>,
+    /// Пул для записи отправок в trx_transfers (опционально)
+    db: Option<DbPool>,
 }
 
 impl TrxTransferService {
@@ -29,10 +111,23 @@ impl TrxTransferService {
     pub fn new(tron_client: TronGridClient) -> Self {
         Self {
             tron_client,
-            transaction_signer: TronTransactionSigner::new(),
+            signing_backend: Arc::new(LocalSigningBackend::new()),
+            db: None,
         }
     }
 
+    /// Включает запись каждой TRX отправки в таблицу trx_transfers
+    pub fn with_persistence(mut self, db: DbPool) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Заменяет бэкенд подписания (по умолчанию - локальный)
+    pub fn with_signing_backend(mut self, signing_backend: Arc<dyn SigningBackend>) -> Self {
+        self.signing_backend = signing_backend;
+        self
+    }
+
     /// Отправка TRX с одного адреса на другой
     pub async fn send_trx(
         &self,
@@ -49,17 +144,31 @@ impl TrxTransferService {
         );
 
         // Шаг 1: Создание неподписанной TRX транзакции
-        let create_result = self
+        let mut create_result = self
             .tron_client
             .create_trx_transaction(from_address, to_address, amount)
             .await?;
 
+        // Если создание задержалось и expiration уже на исходе - пересоздаем
+        if TronGridClient::transaction_expires_soon(&create_result) {
+            tracing::warn!(
+                "⚠️ TRX транзакция {} -> {} истекла до подписания - пересоздаем",
+                from_address,
+                to_address
+            );
+            create_result = self
+                .tron_client
+                .create_trx_transaction(from_address, to_address, amount)
+                .await?;
+        }
+
         tracing::debug!("TRX транзакция создана: {:?}", create_result);
 
         // Шаг 2: Подписание транзакции
         let signed_transaction = self
-            .transaction_signer
-            .sign_transaction(&create_result, from_private_key)?;
+            .signing_backend
+            .sign_transaction(&create_result, from_private_key)
+            .await?;
 
         // Шаг 3: Отправка транзакции
         let tx_hash = self
@@ -70,6 +179,219 @@ impl TrxTransferService {
         tracing::info!("TRX трансфер успешен. TX Hash: {}", tx_hash);
         Ok(tx_hash)
     }
+
+    /// Отправка TRX с записью в trx_transfers с указанием назначения.
+    /// Ошибка записи не роняет отправку - TRX уже ушли в сеть
+    pub async fn send_trx_with_purpose(
+        &self,
+        from_address: &str,
+        from_private_key: &str,
+        to_address: &str,
+        amount: Decimal,
+        purpose: TrxTransferPurpose,
+    ) -> Result<String> {
+        let tx_hash = self
+            .send_trx(from_address, from_private_key, to_address, amount)
+            .await?;
+
+        if let Err(e) = self
+            .record_transfer(from_address, to_address, amount, purpose, &tx_hash)
+            .await
+        {
+            tracing::warn!(
+                "⚠️ Не удалось записать TRX отправку {} в trx_transfers: {}",
+                tx_hash,
+                e
+            );
+        }
+
+        Ok(tx_hash)
+    }
+
+    /// Записывает TRX отправку в БД, связывая ее с кошельком-получателем
+    async fn record_transfer(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        amount: Decimal,
+        purpose: TrxTransferPurpose,
+        tx_hash: &str,
+    ) -> Result<()> {
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+
+        let mut conn = db.get().await?;
+
+        // Получатель может быть нашим кошельком - связываем запись с ним
+        let related_wallet_id: Option<i64> = schema::wallets::table
+            .filter(schema::wallets::address.eq(to_address))
+            .select(schema::wallets::id)
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        let new_transfer = NewTrxTransfer {
+            from_address: from_address.to_string(),
+            to_address: to_address.to_string(),
+            amount: decimal_to_bigdecimal(amount),
+            purpose: purpose.as_db_str().to_string(),
+            tx_hash: tx_hash.to_string(),
+            related_wallet_id,
+        };
+
+        diesel::insert_into(schema::trx_transfers::table)
+            .values(&new_transfer)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// TRX отправки, связанные с кошельком (для ленты активности)
+    pub async fn get_transfers_for_wallet(
+        &self,
+        wallet_id: i64,
+    ) -> Result<Vec<TrxTransferResponse>> {
+        let Some(db) = &self.db else {
+            return Ok(Vec::new());
+        };
+
+        let mut conn = db.get().await?;
+
+        let transfers: Vec<TrxTransferModel> = schema::trx_transfers::table
+            .filter(schema::trx_transfers::related_wallet_id.eq(wallet_id))
+            .order(schema::trx_transfers::created_at.desc())
+            .load(&mut conn)
+            .await?;
+
+        Ok(transfers.into_iter().map(Self::model_to_response).collect())
+    }
+
+    /// TRX отправки с адреса (для истории мастер-кошелька)
+    pub async fn get_transfers_from(
+        &self,
+        from_address: &str,
+        limit: i64,
+    ) -> Result<Vec<TrxTransferResponse>> {
+        let Some(db) = &self.db else {
+            return Ok(Vec::new());
+        };
+
+        let mut conn = db.get().await?;
+
+        let transfers: Vec<TrxTransferModel> = schema::trx_transfers::table
+            .filter(schema::trx_transfers::from_address.eq(from_address))
+            .order(schema::trx_transfers::created_at.desc())
+            .limit(limit)
+            .load(&mut conn)
+            .await?;
+
+        Ok(transfers.into_iter().map(Self::model_to_response).collect())
+    }
+
+    /// Преобразует модель TRX отправки в DTO
+    fn model_to_response(transfer: TrxTransferModel) -> TrxTransferResponse {
+        TrxTransferResponse {
+            id: transfer.id,
+            from_address: transfer.from_address,
+            to_address: transfer.to_address,
+            amount: bigdecimal_to_decimal(transfer.amount),
+            purpose: transfer.purpose,
+            tx_hash: transfer.tx_hash,
+            related_wallet_id: transfer.related_wallet_id,
+            created_at: transfer.created_at,
+        }
+    }
+}
+
+/// Вид исходящего трансфера
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferKind {
+    /// Свип на мастер-кошелек или sweep-назначение из конфига
+    #[default]
+    Sweep,
+    /// Вывод на произвольный адрес назначения
+    Withdrawal,
+    /// Нога разложенного депозита (правила deposit_split_rules)
+    Split,
+}
+
+impl TransferKind {
+    /// Каноническое представление в БД
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::Sweep => "sweep",
+            Self::Withdrawal => "withdrawal",
+            Self::Split => "split",
+        }
+    }
+
+    /// Парсит значение из БД
+    pub fn from_db_str(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "sweep" => Some(Self::Sweep),
+            "withdrawal" => Some(Self::Withdrawal),
+            "split" => Some(Self::Split),
+            _ => None,
+        }
+    }
+}
+
+/// Под-этап обработки трансфера - resume point после рестарта.
+///
+/// process_transfer выполняет заправку газа, создание транзакции,
+/// подписание и broadcast; каждый завершенный шаг фиксируется в
+/// outgoing_transfers.processing_stage, чтобы краш посередине не
+/// приводил к повторной трате газа или повторному переводу
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProcessingStage {
+    /// TRX на газ заправлен на кошелек отправителя
+    GasSponsored,
+    /// Неподписанная транзакция создана, ее txID сохранен в tx_hash
+    TxCreated,
+    /// Транзакция подписана, но broadcast еще не подтвержден записью
+    Signed,
+    /// Транзакция отправлена в сеть
+    Broadcast,
+}
+
+impl ProcessingStage {
+    /// Каноническое представление в БД
+    fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::GasSponsored => "GAS_SPONSORED",
+            Self::TxCreated => "TX_CREATED",
+            Self::Signed => "SIGNED",
+            Self::Broadcast => "BROADCAST",
+        }
+    }
+
+    /// Парсит значение из БД
+    fn from_db_str(value: &str) -> Option<Self> {
+        match value {
+            "GAS_SPONSORED" => Some(Self::GasSponsored),
+            "TX_CREATED" => Some(Self::TxCreated),
+            "SIGNED" => Some(Self::Signed),
+            "BROADCAST" => Some(Self::Broadcast),
+            _ => None,
+        }
+    }
+}
+
+/// Помечает metadata трансфера как симулированную (dry-run режим).
+/// Существующие клиентские поля metadata сохраняются
+fn mark_metadata_simulated(metadata: Option<&str>) -> String {
+    let mut value = metadata
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+
+    if let Some(object) = value.as_object_mut() {
+        object.insert("simulated".to_string(), serde_json::json!(true));
+    }
+
+    value.to_string()
 }
 
 /// Основной сервис для USDT трансферов
@@ -78,8 +400,45 @@ pub struct TransferService {
     pub tron_client: TronGridClient,
     pub fee_service: UnifiedFeeService,
     pub master_wallet_address: String,
+    pub master_wallet_pool: Arc<MasterWalletPool>,
     pub sponsor_gas_service: SponsorGasService,
-    pub transaction_signer: TronTransactionSigner,
+    pub signing_backend: Arc<dyn SigningBackend>,
+    pub circuit_breaker: CircuitBreaker,
+    pub audit_shipper: AuditShipper,
+    /// Неттинг: объединять несколько pending sweep'ов одного кошелька
+    /// в одну on-chain транзакцию для экономии газа
+    pub netting_enabled: bool,
+    /// Переопределение sweep-назначения по символу токена (из конфига)
+    pub sweep_destinations: HashMap<String, String>,
+    /// Откладывать несрочные sweep'ы при высокой загрузке сети
+    congestion_deferral_enabled: bool,
+    /// Максимальное время откладывания в минутах
+    max_deferral_minutes: u64,
+    /// Провайдер риск-скрининга адресов назначения (опционально)
+    risk_provider: Option<Arc<dyn RiskScreeningProvider>>,
+    /// Порог риска, начиная с которого трансфер блокируется (0-100)
+    risk_block_threshold: i32,
+    /// Рантайм-настройки пайплайна обработки (меняются через admin API)
+    processing_tuning: Arc<Mutex<ProcessingTuning>>,
+    /// Снимок последней итерации обработки для статистики
+    last_iteration: Arc<Mutex<ProcessingIterationSnapshot>>,
+    /// Идентификатор инстанса процессора (виден в claimed_by)
+    instance_id: String,
+    /// Белый список адресов выводов (пустой - любой валидный адрес)
+    withdrawal_allowlist: Vec<String>,
+    /// Шина событий статусов для стриминговых подписчиков (опционально)
+    event_bus: Option<Arc<TransferEventBus>>,
+    /// Шифрование колонок на стороне БД (None - режим application)
+    column_encryption: Option<Arc<crate::infrastructure::database::ColumnEncryption>>,
+    /// Координатор graceful shutdown: при завершении новые батчи
+    /// не берутся, а начатые трансферы учитываются в drain'е
+    shutdown: Option<Arc<crate::infrastructure::ShutdownCoordinator>>,
+    /// Часы сервиса: один источник времени для claim'ов, дедлайнов
+    /// и completed_at вместо смешения Utc::now и часов Postgres
+    clock: Arc<dyn crate::utils::Clock>,
+    /// Мультитокенный сервис: контракты, лимиты и балансы не-USDT
+    /// токенов (None - поддерживается только USDT)
+    token_service: Option<Arc<crate::infrastructure::tron::Trc20TokenService>>,
 }
 
 impl TransferService {
@@ -89,18 +448,140 @@ impl TransferService {
         tron_client: TronGridClient,
         fee_service: UnifiedFeeService,
         master_wallet_address: String,
+        master_wallet_pool: Arc<MasterWalletPool>,
         sponsor_gas_service: SponsorGasService,
+        audit_shipper: AuditShipper,
     ) -> Self {
         Self {
             db,
             tron_client,
             fee_service,
             master_wallet_address,
+            master_wallet_pool,
             sponsor_gas_service,
-            transaction_signer: TronTransactionSigner::new(),
+            signing_backend: Arc::new(LocalSigningBackend::new()),
+            circuit_breaker: CircuitBreaker::new(),
+            audit_shipper,
+            netting_enabled: false,
+            sweep_destinations: HashMap::new(),
+            congestion_deferral_enabled: false,
+            max_deferral_minutes: 60,
+            risk_provider: None,
+            risk_block_threshold: 75,
+            processing_tuning: Arc::new(Mutex::new(ProcessingTuning::default())),
+            last_iteration: Arc::new(Mutex::new(ProcessingIterationSnapshot::default())),
+            instance_id: Self::generate_instance_id(),
+            withdrawal_allowlist: Vec::new(),
+            event_bus: None,
+            column_encryption: None,
+            shutdown: None,
+            clock: Arc::new(crate::utils::SystemClock),
+            token_service: None,
+        }
+    }
+
+    /// Включает расшифровку pgcrypto-колонки приватных ключей
+    pub fn with_column_encryption(
+        mut self,
+        column_encryption: Option<Arc<crate::infrastructure::database::ColumnEncryption>>,
+    ) -> Self {
+        self.column_encryption = column_encryption;
+        self
+    }
+
+    /// Подключает координатор graceful shutdown
+    pub fn with_shutdown(
+        mut self,
+        shutdown: Arc<crate::infrastructure::ShutdownCoordinator>,
+    ) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Подменяет часы (контроль времени в тестах)
+    pub fn with_clock(mut self, clock: Arc<dyn crate::utils::Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Подключает мультитокенный сервис (выводы не-USDT токенов)
+    pub fn with_token_service(
+        mut self,
+        token_service: Arc<crate::infrastructure::tron::Trc20TokenService>,
+    ) -> Self {
+        self.token_service = Some(token_service);
+        self
+    }
+
+    /// Приватный ключ кошелька: в режиме pgcrypto расшифровывается
+    /// на стороне БД, иначе берется из plaintext-колонки
+    async fn wallet_private_key(&self, wallet: &WalletModel) -> Result<String> {
+        match &self.column_encryption {
+            Some(encryption) => encryption.wallet_private_key(wallet).await,
+            None => Ok(wallet.private_key.clone()),
         }
     }
 
+    /// Генерирует идентификатор инстанса процессора по умолчанию
+    fn generate_instance_id() -> String {
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        format!("worker-{}", &suffix[..8])
+    }
+
+    /// Задает явный идентификатор инстанса процессора
+    pub fn with_instance_id(mut self, instance_id: String) -> Self {
+        self.instance_id = instance_id;
+        self
+    }
+
+    /// Задает белый список адресов выводов из конфига
+    pub fn with_withdrawal_allowlist(mut self, allowlist: Vec<String>) -> Self {
+        self.withdrawal_allowlist = allowlist;
+        self
+    }
+
+    /// Заменяет бэкенд подписания (по умолчанию - локальный)
+    pub fn with_signing_backend(mut self, signing_backend: Arc<dyn SigningBackend>) -> Self {
+        self.signing_backend = signing_backend;
+        self
+    }
+
+    /// Включает неттинг pending sweep'ов (см. `process_pending_transfers`)
+    pub fn with_netting(mut self, netting_enabled: bool) -> Self {
+        self.netting_enabled = netting_enabled;
+        self
+    }
+
+    /// Задает переопределения sweep-назначений по символу токена
+    pub fn with_sweep_destinations(mut self, sweep_destinations: HashMap<String, String>) -> Self {
+        self.sweep_destinations = sweep_destinations;
+        self
+    }
+
+    /// Включает откладывание несрочных sweep'ов при высокой загрузке сети
+    pub fn with_congestion_deferral(mut self, enabled: bool, max_deferral_minutes: u64) -> Self {
+        self.congestion_deferral_enabled = enabled;
+        self.max_deferral_minutes = max_deferral_minutes;
+        self
+    }
+
+    /// Подключает шину событий статусов (gRPC стримы WatchTransfer)
+    pub fn with_event_bus(mut self, event_bus: Arc<TransferEventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Подключает риск-скрининг адресов назначения с порогом блокировки
+    pub fn with_risk_screening(
+        mut self,
+        provider: Arc<dyn RiskScreeningProvider>,
+        block_threshold: i32,
+    ) -> Self {
+        self.risk_provider = Some(provider);
+        self.risk_block_threshold = block_threshold;
+        self
+    }
+
     /// Получение трансфера по reference_id
     pub async fn get_transfer_by_reference(
         &self,
@@ -175,19 +656,25 @@ impl TransferService {
             })?;
 
         // 2. Рассчитываем комиссии (делаем mutable clone для вызова)
+        let fee_payer = request.fee_payer.unwrap_or_default();
         let mut fee_service = self.fee_service.clone();
         let (gas_cost_usdt, percentage_commission, final_commission, total_amount) = fee_service
-            .calculate_total_amount(request.order_amount, &wallet.address)
+            .calculate_total_amount(
+                request.order_amount,
+                &wallet.address,
+                fee_payer,
+                wallet.owner_id.as_deref(),
+            )
             .await?;
 
-        Ok(TransferPreview {
-            order_amount: request.order_amount,
-            commission: final_commission,
-            gas_cost_in_usdt: gas_cost_usdt,
-            percentage_commission,
-            total_amount,
-            master_wallet_receives: request.order_amount,
-            breakdown: format!(
+        // Мерчант поглощает комиссии - получает сумму заказа за их вычетом
+        let master_wallet_receives = match fee_payer {
+            FeePayer::Customer => request.order_amount,
+            FeePayer::Merchant => request.order_amount - gas_cost_usdt - final_commission,
+        };
+
+        let breakdown = match fee_payer {
+            FeePayer::Customer => format!(
                 "Order: {} USDT + Commission: {} USDT (Gas: {} + Service: {}) = Total: {} USDT",
                 request.order_amount,
                 final_commission,
@@ -195,9 +682,47 @@ impl TransferService {
                 percentage_commission,
                 total_amount
             ),
-            trx_to_usdt_rate: self.fee_service.get_config().trx_to_usdt_rate,
+            FeePayer::Merchant => format!(
+                "Order: {} USDT - Commission: {} USDT (Gas: {} + Service: {}) = Merchant receives: {} USDT",
+                request.order_amount,
+                final_commission,
+                gas_cost_usdt,
+                percentage_commission,
+                master_wallet_receives
+            ),
+        };
+
+        // Структурированная детализация дублирует legacy-строку:
+        // фронтенды рендерят строки по kind, не разбирая текст
+        let item = |label: &str, amount: Decimal, kind: &str| BreakdownItem {
+            label: label.to_string(),
+            amount,
+            currency: "USDT".to_string(),
+            kind: kind.to_string(),
+        };
+        let mut breakdown_items = vec![
+            item("Order", request.order_amount, "order"),
+            item("Gas fee", gas_cost_usdt, "gas_fee"),
+            item("Service fee", percentage_commission, "service_fee"),
+        ];
+        breakdown_items.push(match fee_payer {
+            FeePayer::Customer => item("Total", total_amount, "total"),
+            FeePayer::Merchant => item("Merchant receives", master_wallet_receives, "payout"),
+        });
+
+        Ok(TransferPreview {
+            order_amount: request.order_amount,
+            commission: final_commission,
+            gas_cost_in_usdt: gas_cost_usdt,
+            percentage_commission,
+            total_amount,
+            master_wallet_receives,
+            breakdown,
+            breakdown_items,
+            trx_to_usdt_rate: self.fee_service.trx_to_usdt_rate(),
             from_wallet_id: request.from_wallet_id,
             reference_id: request.reference_id,
+            fee_payer,
         })
     }
 
@@ -246,6 +771,20 @@ impl TransferService {
                 .map_err(|e| anyhow::anyhow!("Валидация reference_id: {}", e))?;
         }
 
+        // TRC-20 переводы не поддерживают on-chain memo,
+        // поэтому тег хранится у нас и попадает в webhook/export метаданные
+        if let Some(tag) = &request.destination_tag {
+            TronValidator::validate_destination_tag(tag)
+                .map_err(|e| anyhow::anyhow!("Валидация destination_tag: {}", e))?;
+        }
+
+        // Метаданные хранятся и возвращаются как есть - валидируем
+        // только форму (объект) и размер
+        if let Some(metadata) = &request.metadata {
+            TronValidator::validate_metadata(metadata)
+                .map_err(|e| anyhow::anyhow!("Валидация metadata: {}", e))?;
+        }
+
         // 2. Получаем кошелек отправителя
         let mut conn = self.db.get().await?;
         let wallet: WalletModel = schema::wallets::table
@@ -254,40 +793,130 @@ impl TransferService {
             .await
             .map_err(|_| anyhow::anyhow!("Кошелек с ID {} не найден", request.from_wallet_id))?;
 
+        // Кошельки на комплаенс-проверке не свипуются до снятия флага
+        if wallet.under_review {
+            return Err(anyhow::anyhow!(
+                "Кошелек {} на комплаенс-проверке, трансферы заблокированы",
+                wallet.address
+            ));
+        }
+
+        // Watch-only адреса без приватного ключа - подписать перевод нечем
+        if wallet.watch_only {
+            return Err(anyhow::anyhow!(
+                "Кошелек {} watch-only, трансферы с него невозможны",
+                wallet.address
+            ));
+        }
+
         // 3. Проверяем баланс кошелька
         let wallet_balance = self.tron_client.get_usdt_balance(&wallet.address).await?;
-        
+
         // 4. Рассчитываем общую сумму включая комиссии (делаем mutable clone)
+        let fee_payer = request.fee_payer.unwrap_or_default();
         let mut fee_service = self.fee_service.clone();
         let (gas_cost_usdt, percentage_commission, final_commission, total_amount) = fee_service
-            .calculate_total_amount(request.order_amount, &wallet.address)
+            .calculate_total_amount(
+                request.order_amount,
+                &wallet.address,
+                fee_payer,
+                wallet.owner_id.as_deref(),
+            )
             .await?;
 
+        // Снимок курса TRX/USDT - сохраняется на трансфере для аудита
+        let rate_snapshot = fee_service.rate_snapshot();
+
         tracing::info!(
             "Расчет комиссий: газ={} USDT, процент={} USDT, итого={} USDT, общая сумма={} USDT",
-            gas_cost_usdt, percentage_commission, final_commission, total_amount
+            gas_cost_usdt,
+            percentage_commission,
+            final_commission,
+            total_amount
         );
 
         // 5. Проверяем достаточность баланса
         if wallet_balance < total_amount {
             return Err(anyhow::anyhow!(
                 "Недостаточно средств на кошельке {}. Требуется: {} USDT, доступно: {} USDT",
-                wallet.address, total_amount, wallet_balance
+                wallet.address,
+                total_amount,
+                wallet_balance
             ));
         }
 
         tracing::info!(
             "Проверка баланса прошла успешно: доступно {} USDT, требуется {} USDT",
-            wallet_balance, total_amount
+            wallet_balance,
+            total_amount
         );
 
         // 6. Создаем новый трансфер в БД со статусом PENDING
+        // Sweep-назначение: переопределение из конфига для USDT, иначе
+        // мастер-кошелек из пула; фиксируется в to_address трансфера
+        let sweep_to_address = match self.sweep_destinations.get("USDT") {
+            Some(address) => address.clone(),
+            None => self.master_wallet_pool.select().await.address,
+        };
+
+        // 7. Риск-скрининг адреса назначения (если провайдер подключен).
+        // Недоступность провайдера не блокирует сweep - скрининг просто
+        // не записывается, о чем остается warn в логах
+        let screening = match &self.risk_provider {
+            Some(provider) => match provider.screen_address(&sweep_to_address).await {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠️ Провайдер риск-скрининга недоступен для {}: {}",
+                        sweep_to_address,
+                        e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let blocked = screening
+            .as_ref()
+            .map(|result| result.risk_score >= self.risk_block_threshold)
+            .unwrap_or(false);
+
+        let status = if blocked {
+            TransactionStatus::Failed
+        } else {
+            TransactionStatus::Pending
+        };
+
+        // Доменные инварианты (сумма, адрес, согласованность статуса)
+        // проверяются типизированным builder'ом сущности
+        let entity = OutgoingTransfer::builder(
+            request.from_wallet_id,
+            sweep_to_address,
+            request.order_amount,
+        )
+        .with_status(status.clone())
+        .with_reference_id(request.reference_id.clone())
+        .build()?;
+
         let new_transfer = NewOutgoingTransfer {
-            from_wallet_id: request.from_wallet_id,
-            to_address: self.master_wallet_address.clone(),
-            amount: decimal_to_bigdecimal(request.order_amount),
-            status: "PENDING".to_string(),
-            reference_id: request.reference_id.clone(),
+            from_wallet_id: entity.from_wallet_id,
+            to_address: entity.to_address,
+            amount: decimal_to_bigdecimal(entity.amount),
+            status: entity.status.as_db_str().to_string(),
+            reference_id: entity.reference_id,
+            destination_tag: request.destination_tag.clone(),
+            risk_score: screening.as_ref().map(|result| result.risk_score),
+            risk_provider: screening.as_ref().map(|result| result.provider.clone()),
+            screened_at: screening.as_ref().map(|result| result.screened_at),
+            metadata: request.metadata.as_ref().map(|value| value.to_string()),
+            fee_payer: fee_payer.as_db_str().to_string(),
+            fee_amount: Some(decimal_to_bigdecimal(gas_cost_usdt + final_commission)),
+            transfer_kind: TransferKind::Sweep.as_db_str().to_string(),
+            token_symbol: "USDT".to_string(),
+            fee_trx_usdt_rate: Some(decimal_to_bigdecimal(rate_snapshot.rate)),
+            fee_rate_source: Some(rate_snapshot.source.clone()),
+            fee_rate_captured_at: Some(rate_snapshot.captured_at),
         };
 
         let transfer: OutgoingTransferModel =
@@ -296,94 +925,917 @@ impl TransferService {
                 .get_result(&mut conn)
                 .await?;
 
+        if blocked {
+            let risk_score = transfer.risk_score.unwrap_or_default();
+            tracing::warn!(
+                "🚫 Трансфер ID {} заблокирован риск-скринингом: адрес {} набрал {} (порог {})",
+                transfer.id,
+                transfer.to_address,
+                risk_score,
+                self.risk_block_threshold
+            );
+            self.mark_transfer_failed(
+                &transfer,
+                &format!(
+                    "Заблокирован риск-скринингом: оценка {} >= порога {}",
+                    risk_score, self.risk_block_threshold
+                ),
+            )
+            .await?;
+        }
+
+        self.audit_shipper
+            .emit(
+                "transfer.created",
+                serde_json::json!({
+                    "transfer_id": transfer.id,
+                    "from_wallet_id": transfer.from_wallet_id,
+                    "to_address": transfer.to_address,
+                    "amount": request.order_amount,
+                    "reference_id": transfer.reference_id,
+                    "destination_tag": transfer.destination_tag,
+                    "percentage_commission": percentage_commission,
+                    "commission_tier": fee_service.applied_commission_tier(),
+                    "fee_rate_snapshot": rate_snapshot,
+                }),
+            )
+            .await;
+
         Ok(TransferResponse {
             id: transfer.id,
             from_wallet_id: transfer.from_wallet_id,
             to_address: transfer.to_address,
             amount: bigdecimal_to_decimal(transfer.amount),
-            status: TransactionStatus::Pending,
+            status,
             tx_hash: transfer.tx_hash,
             reference_id: transfer.reference_id,
+            destination_tag: transfer.destination_tag,
             error_message: None,
             created_at: transfer.created_at,
             completed_at: transfer.completed_at,
+            risk_score: transfer.risk_score,
+            metadata: request.metadata,
+            fee_payer,
+            fee_amount: transfer.fee_amount.map(bigdecimal_to_decimal),
+            token_symbol: transfer.token_symbol,
+            fee_trx_usdt_rate: transfer.fee_trx_usdt_rate.map(bigdecimal_to_decimal),
+            fee_rate_source: transfer.fee_rate_source,
+            fee_rate_captured_at: transfer.fee_rate_captured_at,
         })
     }
 
-    /// Обработка pending трансферов
-    pub async fn process_pending_transfers(&self) -> Result<()> {
-        // Получаем все pending трансферы из БД
-        let mut conn = self.db.get().await?;
-        let pending_transfers: Vec<OutgoingTransferModel> = schema::outgoing_transfers::table
-            .filter(schema::outgoing_transfers::status.eq("PENDING"))
-            .order(schema::outgoing_transfers::created_at.asc())
-            .load(&mut conn)
-            .await?;
+    /// Создание вывода TRC-20 токена на произвольный адрес (сохранение
+    /// в БД как PENDING). По умолчанию выводится USDT; другие токены
+    /// требуют подключенного мультитокенного сервиса и проверяются
+    /// по его реестру (поддержка, включенность, лимиты суммы).
+    ///
+    /// В отличие от sweep'ов, назначение задает клиент: адрес валидируется
+    /// и, если белый список в конфиге непуст, должен входить в него.
+    /// Дальше вывод живет обычным пайплайном обработки, но не объединяется
+    /// неттингом и не откладывается при высокой загрузке сети
+    pub async fn create_withdrawal(
+        &self,
+        request: CreateWithdrawalRequest,
+    ) -> Result<TransferResponse> {
+        tracing::info!("Создание вывода: {:?}", request);
 
-        tracing::info!(
-            "Обрабатываем {} pending трансферов",
-            pending_transfers.len()
-        );
+        // 1. Валидация входных данных
+        TronValidator::validate_amount(request.amount)
+            .map_err(|e| anyhow::anyhow!("Валидация суммы: {}", e))?;
 
-        for transfer in pending_transfers {
-            match self.process_transfer(&transfer).await {
-                Ok(_) => {
-                    tracing::info!("Трансфер ID: {} обработан успешно", transfer.id);
-                }
-                Err(e) => {
-                    tracing::error!("Не удалось обработать трансфер ID: {}: {}", transfer.id, e);
-                    self.mark_transfer_failed(&transfer, &e.to_string()).await?;
-                }
-            }
+        TronValidator::validate_address(&request.to_address)
+            .map_err(|e| anyhow::anyhow!("Валидация адреса назначения: {}", e))?;
+
+        if !self.withdrawal_allowlist.is_empty()
+            && !self.withdrawal_allowlist.contains(&request.to_address)
+        {
+            return Err(anyhow::anyhow!(
+                "Адрес {} не входит в белый список выводов",
+                request.to_address
+            ));
         }
-        Ok(())
-    }
 
-    /// Обработка одного трансфера
-    async fn process_transfer(&self, transfer: &OutgoingTransferModel) -> Result<()> {
-        // Получаем кошелек отправителя
+        if let Some(ref_id) = &request.reference_id {
+            TronValidator::validate_reference_id(ref_id)
+                .map_err(|e| anyhow::anyhow!("Валидация reference_id: {}", e))?;
+        }
+
+        if let Some(tag) = &request.destination_tag {
+            TronValidator::validate_destination_tag(tag)
+                .map_err(|e| anyhow::anyhow!("Валидация destination_tag: {}", e))?;
+        }
+
+        if let Some(metadata) = &request.metadata {
+            TronValidator::validate_metadata(metadata)
+                .map_err(|e| anyhow::anyhow!("Валидация metadata: {}", e))?;
+        }
+
+        // 2. Получаем кошелек отправителя (те же ограничения, что у sweep'ов)
         let mut conn = self.db.get().await?;
         let wallet: WalletModel = schema::wallets::table
-            .find(transfer.from_wallet_id)
+            .find(request.from_wallet_id)
             .first(&mut conn)
-            .await?;
+            .await
+            .map_err(|_| anyhow::anyhow!("Кошелек с ID {} не найден", request.from_wallet_id))?;
 
-        tracing::info!(
-            "Обрабатываем трансфер ID: {} с кошелька {} на {}",
-            transfer.id,
-            wallet.address,
-            transfer.to_address
-        );
+        if wallet.under_review {
+            return Err(anyhow::anyhow!(
+                "Кошелек {} на комплаенс-проверке, выводы заблокированы",
+                wallet.address
+            ));
+        }
 
-        // Шаг 0: Предварительно заправляем пользовательский кошелек TRX для газа
-        tracing::info!(
-            "Предварительно заправляем пользовательский кошелек {} TRX для газа",
-            wallet.address
-        );
-        self.sponsor_gas_service
-            .ensure_gas_for_transfer(
+        if wallet.watch_only {
+            return Err(anyhow::anyhow!(
+                "Кошелек {} watch-only, выводы с него невозможны",
+                wallet.address
+            ));
+        }
+
+        // 3. Токен вывода: по умолчанию USDT, остальные проверяются
+        // по реестру мультитокенного сервиса (поддержка и лимиты)
+        let token_symbol = request
+            .token_symbol
+            .clone()
+            .unwrap_or_else(|| "USDT".to_string());
+
+        if token_symbol != "USDT" {
+            let token_service = self.token_service.as_ref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Выводы токена {} недоступны: мультитокенный сервис не подключен",
+                    token_symbol
+                )
+            })?;
+            let token_info = token_service
+                .token_info(&token_symbol)
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Токен {} не поддерживается", token_symbol))?;
+
+            if !token_info.enabled {
+                return Err(anyhow::anyhow!("Токен {} отключен", token_symbol));
+            }
+
+            token_info
+                .validate_amount(request.amount)
+                .map_err(|e| anyhow::anyhow!("Валидация суммы токена: {}", e))?;
+        }
+
+        // 4. Комиссии и достаточность баланса
+        let fee_payer = request.fee_payer.unwrap_or_default();
+        let mut fee_service = self.fee_service.clone();
+        let (gas_cost_usdt, _percentage_commission, final_commission, total_amount) = fee_service
+            .calculate_total_amount(
+                request.amount,
                 &wallet.address,
-                bigdecimal_to_decimal(transfer.amount.clone()),
+                fee_payer,
+                wallet.owner_id.as_deref(),
             )
             .await?;
 
-        // Ждем немного для подтверждения TRX транзакции
-        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        // Снимок курса TRX/USDT - сохраняется на трансфере для аудита
+        let rate_snapshot = fee_service.rate_snapshot();
+
+        // Баланс проверяется в токене вывода. Комиссии считаются и
+        // учитываются в USDT, поэтому к балансу другого токена
+        // добавляется только сумма самого вывода
+        let (wallet_balance, required_amount) = match &self.token_service {
+            Some(token_service) if token_symbol != "USDT" => (
+                token_service
+                    .get_token_balance(&wallet.address, &token_symbol)
+                    .await?,
+                request.amount,
+            ),
+            _ => (
+                self.tron_client.get_usdt_balance(&wallet.address).await?,
+                total_amount,
+            ),
+        };
+
+        if wallet_balance < required_amount {
+            return Err(anyhow::anyhow!(
+                "Недостаточно средств на кошельке {}. Требуется: {} {}, доступно: {} {}",
+                wallet.address,
+                required_amount,
+                token_symbol,
+                wallet_balance,
+                token_symbol
+            ));
+        }
+
+        // 5. Риск-скрининг произвольного адреса назначения (если подключен)
+        let screening = match &self.risk_provider {
+            Some(provider) => match provider.screen_address(&request.to_address).await {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠️ Провайдер риск-скрининга недоступен для {}: {}",
+                        request.to_address,
+                        e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
 
-        // Шаг 1: Создаем неподписанную USDT транзакцию
-        let tx_result = self
+        let blocked = screening
+            .as_ref()
+            .map(|result| result.risk_score >= self.risk_block_threshold)
+            .unwrap_or(false);
+
+        let status = if blocked {
+            TransactionStatus::Failed
+        } else {
+            TransactionStatus::Pending
+        };
+
+        // 6. Сохраняем вывод через доменный builder (инварианты)
+        let entity = OutgoingTransfer::builder(
+            request.from_wallet_id,
+            request.to_address.clone(),
+            request.amount,
+        )
+        .with_status(status.clone())
+        .with_reference_id(request.reference_id.clone())
+        .build()?;
+
+        let new_transfer = NewOutgoingTransfer {
+            from_wallet_id: entity.from_wallet_id,
+            to_address: entity.to_address,
+            amount: decimal_to_bigdecimal(entity.amount),
+            status: entity.status.as_db_str().to_string(),
+            reference_id: entity.reference_id,
+            destination_tag: request.destination_tag.clone(),
+            risk_score: screening.as_ref().map(|result| result.risk_score),
+            risk_provider: screening.as_ref().map(|result| result.provider.clone()),
+            screened_at: screening.as_ref().map(|result| result.screened_at),
+            metadata: request.metadata.as_ref().map(|value| value.to_string()),
+            fee_payer: fee_payer.as_db_str().to_string(),
+            fee_amount: Some(decimal_to_bigdecimal(gas_cost_usdt + final_commission)),
+            transfer_kind: TransferKind::Withdrawal.as_db_str().to_string(),
+            token_symbol: token_symbol.clone(),
+            fee_trx_usdt_rate: Some(decimal_to_bigdecimal(rate_snapshot.rate)),
+            fee_rate_source: Some(rate_snapshot.source.clone()),
+            fee_rate_captured_at: Some(rate_snapshot.captured_at),
+        };
+
+        let transfer: OutgoingTransferModel =
+            diesel::insert_into(schema::outgoing_transfers::table)
+                .values(&new_transfer)
+                .get_result(&mut conn)
+                .await?;
+
+        if blocked {
+            let risk_score = transfer.risk_score.unwrap_or_default();
+            tracing::warn!(
+                "🚫 Вывод ID {} заблокирован риск-скринингом: адрес {} набрал {} (порог {})",
+                transfer.id,
+                transfer.to_address,
+                risk_score,
+                self.risk_block_threshold
+            );
+            self.mark_transfer_failed(
+                &transfer,
+                &format!(
+                    "Заблокирован риск-скринингом: оценка {} >= порога {}",
+                    risk_score, self.risk_block_threshold
+                ),
+            )
+            .await?;
+        }
+
+        self.audit_shipper
+            .emit(
+                "withdrawal.created",
+                serde_json::json!({
+                    "transfer_id": transfer.id,
+                    "from_wallet_id": transfer.from_wallet_id,
+                    "to_address": transfer.to_address,
+                    "amount": request.amount,
+                    "token_symbol": transfer.token_symbol,
+                    "reference_id": transfer.reference_id,
+                    "fee_rate_snapshot": rate_snapshot,
+                }),
+            )
+            .await;
+
+        Ok(self.model_to_response(transfer))
+    }
+
+    /// Текущие настройки пайплайна обработки
+    pub fn get_processing_tuning(&self) -> ProcessingTuning {
+        self.processing_tuning.lock().unwrap().clone()
+    }
+
+    /// Обновляет настройки пайплайна обработки в рантайме.
+    /// Не переданные поля остаются без изменений, значения зажимаются к >= 1
+    pub fn update_processing_tuning(
+        &self,
+        max_batch_size: Option<i64>,
+        parallelism: Option<usize>,
+    ) -> ProcessingTuning {
+        let mut tuning = self.processing_tuning.lock().unwrap();
+        if let Some(max_batch_size) = max_batch_size {
+            tuning.max_batch_size = max_batch_size.max(1);
+        }
+        if let Some(parallelism) = parallelism {
+            tuning.parallelism = parallelism.max(1);
+        }
+
+        tracing::info!(
+            "📊 Настройки пайплайна обработки обновлены: max_batch_size={}, parallelism={}",
+            tuning.max_batch_size,
+            tuning.parallelism
+        );
+
+        tuning.clone()
+    }
+
+    /// Статистика пайплайна обработки: глубина очереди, средний возраст
+    /// PENDING трансфера и размеры батчей последней итерации
+    pub async fn get_processing_stats(&self) -> Result<ProcessingStats> {
+        let mut conn = self.db.get().await?;
+
+        let pending_created: Vec<chrono::DateTime<chrono::Utc>> = schema::outgoing_transfers::table
+            .filter(schema::outgoing_transfers::status.eq(TransactionStatus::Pending.as_db_str()))
+            .select(schema::outgoing_transfers::created_at)
+            .load(&mut conn)
+            .await?;
+
+        let queue_depth = pending_created.len() as i64;
+        let now = self.clock.now();
+        let avg_pending_wait_seconds = if pending_created.is_empty() {
+            0
+        } else {
+            pending_created
+                .iter()
+                .map(|created_at| (now - *created_at).num_seconds().max(0))
+                .sum::<i64>()
+                / queue_depth
+        };
+
+        let (last_batch_sizes, last_iteration_at) = {
+            let snapshot = self.last_iteration.lock().unwrap();
+            (snapshot.batch_sizes.clone(), snapshot.finished_at)
+        };
+
+        Ok(ProcessingStats {
+            queue_depth,
+            avg_pending_wait_seconds,
+            last_batch_sizes,
+            last_iteration_at,
+            tuning: self.get_processing_tuning(),
+        })
+    }
+
+    /// Обработка pending трансферов
+    pub async fn process_pending_transfers(&self) -> Result<()> {
+        // При graceful shutdown новую работу не берем: уже начатые
+        // батчи досчитываются под своими in-flight guard'ами
+        if self
+            .shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.is_shutting_down())
+        {
+            tracing::info!("🛑 Завершение процесса - новые трансферы не забираются");
+            return Ok(());
+        }
+
+        // Если breaker открыт - обрабатываем только пробный трансфер,
+        // остальные остаются PENDING до восстановления TronGrid
+        let probe_mode = self.circuit_breaker.is_open();
+        if probe_mode && !self.circuit_breaker.probe_allowed() {
+            tracing::warn!(
+                "⚠️ Circuit breaker открыт - обработка трансферов приостановлена до пробной попытки"
+            );
+            return Ok(());
+        }
+
+        let tuning = self.get_processing_tuning();
+
+        // Получаем pending трансферы из БД (не больше max_batch_size за итерацию).
+        // Трансферы с кошельков на комплаенс-проверке остаются PENDING
+        // до снятия флага (hold-on-sweep)
+        let mut conn = self.db.get().await?;
+        let flagged_wallets = schema::wallets::table
+            .filter(schema::wallets::under_review.eq(true))
+            .select(schema::wallets::id);
+
+        let mut pending_transfers: Vec<OutgoingTransferModel> = schema::outgoing_transfers::table
+            .filter(schema::outgoing_transfers::status.eq(TransactionStatus::Pending.as_db_str()))
+            .filter(schema::outgoing_transfers::from_wallet_id.ne_all(flagged_wallets))
+            .order(schema::outgoing_transfers::created_at.asc())
+            .limit(tuning.max_batch_size)
+            .load(&mut conn)
+            .await?;
+
+        // Sweep'ы - несрочная работа: при высокой загрузке сети откладываем
+        // их до дешевого окна, но не дольше max_deferral_minutes
+        if self.congestion_deferral_enabled && !pending_transfers.is_empty() {
+            match self.fee_service.current_congestion_level().await {
+                Ok(CongestionLevel::High) => {
+                    let deferral_cutoff = self.clock.now()
+                        - chrono::Duration::minutes(self.max_deferral_minutes as i64);
+                    let before_deferral = pending_transfers.len();
+                    // Выводы (withdrawal) срочные - откладываются только sweep'ы
+                    pending_transfers.retain(|transfer| {
+                        transfer.transfer_kind != TransferKind::Sweep.as_db_str()
+                            || transfer.created_at <= deferral_cutoff
+                    });
+                    let deferred = before_deferral - pending_transfers.len();
+                    if deferred > 0 {
+                        tracing::info!(
+                            "📊 Высокая загрузка сети: откладываем {} несрочных трансферов (не дольше {} минут)",
+                            deferred,
+                            self.max_deferral_minutes
+                        );
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠️ Не удалось определить загрузку сети, обрабатываем без откладывания: {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        tracing::info!(
+            "Обрабатываем {} pending трансферов (parallelism: {})",
+            pending_transfers.len(),
+            tuning.parallelism
+        );
+
+        // При включенном неттинге sweep'ы одного кошелька на одно назначение
+        // объединяются в одну on-chain транзакцию; окном неттинга служит
+        // итерация обработки
+        let mut batches: Vec<Vec<OutgoingTransferModel>> = Vec::new();
+        if self.netting_enabled {
+            let mut batch_index: HashMap<(i64, String), usize> = HashMap::new();
+            for transfer in pending_transfers {
+                // Выводы не объединяются: per-withdrawal учет on-chain
+                if transfer.transfer_kind == TransferKind::Withdrawal.as_db_str() {
+                    batches.push(vec![transfer]);
+                    continue;
+                }
+
+                let key = (transfer.from_wallet_id, transfer.to_address.clone());
+                match batch_index.get(&key) {
+                    Some(&index) => batches[index].push(transfer),
+                    None => {
+                        batch_index.insert(key, batches.len());
+                        batches.push(vec![transfer]);
+                    }
+                }
+            }
+        } else {
+            batches = pending_transfers
+                .into_iter()
+                .map(|transfer| vec![transfer])
+                .collect();
+        }
+
+        let batch_sizes: Vec<usize> = batches.iter().map(|batch| batch.len()).collect();
+
+        // Батчи обрабатываются чанками по parallelism штук: внутри чанка -
+        // параллельно, между чанками проверяем состояние circuit breaker'а
+        let mut breaker_tripped = false;
+        for chunk in batches.chunks(tuning.parallelism) {
+            // Между чанками реагируем на сигнал завершения: незабранные
+            // батчи остаются PENDING и доедут после рестарта
+            if self
+                .shutdown
+                .as_ref()
+                .is_some_and(|shutdown| shutdown.is_shutting_down())
+            {
+                tracing::info!("🛑 Завершение процесса - оставшиеся батчи остаются PENDING");
+                break;
+            }
+
+            let results = futures_util::future::join_all(chunk.iter().map(|batch| async move {
+                // Начатый батч нельзя обрывать на полпути (TRX на газ уже
+                // мог уйти) - guard заставляет drain дождаться его окончания
+                let _in_flight = self.shutdown.as_ref().map(|shutdown| shutdown.track());
+
+                // Помечаем трансферы как забранные этим инстансом (PROCESSING),
+                // чтобы операторы видели in-flight работу во время инцидентов
+                self.claim_transfers(batch).await?;
+
+                if batch.len() == 1 {
+                    self.process_transfer(&batch[0]).await
+                } else {
+                    self.process_netted_batch(batch).await
+                }
+            }))
+            .await;
+
+            for (batch, result) in chunk.iter().zip(results) {
+                let transfer_ids: Vec<i64> = batch.iter().map(|t| t.id).collect();
+
+                match result {
+                    Ok(_) => {
+                        tracing::info!("Трансферы ID: {:?} обработаны успешно", transfer_ids);
+                        self.circuit_breaker.record_success();
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Не удалось обработать трансферы ID: {:?}: {}",
+                            transfer_ids,
+                            e
+                        );
+
+                        if self.circuit_breaker.record_failure() {
+                            // Алерт: серия ошибок говорит о проблеме с TronGrid,
+                            // а не с конкретным трансфером - оставляем его PENDING
+                            tracing::error!(
+                                "❌ Circuit breaker открыт после {} последовательных ошибок - обработка трансферов приостановлена",
+                                self.circuit_breaker.consecutive_failures()
+                            );
+                            self.release_claim(batch).await?;
+                            breaker_tripped = true;
+                            break;
+                        }
+
+                        if self.circuit_breaker.is_open() {
+                            // Пробная попытка не удалась - ждем следующего окна
+                            tracing::warn!(
+                                "⚠️ Пробная попытка не удалась, breaker остается открытым"
+                            );
+                            self.release_claim(batch).await?;
+                            breaker_tripped = true;
+                            break;
+                        }
+
+                        for transfer in batch.iter() {
+                            self.mark_transfer_failed(transfer, &e.to_string()).await?;
+                        }
+                    }
+                }
+            }
+
+            if breaker_tripped {
+                break;
+            }
+        }
+
+        {
+            let mut snapshot = self.last_iteration.lock().unwrap();
+            snapshot.batch_sizes = batch_sizes;
+            snapshot.finished_at = Some(self.clock.now());
+        }
+
+        Ok(())
+    }
+
+    /// Публикует переход статуса на шину событий (no-op без шины)
+    fn publish_status(
+        &self,
+        transfer_id: i64,
+        wallet_id: i64,
+        status: TransactionStatus,
+        tx_hash: Option<String>,
+    ) {
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.publish(TransferStatusChange {
+                kind: TransferEventKind::Outgoing,
+                record_id: transfer_id,
+                wallet_id,
+                status,
+                tx_hash,
+                occurred_at: self.clock.now(),
+            });
+        }
+    }
+
+    /// Помечает батч трансферов как забранный в работу этим инстансом
+    async fn claim_transfers(&self, transfers: &[OutgoingTransferModel]) -> Result<()> {
+        let mut conn = self.db.get().await?;
+        let ids: Vec<i64> = transfers.iter().map(|t| t.id).collect();
+
+        diesel::update(
+            schema::outgoing_transfers::table.filter(schema::outgoing_transfers::id.eq_any(&ids)),
+        )
+        .set((
+            schema::outgoing_transfers::status.eq(TransactionStatus::Processing.as_db_str()),
+            schema::outgoing_transfers::claimed_at.eq(self.clock.now()),
+            schema::outgoing_transfers::claimed_by.eq(&self.instance_id),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+        for transfer in transfers {
+            self.publish_status(
+                transfer.id,
+                transfer.from_wallet_id,
+                TransactionStatus::Processing,
+                None,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Возвращает забранный батч обратно в PENDING (например, при
+    /// открытии circuit breaker'а - проблема не в трансферах)
+    async fn release_claim(&self, transfers: &[OutgoingTransferModel]) -> Result<()> {
+        let mut conn = self.db.get().await?;
+        let ids: Vec<i64> = transfers.iter().map(|t| t.id).collect();
+
+        diesel::update(
+            schema::outgoing_transfers::table.filter(schema::outgoing_transfers::id.eq_any(&ids)),
+        )
+        .set((
+            schema::outgoing_transfers::status.eq(TransactionStatus::Pending.as_db_str()),
+            schema::outgoing_transfers::claimed_at.eq(None::<chrono::DateTime<chrono::Utc>>),
+            schema::outgoing_transfers::claimed_by.eq(None::<String>),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+        for transfer in transfers {
+            self.publish_status(
+                transfer.id,
+                transfer.from_wallet_id,
+                TransactionStatus::Pending,
+                None,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Трансферы, находящиеся в обработке прямо сейчас (PROCESSING),
+    /// с информацией кто и когда их забрал
+    pub async fn get_in_flight_transfers(&self) -> Result<Vec<InFlightTransferResponse>> {
+        let mut conn = self.db.get().await?;
+
+        let transfers: Vec<OutgoingTransferModel> = schema::outgoing_transfers::table
+            .filter(
+                schema::outgoing_transfers::status.eq(TransactionStatus::Processing.as_db_str()),
+            )
+            .order(schema::outgoing_transfers::claimed_at.asc())
+            .load(&mut conn)
+            .await?;
+
+        let now = self.clock.now();
+        Ok(transfers
+            .into_iter()
+            .map(|transfer| {
+                let elapsed_seconds = transfer
+                    .claimed_at
+                    .map(|claimed_at| (now - claimed_at).num_seconds().max(0));
+
+                InFlightTransferResponse {
+                    id: transfer.id,
+                    from_wallet_id: transfer.from_wallet_id,
+                    to_address: transfer.to_address,
+                    amount: bigdecimal_to_decimal(transfer.amount),
+                    claimed_at: transfer.claimed_at,
+                    claimed_by: transfer.claimed_by,
+                    elapsed_seconds,
+                    created_at: transfer.created_at,
+                }
+            })
+            .collect())
+    }
+
+    /// Обработка группы sweep'ов одного кошелька одной on-chain транзакцией
+    ///
+    /// Per-original учет сохраняется: каждый исходный трансфер закрывается
+    /// отдельной записью с общим tx_hash
+    async fn process_netted_batch(&self, transfers: &[OutgoingTransferModel]) -> Result<()> {
+        let first = &transfers[0];
+
+        let mut conn = self.db.get().await?;
+        let wallet: WalletModel = schema::wallets::table
+            .find(first.from_wallet_id)
+            .first(&mut conn)
+            .await?;
+
+        let total_amount: Decimal = transfers
+            .iter()
+            .map(|transfer| bigdecimal_to_decimal(transfer.amount.clone()))
+            .sum();
+
+        tracing::info!(
+            "📊 Неттинг: объединяем {} sweep'ов с кошелька {} на {} (итого {} USDT)",
+            transfers.len(),
+            wallet.address,
+            first.to_address,
+            total_amount
+        );
+
+        // Resume после краша: если общая транзакция батча уже в сети
+        // (txID сохранен на стадии TX_CREATED) - не отправляем повторно
+        if matches!(
+            first
+                .processing_stage
+                .as_deref()
+                .and_then(ProcessingStage::from_db_str),
+            Some(ProcessingStage::TxCreated | ProcessingStage::Signed)
+        ) {
+            if let Some(txid) = first.tx_hash.as_deref() {
+                if let Ok(Some(_)) = self.tron_client.get_transaction_receipt(txid).await {
+                    tracing::warn!(
+                        "⚠️ Неттинг-транзакция кошелька {} уже в сети ({}) - фиксируем broadcast",
+                        wallet.address,
+                        txid
+                    );
+                    for transfer in transfers {
+                        self.mark_transfer_broadcast(transfer, txid).await?;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+
+        // Заправляем газ один раз на весь батч (при resume не повторяем)
+        if first.processing_stage.is_none() {
+            self.sponsor_gas_service
+                .ensure_gas_for_transfer(&wallet.address, total_amount)
+                .await?;
+            for transfer in transfers {
+                self.set_processing_stage(transfer.id, ProcessingStage::GasSponsored, None)
+                    .await?;
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        }
+
+        let mut tx_result = self
             .tron_client
-            .create_trc20_transaction(
-                &wallet.address,
-                &transfer.to_address,
-                bigdecimal_to_decimal(transfer.amount.clone()),
+            .create_trc20_transaction(&wallet.address, &first.to_address, total_amount)
+            .await?;
+
+        if TronGridClient::transaction_expires_soon(&tx_result) {
+            tracing::warn!(
+                "⚠️ Неттинг-транзакция кошелька {} истекла до подписания - пересоздаем",
+                wallet.address
+            );
+            tx_result = self
+                .tron_client
+                .create_trc20_transaction(&wallet.address, &first.to_address, total_amount)
+                .await?;
+        }
+
+        let created_txid = tx_result
+            .get("txID")
+            .and_then(|t| t.as_str())
+            .map(str::to_string);
+        for transfer in transfers {
+            self.set_processing_stage(
+                transfer.id,
+                ProcessingStage::TxCreated,
+                created_txid.as_deref(),
             )
             .await?;
+        }
+
+        let private_key = self.wallet_private_key(&wallet).await?;
+        let signed_transaction = self
+            .signing_backend
+            .sign_transaction(&tx_result, &private_key)
+            .await?;
+        for transfer in transfers {
+            self.set_processing_stage(transfer.id, ProcessingStage::Signed, None)
+                .await?;
+        }
+
+        let tx_hash = self
+            .tron_client
+            .broadcast_transaction(&signed_transaction)
+            .await?;
+
+        for transfer in transfers {
+            self.mark_transfer_broadcast(transfer, &tx_hash).await?;
+        }
+
+        tracing::info!(
+            "Неттинг-батч из {} трансферов отправлен, ждем подтверждений. TX Hash: {}",
+            transfers.len(),
+            tx_hash
+        );
+
+        Ok(())
+    }
+
+    /// Обработка одного трансфера.
+    ///
+    /// Каждый завершенный шаг фиксируется в processing_stage: после
+    /// рестарта обработка продолжается с последнего resume point'а -
+    /// газ не заправляется повторно, а ушедшая в сеть транзакция
+    /// не отправляется второй раз
+    async fn process_transfer(&self, transfer: &OutgoingTransferModel) -> Result<()> {
+        // Получаем кошелек отправителя
+        let mut conn = self.db.get().await?;
+        let wallet: WalletModel = schema::wallets::table
+            .find(transfer.from_wallet_id)
+            .first(&mut conn)
+            .await?;
+
+        let resume_stage = transfer
+            .processing_stage
+            .as_deref()
+            .and_then(ProcessingStage::from_db_str);
+
+        // Broadcast уже состоялся - завершение фиксирует трекер подтверждений
+        if resume_stage == Some(ProcessingStage::Broadcast) {
+            tracing::info!(
+                "Трансфер ID: {} уже отправлен в сеть (resume point BROADCAST) - пропускаем",
+                transfer.id
+            );
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Обрабатываем трансфер ID: {} с кошелька {} на {}{}",
+            transfer.id,
+            wallet.address,
+            transfer.to_address,
+            resume_stage
+                .map(|stage| format!(" (продолжаем с {})", stage.as_db_str()))
+                .unwrap_or_default()
+        );
+
+        // Шаг 0: Предварительно заправляем пользовательский кошелек TRX для
+        // газа. При resume пропускается - TRX уже потрачен до рестарта
+        if resume_stage.is_none() {
+            tracing::info!(
+                "Предварительно заправляем пользовательский кошелек {} TRX для газа",
+                wallet.address
+            );
+            self.sponsor_gas_service
+                .ensure_gas_for_transfer(
+                    &wallet.address,
+                    bigdecimal_to_decimal(transfer.amount.clone()),
+                )
+                .await?;
+            self.set_processing_stage(transfer.id, ProcessingStage::GasSponsored, None)
+                .await?;
+
+            // Ждем немного для подтверждения TRX транзакции
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        }
+
+        // После TX_CREATED/SIGNED транзакция могла успеть уйти в сеть до
+        // краша - проверяем по сохраненному txID, чтобы не перевести дважды
+        if matches!(
+            resume_stage,
+            Some(ProcessingStage::TxCreated | ProcessingStage::Signed)
+        ) {
+            if let Some(txid) = transfer.tx_hash.as_deref() {
+                if let Ok(Some(_)) = self.tron_client.get_transaction_receipt(txid).await {
+                    tracing::warn!(
+                        "⚠️ Транзакция трансфера ID: {} уже в сети ({}) - фиксируем broadcast без повторной отправки",
+                        transfer.id,
+                        txid
+                    );
+                    self.mark_transfer_broadcast(transfer, txid).await?;
+                    return Ok(());
+                }
+            }
+        }
+
+        // Шаг 1: Создаем неподписанную TRC-20 транзакцию (контракт по
+        // токену трансфера)
+        let mut tx_result = self
+            .create_unsigned_token_transaction(&wallet.address, transfer)
+            .await?;
+
+        // Заправка газа выше занимает секунды - если expiration уже на исходе,
+        // пересоздаем транзакцию вместо гарантированно неудачного broadcast
+        if TronGridClient::transaction_expires_soon(&tx_result) {
+            tracing::warn!(
+                "⚠️ Транзакция для трансфера ID: {} истекла до подписания - пересоздаем",
+                transfer.id
+            );
+            tx_result = self
+                .create_unsigned_token_transaction(&wallet.address, transfer)
+                .await?;
+        }
+
+        // Вместе со стадией сохраняется txID - по нему resume проверяет,
+        // ушла ли транзакция в сеть
+        let created_txid = tx_result
+            .get("txID")
+            .and_then(|t| t.as_str())
+            .map(str::to_string);
+        self.set_processing_stage(
+            transfer.id,
+            ProcessingStage::TxCreated,
+            created_txid.as_deref(),
+        )
+        .await?;
 
         // Шаг 2: Подписываем транзакцию
+        let private_key = self.wallet_private_key(&wallet).await?;
         let signed_transaction = self
-            .transaction_signer
-            .sign_transaction(&tx_result, &wallet.private_key)?;
+            .signing_backend
+            .sign_transaction(&tx_result, &private_key)
+            .await?;
+        self.set_processing_stage(transfer.id, ProcessingStage::Signed, None)
+            .await?;
 
         // Шаг 3: Отправляем транзакцию
         let tx_hash = self
@@ -391,11 +1843,12 @@ impl TransferService {
             .broadcast_transaction(&signed_transaction)
             .await?;
 
-        // Помечаем трансфер как подтвержденный и сохраняем хеш транзакции
-        self.mark_transfer_completed(transfer, &tx_hash).await?;
+        // Сохраняем хеш: COMPLETED выставит трекер подтверждений
+        // после 19 блоков и успешного on-chain receipt'а
+        self.mark_transfer_broadcast(transfer, &tx_hash).await?;
 
         tracing::info!(
-            "Трансфер ID: {} завершен успешно. TX Hash: {}",
+            "Трансфер ID: {} отправлен, ждем подтверждений. TX Hash: {}",
             transfer.id,
             tx_hash
         );
@@ -403,22 +1856,280 @@ impl TransferService {
         Ok(())
     }
 
-    /// Помечает трансфер как завершенный
+    /// Создает неподписанную TRC-20 транзакцию по токену трансфера:
+    /// USDT идет через основной клиент, остальные токены - через
+    /// мультитокенный сервис (контракт из его реестра)
+    async fn create_unsigned_token_transaction(
+        &self,
+        wallet_address: &str,
+        transfer: &OutgoingTransferModel,
+    ) -> Result<serde_json::Value> {
+        if transfer.token_symbol == "USDT" {
+            return self
+                .tron_client
+                .create_trc20_transaction(
+                    wallet_address,
+                    &transfer.to_address,
+                    bigdecimal_to_decimal(transfer.amount.clone()),
+                )
+                .await;
+        }
+
+        let token_service = self.token_service.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Трансфер {} в токене {}, но мультитокенный сервис не подключен",
+                transfer.id,
+                transfer.token_symbol
+            )
+        })?;
+
+        token_service
+            .create_token_transaction(
+                wallet_address,
+                &transfer.to_address,
+                &transfer.token_symbol,
+                bigdecimal_to_decimal(transfer.amount.clone()),
+            )
+            .await
+    }
+
+    /// Фиксирует завершенный под-этап обработки (resume point).
+    /// Для TX_CREATED вместе со стадией сохраняется txID транзакции
+    async fn set_processing_stage(
+        &self,
+        transfer_id: i64,
+        stage: ProcessingStage,
+        tx_hash: Option<&str>,
+    ) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        match tx_hash {
+            Some(tx_hash) => {
+                diesel::update(schema::outgoing_transfers::table.find(transfer_id))
+                    .set((
+                        schema::outgoing_transfers::processing_stage.eq(stage.as_db_str()),
+                        schema::outgoing_transfers::tx_hash.eq(tx_hash),
+                    ))
+                    .execute(&mut conn)
+                    .await?;
+            }
+            None => {
+                diesel::update(schema::outgoing_transfers::table.find(transfer_id))
+                    .set(schema::outgoing_transfers::processing_stage.eq(stage.as_db_str()))
+                    .execute(&mut conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Сохраняет хэш broadcast-транзакции. Трансфер остается в PROCESSING:
+    /// завершение фиксирует confirm_broadcast_transfers по on-chain receipt'у
+    async fn mark_transfer_broadcast(
+        &self,
+        transfer: &OutgoingTransferModel,
+        tx_hash: &str,
+    ) -> Result<()> {
+        let mut conn = self.db.get().await?;
+
+        // В dry-run broadcast был симулирован - строка БД явно
+        // помечается, чтобы репетиционные трансферы были отличимы
+        let metadata = if crate::utils::is_dry_run() {
+            Some(mark_metadata_simulated(transfer.metadata.as_deref()))
+        } else {
+            transfer.metadata.clone()
+        };
+
+        diesel::update(schema::outgoing_transfers::table.find(transfer.id))
+            .set((
+                schema::outgoing_transfers::tx_hash.eq(tx_hash),
+                schema::outgoing_transfers::metadata.eq(metadata),
+                schema::outgoing_transfers::processing_stage
+                    .eq(ProcessingStage::Broadcast.as_db_str()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        // Подписчики стримов видят хэш сразу, не дожидаясь подтверждений
+        self.publish_status(
+            transfer.id,
+            transfer.from_wallet_id,
+            TransactionStatus::Processing,
+            Some(tx_hash.to_string()),
+        );
+
+        Ok(())
+    }
+
+    /// Проверяет подтверждения broadcast-транзакций (вызывается планировщиком).
+    ///
+    /// PROCESSING трансферы с tx_hash сверяются с цепочкой: успешный
+    /// receipt и 19+ подтверждений - COMPLETED с реальным номером блока,
+    /// REVERT/OUT_OF_ENERGY - FAILED, долгое отсутствие в блоке - FAILED
+    /// (транзакция выпала из мемпула). Возвращает число закрытых трансферов
+    pub async fn confirm_broadcast_transfers(&self) -> Result<usize> {
+        let broadcasted: Vec<OutgoingTransferModel> = {
+            let mut conn = self.db.get().await?;
+            schema::outgoing_transfers::table
+                .filter(
+                    schema::outgoing_transfers::status
+                        .eq(TransactionStatus::Processing.as_db_str()),
+                )
+                .filter(schema::outgoing_transfers::tx_hash.is_not_null())
+                // txID появляется уже на стадии TX_CREATED - трекер ждет
+                // только реально отправленные (NULL - строки до миграции)
+                .filter(
+                    schema::outgoing_transfers::processing_stage
+                        .eq(ProcessingStage::Broadcast.as_db_str())
+                        .or(schema::outgoing_transfers::processing_stage.is_null()),
+                )
+                .order(schema::outgoing_transfers::created_at.asc())
+                .load(&mut conn)
+                .await?
+        };
+
+        if broadcasted.is_empty() {
+            return Ok(0);
+        }
+
+        // В dry-run транзакций в сети нет - подтверждения симулируются,
+        // чтобы репетиция проходила полный жизненный цикл до COMPLETED
+        if crate::utils::is_dry_run() {
+            let mut settled = 0usize;
+            for transfer in &broadcasted {
+                let Some(tx_hash) = transfer.tx_hash.clone() else {
+                    continue;
+                };
+                self.mark_transfer_completed(transfer, &tx_hash, None)
+                    .await?;
+                settled += 1;
+            }
+            return Ok(settled);
+        }
+
+        let latest_block = self.tron_client.get_latest_block_number().await?;
+        let mut settled = 0usize;
+
+        for transfer in &broadcasted {
+            let Some(tx_hash) = transfer.tx_hash.clone() else {
+                continue;
+            };
+
+            let receipt = match self.tron_client.get_transaction_receipt(&tx_hash).await {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    tracing::warn!("⚠️  Не удалось получить receipt {}: {}", tx_hash, e);
+                    continue;
+                }
+            };
+
+            match receipt {
+                // Нода транзакцию не видит: либо еще распространяется,
+                // либо выпала из мемпула
+                None => {
+                    let age_minutes = (self.clock.now() - transfer.created_at).num_minutes();
+                    if age_minutes >= BROADCAST_DROP_TIMEOUT_MINUTES {
+                        self.mark_transfer_failed(
+                            transfer,
+                            "Транзакция не попала в блок и выпала из мемпула",
+                        )
+                        .await?;
+                        settled += 1;
+                    }
+                }
+                Some(receipt) if !receipt.success => {
+                    self.mark_transfer_failed(
+                        transfer,
+                        "Транзакция отклонена на цепочке (REVERT/OUT_OF_ENERGY)",
+                    )
+                    .await?;
+                    settled += 1;
+                }
+                Some(receipt) => {
+                    // Успех в блоке: ждем глубины в 19 блоков
+                    let Some(block_number) = receipt.block_number else {
+                        continue;
+                    };
+
+                    let confirmations = latest_block - block_number + 1;
+                    if confirmations >= COMPLETED_CONFIRMATIONS {
+                        self.mark_transfer_completed(transfer, &tx_hash, Some(block_number))
+                            .await?;
+                        settled += 1;
+                    } else {
+                        tracing::debug!(
+                            "🕒 Трансфер {} ждет подтверждений: {}/{}",
+                            transfer.id,
+                            confirmations,
+                            COMPLETED_CONFIRMATIONS
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(settled)
+    }
+
+    /// Помечает трансфер как завершенный с номером блока подтверждения
     async fn mark_transfer_completed(
         &self,
         transfer: &OutgoingTransferModel,
         tx_hash: &str,
+        block_number: Option<i64>,
     ) -> Result<()> {
+        // Проверяем легальность перехода статуса через state machine
+        let current_status = TransactionStatus::from_db_str(&transfer.status)
+            .ok_or_else(|| anyhow::anyhow!("Неизвестный статус трансфера: {}", transfer.status))?;
+        let next_status = current_status.transition_to(TransactionStatus::Completed)?;
+
         let mut conn = self.db.get().await?;
 
         diesel::update(schema::outgoing_transfers::table.find(transfer.id))
             .set((
-                schema::outgoing_transfers::status.eq("CONFIRMED"),
+                schema::outgoing_transfers::status.eq(next_status.as_db_str()),
                 schema::outgoing_transfers::tx_hash.eq(tx_hash),
-                schema::outgoing_transfers::completed_at.eq(diesel::dsl::now),
+                schema::outgoing_transfers::block_number.eq(block_number),
+                schema::outgoing_transfers::completed_at.eq(self.clock.now()),
             ))
             .execute(&mut conn)
             .await?;
+
+        // Обновляем проекцию баланса кошелька-отправителя
+        let balance_service = BalanceService::new(self.db.clone(), self.tron_client.clone());
+        if let Err(e) = balance_service
+            .apply_completed_transfer(
+                transfer.from_wallet_id,
+                bigdecimal_to_decimal(transfer.amount.clone()),
+            )
+            .await
+        {
+            tracing::warn!(
+                "Не удалось обновить проекцию баланса кошелька {}: {}",
+                transfer.from_wallet_id,
+                e
+            );
+        }
+
+        self.publish_status(
+            transfer.id,
+            transfer.from_wallet_id,
+            TransactionStatus::Completed,
+            Some(tx_hash.to_string()),
+        );
+
+        self.audit_shipper
+            .emit(
+                "transfer.completed",
+                serde_json::json!({
+                    "transfer_id": transfer.id,
+                    "from_wallet_id": transfer.from_wallet_id,
+                    "tx_hash": tx_hash,
+                }),
+            )
+            .await;
+
         Ok(())
     }
 
@@ -428,16 +2139,40 @@ impl TransferService {
         transfer: &OutgoingTransferModel,
         error_message: &str,
     ) -> Result<()> {
+        // Проверяем легальность перехода статуса через state machine
+        let current_status = TransactionStatus::from_db_str(&transfer.status)
+            .ok_or_else(|| anyhow::anyhow!("Неизвестный статус трансфера: {}", transfer.status))?;
+        let next_status = current_status.transition_to(TransactionStatus::Failed)?;
+
         let mut conn = self.db.get().await?;
 
         diesel::update(schema::outgoing_transfers::table.find(transfer.id))
             .set((
-                schema::outgoing_transfers::status.eq("FAILED"),
+                schema::outgoing_transfers::status.eq(next_status.as_db_str()),
                 schema::outgoing_transfers::error_message.eq(error_message),
-                schema::outgoing_transfers::completed_at.eq(diesel::dsl::now),
+                schema::outgoing_transfers::completed_at.eq(self.clock.now()),
             ))
             .execute(&mut conn)
             .await?;
+
+        self.publish_status(
+            transfer.id,
+            transfer.from_wallet_id,
+            TransactionStatus::Failed,
+            transfer.tx_hash.clone(),
+        );
+
+        self.audit_shipper
+            .emit(
+                "transfer.failed",
+                serde_json::json!({
+                    "transfer_id": transfer.id,
+                    "from_wallet_id": transfer.from_wallet_id,
+                    "error_message": error_message,
+                }),
+            )
+            .await;
+
         Ok(())
     }
 
@@ -448,18 +2183,22 @@ impl TransferService {
             from_wallet_id: transfer.from_wallet_id,
             to_address: transfer.to_address,
             amount: bigdecimal_to_decimal(transfer.amount),
-            status: match transfer.status.as_str() {
-                "PENDING" => TransactionStatus::Pending,
-                "CONFIRMED" => TransactionStatus::Completed,
-                "COMPLETED" => TransactionStatus::Completed,
-                "FAILED" => TransactionStatus::Failed,
-                _ => TransactionStatus::Pending, // для неизвестных статусов
-            },
+            status: TransactionStatus::from_db_str(&transfer.status)
+                .unwrap_or(TransactionStatus::Pending), // для неизвестных статусов
             tx_hash: transfer.tx_hash,
             reference_id: transfer.reference_id,
+            destination_tag: transfer.destination_tag,
             error_message: transfer.error_message,
             created_at: transfer.created_at,
             completed_at: transfer.completed_at,
+            risk_score: transfer.risk_score,
+            metadata: parse_stored_metadata(transfer.metadata),
+            fee_payer: FeePayer::from_db_str(&transfer.fee_payer).unwrap_or_default(),
+            fee_amount: transfer.fee_amount.map(bigdecimal_to_decimal),
+            token_symbol: transfer.token_symbol,
+            fee_trx_usdt_rate: transfer.fee_trx_usdt_rate.map(bigdecimal_to_decimal),
+            fee_rate_source: transfer.fee_rate_source,
+            fee_rate_captured_at: transfer.fee_rate_captured_at,
         }
     }
 }
@@ -3,18 +3,41 @@
 //! Основной сервис для создания, получения и управления TRON кошельками
 
 use anyhow::Result;
+use base58::FromBase58;
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use rust_decimal::Decimal;
 
-use crate::application::dto::WalletResponse;
+use crate::application::dto::{ComplianceStatusResponse, UpdateComplianceRequest, WalletResponse};
 use crate::domain::DomainError;
 use crate::infrastructure::{
-    database::{models::*, schema, DbPool},
+    database::{models::*, schema, ColumnEncryption, DbPool},
     TronGridClient, TronWalletGenerator,
 };
 
-use super::WalletActivationService;
+use super::{WalletActivationService, WalletLifecycleChange, WebhookService};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Максимальный размер страницы списка кошельков
+pub const WALLET_LIST_MAX_PAGE_SIZE: i64 = 200;
+
+/// Фильтры списка кошельков
+#[derive(Debug, Clone, Default)]
+pub struct WalletListFilter {
+    /// Фильтр по владельцу
+    pub owner_id: Option<String>,
+    /// Созданные не раньше
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Созданные не позже
+    pub created_to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Префикс base58 адреса
+    pub address_prefix: Option<String>,
+    /// Размер страницы (ограничен WALLET_LIST_MAX_PAGE_SIZE)
+    pub limit: Option<i64>,
+    /// Смещение страницы
+    pub offset: Option<i64>,
+}
 
 /// Сервис для работы с кошельками
 pub struct WalletService {
@@ -22,6 +45,9 @@ pub struct WalletService {
     tron_client: TronGridClient,
     wallet_generator: TronWalletGenerator,
     wallet_activation_service: Option<WalletActivationService>,
+    webhook_service: Option<Arc<WebhookService>>,
+    /// Шифрование колонок на стороне БД (None - режим application)
+    column_encryption: Option<Arc<ColumnEncryption>>,
 }
 
 impl WalletService {
@@ -37,6 +63,55 @@ impl WalletService {
             tron_client,
             wallet_generator,
             wallet_activation_service,
+            webhook_service: None,
+            column_encryption: None,
+        }
+    }
+
+    /// Включает pgcrypto шифрование приватных ключей новых кошельков
+    pub fn with_column_encryption(
+        mut self,
+        column_encryption: Option<Arc<ColumnEncryption>>,
+    ) -> Self {
+        self.column_encryption = column_encryption;
+        self
+    }
+
+    /// Включает webhook уведомления об изменениях жизненного цикла кошельков
+    pub fn with_webhooks(mut self, webhook_service: Arc<WebhookService>) -> Self {
+        self.webhook_service = Some(webhook_service);
+        self
+    }
+
+    /// Отправляет webhook об изменении жизненного цикла кошелька.
+    /// Ошибка доставки логируется, но не роняет операцию
+    async fn emit_lifecycle_event(
+        &self,
+        wallet_id: i64,
+        wallet_address: &str,
+        change: WalletLifecycleChange,
+        before: serde_json::Value,
+        after: serde_json::Value,
+    ) {
+        let Some(webhooks) = &self.webhook_service else {
+            return;
+        };
+
+        if let Err(e) = webhooks
+            .notify_wallet_lifecycle_changed(
+                wallet_id,
+                wallet_address.to_string(),
+                change,
+                before,
+                after,
+            )
+            .await
+        {
+            tracing::warn!(
+                "⚠️ Не удалось отправить lifecycle webhook для кошелька {}: {}",
+                wallet_address,
+                e
+            );
         }
     }
 
@@ -53,12 +128,27 @@ impl WalletService {
                 }
             })?;
 
-        // 2. Создаем запись в БД
+        // 2. Создаем запись в БД. В режиме pgcrypto ключ уходит только
+        // в зашифрованную колонку - plaintext остается пустым
+        let (private_key_column, private_key_enc) = match &self.column_encryption {
+            Some(encryption) => {
+                let ciphertext = encryption.encrypt(&private_key).await.map_err(|e| {
+                    DomainError::ConfigurationError {
+                        message: format!("Ошибка шифрования приватного ключа: {}", e),
+                    }
+                })?;
+                (String::new(), Some(ciphertext))
+            }
+            None => (private_key.clone(), None),
+        };
+
         let new_wallet = NewWallet {
             address: address.clone(),
             hex_address,
-            private_key: private_key.clone(),
+            private_key: private_key_column,
             owner_id: owner_id.clone(),
+            watch_only: false,
+            private_key_enc,
         };
 
         let mut conn = self
@@ -79,7 +169,9 @@ impl WalletService {
 
         // 3. Автоматическая активация кошелька (если включена)
         if let Some(ref activation_service) = self.wallet_activation_service {
-            activation_service.activate_wallet(&wallet.address).await
+            activation_service
+                .activate_wallet(&wallet.address)
+                .await
                 .map_err(|e| DomainError::ConfigurationError {
                     message: format!("Ошибка активации кошелька: {}", e),
                 })?;
@@ -91,6 +183,80 @@ impl WalletService {
             owner_id: wallet.owner_id,
             created_at: wallet.created_at,
             balance: Some(Decimal::ZERO), // Новый кошелек имеет нулевой баланс
+            under_review: wallet.under_review,
+            watch_only: wallet.watch_only,
+        })
+    }
+
+    /// Регистрирует внешний watch-only адрес (без приватного ключа).
+    /// Мониторинг отслеживает его депозиты наравне с обычными кошельками,
+    /// но отправителем трансферов такой кошелек быть не может
+    pub async fn register_watch_only_wallet(
+        &self,
+        address: String,
+        owner_id: Option<String>,
+    ) -> Result<WalletResponse, DomainError> {
+        crate::domain::TronValidator::validate_address(&address)?;
+
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        // Адрес не должен быть уже зарегистрирован (обычным или watch-only)
+        let existing: Option<WalletModel> = schema::wallets::table
+            .filter(schema::wallets::address.eq(&address))
+            .first::<WalletModel>(&mut conn)
+            .await
+            .optional()
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка БД".to_string(),
+            })?;
+
+        if existing.is_some() {
+            return Err(DomainError::ConfigurationError {
+                message: format!("Адрес {} уже зарегистрирован", address),
+            });
+        }
+
+        // hex представление для единообразия с обычными кошельками
+        let decoded = address
+            .from_base58()
+            .map_err(|_| DomainError::InvalidTronAddress {
+                address: address.clone(),
+            })?;
+        let hex_address = hex::encode(&decoded[..21]);
+
+        let new_wallet = NewWallet {
+            address: address.clone(),
+            hex_address,
+            private_key: String::new(), // Ключа нет - только наблюдение
+            owner_id,
+            watch_only: true,
+            private_key_enc: None,
+        };
+
+        let wallet: WalletModel = diesel::insert_into(schema::wallets::table)
+            .values(&new_wallet)
+            .get_result(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка регистрации watch-only адреса".to_string(),
+            })?;
+
+        tracing::info!("👁️ Зарегистрирован watch-only адрес {}", wallet.address);
+
+        Ok(WalletResponse {
+            id: wallet.id,
+            address: wallet.address,
+            owner_id: wallet.owner_id,
+            created_at: wallet.created_at,
+            balance: None,
+            under_review: wallet.under_review,
+            watch_only: wallet.watch_only,
         })
     }
 
@@ -123,6 +289,8 @@ impl WalletService {
                     owner_id: wallet.owner_id,
                     created_at: wallet.created_at,
                     balance: Some(usdt_balance),
+                    under_review: wallet.under_review,
+                    watch_only: wallet.watch_only,
                 }))
             }
             Err(diesel::result::Error::NotFound) => {
@@ -134,6 +302,83 @@ impl WalletService {
         }
     }
 
+    /// Список кошельков с фильтрами и пагинацией (новые первыми).
+    /// Возвращает страницу и общее количество под фильтрами; баланс
+    /// не подтягивается - страница не должна стоить N запросов к сети
+    pub async fn list_wallets(
+        &self,
+        filter: WalletListFilter,
+    ) -> Result<(Vec<WalletResponse>, i64), DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        // boxed запросы не клонируются - фильтры накладываются дважды
+        // (подсчет и страница) одной функцией
+        fn apply_filters<'a>(
+            mut query: schema::wallets::BoxedQuery<'a, diesel::pg::Pg>,
+            filter: &'a WalletListFilter,
+        ) -> schema::wallets::BoxedQuery<'a, diesel::pg::Pg> {
+            if let Some(owner_id) = &filter.owner_id {
+                query = query.filter(schema::wallets::owner_id.eq(owner_id.clone()));
+            }
+            if let Some(from) = filter.created_from {
+                query = query.filter(schema::wallets::created_at.ge(from));
+            }
+            if let Some(to) = filter.created_to {
+                query = query.filter(schema::wallets::created_at.le(to));
+            }
+            if let Some(prefix) = &filter.address_prefix {
+                query = query.filter(schema::wallets::address.like(format!("{}%", prefix)));
+            }
+            query
+        }
+
+        let total_count: i64 = apply_filters(schema::wallets::table.into_boxed(), &filter)
+            .count()
+            .get_result(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка БД".to_string(),
+            })?;
+
+        let limit = filter
+            .limit
+            .unwrap_or(50)
+            .clamp(1, WALLET_LIST_MAX_PAGE_SIZE);
+        let offset = filter.offset.unwrap_or(0).max(0);
+
+        let wallets: Vec<WalletModel> = apply_filters(schema::wallets::table.into_boxed(), &filter)
+            .select(WalletModel::as_select())
+            .order(schema::wallets::created_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка БД".to_string(),
+            })?;
+
+        let responses = wallets
+            .into_iter()
+            .map(|wallet| WalletResponse {
+                id: wallet.id,
+                address: wallet.address,
+                owner_id: wallet.owner_id,
+                created_at: wallet.created_at,
+                balance: None,
+                under_review: wallet.under_review,
+                watch_only: wallet.watch_only,
+            })
+            .collect();
+
+        Ok((responses, total_count))
+    }
+
     /// Получение баланса кошелька через TRON API
     pub async fn get_wallet_balance(&self, wallet_id: i64) -> Result<(Decimal, Decimal)> {
         // 1. Получаем адрес кошелька из БД
@@ -192,6 +437,194 @@ impl WalletService {
         }
     }
 
+    /// Изменение комплаенс-статуса кошелька (флаг "на проверке",
+    /// заметка и идентификатор проверяющего)
+    pub async fn set_compliance_status(
+        &self,
+        wallet_id: i64,
+        request: UpdateComplianceRequest,
+    ) -> Result<ComplianceStatusResponse, DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        // Проверяем существование кошелька
+        let before: WalletModel = schema::wallets::table
+            .find(wallet_id)
+            .first::<WalletModel>(&mut conn)
+            .await
+            .map_err(|_| DomainError::WalletNotFound { id: wallet_id })?;
+
+        let flagged_at = request.under_review.then(chrono::Utc::now);
+
+        let updated: WalletModel = diesel::update(schema::wallets::table.find(wallet_id))
+            .set((
+                schema::wallets::under_review.eq(request.under_review),
+                schema::wallets::compliance_notes.eq(&request.notes),
+                schema::wallets::compliance_reviewer.eq(&request.reviewer_id),
+                schema::wallets::flagged_at.eq(flagged_at),
+            ))
+            .get_result(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка обновления комплаенс-статуса".to_string(),
+            })?;
+
+        if updated.under_review {
+            tracing::warn!(
+                "🚫 Кошелек {} помечен на проверку (проверяющий: {})",
+                updated.address,
+                updated.compliance_reviewer.as_deref().unwrap_or("-")
+            );
+        } else {
+            tracing::info!("✅ С кошелька {} снята комплаенс-пометка", updated.address);
+        }
+
+        // Уведомляем мерчантов о заморозке/разморозке (только при смене флага)
+        if before.under_review != updated.under_review {
+            let change = if updated.under_review {
+                WalletLifecycleChange::Frozen
+            } else {
+                WalletLifecycleChange::Unfrozen
+            };
+
+            self.emit_lifecycle_event(
+                updated.id,
+                &updated.address,
+                change,
+                json!({
+                    "under_review": before.under_review,
+                    "compliance_reviewer": before.compliance_reviewer,
+                }),
+                json!({
+                    "under_review": updated.under_review,
+                    "compliance_reviewer": updated.compliance_reviewer,
+                }),
+            )
+            .await;
+        }
+
+        Ok(ComplianceStatusResponse {
+            wallet_id: updated.id,
+            under_review: updated.under_review,
+            notes: updated.compliance_notes,
+            reviewer_id: updated.compliance_reviewer,
+            flagged_at: updated.flagged_at,
+        })
+    }
+
+    /// Передача владения кошельком другому owner_id
+    pub async fn transfer_ownership(
+        &self,
+        wallet_id: i64,
+        new_owner_id: Option<String>,
+    ) -> Result<WalletResponse, DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        let before: WalletModel = schema::wallets::table
+            .find(wallet_id)
+            .first::<WalletModel>(&mut conn)
+            .await
+            .map_err(|_| DomainError::WalletNotFound { id: wallet_id })?;
+
+        let updated: WalletModel = diesel::update(schema::wallets::table.find(wallet_id))
+            .set(schema::wallets::owner_id.eq(&new_owner_id))
+            .get_result(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка смены владельца кошелька".to_string(),
+            })?;
+
+        tracing::info!(
+            "📝 Владелец кошелька {} изменен: {:?} -> {:?}",
+            updated.address,
+            before.owner_id,
+            updated.owner_id
+        );
+
+        self.emit_lifecycle_event(
+            updated.id,
+            &updated.address,
+            WalletLifecycleChange::OwnershipTransferred,
+            json!({ "owner_id": before.owner_id }),
+            json!({ "owner_id": updated.owner_id }),
+        )
+        .await;
+
+        Ok(WalletResponse {
+            id: updated.id,
+            address: updated.address,
+            owner_id: updated.owner_id,
+            created_at: updated.created_at,
+            balance: None,
+            under_review: updated.under_review,
+            watch_only: updated.watch_only,
+        })
+    }
+
+    /// Архивация кошелька: запись остается для истории,
+    /// но кошелек исключается из активных операций
+    pub async fn archive_wallet(&self, wallet_id: i64) -> Result<WalletResponse, DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        let before: WalletModel = schema::wallets::table
+            .find(wallet_id)
+            .first::<WalletModel>(&mut conn)
+            .await
+            .map_err(|_| DomainError::WalletNotFound { id: wallet_id })?;
+
+        if before.archived_at.is_some() {
+            return Err(DomainError::ConfigurationError {
+                message: format!("Кошелек {} уже архивирован", wallet_id),
+            });
+        }
+
+        let updated: WalletModel = diesel::update(schema::wallets::table.find(wallet_id))
+            .set(schema::wallets::archived_at.eq(chrono::Utc::now()))
+            .get_result(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка архивации кошелька".to_string(),
+            })?;
+
+        tracing::info!("🗄️ Кошелек {} архивирован", updated.address);
+
+        self.emit_lifecycle_event(
+            updated.id,
+            &updated.address,
+            WalletLifecycleChange::Archived,
+            json!({ "archived_at": before.archived_at }),
+            json!({ "archived_at": updated.archived_at }),
+        )
+        .await;
+
+        Ok(WalletResponse {
+            id: updated.id,
+            address: updated.address,
+            owner_id: updated.owner_id,
+            created_at: updated.created_at,
+            balance: None,
+            under_review: updated.under_review,
+            watch_only: updated.watch_only,
+        })
+    }
+
     /// Получение кошелька по адресу
     pub async fn get_wallet_by_address(
         &self,
@@ -0,0 +1,262 @@
+//! # Сервис wallet-scoped API токенов
+//!
+//! Выпуск и проверка токенов, привязанных к одному кошельку.
+//! Такой токен позволяет порталу конечного клиента читать баланс
+//! и активность только своего кошелька, не раскрывая операторские ключи.
+
+use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::application::dto::WalletTokenResponse;
+use crate::domain::DomainError;
+use crate::infrastructure::database::{models::*, schema, DbPool};
+
+use super::{WalletLifecycleChange, WebhookService};
+use std::sync::Arc;
+
+/// Префикс wallet-scoped токенов для удобной идентификации в логах клиентов
+const TOKEN_PREFIX: &str = "wst_";
+
+/// Сервис выпуска и проверки wallet-scoped API токенов
+pub struct WalletTokenService {
+    db: DbPool,
+    webhook_service: Option<Arc<WebhookService>>,
+}
+
+impl WalletTokenService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool) -> Self {
+        Self {
+            db,
+            webhook_service: None,
+        }
+    }
+
+    /// Включает webhook уведомления о ротации токенов
+    pub fn with_webhooks(mut self, webhook_service: Arc<WebhookService>) -> Self {
+        self.webhook_service = Some(webhook_service);
+        self
+    }
+
+    /// Выпускает новый токен для кошелька
+    ///
+    /// Сам токен возвращается только один раз, в БД хранится его SHA-256 хеш
+    pub async fn issue_token(
+        &self,
+        wallet_id: i64,
+        label: Option<String>,
+    ) -> Result<WalletTokenResponse, DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        // Проверяем существование кошелька
+        let _wallet: WalletModel = schema::wallets::table
+            .find(wallet_id)
+            .first(&mut conn)
+            .await
+            .map_err(|_| DomainError::WalletNotFound { id: wallet_id })?;
+
+        let token = Self::generate_token();
+        let token_hash = Self::hash_token(&token);
+
+        let new_token = NewWalletApiToken {
+            wallet_id,
+            token_hash,
+            label,
+        };
+
+        let model: WalletApiTokenModel = diesel::insert_into(schema::wallet_api_tokens::table)
+            .values(&new_token)
+            .get_result(&mut conn)
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка сохранения токена".to_string(),
+            })?;
+
+        tracing::info!(
+            "🔑 Выпущен wallet-scoped токен ID: {} для кошелька {}",
+            model.id,
+            wallet_id
+        );
+
+        Ok(WalletTokenResponse {
+            id: model.id,
+            wallet_id: model.wallet_id,
+            token: Some(token),
+            label: model.label,
+            revoked: model.revoked,
+            created_at: model.created_at,
+        })
+    }
+
+    /// Проверяет токен и возвращает ID кошелька, к которому он привязан
+    ///
+    /// Возвращает `None`, если токен не найден или отозван
+    pub async fn authorize_token(&self, token: &str) -> Result<Option<i64>> {
+        let mut conn = self.db.get().await?;
+        let token_hash = Self::hash_token(token);
+
+        let token_result: Result<WalletApiTokenModel, diesel::result::Error> =
+            schema::wallet_api_tokens::table
+                .filter(schema::wallet_api_tokens::token_hash.eq(&token_hash))
+                .filter(schema::wallet_api_tokens::revoked.eq(false))
+                .first(&mut conn)
+                .await;
+
+        match token_result {
+            Ok(model) => {
+                // Фиксируем время последнего использования (best-effort)
+                let _ = diesel::update(schema::wallet_api_tokens::table.find(model.id))
+                    .set(schema::wallet_api_tokens::last_used_at.eq(diesel::dsl::now))
+                    .execute(&mut conn)
+                    .await;
+
+                Ok(Some(model.wallet_id))
+            }
+            Err(diesel::result::Error::NotFound) => Ok(None),
+            Err(e) => Err(anyhow::Error::from(e)),
+        }
+    }
+
+    /// Отзывает токен кошелька
+    pub async fn revoke_token(&self, wallet_id: i64, token_id: i64) -> Result<bool, DomainError> {
+        let mut conn = self
+            .db
+            .get()
+            .await
+            .map_err(|_| DomainError::ConfigurationError {
+                message: "Ошибка подключения к БД".to_string(),
+            })?;
+
+        let updated = diesel::update(
+            schema::wallet_api_tokens::table
+                .find(token_id)
+                .filter(schema::wallet_api_tokens::wallet_id.eq(wallet_id)),
+        )
+        .set(schema::wallet_api_tokens::revoked.eq(true))
+        .execute(&mut conn)
+        .await
+        .map_err(|_| DomainError::ConfigurationError {
+            message: "Ошибка отзыва токена".to_string(),
+        })?;
+
+        Ok(updated > 0)
+    }
+
+    /// Ротация токена: отзывает старый и выпускает новый одной операцией.
+    /// Мерчанты уведомляются через lifecycle webhook (key_rotated)
+    pub async fn rotate_token(
+        &self,
+        wallet_id: i64,
+        token_id: i64,
+    ) -> Result<WalletTokenResponse, DomainError> {
+        let revoked = self.revoke_token(wallet_id, token_id).await?;
+        if !revoked {
+            return Err(DomainError::ConfigurationError {
+                message: format!("Токен {} не найден для кошелька {}", token_id, wallet_id),
+            });
+        }
+
+        let old_label = self.token_label(token_id).await;
+        let new_token = self.issue_token(wallet_id, old_label).await?;
+
+        tracing::info!(
+            "🔑 Токен {} кошелька {} ротирован, новый токен ID: {}",
+            token_id,
+            wallet_id,
+            new_token.id
+        );
+
+        if let Some(webhooks) = &self.webhook_service {
+            let wallet_address = self.wallet_address(wallet_id).await.unwrap_or_default();
+
+            if let Err(e) = webhooks
+                .notify_wallet_lifecycle_changed(
+                    wallet_id,
+                    wallet_address,
+                    WalletLifecycleChange::KeyRotated,
+                    serde_json::json!({ "token_id": token_id }),
+                    serde_json::json!({ "token_id": new_token.id }),
+                )
+                .await
+            {
+                tracing::warn!(
+                    "⚠️ Не удалось отправить webhook о ротации токена {}: {}",
+                    token_id,
+                    e
+                );
+            }
+        }
+
+        Ok(new_token)
+    }
+
+    /// Метка токена (best-effort, для переноса на новый токен при ротации)
+    async fn token_label(&self, token_id: i64) -> Option<String> {
+        let mut conn = self.db.get().await.ok()?;
+
+        schema::wallet_api_tokens::table
+            .find(token_id)
+            .select(schema::wallet_api_tokens::label)
+            .first::<Option<String>>(&mut conn)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Адрес кошелька (best-effort, для webhook payload)
+    async fn wallet_address(&self, wallet_id: i64) -> Option<String> {
+        let mut conn = self.db.get().await.ok()?;
+
+        schema::wallets::table
+            .find(wallet_id)
+            .select(schema::wallets::address)
+            .first::<String>(&mut conn)
+            .await
+            .ok()
+    }
+
+    /// Генерирует случайный токен с префиксом
+    fn generate_token() -> String {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        format!("{}{}", TOKEN_PREFIX, hex::encode(bytes))
+    }
+
+    /// Вычисляет SHA-256 хеш токена для хранения в БД
+    fn hash_token(token: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(token.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_token_format() {
+        let token = WalletTokenService::generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 64);
+    }
+
+    #[test]
+    fn test_token_hash_is_stable() {
+        let token = "wst_test";
+        assert_eq!(
+            WalletTokenService::hash_token(token),
+            WalletTokenService::hash_token(token)
+        );
+        assert_eq!(WalletTokenService::hash_token(token).len(), 64);
+    }
+}
@@ -0,0 +1,65 @@
+//! # Экспорт персистентного лога webhook событий
+//!
+//! Мерчанты периодически пересинхронизируют свои системы запросом
+//! "все события между датами". Экспорт отдает события постранично
+//! с возобновляемым курсором по id - оборвавшийся экспорт можно
+//! продолжить с последнего обработанного события.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+use crate::infrastructure::database::{models::WebhookEventModel, schema, DbPool};
+
+/// Максимальный размер страницы экспорта
+pub const EXPORT_MAX_PAGE_SIZE: i64 = 1000;
+
+/// Сервис экспорта webhook событий для reconciliation
+pub struct WebhookEventService {
+    db: DbPool,
+}
+
+impl WebhookEventService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool) -> Self {
+        Self { db }
+    }
+
+    /// Отдает страницу событий за период, начиная после курсора
+    /// (id последнего обработанного события). События упорядочены
+    /// по id - курсор стабилен под конкурентной записью
+    pub async fn export_events(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        cursor: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<WebhookEventModel>> {
+        let limit = limit.clamp(1, EXPORT_MAX_PAGE_SIZE);
+        let mut conn = self.db.get().await?;
+
+        let mut query = schema::webhook_events::table.into_boxed();
+
+        if let Some(from) = from {
+            query = query.filter(schema::webhook_events::created_at.ge(from));
+        }
+
+        if let Some(to) = to {
+            query = query.filter(schema::webhook_events::created_at.le(to));
+        }
+
+        if let Some(cursor) = cursor {
+            query = query.filter(schema::webhook_events::id.gt(cursor));
+        }
+
+        let events = query
+            .select(WebhookEventModel::as_select())
+            .order(schema::webhook_events::id.asc())
+            .limit(limit)
+            .load(&mut conn)
+            .await?;
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,394 @@
+//! # Реплей исторических webhook событий в staging мерчанта
+//!
+//! Интеграторы тестируют обновления против реалистичного трафика:
+//! срез персистентного лога событий анонимизируется и проигрывается
+//! в указанный staging endpoint с выбранной скоростью - паузы между
+//! событиями повторяют исторические интервалы, деленные на speed.
+//! Реплей идет фоновой задачей, статус доступен по id job'а
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::infrastructure::database::{models::WebhookEventModel, schema, DbPool};
+
+/// Максимум событий одного реплея
+pub const REPLAY_MAX_EVENTS: i64 = 1000;
+
+/// Потолок паузы между событиями - длинные исторические разрывы
+/// не растягивают реплей на часы
+const REPLAY_MAX_GAP_SECONDS: f64 = 30.0;
+
+/// Завершенные job'ы старше этого количества держать незачем
+const REPLAY_MAX_FINISHED_JOBS: usize = 20;
+
+/// Параметры запуска реплея
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ReplayRequest {
+    /// Staging endpoint мерчанта
+    pub target_url: String,
+    /// Начало среза (по created_at события)
+    pub from: Option<DateTime<Utc>>,
+    /// Конец среза
+    pub to: Option<DateTime<Utc>>,
+    /// Фильтр по типу события (например payment_received)
+    pub event_type: Option<String>,
+    /// Ускорение реплея: 1.0 - исторические паузы, 10.0 - в 10 раз
+    /// быстрее (по умолчанию 10.0)
+    pub speed: Option<f64>,
+    /// Максимум событий (по умолчанию и потолок - REPLAY_MAX_EVENTS)
+    pub limit: Option<i64>,
+}
+
+/// Статус job'а реплея
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReplayJobStatus {
+    pub job_id: u64,
+    /// running, completed или failed
+    pub status: String,
+    pub target_url: String,
+    pub speed: f64,
+    pub events_total: usize,
+    pub events_sent: usize,
+    pub events_failed: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Сервис реплея webhook событий
+pub struct WebhookReplayService {
+    db: DbPool,
+    client: reqwest::Client,
+    /// Статусы job'ов, разделяемые с фоновыми задачами реплея
+    jobs: Arc<RwLock<HashMap<u64, ReplayJobStatus>>>,
+    next_job_id: AtomicU64,
+}
+
+impl WebhookReplayService {
+    /// Создает новый экземпляр сервиса
+    pub fn new(db: DbPool) -> Self {
+        Self {
+            db,
+            client: reqwest::Client::new(),
+            jobs: Arc::new(RwLock::new(HashMap::new())),
+            next_job_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Запускает реплей: срез событий загружается, анонимизируется
+    /// и проигрывается фоновой задачей. Возвращает статус job'а
+    pub async fn start_replay(&self, request: ReplayRequest) -> Result<ReplayJobStatus> {
+        if !request.target_url.starts_with("http://") && !request.target_url.starts_with("https://")
+        {
+            anyhow::bail!("target_url должен быть http(s) адресом");
+        }
+
+        let speed = request.speed.unwrap_or(10.0);
+        if !(0.1..=1000.0).contains(&speed) {
+            anyhow::bail!("speed должен быть в диапазоне 0.1..1000");
+        }
+
+        let limit = request
+            .limit
+            .unwrap_or(REPLAY_MAX_EVENTS)
+            .clamp(1, REPLAY_MAX_EVENTS);
+
+        let events = self.load_events(&request, limit).await?;
+
+        let job_id = self.next_job_id.fetch_add(1, Ordering::SeqCst);
+        let status = ReplayJobStatus {
+            job_id,
+            status: if events.is_empty() {
+                "completed".to_string()
+            } else {
+                "running".to_string()
+            },
+            target_url: request.target_url.clone(),
+            speed,
+            events_total: events.len(),
+            events_sent: 0,
+            events_failed: 0,
+            started_at: Utc::now(),
+            finished_at: if events.is_empty() {
+                Some(Utc::now())
+            } else {
+                None
+            },
+        };
+
+        {
+            let mut jobs = self.jobs.write().unwrap();
+            prune_finished_jobs(&mut jobs);
+            jobs.insert(job_id, status.clone());
+        }
+
+        if events.is_empty() {
+            return Ok(status);
+        }
+
+        info!(
+            "📼 Реплей #{}: {} событий на {} (speed {})",
+            job_id,
+            events.len(),
+            request.target_url,
+            speed
+        );
+
+        let client = self.client.clone();
+        let jobs = self.jobs.clone();
+        let target_url = request.target_url.clone();
+        tokio::spawn(async move {
+            run_replay(client, jobs, job_id, target_url, speed, events).await;
+        });
+
+        Ok(status)
+    }
+
+    /// Статус job'а реплея
+    pub fn job_status(&self, job_id: u64) -> Option<ReplayJobStatus> {
+        self.jobs.read().unwrap().get(&job_id).cloned()
+    }
+
+    /// Срез событий по параметрам запроса (порядок записи в лог)
+    async fn load_events(
+        &self,
+        request: &ReplayRequest,
+        limit: i64,
+    ) -> Result<Vec<WebhookEventModel>> {
+        let mut conn = self.db.get().await?;
+
+        let mut query = schema::webhook_events::table.into_boxed();
+
+        if let Some(from) = request.from {
+            query = query.filter(schema::webhook_events::created_at.ge(from));
+        }
+
+        if let Some(to) = request.to {
+            query = query.filter(schema::webhook_events::created_at.le(to));
+        }
+
+        if let Some(event_type) = &request.event_type {
+            query = query.filter(schema::webhook_events::event_type.eq(event_type.clone()));
+        }
+
+        Ok(query
+            .select(WebhookEventModel::as_select())
+            .order(schema::webhook_events::id.asc())
+            .limit(limit)
+            .load(&mut conn)
+            .await?)
+    }
+}
+
+/// Фоновая задача реплея: проигрывает события с историческими паузами
+async fn run_replay(
+    client: reqwest::Client,
+    jobs: Arc<RwLock<HashMap<u64, ReplayJobStatus>>>,
+    job_id: u64,
+    target_url: String,
+    speed: f64,
+    events: Vec<WebhookEventModel>,
+) {
+    let mut previous_at: Option<DateTime<Utc>> = None;
+
+    for event in &events {
+        if let Some(previous) = previous_at {
+            let gap = replay_gap_seconds(previous, event.created_at, speed);
+            if gap > 0.0 {
+                tokio::time::sleep(std::time::Duration::from_secs_f64(gap)).await;
+            }
+        }
+        previous_at = Some(event.created_at);
+
+        let payload = anonymize_payload(&event.payload);
+        let sent = client
+            .post(&target_url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "TRON-Gateway-Webhook/1.0")
+            .header("X-Webhook-Replay", "true")
+            .header("X-Replay-Event-Id", event.id.to_string())
+            .body(payload)
+            .send()
+            .await;
+
+        let ok = match sent {
+            Ok(response) if response.status().is_success() => true,
+            Ok(response) => {
+                warn!(
+                    "⚠️ Реплей #{}: событие {} отклонено endpoint'ом ({})",
+                    job_id,
+                    event.id,
+                    response.status()
+                );
+                false
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ Реплей #{}: событие {} не доставлено: {}",
+                    job_id, event.id, e
+                );
+                false
+            }
+        };
+
+        if let Some(status) = jobs.write().unwrap().get_mut(&job_id) {
+            if ok {
+                status.events_sent += 1;
+            } else {
+                status.events_failed += 1;
+            }
+        }
+    }
+
+    if let Some(status) = jobs.write().unwrap().get_mut(&job_id) {
+        status.status = if status.events_failed == 0 {
+            "completed".to_string()
+        } else {
+            "failed".to_string()
+        };
+        status.finished_at = Some(Utc::now());
+    }
+
+    info!("📼 Реплей #{} завершен: {} событий", job_id, events.len());
+}
+
+/// Пауза перед событием: исторический интервал, деленный на speed,
+/// с потолком против многочасовых разрывов
+fn replay_gap_seconds(previous: DateTime<Utc>, current: DateTime<Utc>, speed: f64) -> f64 {
+    let gap_ms = (current - previous).num_milliseconds().max(0) as f64;
+    (gap_ms / 1000.0 / speed).min(REPLAY_MAX_GAP_SECONDS)
+}
+
+/// Ключи payload'а, значения которых маскируются при анонимизации
+const SENSITIVE_KEYS: [&str; 7] = [
+    "wallet_address",
+    "from_address",
+    "to_address",
+    "address",
+    "tx_hash",
+    "owner_id",
+    "reference_id",
+];
+
+/// Анонимизирует payload события: адреса, хэши и идентификаторы
+/// мерчантов маскируются с сохранением формы и длины трафика.
+/// Невалидный JSON отдается как есть - реплей не должен падать
+/// на исторических записях
+fn anonymize_payload(payload: &str) -> String {
+    let Ok(mut value) = serde_json::from_str::<Value>(payload) else {
+        return payload.to_string();
+    };
+    anonymize_value(&mut value);
+    value.to_string()
+}
+
+/// Рекурсивно маскирует чувствительные поля JSON
+fn anonymize_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.as_str()) {
+                    if let Value::String(text) = child {
+                        *text = mask(text);
+                    }
+                } else {
+                    anonymize_value(child);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                anonymize_value(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Маскирует строку: первые и последние 4 символа сохраняются
+/// (узнаваемость в логах), середина заменяется звездочками
+fn mask(text: &str) -> String {
+    if text.len() <= 12 {
+        return "*".repeat(text.len());
+    }
+    format!(
+        "{}{}{}",
+        &text[..4],
+        "*".repeat(text.len() - 8),
+        &text[text.len() - 4..]
+    )
+}
+
+/// Убирает старые завершенные job'ы, оставляя последние
+fn prune_finished_jobs(jobs: &mut HashMap<u64, ReplayJobStatus>) {
+    let mut finished: Vec<u64> = jobs
+        .iter()
+        .filter(|(_, status)| status.finished_at.is_some())
+        .map(|(id, _)| *id)
+        .collect();
+    if finished.len() <= REPLAY_MAX_FINISHED_JOBS {
+        return;
+    }
+    finished.sort_unstable();
+    let excess = finished.len() - REPLAY_MAX_FINISHED_JOBS;
+    for id in finished.into_iter().take(excess) {
+        jobs.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize_masks_sensitive_fields() {
+        let payload = serde_json::json!({
+            "event_type": "payment_received",
+            "data": {
+                "wallet_address": "TWd4WrZ9wn84f5x1hZhL4DHvk738ns5jwb",
+                "tx_hash": "abc123",
+                "amount": "100.5"
+            }
+        })
+        .to_string();
+
+        let anonymized: Value = serde_json::from_str(&anonymize_payload(&payload)).unwrap();
+        let data = &anonymized["data"];
+
+        // Адрес замаскирован, но форма сохранена
+        let masked = data["wallet_address"].as_str().unwrap();
+        assert!(masked.starts_with("TWd4"));
+        assert!(masked.ends_with("5jwb"));
+        assert!(masked.contains("***"));
+
+        // Короткий хэш замаскирован целиком
+        assert_eq!(data["tx_hash"].as_str().unwrap(), "******");
+
+        // Нечувствительные поля не тронуты
+        assert_eq!(data["amount"].as_str().unwrap(), "100.5");
+        assert_eq!(
+            anonymized["event_type"].as_str().unwrap(),
+            "payment_received"
+        );
+    }
+
+    #[test]
+    fn test_replay_gap_scales_and_caps() {
+        let start = Utc::now();
+
+        // 10 секунд истории при speed 10 - секунда реплея
+        let gap = replay_gap_seconds(start, start + chrono::Duration::seconds(10), 10.0);
+        assert!((gap - 1.0).abs() < 1e-9);
+
+        // Многочасовой разрыв упирается в потолок
+        let gap = replay_gap_seconds(start, start + chrono::Duration::hours(5), 1.0);
+        assert!((gap - REPLAY_MAX_GAP_SECONDS).abs() < 1e-9);
+    }
+}
@@ -11,8 +11,23 @@ use std::time::Duration;
 use tokio::time::timeout;
 use tracing::{error, info, warn};
 
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::config::EgressConfig;
+
 use crate::domain::TransactionStatus;
+use crate::infrastructure::database::{
+    models::{
+        IncomingTransactionModel, NewWebhookDelivery, NewWebhookEndpoint, NewWebhookEvent,
+        OutgoingTransferModel, WebhookDeliveryModel, WebhookEndpointModel,
+    },
+    schema, DbPool,
+};
 use crate::infrastructure::retry::{classify_reqwest_error, RetryConfig, RetryableService};
+use crate::utils::parse_stored_metadata;
 
 /// Конфигурация webhook
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +36,73 @@ pub struct WebhookConfig {
     pub url: String,
     pub timeout_seconds: u64,
     pub secret_key: Option<String>, // Для подписи payload
+    /// Требовать подтверждение владения endpoint перед отправкой событий
+    #[serde(default)]
+    pub require_verification: bool,
+    /// Egress-настройки доставки webhook'ов (прокси, pinning IP)
+    #[serde(default)]
+    pub egress: EgressConfig,
+    /// Максимум одновременных доставок на endpoint
+    #[serde(default = "default_max_concurrent_deliveries")]
+    pub max_concurrent_deliveries: usize,
+    /// Максимальная глубина очереди доставки на endpoint
+    #[serde(default = "default_max_queue_depth")]
+    pub max_queue_depth: usize,
+    /// Политика при переполнении очереди доставки
+    #[serde(default)]
+    pub overflow_policy: WebhookOverflowPolicy,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            timeout_seconds: 10,
+            secret_key: None,
+            require_verification: false,
+            egress: EgressConfig::default(),
+            max_concurrent_deliveries: default_max_concurrent_deliveries(),
+            max_queue_depth: default_max_queue_depth(),
+            overflow_policy: WebhookOverflowPolicy::default(),
+        }
+    }
+}
+
+fn default_max_concurrent_deliveries() -> usize {
+    4
+}
+
+fn default_max_queue_depth() -> usize {
+    1000
+}
+
+/// Максимум попыток доставки одной записи outbox'а
+const DELIVERY_MAX_ATTEMPTS: i32 = 8;
+
+/// Статусы записи outbox'а доставки
+const DELIVERY_STATUS_PENDING: &str = "PENDING";
+const DELIVERY_STATUS_DELIVERED: &str = "DELIVERED";
+const DELIVERY_STATUS_FAILED: &str = "FAILED";
+
+/// Backoff перед повторной доставкой: 60с * 2^(attempts-1), максимум час
+fn redelivery_backoff(attempts: i32) -> chrono::Duration {
+    let exponent = (attempts - 1).clamp(0, 6) as u32;
+    let seconds = (60_i64 * 2_i64.pow(exponent)).min(3600);
+    chrono::Duration::seconds(seconds)
+}
+
+/// Политика при переполнении очереди доставки webhook'ов.
+/// В обоих случаях событие уже записано в персистентный лог -
+/// мерчант доберет пропущенное через bulk export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookOverflowPolicy {
+    /// Вытеснить самое старое событие из очереди в пользу нового
+    #[default]
+    DropOldest,
+    /// Новое событие не доставлять - остается только в персистентном логе
+    PersistOnly,
 }
 
 /// Типы webhook событий
@@ -33,6 +115,89 @@ pub enum WebhookEventType {
     TransferFailed,
     WalletCreated,
     WalletActivated,
+    WalletLifecycleChanged,
+    BalanceThresholdCrossed,
+    InvoicePaid,
+    InvoiceExpired,
+    EndpointVerification,
+}
+
+impl WebhookEventType {
+    /// Строковое представление для персистентного лога событий
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::IncomingTransaction => "incoming_transaction",
+            Self::OutgoingTransfer => "outgoing_transfer",
+            Self::TransferCompleted => "transfer_completed",
+            Self::TransferFailed => "transfer_failed",
+            Self::WalletCreated => "wallet_created",
+            Self::WalletActivated => "wallet_activated",
+            Self::WalletLifecycleChanged => "wallet_lifecycle_changed",
+            Self::BalanceThresholdCrossed => "balance_threshold_crossed",
+            Self::InvoicePaid => "invoice_paid",
+            Self::InvoiceExpired => "invoice_expired",
+            Self::EndpointVerification => "endpoint_verification",
+        }
+    }
+
+    /// Разбор из строкового представления (для подписок endpoint'ов)
+    pub fn from_db_str(value: &str) -> Option<Self> {
+        match value {
+            "incoming_transaction" => Some(Self::IncomingTransaction),
+            "outgoing_transfer" => Some(Self::OutgoingTransfer),
+            "transfer_completed" => Some(Self::TransferCompleted),
+            "transfer_failed" => Some(Self::TransferFailed),
+            "wallet_created" => Some(Self::WalletCreated),
+            "wallet_activated" => Some(Self::WalletActivated),
+            "wallet_lifecycle_changed" => Some(Self::WalletLifecycleChanged),
+            "balance_threshold_crossed" => Some(Self::BalanceThresholdCrossed),
+            "invoice_paid" => Some(Self::InvoicePaid),
+            "invoice_expired" => Some(Self::InvoiceExpired),
+            "endpoint_verification" => Some(Self::EndpointVerification),
+            _ => None,
+        }
+    }
+}
+
+/// Подписан ли endpoint на событие: event_types хранится как типы
+/// через запятую, пустая строка означает подписку на все события
+fn endpoint_subscribed(event_types: &str, event: &str) -> bool {
+    if event_types.trim().is_empty() {
+        return true;
+    }
+    event_types
+        .split(',')
+        .any(|subscribed| subscribed.trim() == event)
+}
+
+/// Вид изменения жизненного цикла кошелька
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletLifecycleChange {
+    /// Кошелек заморожен (комплаенс-пометка)
+    Frozen,
+    /// С кошелька снята заморозка
+    Unfrozen,
+    /// Кошелек архивирован
+    Archived,
+    /// Владелец кошелька изменен
+    OwnershipTransferred,
+    /// API токен кошелька ротирован
+    KeyRotated,
+}
+
+/// Пересечение порога баланса (передается из сервиса алертов)
+#[derive(Debug, Clone)]
+pub struct BalanceThresholdEvent {
+    pub alert_id: i64,
+    /// None - алерт на мастер-кошелек
+    pub wallet_id: Option<i64>,
+    pub wallet_address: String,
+    pub asset: String,
+    /// above или below
+    pub direction: String,
+    pub threshold: String, // Decimal as string
+    pub current_value: String,
 }
 
 /// Данные webhook события
@@ -52,7 +217,11 @@ pub enum WebhookData {
         wallet_address: String,
         tx_hash: String,
         from_address: String,
+        /// Метка источника депозита (exchange, mixer, internal, private_wallet)
+        source_label: Option<String>,
         amount: String, // Decimal as string
+        /// Символ токена депозита (USDT для событий до мультитокенного учета)
+        token_symbol: String,
         status: TransactionStatus,
     },
     OutgoingTransfer {
@@ -61,7 +230,12 @@ pub enum WebhookData {
         wallet_address: String,
         to_address: String,
         amount: String,
+        /// Символ токена трансфера
+        token_symbol: String,
         reference_id: Option<String>,
+        destination_tag: Option<String>,
+        /// Метаданные клиента, переданные при создании трансфера
+        metadata: Option<serde_json::Value>,
         status: TransactionStatus,
         tx_hash: Option<String>,
     },
@@ -75,6 +249,39 @@ pub enum WebhookData {
         activation_amount: String,
         activation_tx_hash: String,
     },
+    WalletLifecycleChanged {
+        wallet_id: i64,
+        wallet_address: String,
+        change: WalletLifecycleChange,
+        /// Состояние затронутых полей до изменения
+        before: serde_json::Value,
+        /// Состояние затронутых полей после изменения
+        after: serde_json::Value,
+    },
+    BalanceThresholdCrossed {
+        alert_id: i64,
+        /// None - алерт на мастер-кошелек
+        wallet_id: Option<i64>,
+        wallet_address: String,
+        asset: String,
+        /// above или below
+        direction: String,
+        threshold: String, // Decimal as string
+        current_value: String,
+    },
+    InvoiceEvent {
+        invoice_id: i64,
+        wallet_id: i64,
+        /// Ссылка интегратора (номер заказа)
+        reference: String,
+        expected_amount: String, // Decimal as string
+        paid_amount: String,
+        /// PAID, OVERPAID или EXPIRED
+        status: String,
+    },
+    EndpointVerification {
+        nonce: String,
+    },
 }
 
 /// Сервис webhook уведомлений
@@ -83,6 +290,61 @@ pub struct WebhookService {
     config: WebhookConfig,
     client: Client,
     retry_service: RetryableService<()>,
+    /// Подтверждено ли владение endpoint (challenge с nonce)
+    endpoint_verified: Arc<AtomicBool>,
+    /// Пул БД для персистентного лога событий (bulk export мерчантам)
+    db: Option<DbPool>,
+    /// Очередь доставки endpoint'а (глубина ограничена max_queue_depth)
+    queue: Arc<Mutex<VecDeque<QueuedDelivery>>>,
+    /// Слоты одновременных доставок (backpressure на медленных консюмеров)
+    delivery_slots: Arc<tokio::sync::Semaphore>,
+    /// Счетчики доставки для метрик
+    counters: Arc<DeliveryCounters>,
+    /// JWS подписант payload'ов (None - мерчант проверяет только HMAC)
+    jws_signer: Option<Arc<crate::infrastructure::JwsSigner>>,
+    /// Шифрование секретов endpoint'ов на стороне БД (None - application)
+    column_encryption: Option<Arc<crate::infrastructure::database::ColumnEncryption>>,
+}
+
+/// Событие в очереди доставки
+struct QueuedDelivery {
+    payload_json: String,
+    /// URL endpoint'а-получателя (primary или зарегистрированный)
+    endpoint_url: String,
+    /// Секрет HMAC endpoint'а-получателя
+    secret_key: Option<String>,
+    enqueued_at: Instant,
+    /// id записи в outbox'е webhook_deliveries (None без персистентности)
+    delivery_id: Option<i64>,
+}
+
+/// Счетчики доставки одного endpoint'а
+#[derive(Default)]
+struct DeliveryCounters {
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    dropped_oldest: AtomicU64,
+    persist_only_skipped: AtomicU64,
+    last_lag_ms: AtomicU64,
+    total_lag_ms: AtomicU64,
+}
+
+/// Метрики доставки webhook'ов по endpoint'у
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDeliveryMetrics {
+    pub endpoint_url: String,
+    /// Текущая глубина очереди доставки
+    pub queue_depth: usize,
+    pub delivered: u64,
+    pub failed: u64,
+    /// Вытеснено политикой drop_oldest
+    pub dropped_oldest: u64,
+    /// Пропущено политикой persist_only
+    pub persist_only_skipped: u64,
+    /// Лаг последней доставки (от постановки в очередь до отправки), мс
+    pub last_delivery_lag_ms: u64,
+    /// Средний лаг доставки, мс
+    pub avg_delivery_lag_ms: u64,
 }
 
 impl WebhookService {
@@ -94,24 +356,165 @@ impl WebhookService {
             initial_delay: Duration::from_millis(500),
             max_delay: Duration::from_secs(10),
             backoff_multiplier: 2.0,
+            jitter: 0.1,
         };
 
+        let delivery_slots = Arc::new(tokio::sync::Semaphore::new(
+            config.max_concurrent_deliveries.max(1),
+        ));
+
         Self {
+            client: config.egress.build_client(),
             config,
-            client: Client::new(),
             retry_service: RetryableService::with_config((), retry_config),
+            endpoint_verified: Arc::new(AtomicBool::new(false)),
+            db: None,
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+            delivery_slots,
+            counters: Arc::new(DeliveryCounters::default()),
+            jws_signer: None,
+            column_encryption: None,
+        }
+    }
+
+    /// Включает pgcrypto шифрование секретов зарегистрированных endpoint'ов
+    pub fn with_column_encryption(
+        mut self,
+        column_encryption: Option<Arc<crate::infrastructure::database::ColumnEncryption>>,
+    ) -> Self {
+        self.column_encryption = column_encryption;
+        self
+    }
+
+    /// Секрет endpoint'а: в режиме pgcrypto расшифровывается на стороне БД
+    async fn endpoint_secret(&self, endpoint: &WebhookEndpointModel) -> Option<String> {
+        if let (Some(encryption), Some(ciphertext)) =
+            (&self.column_encryption, &endpoint.secret_key_enc)
+        {
+            return match encryption.decrypt(ciphertext).await {
+                Ok(secret) => Some(secret),
+                Err(e) => {
+                    warn!(
+                        "⚠️  Не удалось расшифровать секрет endpoint'а {}: {}",
+                        endpoint.id, e
+                    );
+                    None
+                }
+            };
+        }
+        endpoint.secret_key.clone()
+    }
+
+    /// Метрики доставки endpoint'а (глубина очереди, лаг, потери)
+    pub fn delivery_metrics(&self) -> WebhookDeliveryMetrics {
+        let queue_depth = self.queue.lock().unwrap().len();
+        let delivered = self.counters.delivered.load(Ordering::Relaxed);
+
+        WebhookDeliveryMetrics {
+            endpoint_url: self.config.url.clone(),
+            queue_depth,
+            delivered,
+            failed: self.counters.failed.load(Ordering::Relaxed),
+            dropped_oldest: self.counters.dropped_oldest.load(Ordering::Relaxed),
+            persist_only_skipped: self.counters.persist_only_skipped.load(Ordering::Relaxed),
+            last_delivery_lag_ms: self.counters.last_lag_ms.load(Ordering::Relaxed),
+            avg_delivery_lag_ms: self
+                .counters
+                .total_lag_ms
+                .load(Ordering::Relaxed)
+                .checked_div(delivered)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Подключает персистентный лог событий для bulk export'а
+    pub fn with_persistence(mut self, db: DbPool) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Подключает JWS подпись payload'ов (ES256, ключи в JWKS)
+    pub fn with_jws_signer(mut self, signer: Arc<crate::infrastructure::JwsSigner>) -> Self {
+        self.jws_signer = Some(signer);
+        self
+    }
+
+    /// Подтверждено ли владение endpoint
+    pub fn is_endpoint_verified(&self) -> bool {
+        self.endpoint_verified.load(Ordering::Relaxed)
+    }
+
+    /// Проверяет владение webhook endpoint через challenge с nonce
+    ///
+    /// Endpoint должен вернуть JSON с тем же nonce (`{"nonce": "..."}`),
+    /// иначе события на него отправляться не будут (при require_verification)
+    pub async fn verify_endpoint(&self) -> Result<bool> {
+        if !self.config.enabled {
+            return Ok(false);
+        }
+
+        let nonce = uuid::Uuid::new_v4().to_string();
+        let challenge = WebhookPayload {
+            event_type: WebhookEventType::EndpointVerification,
+            timestamp: chrono::Utc::now(),
+            data: WebhookData::EndpointVerification {
+                nonce: nonce.clone(),
+            },
+        };
+
+        let request = self
+            .client
+            .post(&self.config.url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Verification", "true")
+            .json(&challenge);
+
+        let request_timeout = Duration::from_secs(self.config.timeout_seconds);
+
+        let response = match timeout(request_timeout, request.send()).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                warn!("⚠️  Верификация webhook endpoint не удалась: {}", e);
+                return Ok(false);
+            }
+            Err(_) => {
+                warn!("⚠️  Верификация webhook endpoint: timeout");
+                return Ok(false);
+            }
+        };
+
+        if !response.status().is_success() {
+            warn!(
+                "⚠️  Верификация webhook endpoint: статус {}",
+                response.status()
+            );
+            return Ok(false);
+        }
+
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let echoed_nonce = body.get("nonce").and_then(|v| v.as_str());
+
+        if echoed_nonce == Some(nonce.as_str()) {
+            self.endpoint_verified.store(true, Ordering::Relaxed);
+            info!(
+                "✅ Владение webhook endpoint {} подтверждено",
+                self.config.url
+            );
+            Ok(true)
+        } else {
+            warn!(
+                "⚠️  Webhook endpoint {} не вернул ожидаемый nonce - события отправляться не будут",
+                self.config.url
+            );
+            Ok(false)
         }
     }
 
     /// Отправляет webhook уведомление о входящей транзакции
     pub async fn notify_incoming_transaction(
         &self,
-        wallet_id: i64,
         wallet_address: String,
-        tx_hash: String,
-        from_address: String,
-        amount: Decimal,
-        status: TransactionStatus,
+        tx: &IncomingTransactionModel,
     ) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
@@ -121,12 +524,15 @@ impl WebhookService {
             event_type: WebhookEventType::IncomingTransaction,
             timestamp: chrono::Utc::now(),
             data: WebhookData::IncomingTransaction {
-                wallet_id,
+                wallet_id: tx.wallet_id,
                 wallet_address,
-                tx_hash,
-                from_address,
-                amount: amount.to_string(),
-                status,
+                tx_hash: tx.tx_hash.clone(),
+                from_address: tx.from_address.clone(),
+                source_label: tx.source_label.clone(),
+                amount: tx.amount.to_string(),
+                token_symbol: tx.token_symbol.clone(),
+                status: TransactionStatus::from_db_str(&tx.status)
+                    .unwrap_or(TransactionStatus::Pending),
             },
         };
 
@@ -136,14 +542,8 @@ impl WebhookService {
     /// Отправляет webhook уведомление об исходящем трансфере
     pub async fn notify_outgoing_transfer(
         &self,
-        transfer_id: i64,
-        wallet_id: i64,
         wallet_address: String,
-        to_address: String,
-        amount: Decimal,
-        reference_id: Option<String>,
-        status: TransactionStatus,
-        tx_hash: Option<String>,
+        transfer: &OutgoingTransferModel,
     ) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
@@ -153,14 +553,18 @@ impl WebhookService {
             event_type: WebhookEventType::OutgoingTransfer,
             timestamp: chrono::Utc::now(),
             data: WebhookData::OutgoingTransfer {
-                transfer_id,
-                wallet_id,
+                transfer_id: transfer.id,
+                wallet_id: transfer.from_wallet_id,
                 wallet_address,
-                to_address,
-                amount: amount.to_string(),
-                reference_id,
-                status,
-                tx_hash,
+                to_address: transfer.to_address.clone(),
+                amount: transfer.amount.to_string(),
+                token_symbol: transfer.token_symbol.clone(),
+                reference_id: transfer.reference_id.clone(),
+                destination_tag: transfer.destination_tag.clone(),
+                metadata: parse_stored_metadata(transfer.metadata.clone()),
+                status: TransactionStatus::from_db_str(&transfer.status)
+                    .unwrap_or(TransactionStatus::Pending),
+                tx_hash: transfer.tx_hash.clone(),
             },
         };
 
@@ -215,26 +619,325 @@ impl WebhookService {
         self.send_webhook(payload).await
     }
 
-    /// Внутренний метод для отправки webhook с retry логикой
+    /// Отправляет webhook о изменении жизненного цикла кошелька
+    /// (заморозка, архивация, смена владельца, ротация ключа)
+    pub async fn notify_wallet_lifecycle_changed(
+        &self,
+        wallet_id: i64,
+        wallet_address: String,
+        change: WalletLifecycleChange,
+        before: serde_json::Value,
+        after: serde_json::Value,
+    ) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let payload = WebhookPayload {
+            event_type: WebhookEventType::WalletLifecycleChanged,
+            timestamp: chrono::Utc::now(),
+            data: WebhookData::WalletLifecycleChanged {
+                wallet_id,
+                wallet_address,
+                change,
+                before,
+                after,
+            },
+        };
+
+        self.send_webhook(payload).await
+    }
+
+    /// Отправляет webhook о пересечении порога баланса
+    pub async fn notify_balance_threshold(&self, event: BalanceThresholdEvent) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let payload = WebhookPayload {
+            event_type: WebhookEventType::BalanceThresholdCrossed,
+            timestamp: chrono::Utc::now(),
+            data: WebhookData::BalanceThresholdCrossed {
+                alert_id: event.alert_id,
+                wallet_id: event.wallet_id,
+                wallet_address: event.wallet_address,
+                asset: event.asset,
+                direction: event.direction,
+                threshold: event.threshold,
+                current_value: event.current_value,
+            },
+        };
+
+        self.send_webhook(payload).await
+    }
+
+    /// Отправляет событие инвойса (invoice_paid / invoice_expired)
+    pub async fn notify_invoice_event(
+        &self,
+        event_type: WebhookEventType,
+        invoice: &crate::infrastructure::database::models::InvoiceModel,
+    ) -> Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let payload = WebhookPayload {
+            event_type,
+            timestamp: chrono::Utc::now(),
+            data: WebhookData::InvoiceEvent {
+                invoice_id: invoice.id,
+                wallet_id: invoice.wallet_id,
+                reference: invoice.reference.clone(),
+                expected_amount: invoice.expected_amount.to_string(),
+                paid_amount: invoice.paid_amount.to_string(),
+                status: invoice.status.clone(),
+            },
+        };
+
+        self.send_webhook(payload).await
+    }
+
+    /// Ставит webhook в очередь доставки endpoint'а.
+    ///
+    /// Доставка асинхронная: глубина очереди ограничена max_queue_depth,
+    /// параллелизм - max_concurrent_deliveries, чтобы всплеск депозитов
+    /// не завалил медленного консюмера. Событие в любом случае сначала
+    /// попадает в персистентный лог
     async fn send_webhook(&self, payload: WebhookPayload) -> Result<()> {
+        // Не отправляем события на неподтвержденный endpoint,
+        // чтобы не лить firehose на чужой URL по ошибке конфигурации
+        if self.config.require_verification && !self.is_endpoint_verified() {
+            return Err(anyhow::anyhow!(
+                "Webhook endpoint {} не прошел верификацию владения",
+                self.config.url
+            ));
+        }
+
+        // В dry-run режиме события помечаются, чтобы консюмер отличал
+        // репетиционные уведомления от настоящих
+        let payload_json = if crate::utils::is_dry_run() {
+            let mut value = serde_json::to_value(&payload)?;
+            value["simulated"] = serde_json::json!(true);
+            value.to_string()
+        } else {
+            serde_json::to_string(&payload)?
+        };
+
+        // Сохраняем событие в персистентный лог до попытки доставки.
+        // Ошибка записи не должна блокировать отправку
+        if let Err(e) = self.record_event(&payload, &payload_json).await {
+            warn!("⚠️  Не удалось сохранить webhook событие в лог: {}", e);
+        }
+
+        // Запись в outbox: если inline-доставка не справится, фоновый
+        // воркер дотянет запись с экспоненциальным backoff
+        let delivery_id = match self
+            .record_delivery(&payload, &payload_json, &self.config.url)
+            .await
+        {
+            Ok(id) => id,
+            Err(e) => {
+                warn!("⚠️  Не удалось записать доставку в outbox: {}", e);
+                None
+            }
+        };
+
+        let mut enqueued = self.enqueue_delivery(
+            payload_json.clone(),
+            self.config.url.clone(),
+            self.config.secret_key.clone(),
+            delivery_id,
+        );
+
+        // Fan-out на зарегистрированные endpoint'ы, подписанные на событие.
+        // Ошибка чтения подписок не блокирует доставку на primary
+        match self.subscribed_endpoints(&payload.event_type).await {
+            Ok(endpoints) => {
+                for endpoint in endpoints {
+                    let delivery_id = match self
+                        .record_delivery(&payload, &payload_json, &endpoint.url)
+                        .await
+                    {
+                        Ok(id) => id,
+                        Err(e) => {
+                            warn!("⚠️  Не удалось записать доставку в outbox: {}", e);
+                            None
+                        }
+                    };
+                    let secret_key = self.endpoint_secret(&endpoint).await;
+                    enqueued |= self.enqueue_delivery(
+                        payload_json.clone(),
+                        endpoint.url,
+                        secret_key,
+                        delivery_id,
+                    );
+                }
+            }
+            Err(e) => warn!("⚠️  Не удалось загрузить webhook endpoint'ы: {}", e),
+        }
+
+        if enqueued {
+            self.spawn_drain();
+        }
+
+        Ok(())
+    }
+
+    /// Кладет событие в очередь с учетом политики переполнения.
+    /// Возвращает false, если событие не попало в очередь (persist_only)
+    fn enqueue_delivery(
+        &self,
+        payload_json: String,
+        endpoint_url: String,
+        secret_key: Option<String>,
+        delivery_id: Option<i64>,
+    ) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+
+        if queue.len() >= self.config.max_queue_depth.max(1) {
+            match self.config.overflow_policy {
+                WebhookOverflowPolicy::DropOldest => {
+                    queue.pop_front();
+                    self.counters.dropped_oldest.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "⚠️  Очередь webhook'ов {} переполнена - вытеснено старейшее событие",
+                        self.config.url
+                    );
+                }
+                WebhookOverflowPolicy::PersistOnly => {
+                    self.counters
+                        .persist_only_skipped
+                        .fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "⚠️  Очередь webhook'ов {} переполнена - событие останется только в логе",
+                        self.config.url
+                    );
+                    return false;
+                }
+            }
+        }
+
+        queue.push_back(QueuedDelivery {
+            payload_json,
+            endpoint_url,
+            secret_key,
+            enqueued_at: Instant::now(),
+            delivery_id,
+        });
+        true
+    }
+
+    /// Запускает воркер доставки, если есть свободный слот.
+    /// Воркер выгребает очередь до дна и освобождает слот
+    fn spawn_drain(&self) {
+        let Ok(permit) = Arc::clone(&self.delivery_slots).try_acquire_owned() else {
+            // Все слоты заняты - событие заберет один из активных воркеров
+            return;
+        };
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let _permit = permit;
+
+            loop {
+                let item = service.queue.lock().unwrap().pop_front();
+                let Some(item) = item else { break };
+
+                let lag_ms = item.enqueued_at.elapsed().as_millis() as u64;
+                service
+                    .counters
+                    .last_lag_ms
+                    .store(lag_ms, Ordering::Relaxed);
+
+                match service
+                    .deliver_payload(
+                        item.payload_json,
+                        &item.endpoint_url,
+                        item.secret_key.as_deref(),
+                    )
+                    .await
+                {
+                    Ok(()) => {
+                        service.counters.delivered.fetch_add(1, Ordering::Relaxed);
+                        service
+                            .counters
+                            .total_lag_ms
+                            .fetch_add(lag_ms, Ordering::Relaxed);
+                        service.mark_delivery_succeeded(item.delivery_id).await;
+                    }
+                    Err(e) => {
+                        service.counters.failed.fetch_add(1, Ordering::Relaxed);
+                        service
+                            .mark_delivery_failed(item.delivery_id, &e.to_string())
+                            .await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Дожидается опустошения очереди доставки при graceful shutdown,
+    /// но не дольше timeout. Очередь in-memory: недоставленные на момент
+    /// выхода события останутся только в персистентном outbox'е.
+    /// Возвращает true, если очередь опустела и все воркеры закончили
+    pub async fn flush_queue(&self, timeout: std::time::Duration) -> bool {
+        // Будим воркеров на случай, если события лежат без активного слота
+        self.spawn_drain();
+
+        let max_slots = self.config.max_concurrent_deliveries.max(1);
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let queue_depth = self.queue.lock().unwrap().len();
+            // Все слоты свободны - значит, ни один воркер не в полете
+            let workers_idle = self.delivery_slots.available_permits() == max_slots;
+            if queue_depth == 0 && workers_idle {
+                return true;
+            }
+
+            if Instant::now() >= deadline {
+                warn!(
+                    "⚠️ Таймаут flush'а webhook очереди: {} событий не доставлено, \
+                     они остаются в персистентном outbox'е",
+                    queue_depth
+                );
+                return false;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    }
+
+    /// Доставляет один payload с retry логикой
+    async fn deliver_payload(
+        &self,
+        payload_json: String,
+        endpoint_url: &str,
+        secret_key: Option<&str>,
+    ) -> Result<()> {
         let config = self.config.clone();
         let client = self.client.clone();
-        let payload_json = serde_json::to_string(&payload)?;
+        let jws_signer = self.jws_signer.clone();
+        let endpoint_url = endpoint_url.to_string();
+        let secret_key = secret_key.map(str::to_string);
 
         self.retry_service
             .retry("send_webhook", || {
                 let config = config.clone();
                 let client = client.clone();
+                let jws_signer = jws_signer.clone();
                 let payload_json = payload_json.clone();
+                let endpoint_url = endpoint_url.clone();
+                let secret_key = secret_key.clone();
 
                 async move {
                     let mut request = client
-                        .post(&config.url)
+                        .post(&endpoint_url)
                         .header("Content-Type", "application/json")
                         .header("User-Agent", "TRON-Gateway-Webhook/1.0");
 
                     // Добавляем подпись если есть secret key
-                    if let Some(secret_key) = &config.secret_key {
+                    if let Some(secret_key) = &secret_key {
                         let signature = format!(
                             "sha256={}",
                             hex::encode(
@@ -248,6 +951,13 @@ impl WebhookService {
                         request = request.header("X-Webhook-Signature", signature);
                     }
 
+                    // JWS подпись (ES256): мерчант проверяет стандартной JWT
+                    // библиотекой по ключам из /.well-known/jwks.json
+                    if let Some(signer) = &jws_signer {
+                        request =
+                            request.header("X-Webhook-Signature-Jws", signer.sign(&payload_json));
+                    }
+
                     let request = request.body(payload_json);
 
                     let request_timeout = Duration::from_secs(config.timeout_seconds);
@@ -262,7 +972,7 @@ impl WebhookService {
                         .map_err(classify_reqwest_error)?;
 
                     if response.status().is_success() {
-                        info!("✅ Webhook отправлен успешно на {}", config.url);
+                        info!("✅ Webhook отправлен успешно на {}", endpoint_url);
                         Ok(())
                     } else {
                         let status = response.status();
@@ -285,6 +995,516 @@ impl WebhookService {
             })
     }
 
+    /// Сохраняет событие в персистентный лог (no-op без пула БД)
+    async fn record_event(&self, payload: &WebhookPayload, payload_json: &str) -> Result<()> {
+        // Локальный импорт: глобальный конфликтует с AtomicBool::load
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+
+        let mut conn = db.get().await?;
+
+        let event = NewWebhookEvent {
+            event_type: payload.event_type.as_db_str().to_string(),
+            payload: payload_json.to_string(),
+        };
+
+        diesel::insert_into(schema::webhook_events::table)
+            .values(&event)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Записывает доставку в outbox webhook_deliveries (no-op без пула БД).
+    /// next_retry_at с запасом: воркер возьмет запись, только если
+    /// inline-доставка не завершила ее за это время (например, после рестарта)
+    async fn record_delivery(
+        &self,
+        payload: &WebhookPayload,
+        payload_json: &str,
+        endpoint_url: &str,
+    ) -> Result<Option<i64>> {
+        // Локальный импорт: глобальный конфликтует с AtomicBool::load
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Ok(None);
+        };
+
+        let mut conn = db.get().await?;
+
+        let delivery = NewWebhookDelivery {
+            event_type: payload.event_type.as_db_str().to_string(),
+            payload: payload_json.to_string(),
+            endpoint_url: endpoint_url.to_string(),
+            status: DELIVERY_STATUS_PENDING.to_string(),
+            next_retry_at: Some(chrono::Utc::now() + chrono::Duration::minutes(2)),
+        };
+
+        let id = diesel::insert_into(schema::webhook_deliveries::table)
+            .values(&delivery)
+            .returning(schema::webhook_deliveries::id)
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(Some(id))
+    }
+
+    /// Помечает доставку успешной (best-effort)
+    async fn mark_delivery_succeeded(&self, delivery_id: Option<i64>) {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let (Some(db), Some(delivery_id)) = (&self.db, delivery_id) else {
+            return;
+        };
+
+        let result = async {
+            let mut conn = db.get().await?;
+            diesel::update(schema::webhook_deliveries::table.find(delivery_id))
+                .set((
+                    schema::webhook_deliveries::status.eq(DELIVERY_STATUS_DELIVERED),
+                    schema::webhook_deliveries::attempts
+                        .eq(schema::webhook_deliveries::attempts + 1),
+                    schema::webhook_deliveries::delivered_at.eq(chrono::Utc::now()),
+                    schema::webhook_deliveries::next_retry_at
+                        .eq(None::<chrono::DateTime<chrono::Utc>>),
+                ))
+                .execute(&mut conn)
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!(
+                "⚠️  Не удалось пометить доставку {} успешной: {}",
+                delivery_id, e
+            );
+        }
+    }
+
+    /// Фиксирует неудачную попытку: инкремент attempts, backoff для
+    /// следующего retry, терминальный FAILED после DELIVERY_MAX_ATTEMPTS
+    async fn mark_delivery_failed(&self, delivery_id: Option<i64>, error_text: &str) {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let (Some(db), Some(delivery_id)) = (&self.db, delivery_id) else {
+            return;
+        };
+
+        let result = async {
+            let mut conn = db.get().await?;
+
+            let attempts: i32 = diesel::update(schema::webhook_deliveries::table.find(delivery_id))
+                .set((
+                    schema::webhook_deliveries::attempts
+                        .eq(schema::webhook_deliveries::attempts + 1),
+                    schema::webhook_deliveries::last_error.eq(error_text),
+                ))
+                .returning(schema::webhook_deliveries::attempts)
+                .get_result(&mut conn)
+                .await?;
+
+            if attempts >= DELIVERY_MAX_ATTEMPTS {
+                diesel::update(schema::webhook_deliveries::table.find(delivery_id))
+                    .set((
+                        schema::webhook_deliveries::status.eq(DELIVERY_STATUS_FAILED),
+                        schema::webhook_deliveries::next_retry_at
+                            .eq(None::<chrono::DateTime<chrono::Utc>>),
+                    ))
+                    .execute(&mut conn)
+                    .await?;
+            } else {
+                diesel::update(schema::webhook_deliveries::table.find(delivery_id))
+                    .set(
+                        schema::webhook_deliveries::next_retry_at
+                            .eq(chrono::Utc::now() + redelivery_backoff(attempts)),
+                    )
+                    .execute(&mut conn)
+                    .await?;
+            }
+
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!(
+                "⚠️  Не удалось зафиксировать неудачную доставку {}: {}",
+                delivery_id, e
+            );
+        }
+    }
+
+    /// Дотягивает due-записи outbox'а: PENDING с наступившим next_retry_at.
+    /// Вызывается фоновым воркером планировщика. Возвращает число
+    /// обработанных записей
+    pub async fn process_due_deliveries(&self, limit: i64) -> Result<usize> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Ok(0);
+        };
+
+        let due: Vec<WebhookDeliveryModel> = {
+            let mut conn = db.get().await?;
+            schema::webhook_deliveries::table
+                .filter(schema::webhook_deliveries::status.eq(DELIVERY_STATUS_PENDING))
+                .filter(schema::webhook_deliveries::next_retry_at.le(chrono::Utc::now()))
+                .order(schema::webhook_deliveries::next_retry_at.asc())
+                .limit(limit.clamp(1, 200))
+                .select(WebhookDeliveryModel::as_select())
+                .load(&mut conn)
+                .await?
+        };
+
+        let processed = due.len();
+
+        for delivery in due {
+            let secret_key = self.secret_for_endpoint(&delivery.endpoint_url).await;
+            match self
+                .deliver_payload(
+                    delivery.payload.clone(),
+                    &delivery.endpoint_url,
+                    secret_key.as_deref(),
+                )
+                .await
+            {
+                Ok(()) => self.mark_delivery_succeeded(Some(delivery.id)).await,
+                Err(e) => {
+                    self.mark_delivery_failed(Some(delivery.id), &e.to_string())
+                        .await
+                }
+            }
+        }
+
+        Ok(processed)
+    }
+
+    /// Список записей outbox'а для инспекции (новые первыми)
+    pub async fn list_deliveries(
+        &self,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryModel>> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!("Outbox доставок требует подключенной БД"));
+        };
+
+        let mut conn = db.get().await?;
+
+        let mut query = schema::webhook_deliveries::table
+            .order(schema::webhook_deliveries::id.desc())
+            .limit(limit.clamp(1, 500))
+            .into_boxed();
+
+        if let Some(status) = status {
+            query =
+                query.filter(schema::webhook_deliveries::status.eq(status.to_ascii_uppercase()));
+        }
+
+        Ok(query
+            .select(WebhookDeliveryModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Принудительная повторная доставка записи outbox'а (включая FAILED).
+    /// Возвращает обновленную запись
+    pub async fn redeliver(&self, delivery_id: i64) -> Result<WebhookDeliveryModel> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!("Outbox доставок требует подключенной БД"));
+        };
+
+        let delivery: WebhookDeliveryModel = {
+            let mut conn = db.get().await?;
+            schema::webhook_deliveries::table
+                .find(delivery_id)
+                .select(WebhookDeliveryModel::as_select())
+                .first(&mut conn)
+                .await
+                .map_err(|_| anyhow::anyhow!("Доставка {} не найдена", delivery_id))?
+        };
+
+        let secret_key = self.secret_for_endpoint(&delivery.endpoint_url).await;
+        match self
+            .deliver_payload(
+                delivery.payload.clone(),
+                &delivery.endpoint_url,
+                secret_key.as_deref(),
+            )
+            .await
+        {
+            Ok(()) => self.mark_delivery_succeeded(Some(delivery.id)).await,
+            Err(e) => {
+                self.mark_delivery_failed(Some(delivery.id), &e.to_string())
+                    .await
+            }
+        }
+
+        let mut conn = db.get().await?;
+        Ok(schema::webhook_deliveries::table
+            .find(delivery_id)
+            .select(WebhookDeliveryModel::as_select())
+            .first(&mut conn)
+            .await?)
+    }
+
+    /// Включенные endpoint'ы, подписанные на тип события (пустая БД - пусто)
+    async fn subscribed_endpoints(
+        &self,
+        event_type: &WebhookEventType,
+    ) -> Result<Vec<WebhookEndpointModel>> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Ok(Vec::new());
+        };
+
+        let mut conn = db.get().await?;
+
+        let endpoints: Vec<WebhookEndpointModel> = schema::webhook_endpoints::table
+            .filter(schema::webhook_endpoints::enabled.eq(true))
+            .select(WebhookEndpointModel::as_select())
+            .load(&mut conn)
+            .await?;
+
+        let event = event_type.as_db_str();
+        Ok(endpoints
+            .into_iter()
+            .filter(|endpoint| endpoint_subscribed(&endpoint.event_types, event))
+            .collect())
+    }
+
+    /// Секрет HMAC endpoint'а по URL: primary берет секрет из конфигурации,
+    /// зарегистрированные - из webhook_endpoints
+    async fn secret_for_endpoint(&self, endpoint_url: &str) -> Option<String> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        if endpoint_url == self.config.url {
+            return self.config.secret_key.clone();
+        }
+
+        let db = self.db.as_ref()?;
+        let mut conn = db.get().await.ok()?;
+
+        let endpoint: WebhookEndpointModel = schema::webhook_endpoints::table
+            .filter(schema::webhook_endpoints::url.eq(endpoint_url))
+            .select(WebhookEndpointModel::as_select())
+            .first(&mut conn)
+            .await
+            .ok()?;
+        drop(conn);
+
+        self.endpoint_secret(&endpoint).await
+    }
+
+    /// Регистрирует дополнительный endpoint. Пустой список типов
+    /// означает подписку на все события; неизвестные типы отклоняются
+    pub async fn register_endpoint(
+        &self,
+        url: &str,
+        event_types: &[String],
+        secret_key: Option<String>,
+    ) -> Result<WebhookEndpointModel> {
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!(
+                "Управление endpoint'ами требует подключенной БД"
+            ));
+        };
+
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            anyhow::bail!("URL endpoint'а должен начинаться с http:// или https://");
+        }
+
+        for event_type in event_types {
+            if WebhookEventType::from_db_str(event_type).is_none() {
+                anyhow::bail!("Неизвестный тип события: {}", event_type);
+            }
+        }
+
+        // В режиме pgcrypto секрет уходит только в зашифрованную колонку
+        let (secret_key, secret_key_enc) = match (&self.column_encryption, secret_key) {
+            (Some(encryption), Some(secret)) => (None, Some(encryption.encrypt(&secret).await?)),
+            (_, secret_key) => (secret_key, None),
+        };
+
+        let mut conn = db.get().await?;
+
+        let endpoint: WebhookEndpointModel = diesel::insert_into(schema::webhook_endpoints::table)
+            .values(&NewWebhookEndpoint {
+                url: url.to_string(),
+                event_types: event_types.join(","),
+                secret_key,
+                enabled: true,
+                secret_key_enc,
+            })
+            .get_result(&mut conn)
+            .await?;
+
+        info!(
+            "📮 Webhook endpoint #{} зарегистрирован: {} ({})",
+            endpoint.id,
+            endpoint.url,
+            if endpoint.event_types.is_empty() {
+                "все события"
+            } else {
+                &endpoint.event_types
+            }
+        );
+
+        Ok(endpoint)
+    }
+
+    /// Список зарегистрированных endpoint'ов
+    pub async fn list_endpoints(&self) -> Result<Vec<WebhookEndpointModel>> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!(
+                "Управление endpoint'ами требует подключенной БД"
+            ));
+        };
+
+        let mut conn = db.get().await?;
+
+        Ok(schema::webhook_endpoints::table
+            .order(schema::webhook_endpoints::id.asc())
+            .select(WebhookEndpointModel::as_select())
+            .load(&mut conn)
+            .await?)
+    }
+
+    /// Обновляет подписку и/или включенность endpoint'а
+    pub async fn update_endpoint(
+        &self,
+        endpoint_id: i64,
+        event_types: Option<&[String]>,
+        enabled: Option<bool>,
+    ) -> Result<WebhookEndpointModel> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!(
+                "Управление endpoint'ами требует подключенной БД"
+            ));
+        };
+
+        if let Some(event_types) = event_types {
+            for event_type in event_types {
+                if WebhookEventType::from_db_str(event_type).is_none() {
+                    anyhow::bail!("Неизвестный тип события: {}", event_type);
+                }
+            }
+        }
+
+        let mut conn = db.get().await?;
+
+        let mut endpoint: WebhookEndpointModel = schema::webhook_endpoints::table
+            .find(endpoint_id)
+            .select(WebhookEndpointModel::as_select())
+            .first(&mut conn)
+            .await
+            .map_err(|_| anyhow::anyhow!("Endpoint {} не найден", endpoint_id))?;
+
+        if let Some(event_types) = event_types {
+            endpoint.event_types = event_types.join(",");
+        }
+        if let Some(enabled) = enabled {
+            endpoint.enabled = enabled;
+        }
+
+        let updated: WebhookEndpointModel =
+            diesel::update(schema::webhook_endpoints::table.find(endpoint_id))
+                .set((
+                    schema::webhook_endpoints::event_types.eq(&endpoint.event_types),
+                    schema::webhook_endpoints::enabled.eq(endpoint.enabled),
+                    schema::webhook_endpoints::updated_at.eq(chrono::Utc::now()),
+                ))
+                .get_result(&mut conn)
+                .await?;
+
+        Ok(updated)
+    }
+
+    /// Удаляет endpoint
+    pub async fn delete_endpoint(&self, endpoint_id: i64) -> Result<bool> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!(
+                "Управление endpoint'ами требует подключенной БД"
+            ));
+        };
+
+        let mut conn = db.get().await?;
+
+        let deleted = diesel::delete(schema::webhook_endpoints::table.find(endpoint_id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Ротирует секрет HMAC endpoint'а. Новый секрет возвращается
+    /// один раз - дальше хранится только на стороне шлюза
+    pub async fn rotate_endpoint_secret(&self, endpoint_id: i64) -> Result<String> {
+        use diesel::prelude::*;
+        use diesel_async::RunQueryDsl;
+
+        let Some(db) = &self.db else {
+            return Err(anyhow::anyhow!(
+                "Управление endpoint'ами требует подключенной БД"
+            ));
+        };
+
+        let new_secret = uuid::Uuid::new_v4().simple().to_string();
+
+        // В режиме pgcrypto секрет уходит только в зашифрованную колонку
+        let (secret_column, secret_enc_column) = match &self.column_encryption {
+            Some(encryption) => (None, Some(encryption.encrypt(&new_secret).await?)),
+            None => (Some(new_secret.clone()), None),
+        };
+
+        let mut conn = db.get().await?;
+
+        let updated = diesel::update(schema::webhook_endpoints::table.find(endpoint_id))
+            .set((
+                schema::webhook_endpoints::secret_key.eq(secret_column),
+                schema::webhook_endpoints::secret_key_enc.eq(secret_enc_column),
+                schema::webhook_endpoints::updated_at.eq(chrono::Utc::now()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        if updated == 0 {
+            anyhow::bail!("Endpoint {} не найден", endpoint_id);
+        }
+
+        info!("🔐 Секрет webhook endpoint'а {} ротирован", endpoint_id);
+        Ok(new_secret)
+    }
+
     /// Вычисляет HMAC подпись для webhook payload
     fn calculate_signature(&self, payload: &str, secret_key: &str) -> String {
         use hmac::{Hmac, Mac};
@@ -314,7 +1534,9 @@ impl WebhookService {
                 wallet_address: "TEST".to_string(),
                 tx_hash: "TEST".to_string(),
                 from_address: "TEST".to_string(),
+                source_label: None,
                 amount: "0".to_string(),
+                token_symbol: "USDT".to_string(),
                 status: TransactionStatus::Pending,
             },
         };
@@ -350,3 +1572,83 @@ impl WebhookService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_service(
+        max_queue_depth: usize,
+        overflow_policy: WebhookOverflowPolicy,
+    ) -> WebhookService {
+        WebhookService::new(WebhookConfig {
+            enabled: true,
+            url: "http://localhost:1/hook".to_string(),
+            timeout_seconds: 5,
+            secret_key: None,
+            require_verification: false,
+            egress: EgressConfig::default(),
+            max_concurrent_deliveries: 1,
+            max_queue_depth,
+            overflow_policy,
+        })
+    }
+
+    fn enqueue(service: &WebhookService, payload: &str) -> bool {
+        service.enqueue_delivery(payload.to_string(), service.config.url.clone(), None, None)
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_queue_bounded() {
+        let service = test_service(2, WebhookOverflowPolicy::DropOldest);
+
+        assert!(enqueue(&service, "{\"n\":1}"));
+        assert!(enqueue(&service, "{\"n\":2}"));
+        assert!(enqueue(&service, "{\"n\":3}"));
+
+        let metrics = service.delivery_metrics();
+        assert_eq!(metrics.queue_depth, 2);
+        assert_eq!(metrics.dropped_oldest, 1);
+
+        // Вытеснено именно старейшее событие
+        let head = service.queue.lock().unwrap().pop_front().unwrap();
+        assert_eq!(head.payload_json, "{\"n\":2}");
+    }
+
+    #[test]
+    fn test_persist_only_skips_new_events() {
+        let service = test_service(1, WebhookOverflowPolicy::PersistOnly);
+
+        assert!(enqueue(&service, "{\"n\":1}"));
+        assert!(!enqueue(&service, "{\"n\":2}"));
+
+        let metrics = service.delivery_metrics();
+        assert_eq!(metrics.queue_depth, 1);
+        assert_eq!(metrics.persist_only_skipped, 1);
+    }
+
+    #[test]
+    fn test_endpoint_subscribed_matches_csv_and_wildcard() {
+        // Пустая подписка означает все события
+        assert!(endpoint_subscribed("", "invoice_paid"));
+        assert!(endpoint_subscribed("   ", "invoice_paid"));
+
+        assert!(endpoint_subscribed(
+            "invoice_paid, transfer_completed",
+            "transfer_completed"
+        ));
+        assert!(!endpoint_subscribed(
+            "invoice_paid,transfer_completed",
+            "wallet_created"
+        ));
+    }
+
+    #[test]
+    fn test_redelivery_backoff_is_exponential_and_capped() {
+        assert_eq!(redelivery_backoff(1), chrono::Duration::seconds(60));
+        assert_eq!(redelivery_backoff(2), chrono::Duration::seconds(120));
+        assert_eq!(redelivery_backoff(4), chrono::Duration::seconds(480));
+        // Потолок - час
+        assert_eq!(redelivery_backoff(8), chrono::Duration::seconds(3600));
+    }
+}
@@ -2,17 +2,27 @@
 
 use std::sync::Arc;
 
+use crate::application::dto::{
+    AmountLimits, ApiVersioningPolicy, ConfirmationPolicy, GatewayCapabilities,
+};
 use crate::application::services::{
-    FeeConfig, SponsorGasService, TransferService, TrxTransferService,
-    UnifiedFeeService, WalletActivationService, WalletService,
+    AllowanceService, BalanceAlertService, BalanceService, CommissionTier, DepositHookRegistry,
+    DepositSplitHook, DepositSplitService, FaucetService, FeeConfig, InvoicePaymentHook,
+    InvoiceService, MasterWalletPool, OrphanRecoveryService, PaymentIntentService, QrCodeService,
+    SchedulerRunLog, SponsorGasService, SweepService, TransactionMonitoringService,
+    TransferEventBus, TransferIngestionService, TransferService, TrxTransferService,
+    UnifiedFeeService, WalletActivationService, WalletService, WalletTokenService, WebhookConfig,
+    WebhookEventService, WebhookReplayService, WebhookService,
 };
 use crate::config::Settings;
 use crate::domain::tokens::TokenRegistry;
 use crate::infrastructure::{
-    database::create_db_pool, 
-    TronGridClient, 
-    TronWalletGenerator,
-    tron::{Trc20TokenService, Trc20ServiceConfig},
+    database::create_db_pool,
+    tron::{Trc20ServiceConfig, Trc20TokenService},
+    ApiKeyRegistry, AuditShipper, ChainRegistry, DegradationMonitor, DepositSourceLabeler,
+    HttpAuditSink, HttpRiskScreeningProvider, InstanceIdentity, LocalSigningBackend, RateLimiter,
+    RemoteSigningBackend, SigningBackend, TracingAuditSink, TronBlockchain, TronGridClient,
+    TronWalletGenerator, CHAIN_ID_TRON,
 };
 
 /// Состояние приложения с всеми сервисами
@@ -22,21 +32,135 @@ pub struct AppState {
     pub transfer_service: Arc<TransferService>,
     pub fee_service: Arc<UnifiedFeeService>,
     pub trc20_service: Arc<Trc20TokenService>, // 🪙 Новый мультитокенный сервис
+    pub balance_service: Arc<BalanceService>,
+    pub payment_intent_service: Arc<PaymentIntentService>,
+    pub wallet_token_service: Arc<WalletTokenService>,
+    pub webhook_event_service: Arc<WebhookEventService>,
+    /// Сервис реплея исторических событий в staging мерчанта
+    pub webhook_replay_service: Arc<WebhookReplayService>,
+    /// Сервис доставки webhook'ов (None - url не задан в конфиге)
+    pub webhook_service: Option<Arc<WebhookService>>,
+    pub monitoring_service: Arc<TransactionMonitoringService>,
+    pub recovery_service: Arc<OrphanRecoveryService>,
+    /// Сервис консолидации средств на мастер-кошелек (sweep)
+    pub sweep_service: Arc<SweepService>,
+    /// Сервис массовой загрузки трансферов (NDJSON ingestion)
+    pub ingestion_service: Arc<TransferIngestionService>,
+    /// Сервис пороговых подписок на балансы
+    pub balance_alert_service: Arc<BalanceAlertService>,
+    /// Сервис TRC-20 разрешений (pull-платежи)
+    pub allowance_service: Arc<AllowanceService>,
+    /// Сервис инвойсов (payment requests)
+    pub invoice_service: Arc<InvoiceService>,
+    /// Сервис маршрутизации депозитов по правилам разложения
+    pub deposit_split_service: Arc<DepositSplitService>,
+    /// Шина переходов статусов для стриминговых gRPC подписчиков
+    pub transfer_events: Arc<TransferEventBus>,
+    /// Журнал запусков задач планировщика (чтение для debug API)
+    pub scheduler_run_log: Arc<SchedulerRunLog>,
+    pub faucet_service: Arc<FaucetService>,
+    /// Генерация QR кодов платежных URI адресов кошельков
+    pub qr_service: Arc<QrCodeService>,
+    pub trx_transfer_service: Arc<TrxTransferService>,
+    pub capabilities: Arc<GatewayCapabilities>,
+    /// Реестр блокчейн-бэкендов по идентификатору сети (пока только tron)
+    pub chains: Arc<ChainRegistry>,
+    pub instance_identity: Arc<InstanceIdentity>,
+    /// Монитор режима деградации (read-only БД во время failover)
+    pub degradation: DegradationMonitor,
+    /// Ключ встроенного админ UI (None - UI выключен)
+    pub admin_api_key: Option<String>,
+    /// Реестр API ключей для HTTP middleware и gRPC interceptor'а
+    pub api_keys: ApiKeyRegistry,
+    /// Rate limiter HTTP API (по API ключу с fallback на IP)
+    pub rate_limiter: RateLimiter,
+    /// JWS подписант webhook payload'ов (None - JWKS endpoint отвечает 404)
+    pub jws_signer: Option<Arc<crate::infrastructure::JwsSigner>>,
+    /// Координатор graceful shutdown (SIGTERM/SIGINT)
+    pub shutdown: Arc<crate::infrastructure::ShutdownCoordinator>,
 }
 
 impl AppState {
     /// Создание нового состояния приложения
     pub async fn new(settings: Settings) -> anyhow::Result<Self> {
+        // Глобальная политика сериализации денежных полей (HTTP и gRPC)
+        crate::utils::init_amount_policy(crate::utils::AmountSerializationPolicy {
+            as_strings: settings.serialization.amounts_as_strings,
+            precision: settings.serialization.amount_precision,
+        });
+
+        // Глобальный dry-run: broadcast и TRX переводы симулируются
+        crate::utils::init_dry_run(settings.dry_run);
+        if settings.dry_run {
+            tracing::warn!(
+                "🧪 DRY-RUN режим включен: транзакции в сеть не отправляются, \
+                 результаты симулируются и помечаются как simulated"
+            );
+        }
+
+        // 0. Идентичность инстанса: все действия узла атрибутируются ей
+        let instance_identity = InstanceIdentity::from_config(&settings.instance);
+        tracing::info!(
+            "🌍 Инстанс шлюза: {} (регион: {})",
+            instance_identity.id,
+            instance_identity.region.as_deref().unwrap_or("-")
+        );
+
+        // 0б. Координатор graceful shutdown: раздается сервисам,
+        // которым нельзя обрывать работу на полпути
+        let shutdown = Arc::new(crate::infrastructure::ShutdownCoordinator::new());
+
         // 1. Создаем пул соединений с БД
-        let db_pool = create_db_pool(&settings.database.url).await?;
+        let db_pool = create_db_pool(
+            &settings.database.url,
+            settings.database.schema.as_deref(),
+            settings.database.migration_url.as_deref(),
+        )
+        .await?;
+
+        // 1а. Шифрование чувствительных колонок на стороне БД (pgcrypto).
+        // None - режим application: колонки хранятся как раньше
+        let column_encryption = crate::infrastructure::database::ColumnEncryption::from_config(
+            db_pool.clone(),
+            &settings.database.column_encryption,
+        )?
+        .map(Arc::new);
+        if column_encryption.is_some() {
+            tracing::info!("🔐 Шифрование колонок включено (режим pgcrypto)");
+        }
 
         // 2. Создаем TRON клиент
         let tron_client = TronGridClient::new(settings.tron.clone());
+        // Завершенные дни использования TronGrid уходят в trongrid_usage_daily
+        tron_client.usage_tracker().set_persistence(db_pool.clone());
+
+        // 2а. Бэкенд подписания: локальный или внешний KMS/HSM (из конфига)
+        let signing_backend: Arc<dyn SigningBackend> = match settings.signing.backend.as_str() {
+            "remote" => {
+                let endpoint_url = settings
+                    .signing
+                    .endpoint_url
+                    .clone()
+                    .expect("проверено в Settings::validate");
+                tracing::info!("🔐 Подписание транзакций: remote KMS ({})", endpoint_url);
+                Arc::new(RemoteSigningBackend::new(
+                    endpoint_url,
+                    settings.signing.auth_token.clone(),
+                ))
+            }
+            _ => Arc::new(LocalSigningBackend::new()),
+        };
 
-        // 3. Создаем генератор кошельков
+        // 3. Создаем пул мастер-кошельков
+        let master_wallet_pool = Arc::new(MasterWalletPool::from_config(
+            &settings.tron,
+            tron_client.clone(),
+        ));
+
+        // 4. Создаем генератор кошельков
         let wallet_generator = TronWalletGenerator::new();
 
-        // 4. Создаем единый сервис комиссий
+        // 5. Создаем единый сервис комиссий
         let fee_config = FeeConfig {
             base_trx_per_transaction: settings.fees.trx_per_transaction,
             trx_to_usdt_rate: settings.fees.trx_to_usdt_rate,
@@ -44,24 +168,61 @@ impl AppState {
             min_commission_usdt: settings.fees.min_commission_usdt,
             max_commission_usdt: settings.fees.max_commission_usdt,
             dynamic_fees_enabled: true, // Включаем динамические комиссии
+            volume_tiers: settings
+                .fees
+                .volume_tiers
+                .iter()
+                .map(|tier| CommissionTier {
+                    min_volume_usdt: tier.min_volume_usdt,
+                    commission_percentage: tier.commission_percentage,
+                })
+                .collect(),
             ..Default::default()
         };
 
+        // Теневой конфиг комиссий (dry run, не влияет на списания)
+        let shadow_fee_config = settings.shadow_fees.as_ref().map(|shadow| FeeConfig {
+            base_trx_per_transaction: shadow.trx_per_transaction,
+            trx_to_usdt_rate: shadow.trx_to_usdt_rate,
+            commission_percentage: shadow.commission_percentage,
+            min_commission_usdt: shadow.min_commission_usdt,
+            max_commission_usdt: shadow.max_commission_usdt,
+            ..Default::default()
+        });
+
+        // Живой курс TRX/USDT (CoinGecko/Binance): фоновое обновление
+        // с лимитом протухания, fallback - trx_to_usdt_rate из конфига
+        let price_feed = crate::infrastructure::oracle_from_provider(&settings.fees.rate_provider)
+            .map(|oracle| {
+                let feed = Arc::new(crate::infrastructure::PriceFeed::new(
+                    oracle,
+                    settings.fees.rate_refresh_interval_secs,
+                    settings.fees.rate_max_staleness_secs,
+                ));
+                tokio::spawn(feed.clone().run_refresh_loop());
+                feed
+            });
+
         let fee_service = UnifiedFeeService::new(
             fee_config,
             tron_client.clone(),
             settings.tron.master_wallet_address.clone(),
-        );
+        )
+        .with_shadow_config(shadow_fee_config)
+        .with_db(db_pool.clone())
+        .with_price_feed(price_feed);
 
-        // 5. Создаем TRX transfer service для активации кошельков
-        let trx_transfer_service = TrxTransferService::new(tron_client.clone());
+        // 6. Создаем TRX transfer service для активации кошельков
+        // (каждая отправка записывается в trx_transfers)
+        let trx_transfer_service = TrxTransferService::new(tron_client.clone())
+            .with_persistence(db_pool.clone())
+            .with_signing_backend(signing_backend.clone());
 
-        // 6. Создаем wallet activation service (если включен в конфиге)
+        // 7. Создаем wallet activation service (если включен в конфиге)
         let wallet_activation_service = if settings.wallet.activation.enabled {
             Some(WalletActivationService::new(
                 trx_transfer_service.clone(),
-                settings.tron.master_wallet_address.clone(),
-                settings.tron.master_wallet_private_key.clone(),
+                master_wallet_pool.clone(),
                 settings.wallet.activation.amount,
                 settings.wallet.activation.enabled,
             ))
@@ -74,40 +235,342 @@ impl AppState {
             tron_client.clone(),
             wallet_generator,
             wallet_activation_service,
-        );
+        )
+        .with_column_encryption(column_encryption.clone());
+
+        // 8. Создаем диспетчер аудит-событий (no-op если не включен в конфиге)
+        let mut audit_shipper = AuditShipper::new().with_instance(instance_identity.clone());
+        if settings.audit.enabled {
+            audit_shipper = audit_shipper.with_sink(Arc::new(TracingAuditSink));
+            if let Some(endpoint_url) = &settings.audit.endpoint_url {
+                audit_shipper =
+                    audit_shipper.with_sink(Arc::new(HttpAuditSink::new(endpoint_url.clone())));
+            }
+        }
 
-        // 7. Создаем sponsor gas service для автоматической отправки TRX для газа
+        // 9. Создаем sponsor gas service для автоматической отправки TRX для газа
         let sponsor_gas_service = SponsorGasService::new(
             tron_client.clone(),
             trx_transfer_service.clone(),
             rust_decimal::Decimal::new(15, 0), // 15.0 TRX
             true,                              // включен по умолчанию
-            settings.tron.master_wallet_address.clone(),
-            settings.tron.master_wallet_private_key.clone(),
+            master_wallet_pool.clone(),
         );
 
-        let transfer_service = TransferService::new(
+        // Шина событий статусов: TransferService и мониторинг публикуют,
+        // стриминговые gRPC методы подписываются
+        let transfer_events = Arc::new(TransferEventBus::new());
+
+        let mut transfer_service = TransferService::new(
             db_pool.clone(),
             tron_client.clone(),
             fee_service.clone(),
             settings.tron.master_wallet_address.clone(),
+            master_wallet_pool.clone(),
             sponsor_gas_service,
-        );
+            audit_shipper.clone(),
+        )
+        .with_netting(settings.transfers.netting_enabled)
+        .with_column_encryption(column_encryption.clone())
+        .with_shutdown(shutdown.clone())
+        .with_sweep_destinations(settings.transfers.token_sweep_destinations.clone())
+        .with_congestion_deferral(
+            settings.transfers.congestion_deferral_enabled,
+            settings.transfers.max_deferral_minutes,
+        )
+        .with_signing_backend(signing_backend.clone())
+        .with_instance_id(instance_identity.label())
+        .with_withdrawal_allowlist(settings.transfers.withdrawal_allowlist.clone())
+        .with_event_bus(transfer_events.clone());
+
+        // Риск-скрининг адресов назначения (если включен в конфиге)
+        if settings.risk_screening.enabled {
+            if let Some(endpoint_url) = &settings.risk_screening.endpoint_url {
+                transfer_service = transfer_service.with_risk_screening(
+                    Arc::new(HttpRiskScreeningProvider::new(
+                        endpoint_url.clone(),
+                        settings.risk_screening.api_key.clone(),
+                    )),
+                    settings.risk_screening.block_threshold,
+                );
+            }
+        }
 
-        // 8. Создаем мультитокенный сервис
-        let token_registry = TokenRegistry::new(); // Инициализируем с базовыми токенами
+        // 10. Создаем мультитокенный сервис с реестром активной сети
+        let active_network = settings.tron.active_network()?;
+        tracing::info!(
+            "🌍 Активная сеть TRON: {} ({})",
+            active_network,
+            settings.tron.base_url
+        );
+        let token_registry = TokenRegistry::for_network(active_network)
+            .with_usdt_contract(&settings.tron.usdt_contract);
         let trc20_service_config = Trc20ServiceConfig::default();
-        let trc20_service = Trc20TokenService::new(
-            settings.tron.clone(),
-            trc20_service_config,
-            token_registry,
+        let trc20_service = Arc::new(
+            Trc20TokenService::new(settings.tron.clone(), trc20_service_config, token_registry)
+                .with_db(db_pool.clone())
+                .with_sweep_destinations(settings.transfers.token_sweep_destinations.clone()),
+        );
+
+        // Загружаем сохраненные токены из БД (добавленные через admin API)
+        trc20_service.load_tokens_from_db().await?;
+
+        // Выводы не-USDT токенов идут тем же пайплайном обработки -
+        // трансферный сервис берет контракты из реестра токенов
+        transfer_service = transfer_service.with_token_service(trc20_service.clone());
+
+        // 11. Создаем сервис проекции балансов
+        let balance_service = BalanceService::new(db_pool.clone(), tron_client.clone());
+
+        // 12. Создаем сервис платежных намерений с допусками недоплаты
+        let payment_intent_service = Arc::new(
+            PaymentIntentService::new(db_pool.clone()).with_amount_tolerance(
+                settings.payment_intents.amount_tolerance_absolute,
+                settings.payment_intents.amount_tolerance_percent,
+            ),
+        );
+
+        // 13. Создаем сервис wallet-scoped API токенов
+        let wallet_token_service = WalletTokenService::new(db_pool.clone());
+
+        // 13а. Сервис экспорта персистентного лога webhook событий
+        let webhook_event_service = WebhookEventService::new(db_pool.clone());
+
+        // 13а'. Сервис реплея анонимизированных событий в staging мерчанта
+        let webhook_replay_service = WebhookReplayService::new(db_pool.clone());
+
+        // 13б. JWS подписант webhook payload'ов (ES256, публичные ключи
+        // публикуются в /.well-known/jwks.json)
+        let jws_signer =
+            crate::infrastructure::JwsSigner::from_config(&settings.webhooks.jws)?.map(Arc::new);
+
+        // 13в. Сервис доставки webhook'ов с персистентным outbox'ом
+        let webhook_service = settings.webhooks.url.as_ref().map(|url| {
+            let mut service = WebhookService::new(WebhookConfig {
+                enabled: settings.webhooks.enabled,
+                url: url.clone(),
+                timeout_seconds: settings.webhooks.timeout_seconds,
+                secret_key: settings.webhooks.secret_key.clone(),
+                require_verification: settings.webhooks.require_verification,
+                egress: settings.webhooks.egress.clone(),
+                ..WebhookConfig::default()
+            })
+            .with_persistence(db_pool.clone())
+            .with_column_encryption(column_encryption.clone());
+
+            if let Some(signer) = &jws_signer {
+                service = service.with_jws_signer(signer.clone());
+            }
+
+            Arc::new(service)
+        });
+
+        // 14. Создаем сервис мониторинга входящих транзакций.
+        // Мастер-кошельки пула помечаются как internal источники депозитов
+        let source_labeler = Arc::new(
+            DepositSourceLabeler::from_config(&settings.deposit_labeling)
+                .with_internal_addresses(master_wallet_pool.addresses()),
+        );
+
+        // Маршрутизация депозитов: правила разложения per-wallet
+        // (95% мастеру, 5% партнеру и т.п.)
+        let deposit_split_service = Arc::new(DepositSplitService::new(
+            db_pool.clone(),
+            settings.tron.master_wallet_address.clone(),
+        ));
+
+        // Инвойсы: платежные запросы с истечением, депозиты засчитывает хук
+        let mut invoice_service = InvoiceService::new(db_pool.clone());
+        if let Some(webhook_service) = &webhook_service {
+            invoice_service = invoice_service.with_webhooks(webhook_service.clone());
+        }
+        let invoice_service = Arc::new(invoice_service);
+
+        // Реестр хуков пост-обработки депозитов. Деплойменты регистрируют
+        // здесь свои действия (авто-форвардинг, CRM, лояльность) через .register()
+        let deposit_hooks = Arc::new(
+            DepositHookRegistry::new()
+                .register(Arc::new(DepositSplitHook::new(
+                    deposit_split_service.clone(),
+                )))
+                .register(Arc::new(InvoicePaymentHook::new(invoice_service.clone()))),
+        );
+
+        let monitoring_service = TransactionMonitoringService::new(
+            db_pool.clone(),
+            tron_client.clone(),
+            settings.tron.usdt_contract.clone(),
+            true,
+        )
+        .with_payment_intents(payment_intent_service.clone())
+        .with_source_labeler(source_labeler)
+        .with_deposit_hooks(deposit_hooks)
+        .with_event_bus(transfer_events.clone());
+
+        // 14а. Сервис восстановления потерянных связей с on-chain транзакциями
+        let recovery_service = OrphanRecoveryService::new(
+            db_pool.clone(),
+            tron_client.clone(),
+            settings.tron.usdt_contract.clone(),
+            master_wallet_pool.addresses(),
         );
 
+        // 15. Создаем faucet сервис для sandbox окружений
+        let faucet_service = FaucetService::new(
+            tron_client.clone(),
+            trx_transfer_service.clone(),
+            master_wallet_pool.clone(),
+            settings.faucet.clone(),
+            active_network.is_test(),
+        )
+        .with_signing_backend(signing_backend.clone());
+
+        // 15а. Генератор QR кодов платежных URI для фронтендов мерчантов
+        let qr_service =
+            QrCodeService::new(settings.qr.clone(), settings.tron.usdt_contract.clone());
+
+        // 15б. Реестр блокчейн-бэкендов: TRON за трейтом Blockchain.
+        // Будущие сети (TON/ETH) добавляются регистрацией своих адаптеров
+        let chains = Arc::new(ChainRegistry::new(CHAIN_ID_TRON).register(Arc::new(
+            TronBlockchain::new(tron_client.clone(), active_network, signing_backend),
+        )));
+
+        // 16. Снимок возможностей шлюза для feature-detection клиентов
+        let capabilities = GatewayCapabilities {
+            version: crate::VERSION.to_string(),
+            network: active_network.to_string(),
+            chains: chains.network_ids(),
+            grpc_enabled: settings.grpc.enabled,
+            payment_intents_enabled: true,
+            refund_addresses_supported: true,
+            netting_enabled: settings.transfers.netting_enabled,
+            gas_sponsorship_enabled: settings.gas_sponsorship.enabled,
+            webhook_events_version: "v1".to_string(),
+            amounts: AmountLimits {
+                // Лимиты TronValidator::validate_amount
+                min_amount: rust_decimal::Decimal::new(1, 6),
+                max_amount: rust_decimal::Decimal::new(1_000_000_000, 6),
+                max_decimals: 6,
+            },
+            confirmation_policy: ConfirmationPolicy {
+                processing_confirmations: 1,
+                completed_confirmations: 19,
+            },
+            api_versioning: ApiVersioningPolicy {
+                current: "v1".to_string(),
+                supported: vec!["v1".to_string()],
+                unversioned_alias_deprecated: true,
+                unversioned_alias_sunset: crate::infrastructure::middleware::UNVERSIONED_API_SUNSET
+                    .to_string(),
+            },
+        };
+
+        // 17. Сервис консолидации средств: делит TransferService с HTTP/gRPC
+        // и держит собственный экземпляр спонсорства газа
+        let transfer_service = Arc::new(transfer_service);
+        let sweep_service = Arc::new(SweepService::new(
+            db_pool.clone(),
+            tron_client.clone(),
+            transfer_service.clone(),
+            SponsorGasService::new(
+                tron_client.clone(),
+                trx_transfer_service.clone(),
+                settings.gas_sponsorship.min_trx_amount,
+                settings.gas_sponsorship.enabled,
+                master_wallet_pool.clone(),
+            ),
+            settings.sweep.clone(),
+        ));
+
+        // 17а. Сервис массовой загрузки: очередь NDJSON строк поверх
+        // того же TransferService
+        let ingestion_service = Arc::new(TransferIngestionService::new(
+            db_pool.clone(),
+            transfer_service.clone(),
+        ));
+
+        // 17б. Пороговые подписки на балансы: webhook при пересечении
+        let mut balance_alert_service = BalanceAlertService::new(
+            db_pool.clone(),
+            tron_client.clone(),
+            settings.tron.master_wallet_address.clone(),
+        );
+        if let Some(webhook_service) = &webhook_service {
+            balance_alert_service = balance_alert_service.with_webhooks(webhook_service.clone());
+        }
+        let balance_alert_service = Arc::new(balance_alert_service);
+
+        // 17в. TRC-20 разрешения для approve/transferFrom флоу
+        let allowance_service = Arc::new(AllowanceService::new(
+            db_pool.clone(),
+            tron_client.clone(),
+            settings.tron.usdt_contract.clone(),
+            settings.tron.master_wallet_address.clone(),
+        ));
+
+        // 17г. Rate limiter HTTP API: по ключу X-Api-Key с fallback на IP;
+        // при заданном redis_url счетчики делятся между инстансами
+        let mut rate_limiter = RateLimiter::from_config(&settings.rate_limit, &settings.auth);
+        if let Some(redis_url) = &settings.rate_limit.redis_url {
+            rate_limiter = rate_limiter.with_redis(redis_url).await?;
+            tracing::info!("📊 Rate limiter использует общие счетчики в Redis");
+        }
+
         Ok(Self {
             wallet_service: Arc::new(wallet_service),
-            transfer_service: Arc::new(transfer_service),
+            transfer_service,
             fee_service: Arc::new(fee_service),
-            trc20_service: Arc::new(trc20_service),
+            trc20_service,
+            balance_service: Arc::new(balance_service),
+            payment_intent_service,
+            wallet_token_service: Arc::new(wallet_token_service),
+            webhook_event_service: Arc::new(webhook_event_service),
+            webhook_replay_service: Arc::new(webhook_replay_service),
+            webhook_service,
+            monitoring_service: Arc::new(monitoring_service),
+            recovery_service: Arc::new(recovery_service),
+            sweep_service,
+            ingestion_service,
+            balance_alert_service,
+            allowance_service,
+            invoice_service,
+            deposit_split_service,
+            transfer_events,
+            scheduler_run_log: Arc::new(SchedulerRunLog::new(db_pool.clone())),
+            faucet_service: Arc::new(faucet_service),
+            qr_service: Arc::new(qr_service),
+            trx_transfer_service: Arc::new(trx_transfer_service),
+            capabilities: Arc::new(capabilities),
+            chains,
+            instance_identity: Arc::new(instance_identity),
+            degradation: DegradationMonitor::new(),
+            admin_api_key: settings.server.admin_api_key.clone(),
+            api_keys: ApiKeyRegistry::from_config(&settings.auth),
+            rate_limiter,
+            jws_signer,
+            shutdown,
         })
     }
+
+    /// Координированное завершение процесса: новая работа перестает
+    /// браться, начатые трансферы досчитываются (с таймаутом), очередь
+    /// webhook'ов доставляется. Пул БД закрывается при Drop состояния -
+    /// после этого метода процесс может выходить
+    pub async fn shutdown_gracefully(&self, timeout: std::time::Duration) {
+        self.shutdown.begin_shutdown();
+
+        // Дожидаемся in-flight трансферов: обрыв между спонсированием
+        // TRX и broadcast'ом USDT оставил бы перевод наполовину сделанным
+        if self.shutdown.drain(timeout).await {
+            tracing::info!("✅ Все in-flight операции завершены");
+        }
+
+        // Доставляем накопленные webhook'и: очередь in-memory,
+        // недоставленное останется только в персистентном outbox'е
+        if let Some(webhook_service) = &self.webhook_service {
+            webhook_service.flush_queue(timeout).await;
+        }
+
+        tracing::info!("🛑 Graceful завершение закончено, пул БД закрывается");
+    }
 }
@@ -0,0 +1,177 @@
+//! # Recovery CLI для осиротевших on-chain транзакций
+//!
+//! Иногда broadcast проходит, но хэш теряется до записи в БД.
+//! CLI сканирует on-chain историю мастер-кошельков и кошельков
+//! отправителей, показывает кандидатов для каждого трансфера без
+//! tx_hash и интерактивно привязывает выбранный хэш.
+//!
+//! Запуск (окно поиска в часах - опциональный аргумент, по умолчанию 24):
+//!
+//! ```text
+//! cargo run --bin recover_orphans -- 48
+//! ```
+//!
+//! Тот же функционал доступен через admin API:
+//! GET /api/recovery/orphans и POST /api/recovery/orphans/{id}/link
+
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+use tron_gateway_rust::{
+    application::services::{MasterWalletPool, OrphanRecoveryService, OrphanedTransferReport},
+    infrastructure::{database::create_db_pool, TronGridClient},
+    Settings,
+};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Warn-уровень: интерактивный вывод не должен тонуть в логах сервисов
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::WARN)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let window_hours: i64 = std::env::args()
+        .nth(1)
+        .map(|arg| {
+            arg.parse()
+                .map_err(|_| anyhow::anyhow!("Окно поиска должно быть числом часов: {}", arg))
+        })
+        .transpose()?
+        .unwrap_or(24);
+
+    let settings =
+        Settings::new().map_err(|e| anyhow::anyhow!("Ошибка загрузки конфигурации: {}", e))?;
+
+    let db_pool = create_db_pool(
+        &settings.database.url,
+        settings.database.schema.as_deref(),
+        settings.database.migration_url.as_deref(),
+    )
+    .await?;
+
+    let tron_client = TronGridClient::new(settings.tron.clone());
+    let master_pool = MasterWalletPool::from_config(&settings.tron, tron_client.clone());
+
+    let recovery_service = OrphanRecoveryService::new(
+        db_pool,
+        tron_client,
+        settings.tron.usdt_contract.clone(),
+        master_pool.addresses(),
+    );
+
+    println!(
+        "🔍 Скан осиротевших трансферов за последние {} ч...",
+        window_hours
+    );
+    let reports = recovery_service
+        .scan_orphaned_transfers(window_hours)
+        .await?;
+
+    if reports.is_empty() {
+        println!("✅ Осиротевших трансферов не найдено");
+        return Ok(());
+    }
+
+    println!("Найдено осиротевших трансферов: {}\n", reports.len());
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut linked = 0usize;
+
+    for report in &reports {
+        print_report(report);
+
+        if report.candidates.is_empty() {
+            println!("   Кандидатов на цепочке не найдено - пропуск\n");
+            continue;
+        }
+
+        match prompt_choice(&mut lines, report.candidates.len())? {
+            Choice::Candidate(index) => {
+                let tx_hash = &report.candidates[index].tx_hash;
+                match recovery_service
+                    .link_transfer(report.transfer_id, tx_hash)
+                    .await
+                {
+                    Ok(_) => {
+                        linked += 1;
+                        println!("   ✅ Привязано: {}\n", tx_hash);
+                    }
+                    Err(e) => println!("   ❌ Ошибка привязки: {}\n", e),
+                }
+            }
+            Choice::Skip => println!("   Пропущено\n"),
+            Choice::Quit => break,
+        }
+    }
+
+    println!("Готово: привязано {} из {}", linked, reports.len());
+    Ok(())
+}
+
+/// Решение оператора по одному трансферу
+enum Choice {
+    /// Привязать кандидата с указанным индексом
+    Candidate(usize),
+    Skip,
+    Quit,
+}
+
+/// Печатает трансфер и его on-chain кандидатов
+fn print_report(report: &OrphanedTransferReport) {
+    println!(
+        "Трансфер #{} [{}]: {} USDT на {} (создан {})",
+        report.transfer_id,
+        report.status,
+        report.amount,
+        report.to_address,
+        report.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    for (index, candidate) in report.candidates.iter().enumerate() {
+        println!(
+            "   [{}] {} - {} USDT от {} в {}{}",
+            index + 1,
+            candidate.tx_hash,
+            candidate.amount,
+            candidate.from_address,
+            candidate.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            if candidate.exact_amount_match {
+                ""
+            } else {
+                " (сумма за вычетом комиссии)"
+            }
+        );
+    }
+}
+
+/// Запрашивает у оператора номер кандидата, s (пропустить) или q (выход)
+fn prompt_choice(
+    lines: &mut impl Iterator<Item = std::io::Result<String>>,
+    candidates: usize,
+) -> Result<Choice> {
+    loop {
+        print!("   Привязать? [1-{}/s/q]: ", candidates);
+        std::io::stdout().flush()?;
+
+        let Some(line) = lines.next() else {
+            // EOF (не-интерактивный запуск) - выходим без привязок
+            return Ok(Choice::Quit);
+        };
+
+        match line?.trim().to_ascii_lowercase().as_str() {
+            "s" | "" => return Ok(Choice::Skip),
+            "q" => return Ok(Choice::Quit),
+            input => match input.parse::<usize>() {
+                Ok(number) if (1..=candidates).contains(&number) => {
+                    return Ok(Choice::Candidate(number - 1))
+                }
+                _ => println!("   Непонятный ввод: {}", input),
+            },
+        }
+    }
+}
@@ -16,4 +16,18 @@ pub struct BlockchainTransaction {
     pub amount: Decimal,
     pub timestamp: DateTime<Utc>,
     pub confirmations: u32,
+    /// Символ токена (из token_info TronGrid), None - неизвестен
+    pub token_symbol: Option<String>,
+    /// Адрес TRC-20 контракта токена
+    pub contract_address: Option<String>,
+}
+
+/// Исход транзакции на цепочке (из gettransactioninfobyid)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub tx_hash: String,
+    /// Блок, в который попала транзакция (None - еще в мемпуле)
+    pub block_number: Option<i64>,
+    /// Успех выполнения: false при REVERT или OUT_OF_ENERGY
+    pub success: bool,
 }
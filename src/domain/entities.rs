@@ -3,6 +3,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use super::enums::TransactionStatus;
+use super::errors::{DomainError, DomainResult};
+use super::validation::TronValidator;
 
 /// Custodial кошелек для приема TRC-20 токенов на сети TRON
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,27 +85,96 @@ pub struct IncomingTransaction {
 }
 
 impl IncomingTransaction {
-    /// Создает новую входящую транзакцию
-    pub fn new(
+    /// Начинает построение входящей транзакции.
+    /// Инварианты (положительная сумма, непустой хэш, валидные адреса)
+    /// проверяются в build()
+    pub fn builder(
         wallet_id: i64,
         tx_hash: String,
         from_address: String,
         to_address: String,
         amount: Decimal,
-    ) -> Self {
-        Self {
-            id: None,
+    ) -> IncomingTransactionBuilder {
+        IncomingTransactionBuilder {
             wallet_id,
             tx_hash,
-            block_number: None,
             from_address,
             to_address,
             amount,
+            block_number: None,
             status: TransactionStatus::Pending,
             error_message: None,
+        }
+    }
+}
+
+/// Builder входящей транзакции с проверкой доменных инвариантов
+#[derive(Debug, Clone)]
+pub struct IncomingTransactionBuilder {
+    wallet_id: i64,
+    tx_hash: String,
+    from_address: String,
+    to_address: String,
+    amount: Decimal,
+    block_number: Option<i64>,
+    status: TransactionStatus,
+    error_message: Option<String>,
+}
+
+impl IncomingTransactionBuilder {
+    /// Номер блока (известен для транзакций вне мемпула)
+    pub fn with_block_number(mut self, block_number: Option<i64>) -> Self {
+        self.block_number = block_number;
+        self
+    }
+
+    /// Статус обработки (по умолчанию Pending)
+    pub fn with_status(mut self, status: TransactionStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Сообщение об ошибке для Failed транзакций
+    pub fn with_error_message(mut self, error_message: Option<String>) -> Self {
+        self.error_message = error_message;
+        self
+    }
+
+    /// Собирает транзакцию, проверяя инварианты:
+    /// положительная сумма, непустой хэш, валидный адрес получателя,
+    /// Completed возможен только для транзакции в блоке
+    pub fn build(self) -> DomainResult<IncomingTransaction> {
+        TronValidator::validate_amount(self.amount)?;
+        TronValidator::validate_address(&self.to_address)?;
+
+        if self.tx_hash.trim().is_empty() {
+            return Err(DomainError::InvariantViolation {
+                message: "Входящая транзакция без хэша".to_string(),
+            });
+        }
+
+        if self.status == TransactionStatus::Completed && self.block_number.is_none() {
+            return Err(DomainError::InvariantViolation {
+                message: format!(
+                    "Транзакция {} не может быть Completed без номера блока",
+                    self.tx_hash
+                ),
+            });
+        }
+
+        Ok(IncomingTransaction {
+            id: None,
+            wallet_id: self.wallet_id,
+            tx_hash: self.tx_hash,
+            block_number: self.block_number,
+            from_address: self.from_address,
+            to_address: self.to_address,
+            amount: self.amount,
+            status: self.status,
+            error_message: self.error_message,
             detected_at: Utc::now(),
             confirmed_at: None,
-        }
+        })
     }
 }
 
@@ -142,38 +213,214 @@ pub struct OutgoingTransfer {
 }
 
 impl OutgoingTransfer {
-    /// Создает новый исходящий трансфер
-    pub fn new(
+    /// Начинает построение исходящего трансфера.
+    /// Инварианты (положительная сумма, валидный адрес, Completed только
+    /// с хэшем) проверяются в build()
+    pub fn builder(
         from_wallet_id: i64,
         to_address: String,
         amount: Decimal,
-        reference_id: Option<String>,
-    ) -> Self {
-        Self {
-            id: None,
+    ) -> OutgoingTransferBuilder {
+        OutgoingTransferBuilder {
             from_wallet_id,
             to_address,
             amount,
             status: TransactionStatus::Pending,
             tx_hash: None,
-            reference_id,
+            reference_id: None,
             error_message: None,
-            created_at: Utc::now(),
-            completed_at: None,
         }
     }
 
-    /// Помечает трансфер как завершенный с указанием хэша транзакции
-    pub fn complete_with_hash(&mut self, tx_hash: String) {
-        self.status = TransactionStatus::Completed;
+    /// Помечает трансфер как завершенный с указанием хэша транзакции.
+    /// Переход проверяется через state machine, хэш не может быть пустым
+    pub fn complete_with_hash(&mut self, tx_hash: String) -> DomainResult<()> {
+        if tx_hash.trim().is_empty() {
+            return Err(DomainError::InvariantViolation {
+                message: "Завершение трансфера без хэша транзакции".to_string(),
+            });
+        }
+
+        self.status = self
+            .status
+            .transition_to(TransactionStatus::Completed)
+            .map_err(|e| DomainError::InvariantViolation {
+                message: e.to_string(),
+            })?;
         self.tx_hash = Some(tx_hash);
         self.completed_at = Some(Utc::now());
+        Ok(())
     }
 
     /// Помечает трансфер как неудачный с указанием ошибки
-    pub fn fail_with_error(&mut self, error: String) {
-        self.status = TransactionStatus::Failed;
+    pub fn fail_with_error(&mut self, error: String) -> DomainResult<()> {
+        self.status = self
+            .status
+            .transition_to(TransactionStatus::Failed)
+            .map_err(|e| DomainError::InvariantViolation {
+                message: e.to_string(),
+            })?;
         self.error_message = Some(error);
         self.completed_at = Some(Utc::now());
+        Ok(())
+    }
+}
+
+/// Builder исходящего трансфера с проверкой доменных инвариантов
+#[derive(Debug, Clone)]
+pub struct OutgoingTransferBuilder {
+    from_wallet_id: i64,
+    to_address: String,
+    amount: Decimal,
+    status: TransactionStatus,
+    tx_hash: Option<String>,
+    reference_id: Option<String>,
+    error_message: Option<String>,
+}
+
+impl OutgoingTransferBuilder {
+    /// Начальный статус (по умолчанию Pending)
+    pub fn with_status(mut self, status: TransactionStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Хэш транзакции (обязателен для Completed)
+    pub fn with_tx_hash(mut self, tx_hash: Option<String>) -> Self {
+        self.tx_hash = tx_hash;
+        self
+    }
+
+    /// Референс заказа e-commerce системы
+    pub fn with_reference_id(mut self, reference_id: Option<String>) -> Self {
+        self.reference_id = reference_id;
+        self
+    }
+
+    /// Сообщение об ошибке (для Failed трансферов)
+    pub fn with_error_message(mut self, error_message: Option<String>) -> Self {
+        self.error_message = error_message;
+        self
+    }
+
+    /// Собирает трансфер, проверяя инварианты:
+    /// положительная сумма в пределах лимитов, валидный TRON адрес,
+    /// Completed возможен только с непустым хэшем
+    pub fn build(self) -> DomainResult<OutgoingTransfer> {
+        TronValidator::validate_amount(self.amount)?;
+        TronValidator::validate_address(&self.to_address)?;
+
+        if self.status == TransactionStatus::Completed
+            && self
+                .tx_hash
+                .as_ref()
+                .is_none_or(|hash| hash.trim().is_empty())
+        {
+            return Err(DomainError::InvariantViolation {
+                message: "Трансфер не может быть Completed без хэша транзакции".to_string(),
+            });
+        }
+
+        Ok(OutgoingTransfer {
+            id: None,
+            from_wallet_id: self.from_wallet_id,
+            to_address: self.to_address,
+            amount: self.amount,
+            status: self.status,
+            tx_hash: self.tx_hash,
+            reference_id: self.reference_id,
+            error_message: self.error_message,
+            created_at: Utc::now(),
+            completed_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_ADDRESS: &str = "TH3QBLNLsimQbNwq2DxTGhoDYeeCZYTvK3";
+
+    #[test]
+    fn test_outgoing_builder_rejects_negative_amount() {
+        let result =
+            OutgoingTransfer::builder(1, VALID_ADDRESS.to_string(), Decimal::new(-100, 2)).build();
+
+        assert!(matches!(result, Err(DomainError::InvalidAmount { .. })));
+    }
+
+    #[test]
+    fn test_outgoing_builder_rejects_completed_without_hash() {
+        let result = OutgoingTransfer::builder(1, VALID_ADDRESS.to_string(), Decimal::ONE)
+            .with_status(TransactionStatus::Completed)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(DomainError::InvariantViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_outgoing_builder_accepts_valid_transfer() {
+        let transfer = OutgoingTransfer::builder(1, VALID_ADDRESS.to_string(), Decimal::ONE)
+            .with_reference_id(Some("order-1".to_string()))
+            .build()
+            .unwrap();
+
+        assert_eq!(transfer.status, TransactionStatus::Pending);
+        assert_eq!(transfer.reference_id.as_deref(), Some("order-1"));
+    }
+
+    #[test]
+    fn test_complete_with_hash_enforces_state_machine() {
+        let mut transfer = OutgoingTransfer::builder(1, VALID_ADDRESS.to_string(), Decimal::ONE)
+            .build()
+            .unwrap();
+
+        // Пустой хэш отклоняется
+        assert!(transfer.complete_with_hash("  ".to_string()).is_err());
+
+        transfer.complete_with_hash("abc123".to_string()).unwrap();
+        assert_eq!(transfer.status, TransactionStatus::Completed);
+
+        // Повторное завершение - нелегальный переход
+        assert!(transfer.complete_with_hash("def456".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_incoming_builder_rejects_empty_hash() {
+        let result = IncomingTransaction::builder(
+            1,
+            String::new(),
+            VALID_ADDRESS.to_string(),
+            VALID_ADDRESS.to_string(),
+            Decimal::ONE,
+        )
+        .build();
+
+        assert!(matches!(
+            result,
+            Err(DomainError::InvariantViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_incoming_builder_rejects_completed_without_block() {
+        let result = IncomingTransaction::builder(
+            1,
+            "abc123".to_string(),
+            VALID_ADDRESS.to_string(),
+            VALID_ADDRESS.to_string(),
+            Decimal::ONE,
+        )
+        .with_status(TransactionStatus::Completed)
+        .build();
+
+        assert!(matches!(
+            result,
+            Err(DomainError::InvariantViolation { .. })
+        ));
     }
 }
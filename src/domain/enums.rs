@@ -16,6 +16,64 @@ pub enum TransactionStatus {
     Cancelled,
 }
 
+impl TransactionStatus {
+    /// Каноническое представление статуса в БД (UPPERCASE)
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "PENDING",
+            Self::Processing => "PROCESSING",
+            Self::Completed => "COMPLETED",
+            Self::Failed => "FAILED",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
+
+    /// Парсит статус из БД, принимая и легаси-представления
+    /// ("Pending", "Confirmed", "CONFIRMED" и т.д.)
+    pub fn from_db_str(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "PENDING" => Some(Self::Pending),
+            "PROCESSING" => Some(Self::Processing),
+            // CONFIRMED - легаси-статус исходящих трансферов
+            "COMPLETED" | "CONFIRMED" => Some(Self::Completed),
+            "FAILED" => Some(Self::Failed),
+            "CANCELLED" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Терминальный ли статус (переходы из него запрещены)
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+
+    /// Допустим ли переход в статус `next`
+    pub fn can_transition_to(&self, next: &Self) -> bool {
+        match self {
+            Self::Pending => matches!(
+                next,
+                Self::Processing | Self::Completed | Self::Failed | Self::Cancelled
+            ),
+            Self::Processing => {
+                matches!(next, Self::Completed | Self::Failed | Self::Cancelled)
+            }
+            // Из терминальных статусов переходов нет
+            Self::Completed | Self::Failed | Self::Cancelled => false,
+        }
+    }
+
+    /// Выполняет переход в статус `next`, отклоняя недопустимые переходы
+    pub fn transition_to(&self, next: Self) -> Result<Self, super::DomainError> {
+        if self.can_transition_to(&next) {
+            Ok(next)
+        } else {
+            Err(super::DomainError::ConfigurationError {
+                message: format!("Недопустимый переход статуса: {} -> {}", self, next),
+            })
+        }
+    }
+}
+
 impl Default for TransactionStatus {
     fn default() -> Self {
         Self::Pending
@@ -24,12 +82,150 @@ impl Default for TransactionStatus {
 
 impl std::fmt::Display for TransactionStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_db_str())
+    }
+}
+
+/// Сеть TRON, с которой работает шлюз.
+/// Endpoint TronGrid, адрес USDT контракта и префиксный байт адресов
+/// резолвятся через сеть, а не через разбросанные константы
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    /// Основная сеть
+    #[default]
+    Mainnet,
+    /// Тестовая сеть Shasta
+    Shasta,
+    /// Тестовая сеть Nile
+    Nile,
+}
+
+impl Network {
+    /// Парсит имя сети из конфига (без учета регистра)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "mainnet" => Some(Self::Mainnet),
+            "shasta" => Some(Self::Shasta),
+            "nile" => Some(Self::Nile),
+            _ => None,
+        }
+    }
+
+    /// Каноническое имя сети (как в конфиге)
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::Pending => write!(f, "PENDING"),
-            Self::Processing => write!(f, "PROCESSING"),
-            Self::Completed => write!(f, "COMPLETED"),
-            Self::Failed => write!(f, "FAILED"),
-            Self::Cancelled => write!(f, "CANCELLED"),
+            Self::Mainnet => "mainnet",
+            Self::Shasta => "shasta",
+            Self::Nile => "nile",
         }
     }
+
+    /// Тестовая ли сеть (средства не имеют ценности)
+    pub fn is_test(&self) -> bool {
+        !matches!(self, Self::Mainnet)
+    }
+
+    /// Префиксный байт base58 адресов. Все текущие сети TRON используют
+    /// 0x41, но валидация берет байт отсюда - будущие сети с другим
+    /// префиксом добавляются в одном месте
+    pub fn address_prefix(&self) -> u8 {
+        match self {
+            Self::Mainnet | Self::Shasta | Self::Nile => 0x41,
+        }
+    }
+
+    /// Дефолтный endpoint TronGrid для сети
+    pub fn default_base_url(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "https://api.trongrid.io",
+            Self::Shasta => "https://api.shasta.trongrid.io",
+            Self::Nile => "https://nile.trongrid.io",
+        }
+    }
+
+    /// Дефолтный адрес USDT контракта в сети
+    pub fn default_usdt_contract(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t",
+            Self::Shasta => "TG3XXyExBkPp9nzdajDZsozEu4BkaSJozs",
+            Self::Nile => "TXYZopYRdj2D9XRtbG411XZZ3kM5VkAeBf",
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_db_str_accepts_legacy_values() {
+        assert_eq!(
+            TransactionStatus::from_db_str("Pending"),
+            Some(TransactionStatus::Pending)
+        );
+        assert_eq!(
+            TransactionStatus::from_db_str("CONFIRMED"),
+            Some(TransactionStatus::Completed)
+        );
+        assert_eq!(
+            TransactionStatus::from_db_str("Completed"),
+            Some(TransactionStatus::Completed)
+        );
+        assert_eq!(TransactionStatus::from_db_str("UNKNOWN"), None);
+    }
+
+    #[test]
+    fn test_legal_transitions() {
+        assert!(TransactionStatus::Pending
+            .transition_to(TransactionStatus::Processing)
+            .is_ok());
+        assert!(TransactionStatus::Processing
+            .transition_to(TransactionStatus::Completed)
+            .is_ok());
+        assert!(TransactionStatus::Pending
+            .transition_to(TransactionStatus::Failed)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_network_from_name() {
+        assert_eq!(Network::from_name("mainnet"), Some(Network::Mainnet));
+        assert_eq!(Network::from_name("Shasta"), Some(Network::Shasta));
+        assert_eq!(Network::from_name("NILE"), Some(Network::Nile));
+        assert_eq!(Network::from_name("ropsten"), None);
+    }
+
+    #[test]
+    fn test_network_defaults() {
+        assert!(!Network::Mainnet.is_test());
+        assert!(Network::Shasta.is_test());
+        assert_eq!(Network::Mainnet.address_prefix(), 0x41);
+        assert_eq!(Network::Nile.default_base_url(), "https://nile.trongrid.io");
+        assert_ne!(
+            Network::Mainnet.default_usdt_contract(),
+            Network::Shasta.default_usdt_contract()
+        );
+    }
+
+    #[test]
+    fn test_illegal_transitions_rejected() {
+        // Терминальные статусы не меняются
+        assert!(TransactionStatus::Completed
+            .transition_to(TransactionStatus::Pending)
+            .is_err());
+        assert!(TransactionStatus::Failed
+            .transition_to(TransactionStatus::Completed)
+            .is_err());
+        // Откат из Processing в Pending запрещен
+        assert!(TransactionStatus::Processing
+            .transition_to(TransactionStatus::Pending)
+            .is_err());
+    }
 }
@@ -10,9 +10,9 @@ pub enum DomainError {
     WalletNotFoundByAddress { address: String },
 
     #[error("Недостаточно средств: требуется {required}, доступно {available}")]
-    InsufficientBalance { 
-        required: rust_decimal::Decimal, 
-        available: rust_decimal::Decimal 
+    InsufficientBalance {
+        required: rust_decimal::Decimal,
+        available: rust_decimal::Decimal,
     },
 
     #[error("Неверный адрес TRON: {address}")]
@@ -32,6 +32,30 @@ pub enum DomainError {
 
     #[error("Ошибка конфигурации: {message}")]
     ConfigurationError { message: String },
+
+    #[error("Нарушение доменного инварианта: {message}")]
+    InvariantViolation { message: String },
+}
+
+impl DomainError {
+    /// Стабильный машиночитаемый код ошибки. Общий для HTTP
+    /// (поле code в теле ответа) и gRPC (metadata x-error-code) -
+    /// клиенты матчатся по коду, а не по тексту сообщения
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::WalletNotFound { .. } | Self::WalletNotFoundByAddress { .. } => {
+                "wallet_not_found"
+            }
+            Self::InsufficientBalance { .. } => "insufficient_balance",
+            Self::InvalidTronAddress { .. } => "invalid_address",
+            Self::InvalidAmount { .. } => "invalid_amount",
+            Self::TransferNotFound { .. } => "transfer_not_found",
+            Self::TransactionAlreadyExists { .. } => "transaction_already_exists",
+            Self::CryptoError { .. } => "crypto_error",
+            Self::ConfigurationError { .. } => "configuration_error",
+            Self::InvariantViolation { .. } => "invariant_violation",
+        }
+    }
 }
 
 /// Результат операций доменного слоя
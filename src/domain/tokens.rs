@@ -6,6 +6,8 @@ use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::enums::Network;
+
 /// Информация о TRC-20 токене
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenInfo {
@@ -136,6 +138,35 @@ impl TokenRegistry {
         }
     }
 
+    /// Создает реестр для конкретной сети: USDT получает контракт
+    /// этой сети, токены с только-mainnet контрактами в тестовых
+    /// сетях отключаются
+    pub fn for_network(network: Network) -> Self {
+        let mut registry = Self::new();
+
+        if let Some(usdt) = registry.tokens.get_mut("USDT") {
+            usdt.contract_address = network.default_usdt_contract().to_string();
+        }
+
+        if network.is_test() {
+            for token in registry.tokens.values_mut() {
+                if token.symbol != "USDT" {
+                    token.enabled = false;
+                }
+            }
+        }
+
+        registry
+    }
+
+    /// Переопределяет контракт USDT (из конфига активной сети)
+    pub fn with_usdt_contract(mut self, contract_address: &str) -> Self {
+        if let Some(usdt) = self.tokens.get_mut("USDT") {
+            usdt.contract_address = contract_address.to_string();
+        }
+        self
+    }
+
     /// Получает информацию о токене по символу
     pub fn get_token(&self, symbol: &str) -> Option<&TokenInfo> {
         self.tokens.get(symbol)
@@ -270,6 +301,28 @@ mod tests {
         assert_eq!(token_by_contract.unwrap().symbol, "USDT");
     }
 
+    #[test]
+    fn test_registry_for_network() {
+        let registry = TokenRegistry::for_network(Network::Nile);
+        let usdt = registry.get_primary_token();
+        assert_eq!(usdt.contract_address, Network::Nile.default_usdt_contract());
+        assert!(usdt.enabled);
+
+        // В тестовых сетях токены с mainnet-контрактами отключены
+        assert!(registry
+            .get_enabled_tokens()
+            .iter()
+            .all(|t| t.symbol == "USDT"));
+
+        // Переопределение контракта из конфига
+        let registry = TokenRegistry::for_network(Network::Shasta)
+            .with_usdt_contract("TH3QBLNLsimQbNwq2DxTGhoDYeeCZYTvK3");
+        assert_eq!(
+            registry.get_primary_token().contract_address,
+            "TH3QBLNLsimQbNwq2DxTGhoDYeeCZYTvK3"
+        );
+    }
+
     #[test]
     fn test_token_amount_conversion() {
         let registry = TokenRegistry::new();
@@ -6,14 +6,25 @@ use base58::{FromBase58, ToBase58};
 use rust_decimal::Decimal;
 use sha2::{Digest, Sha256};
 
+use crate::domain::enums::Network;
 use crate::domain::errors::{DomainError, DomainResult};
 
+/// Максимальный размер сериализованных клиентских метаданных в байтах
+pub const METADATA_MAX_BYTES: usize = 4096;
+
 /// Сервис валидации TRON адресов и данных
 pub struct TronValidator;
 
 impl TronValidator {
-    /// Валидирует TRON адрес (base58 формат)
+    /// Валидирует TRON адрес (base58 формат) для mainnet.
+    /// Код с известной сетью использует validate_address_on
     pub fn validate_address(address: &str) -> DomainResult<()> {
+        Self::validate_address_on(address, Network::Mainnet)
+    }
+
+    /// Валидирует TRON адрес для конкретной сети:
+    /// префиксный байт берется из сети, а не из константы
+    pub fn validate_address_on(address: &str, network: Network) -> DomainResult<()> {
         // Проверяем базовый формат
         if address.is_empty() {
             return Err(DomainError::InvalidTronAddress {
@@ -59,8 +70,8 @@ impl TronValidator {
             });
         }
 
-        // Проверяем, что первый байт правильный (0x41 для mainnet)
-        if address_bytes[0] != 0x41 {
+        // Проверяем префиксный байт сети (0x41 для всех сетей TRON)
+        if address_bytes[0] != network.address_prefix() {
             return Err(DomainError::InvalidTronAddress {
                 address: address.to_string(),
             });
@@ -118,6 +129,57 @@ impl TronValidator {
         Ok(())
     }
 
+    /// Валидирует destination_tag (memo для биржевых депозитов)
+    pub fn validate_destination_tag(destination_tag: &str) -> DomainResult<()> {
+        if destination_tag.is_empty() {
+            return Err(DomainError::ConfigurationError {
+                message: "Destination tag не может быть пустым".to_string(),
+            });
+        }
+
+        if destination_tag.len() > 64 {
+            return Err(DomainError::ConfigurationError {
+                message: "Destination tag слишком длинный (максимум 64 символа)".to_string(),
+            });
+        }
+
+        // Биржи обычно требуют только буквы, цифры, дефис и подчеркивание
+        let is_valid = destination_tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_valid {
+            return Err(DomainError::ConfigurationError {
+                message: "Destination tag содержит недопустимые символы".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Валидирует клиентские метаданные трансфера/платежного намерения.
+    /// Принимается только JSON объект ограниченного размера - метаданные
+    /// возвращаются во всех ответах и попадают в webhook payload как есть
+    pub fn validate_metadata(metadata: &serde_json::Value) -> DomainResult<()> {
+        if !metadata.is_object() {
+            return Err(DomainError::ConfigurationError {
+                message: "Метаданные должны быть JSON объектом".to_string(),
+            });
+        }
+
+        let serialized_len = metadata.to_string().len();
+        if serialized_len > METADATA_MAX_BYTES {
+            return Err(DomainError::ConfigurationError {
+                message: format!(
+                    "Метаданные слишком большие: {} байт (максимум {})",
+                    serialized_len, METADATA_MAX_BYTES
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Валидирует приватный ключ TRON (hex формат)
     pub fn validate_private_key(private_key: &str) -> DomainResult<()> {
         if private_key.is_empty() {
@@ -217,6 +279,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_address_on_network() {
+        // Все сети TRON делят префикс 0x41 - валидный mainnet адрес
+        // проходит и в тестовых сетях
+        let address = "TH3QBLNLsimQbNwq2DxTGhoDYeeCZYTvK3";
+        assert!(TronValidator::validate_address_on(address, Network::Mainnet).is_ok());
+        assert!(TronValidator::validate_address_on(address, Network::Shasta).is_ok());
+        assert!(TronValidator::validate_address_on(address, Network::Nile).is_ok());
+    }
+
     #[test]
     fn test_validate_amount() {
         // Валидные суммы
@@ -244,6 +316,37 @@ mod tests {
         // Слишком длинный
     }
 
+    #[test]
+    fn test_validate_destination_tag() {
+        // Валидные destination tag
+        assert!(TronValidator::validate_destination_tag("12345678").is_ok());
+        assert!(TronValidator::validate_destination_tag("MEMO_abc-123").is_ok());
+
+        // Невалидные destination tag
+        assert!(TronValidator::validate_destination_tag("").is_err()); // Пустой
+        assert!(TronValidator::validate_destination_tag("memo#1").is_err()); // Недопустимый символ
+        assert!(TronValidator::validate_destination_tag(&"x".repeat(65)).is_err());
+        // Слишком длинный
+    }
+
+    #[test]
+    fn test_validate_metadata() {
+        // Валидные метаданные - объекты в пределах лимита
+        assert!(TronValidator::validate_metadata(&serde_json::json!({})).is_ok());
+        assert!(
+            TronValidator::validate_metadata(&serde_json::json!({"order": "A-1", "items": 3}))
+                .is_ok()
+        );
+
+        // Невалидные: не объект
+        assert!(TronValidator::validate_metadata(&serde_json::json!("строка")).is_err());
+        assert!(TronValidator::validate_metadata(&serde_json::json!([1, 2, 3])).is_err());
+
+        // Невалидные: превышение лимита размера
+        let oversized = serde_json::json!({ "blob": "x".repeat(METADATA_MAX_BYTES) });
+        assert!(TronValidator::validate_metadata(&oversized).is_err());
+    }
+
     #[test]
     fn test_validate_private_key() {
         // Валидный приватный ключ (пример)
@@ -0,0 +1,214 @@
+//! # Append-only аудит события
+//!
+//! Опциональная доставка аудит-событий и доменных событий во внешние
+//! системы хранения (Kafka topic, S3 bucket с object-lock и т.д.)
+//! через подключаемый трейт `AuditSink` - для деплоев с регуляторными
+//! требованиями к хранению истории операций.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::infrastructure::instance::InstanceIdentity;
+
+/// Аудит-событие для внешнего хранилища
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    /// Уникальный идентификатор события
+    pub id: Uuid,
+    /// Тип события (например "transfer.completed", "wallet.created")
+    pub event_type: String,
+    /// Время возникновения события
+    pub occurred_at: DateTime<Utc>,
+    /// Произвольные данные события
+    pub payload: serde_json::Value,
+    /// Инстанс, выполнивший действие (мульти-региональные деплои)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    /// Регион инстанса
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+impl AuditEvent {
+    /// Создает новое событие с текущим временем
+    pub fn new(event_type: &str, payload: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            occurred_at: Utc::now(),
+            payload,
+            instance_id: None,
+            region: None,
+        }
+    }
+}
+
+/// Подключаемый sink для append-only доставки аудит-событий
+///
+/// Реализации должны гарантировать только доставку (at-least-once),
+/// неизменяемость обеспечивает внешнее хранилище (object-lock, compaction-off)
+#[tonic::async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Имя sink для логирования
+    fn name(&self) -> &str;
+
+    /// Добавляет событие в хранилище
+    async fn append(&self, event: &AuditEvent) -> Result<()>;
+}
+
+/// Sink по умолчанию - пишет события в структурированный лог
+///
+/// Полезен как fallback и для деплоев, где логи уже собираются
+/// в неизменяемое хранилище
+pub struct TracingAuditSink;
+
+#[tonic::async_trait]
+impl AuditSink for TracingAuditSink {
+    fn name(&self) -> &str {
+        "tracing"
+    }
+
+    async fn append(&self, event: &AuditEvent) -> Result<()> {
+        tracing::info!(
+            "📝 AUDIT [{}] {}: {}",
+            event.id,
+            event.event_type,
+            event.payload
+        );
+        Ok(())
+    }
+}
+
+/// Sink с доставкой по HTTP (Kafka REST proxy, S3-совместимый collector)
+pub struct HttpAuditSink {
+    endpoint_url: String,
+    client: reqwest::Client,
+}
+
+impl HttpAuditSink {
+    /// Создает новый HTTP sink для указанного endpoint
+    pub fn new(endpoint_url: String) -> Self {
+        Self {
+            endpoint_url,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl AuditSink for HttpAuditSink {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn append(&self, event: &AuditEvent) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.endpoint_url)
+            .json(event)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Audit endpoint вернул статус {}",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Диспетчер доставки аудит-событий в зарегистрированные sinks
+///
+/// Ошибки доставки логируются, но не прерывают основной поток обработки
+#[derive(Clone, Default)]
+pub struct AuditShipper {
+    sinks: Vec<Arc<dyn AuditSink>>,
+    /// Идентичность инстанса, проставляемая на каждое событие
+    instance: Option<InstanceIdentity>,
+}
+
+impl AuditShipper {
+    /// Создает новый диспетчер без sinks (no-op)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Регистрирует sink для доставки событий
+    pub fn with_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Проставляет идентичность инстанса на все отправляемые события
+    pub fn with_instance(mut self, instance: InstanceIdentity) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+
+    /// Включена ли доставка (есть хотя бы один sink)
+    pub fn is_enabled(&self) -> bool {
+        !self.sinks.is_empty()
+    }
+
+    /// Отправляет событие во все зарегистрированные sinks
+    pub async fn ship(&self, mut event: AuditEvent) {
+        if let Some(instance) = &self.instance {
+            event.instance_id = Some(instance.id.clone());
+            event.region = instance.region.clone();
+        }
+
+        for sink in &self.sinks {
+            if let Err(e) = sink.append(&event).await {
+                tracing::warn!(
+                    "⚠️ Не удалось доставить аудит-событие {} в sink '{}': {}",
+                    event.id,
+                    sink.name(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Создает и отправляет событие указанного типа
+    pub async fn emit(&self, event_type: &str, payload: serde_json::Value) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.ship(AuditEvent::new(event_type, payload)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_audit_event_creation() {
+        let event = AuditEvent::new("transfer.completed", json!({"transfer_id": 42}));
+        assert_eq!(event.event_type, "transfer.completed");
+        assert_eq!(event.payload["transfer_id"], 42);
+    }
+
+    #[test]
+    fn test_shipper_disabled_without_sinks() {
+        let shipper = AuditShipper::new();
+        assert!(!shipper.is_enabled());
+
+        let shipper = shipper.with_sink(Arc::new(TracingAuditSink));
+        assert!(shipper.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_tracing_sink_appends() {
+        let sink = TracingAuditSink;
+        let event = AuditEvent::new("wallet.created", json!({"wallet_id": 1}));
+        assert!(sink.append(&event).await.is_ok());
+    }
+}
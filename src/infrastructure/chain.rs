@@ -0,0 +1,316 @@
+//! # Абстракция блокчейна для будущих сетей
+//!
+//! Шлюз планирует поддержку TON/ETH: трейт `Blockchain` описывает
+//! контракт сети (генерация кошельков, балансы, создание/подписание/
+//! broadcast трансферов, семантика подтверждений), TRON спрятан за
+//! ним адаптером `TronBlockchain`. `ChainRegistry` держит бэкенды
+//! по идентификатору сети - новая сеть добавляется регистрацией
+//! своего адаптера, не трогая остальной код
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::domain::{DomainResult, Network, TransactionReceipt, TronValidator};
+
+use super::tron::{TronGridClient, TronWalletGenerator};
+use super::SigningBackend;
+
+/// Идентификатор TRON в реестре сетей
+pub const CHAIN_ID_TRON: &str = "tron";
+
+/// Сгенерированный кошелек сети
+#[derive(Debug, Clone)]
+pub struct ChainWallet {
+    pub address: String,
+    pub hex_address: String,
+    pub private_key: String,
+}
+
+/// Семантика подтверждений сети: сколько блоков до processing
+/// и до финального completed
+#[derive(Debug, Clone, Copy)]
+pub struct ChainConfirmationSemantics {
+    pub processing_confirmations: i32,
+    pub completed_confirmations: i32,
+}
+
+/// Контракт блокчейн-бэкенда
+///
+/// Покрывает то, что шлюзу нужно от любой сети: кошельки, балансы,
+/// жизненный цикл трансфера основного токена (создание неподписанной
+/// транзакции, подписание через key_ref, broadcast, чек), семантику
+/// подтверждений. `key_ref` - ссылка на ключ в терминах SigningBackend
+#[tonic::async_trait]
+pub trait Blockchain: Send + Sync {
+    /// Идентификатор сети в реестре (tron, ton, eth)
+    fn network_id(&self) -> &str;
+
+    /// Тикер нативной монеты сети (TRX, TON, ETH)
+    fn native_asset(&self) -> &str;
+
+    /// Семантика подтверждений сети
+    fn confirmation_semantics(&self) -> ChainConfirmationSemantics;
+
+    /// Генерирует новый кошелек
+    fn generate_wallet(&self) -> Result<ChainWallet>;
+
+    /// Валидирует адрес в формате сети
+    fn validate_address(&self, address: &str) -> DomainResult<()>;
+
+    /// Баланс нативной монеты
+    async fn get_native_balance(&self, address: &str) -> Result<Decimal>;
+
+    /// Баланс произвольного токена по адресу контракта
+    async fn get_token_balance(
+        &self,
+        address: &str,
+        token_contract: &str,
+        decimals: u32,
+    ) -> Result<Decimal>;
+
+    /// Создает неподписанную транзакцию трансфера основного токена
+    /// шлюза (USDT для TRON)
+    async fn create_token_transfer(&self, from: &str, to: &str, amount: Decimal) -> Result<Value>;
+
+    /// Подписывает транзакцию по ссылке на ключ
+    async fn sign_transaction(&self, transaction: &Value, key_ref: &str) -> Result<Value>;
+
+    /// Отправляет подписанную транзакцию в сеть; возвращает tx hash
+    async fn broadcast_transaction(&self, signed_transaction: &Value) -> Result<String>;
+
+    /// Чек транзакции; None - транзакция еще не видна сети
+    async fn get_transaction_receipt(&self, tx_hash: &str) -> Result<Option<TransactionReceipt>>;
+}
+
+/// Адаптер TRON за контрактом `Blockchain`: делегирует существующим
+/// `TronGridClient`, `TronWalletGenerator` и бэкенду подписания
+pub struct TronBlockchain {
+    client: TronGridClient,
+    wallet_generator: TronWalletGenerator,
+    signing_backend: Arc<dyn SigningBackend>,
+    /// Сеть TRON (mainnet/shasta/nile) для валидации адресов
+    network: Network,
+}
+
+impl TronBlockchain {
+    /// Создает адаптер поверх существующих TRON компонентов
+    pub fn new(
+        client: TronGridClient,
+        network: Network,
+        signing_backend: Arc<dyn SigningBackend>,
+    ) -> Self {
+        Self {
+            client,
+            wallet_generator: TronWalletGenerator::new(),
+            signing_backend,
+            network,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Blockchain for TronBlockchain {
+    fn network_id(&self) -> &str {
+        CHAIN_ID_TRON
+    }
+
+    fn native_asset(&self) -> &str {
+        "TRX"
+    }
+
+    fn confirmation_semantics(&self) -> ChainConfirmationSemantics {
+        // Соответствует ConfirmationPolicy из capabilities: первый блок
+        // переводит депозит в processing, 19 solid-блоков - финальность
+        ChainConfirmationSemantics {
+            processing_confirmations: 1,
+            completed_confirmations: 19,
+        }
+    }
+
+    fn generate_wallet(&self) -> Result<ChainWallet> {
+        let (address, hex_address, private_key) = self.wallet_generator.generate_wallet()?;
+        Ok(ChainWallet {
+            address,
+            hex_address,
+            private_key,
+        })
+    }
+
+    fn validate_address(&self, address: &str) -> DomainResult<()> {
+        TronValidator::validate_address_on(address, self.network)
+    }
+
+    async fn get_native_balance(&self, address: &str) -> Result<Decimal> {
+        self.client.get_trx_balance(address).await
+    }
+
+    async fn get_token_balance(
+        &self,
+        address: &str,
+        token_contract: &str,
+        decimals: u32,
+    ) -> Result<Decimal> {
+        self.client
+            .get_trc20_balance_of(address, token_contract, decimals)
+            .await
+    }
+
+    async fn create_token_transfer(&self, from: &str, to: &str, amount: Decimal) -> Result<Value> {
+        self.client.create_trc20_transaction(from, to, amount).await
+    }
+
+    async fn sign_transaction(&self, transaction: &Value, key_ref: &str) -> Result<Value> {
+        self.signing_backend
+            .sign_transaction(transaction, key_ref)
+            .await
+    }
+
+    async fn broadcast_transaction(&self, signed_transaction: &Value) -> Result<String> {
+        self.client.broadcast_transaction(signed_transaction).await
+    }
+
+    async fn get_transaction_receipt(&self, tx_hash: &str) -> Result<Option<TransactionReceipt>> {
+        self.client.get_transaction_receipt(tx_hash).await
+    }
+}
+
+/// Реестр блокчейн-бэкендов по идентификатору сети
+pub struct ChainRegistry {
+    chains: HashMap<String, Arc<dyn Blockchain>>,
+    default_network_id: String,
+}
+
+impl ChainRegistry {
+    /// Создает пустой реестр с сетью по умолчанию
+    pub fn new(default_network_id: impl Into<String>) -> Self {
+        Self {
+            chains: HashMap::new(),
+            default_network_id: default_network_id.into(),
+        }
+    }
+
+    /// Регистрирует бэкенд под его network_id
+    pub fn register(mut self, chain: Arc<dyn Blockchain>) -> Self {
+        self.chains.insert(chain.network_id().to_string(), chain);
+        self
+    }
+
+    /// Бэкенд по идентификатору сети
+    pub fn get(&self, network_id: &str) -> Option<Arc<dyn Blockchain>> {
+        self.chains.get(network_id).cloned()
+    }
+
+    /// Бэкенд сети по умолчанию
+    pub fn default_chain(&self) -> Option<Arc<dyn Blockchain>> {
+        self.get(&self.default_network_id)
+    }
+
+    /// Идентификаторы зарегистрированных сетей (отсортированы)
+    pub fn network_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.chains.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тестовый бэкенд: только идентификатор сети
+    struct StubChain {
+        network_id: &'static str,
+    }
+
+    #[tonic::async_trait]
+    impl Blockchain for StubChain {
+        fn network_id(&self) -> &str {
+            self.network_id
+        }
+
+        fn native_asset(&self) -> &str {
+            "STUB"
+        }
+
+        fn confirmation_semantics(&self) -> ChainConfirmationSemantics {
+            ChainConfirmationSemantics {
+                processing_confirmations: 1,
+                completed_confirmations: 1,
+            }
+        }
+
+        fn generate_wallet(&self) -> Result<ChainWallet> {
+            anyhow::bail!("не реализовано")
+        }
+
+        fn validate_address(&self, _address: &str) -> DomainResult<()> {
+            Ok(())
+        }
+
+        async fn get_native_balance(&self, _address: &str) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+
+        async fn get_token_balance(
+            &self,
+            _address: &str,
+            _token_contract: &str,
+            _decimals: u32,
+        ) -> Result<Decimal> {
+            Ok(Decimal::ZERO)
+        }
+
+        async fn create_token_transfer(
+            &self,
+            _from: &str,
+            _to: &str,
+            _amount: Decimal,
+        ) -> Result<Value> {
+            anyhow::bail!("не реализовано")
+        }
+
+        async fn sign_transaction(&self, _transaction: &Value, _key_ref: &str) -> Result<Value> {
+            anyhow::bail!("не реализовано")
+        }
+
+        async fn broadcast_transaction(&self, _signed_transaction: &Value) -> Result<String> {
+            anyhow::bail!("не реализовано")
+        }
+
+        async fn get_transaction_receipt(
+            &self,
+            _tx_hash: &str,
+        ) -> Result<Option<TransactionReceipt>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn test_registry_resolves_by_network_id() {
+        let registry = ChainRegistry::new(CHAIN_ID_TRON)
+            .register(Arc::new(StubChain {
+                network_id: CHAIN_ID_TRON,
+            }))
+            .register(Arc::new(StubChain { network_id: "ton" }));
+
+        assert!(registry.get(CHAIN_ID_TRON).is_some());
+        assert!(registry.get("ton").is_some());
+        assert!(registry.get("eth").is_none());
+        assert_eq!(
+            registry.default_chain().unwrap().network_id(),
+            CHAIN_ID_TRON
+        );
+        assert_eq!(registry.network_ids(), vec!["ton", "tron"]);
+    }
+
+    #[test]
+    fn test_empty_registry_has_no_default() {
+        let registry = ChainRegistry::new(CHAIN_ID_TRON);
+        assert!(registry.default_chain().is_none());
+        assert!(registry.network_ids().is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+//! # Circuit breaker для обращений к блокчейну
+//!
+//! Защищает обработку трансферов от каскадных сбоев TronGrid:
+//! после N последовательных ошибок обработка приостанавливается
+//! (трансферы остаются PENDING), а восстановление происходит
+//! автоматически после успешной пробной попытки.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Конфигурация circuit breaker
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Количество последовательных ошибок для открытия breaker
+    pub failure_threshold: u32,
+    /// Минимальный интервал между пробными попытками в открытом состоянии
+    pub probe_interval: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            probe_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Состояние circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Закрыт - операции выполняются нормально
+    Closed,
+    /// Открыт - операции приостановлены до успешной пробы
+    Open,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    last_probe_at: Option<Instant>,
+}
+
+/// Circuit breaker с автоматическим восстановлением через пробные попытки
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Arc<Mutex<BreakerInner>>,
+}
+
+impl CircuitBreaker {
+    /// Создает новый breaker с конфигурацией по умолчанию
+    pub fn new() -> Self {
+        Self::with_config(CircuitBreakerConfig::default())
+    }
+
+    /// Создает новый breaker с кастомной конфигурацией
+    pub fn with_config(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Arc::new(Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                last_probe_at: None,
+            })),
+        }
+    }
+
+    /// Текущее состояние breaker
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Открыт ли breaker
+    pub fn is_open(&self) -> bool {
+        self.state() == CircuitState::Open
+    }
+
+    /// Можно ли выполнить пробную попытку (breaker открыт и интервал прошел)
+    pub fn probe_allowed(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.state != CircuitState::Open {
+            return false;
+        }
+
+        let since_last = inner
+            .last_probe_at
+            .or(inner.opened_at)
+            .map(|at| at.elapsed())
+            .unwrap_or(self.config.probe_interval);
+
+        if since_last >= self.config.probe_interval {
+            inner.last_probe_at = Some(Instant::now());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Регистрирует успешную операцию (закрывает breaker и сбрасывает счетчик)
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.state == CircuitState::Open {
+            tracing::info!("✅ Circuit breaker закрыт: пробная операция выполнена успешно");
+        }
+
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.last_probe_at = None;
+    }
+
+    /// Регистрирует ошибку операции
+    ///
+    /// Возвращает `true`, если breaker только что открылся (момент для алерта)
+    pub fn record_failure(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.consecutive_failures += 1;
+
+        if inner.state == CircuitState::Closed
+            && inner.consecutive_failures >= self.config.failure_threshold
+        {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            return true;
+        }
+
+        false
+    }
+
+    /// Количество последовательных ошибок
+    pub fn consecutive_failures(&self) -> u32 {
+        self.inner.lock().unwrap().consecutive_failures
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_after_threshold() {
+        let breaker = CircuitBreaker::with_config(CircuitBreakerConfig {
+            failure_threshold: 3,
+            probe_interval: Duration::from_secs(60),
+        });
+
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_failure());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        // Третья ошибка открывает breaker
+        assert!(breaker.record_failure());
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = CircuitBreaker::with_config(CircuitBreakerConfig {
+            failure_threshold: 2,
+            probe_interval: Duration::from_secs(60),
+        });
+
+        breaker.record_failure();
+        breaker.record_success();
+        assert_eq!(breaker.consecutive_failures(), 0);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_probe_allowed_after_interval() {
+        let breaker = CircuitBreaker::with_config(CircuitBreakerConfig {
+            failure_threshold: 1,
+            probe_interval: Duration::ZERO,
+        });
+
+        assert!(!breaker.probe_allowed()); // закрыт - пробы не нужны
+
+        breaker.record_failure();
+        assert!(breaker.probe_allowed());
+
+        breaker.record_success();
+        assert!(!breaker.is_open());
+    }
+}
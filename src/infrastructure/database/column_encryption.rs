@@ -0,0 +1,132 @@
+//! # Шифрование чувствительных колонок через pgcrypto
+//!
+//! Альтернатива прикладному шифрованию/KMS: в режиме pgcrypto приватные
+//! ключи кошельков и секреты webhook endpoint'ов хранятся зашифрованными
+//! на стороне БД (pgp_sym_encrypt), plaintext-колонки остаются пустыми.
+//! Режим выбирается конфигом database.column_encryption.mode
+
+use anyhow::Result;
+use diesel::sql_types::{Binary, Text};
+use diesel_async::RunQueryDsl;
+
+use crate::config::ColumnEncryptionConfig;
+
+use super::models::WalletModel;
+use super::DbPool;
+
+/// Режим шифрования чувствительных колонок
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnEncryptionMode {
+    /// Шифрует прикладной слой или KMS - БД хранит как есть
+    Application,
+    /// Колонки шифруются на стороне БД через pgcrypto
+    Pgcrypto,
+}
+
+impl ColumnEncryptionMode {
+    /// Разбор из строки конфига
+    pub fn from_config_str(value: &str) -> Option<Self> {
+        match value {
+            "application" => Some(Self::Application),
+            "pgcrypto" => Some(Self::Pgcrypto),
+            _ => None,
+        }
+    }
+}
+
+/// Результат pgp_sym_encrypt
+#[derive(diesel::QueryableByName)]
+struct EncryptedRow {
+    #[diesel(sql_type = Binary)]
+    value: Vec<u8>,
+}
+
+/// Результат pgp_sym_decrypt
+#[derive(diesel::QueryableByName)]
+struct DecryptedRow {
+    #[diesel(sql_type = Text)]
+    value: String,
+}
+
+/// Шифрование колонок на стороне БД.
+/// Создается только в режиме pgcrypto - в режиме application
+/// репозиторный слой работает с plaintext-колонками как раньше
+pub struct ColumnEncryption {
+    db: DbPool,
+    key: String,
+}
+
+impl ColumnEncryption {
+    /// Создает шифратор из конфига. None - режим application
+    pub fn from_config(db: DbPool, config: &ColumnEncryptionConfig) -> Result<Option<Self>> {
+        match ColumnEncryptionMode::from_config_str(&config.mode) {
+            Some(ColumnEncryptionMode::Application) => Ok(None),
+            Some(ColumnEncryptionMode::Pgcrypto) => {
+                let key = config.pgcrypto_key.clone().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Режим pgcrypto требует database.column_encryption.pgcrypto_key"
+                    )
+                })?;
+                Ok(Some(Self { db, key }))
+            }
+            None => Err(anyhow::anyhow!(
+                "Неизвестный режим шифрования колонок: {} (поддерживаются application и pgcrypto)",
+                config.mode
+            )),
+        }
+    }
+
+    /// Шифрует значение через pgp_sym_encrypt
+    pub async fn encrypt(&self, plaintext: &str) -> Result<Vec<u8>> {
+        let mut conn = self.db.get().await?;
+
+        let row: EncryptedRow = diesel::sql_query("SELECT pgp_sym_encrypt($1, $2) AS value")
+            .bind::<Text, _>(plaintext)
+            .bind::<Text, _>(&self.key)
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(row.value)
+    }
+
+    /// Расшифровывает значение через pgp_sym_decrypt
+    pub async fn decrypt(&self, ciphertext: &[u8]) -> Result<String> {
+        let mut conn = self.db.get().await?;
+
+        let row: DecryptedRow = diesel::sql_query("SELECT pgp_sym_decrypt($1, $2) AS value")
+            .bind::<Binary, _>(ciphertext)
+            .bind::<Text, _>(&self.key)
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(row.value)
+    }
+
+    /// Приватный ключ кошелька: расшифровывает private_key_enc, если
+    /// запись создана в режиме pgcrypto, иначе отдает plaintext-колонку
+    /// (записи, созданные до включения режима, продолжают работать)
+    pub async fn wallet_private_key(&self, wallet: &WalletModel) -> Result<String> {
+        match &wallet.private_key_enc {
+            Some(ciphertext) => self.decrypt(ciphertext).await,
+            None => Ok(wallet.private_key.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_parsing() {
+        assert_eq!(
+            ColumnEncryptionMode::from_config_str("application"),
+            Some(ColumnEncryptionMode::Application)
+        );
+        assert_eq!(
+            ColumnEncryptionMode::from_config_str("pgcrypto"),
+            Some(ColumnEncryptionMode::Pgcrypto)
+        );
+        assert_eq!(ColumnEncryptionMode::from_config_str("aes"), None);
+    }
+}
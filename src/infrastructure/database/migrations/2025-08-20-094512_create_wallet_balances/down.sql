@@ -0,0 +1,3 @@
+-- Откат миграции - удаление таблицы проекции балансов
+DROP INDEX IF EXISTS idx_wallet_balances_last_reconciled_at;
+DROP TABLE IF EXISTS wallet_balances;
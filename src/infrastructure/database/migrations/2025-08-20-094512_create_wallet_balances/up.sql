@@ -0,0 +1,10 @@
+-- Создание таблицы проекции балансов кошельков
+CREATE TABLE wallet_balances (
+    wallet_id BIGINT PRIMARY KEY REFERENCES wallets(id) ON DELETE CASCADE,
+    usdt_balance DECIMAL(30,18) NOT NULL DEFAULT 0,
+    last_reconciled_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для поиска давно не сверявшихся балансов
+CREATE INDEX idx_wallet_balances_last_reconciled_at ON wallet_balances(last_reconciled_at);
@@ -0,0 +1,4 @@
+-- Откат миграции - удаление таблицы реестра токенов
+DROP INDEX IF EXISTS idx_tokens_enabled;
+DROP INDEX IF EXISTS idx_tokens_contract_address;
+DROP TABLE IF EXISTS tokens;
@@ -0,0 +1,21 @@
+-- Создание таблицы реестра TRC-20 токенов
+CREATE TABLE tokens (
+    id BIGSERIAL PRIMARY KEY,
+    symbol VARCHAR(16) NOT NULL UNIQUE,
+    name VARCHAR(64) NOT NULL,
+    contract_address VARCHAR(64) NOT NULL,
+    decimals SMALLINT NOT NULL,
+    is_stable BOOLEAN NOT NULL DEFAULT FALSE,
+    min_transfer_amount DECIMAL(30,18) NOT NULL DEFAULT 0,
+    max_transfer_amount DECIMAL(30,18),
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    icon_url VARCHAR(255),
+    coingecko_id VARCHAR(64),
+    version BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Индексы для оптимизации поиска
+CREATE INDEX idx_tokens_contract_address ON tokens(contract_address);
+CREATE INDEX idx_tokens_enabled ON tokens(enabled);
@@ -0,0 +1,5 @@
+-- Откат миграции - удаление таблицы платежных намерений
+DROP INDEX IF EXISTS idx_payment_intents_reference_id;
+DROP INDEX IF EXISTS idx_payment_intents_status;
+DROP INDEX IF EXISTS idx_payment_intents_wallet_id;
+DROP TABLE IF EXISTS payment_intents;
@@ -0,0 +1,18 @@
+-- Создание таблицы платежных намерений (payment intents)
+CREATE TABLE payment_intents (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_id BIGINT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+    expected_amount DECIMAL(30,18) NOT NULL,
+    reference_id VARCHAR(128),
+    refund_address VARCHAR(64),
+    status VARCHAR(16) NOT NULL DEFAULT 'PENDING',
+    matched_tx_hash VARCHAR(128),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Индексы для оптимизации поиска
+CREATE INDEX idx_payment_intents_wallet_id ON payment_intents(wallet_id);
+CREATE INDEX idx_payment_intents_status ON payment_intents(status);
+CREATE INDEX idx_payment_intents_reference_id ON payment_intents(reference_id);
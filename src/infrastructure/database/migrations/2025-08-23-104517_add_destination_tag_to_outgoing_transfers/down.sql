@@ -0,0 +1,3 @@
+-- Откат добавления destination_tag
+ALTER TABLE outgoing_transfers
+    DROP COLUMN destination_tag;
@@ -0,0 +1,5 @@
+-- Добавляем опциональный destination_tag (memo) для выводов на биржи.
+-- TRC-20 переводы через triggersmartcontract не поддерживают memo on-chain,
+-- поэтому тег хранится у нас и передается в webhook/export метаданных.
+ALTER TABLE outgoing_transfers
+    ADD COLUMN destination_tag VARCHAR(64);
@@ -0,0 +1,3 @@
+-- Откат создания таблицы wallet_api_tokens
+DROP INDEX IF EXISTS idx_wallet_api_tokens_wallet_id;
+DROP TABLE IF EXISTS wallet_api_tokens;
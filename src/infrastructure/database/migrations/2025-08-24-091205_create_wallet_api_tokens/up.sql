@@ -0,0 +1,14 @@
+-- Таблица API токенов, привязанных к одному кошельку.
+-- Храним только SHA-256 хеш токена, сам токен показывается один раз при выпуске.
+CREATE TABLE wallet_api_tokens (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_id BIGINT NOT NULL REFERENCES wallets(id),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    label VARCHAR(64),
+    revoked BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ
+);
+
+-- Индекс для выборки токенов кошелька
+CREATE INDEX idx_wallet_api_tokens_wallet_id ON wallet_api_tokens(wallet_id);
@@ -0,0 +1,3 @@
+-- Возвращаем легаси-статус трансферов; исходный регистр статусов
+-- incoming_transactions восстановить невозможно, код принимает оба варианта.
+UPDATE outgoing_transfers SET status = 'CONFIRMED' WHERE status = 'COMPLETED';
@@ -0,0 +1,7 @@
+-- Приводим статусы к каноническому UPPERCASE представлению state machine.
+-- Исторически мониторинг писал Debug-представление ("Pending", "Completed"),
+-- а трансферы использовали легаси-статус "CONFIRMED".
+UPDATE incoming_transactions SET status = UPPER(status);
+
+UPDATE outgoing_transfers SET status = UPPER(status);
+UPDATE outgoing_transfers SET status = 'COMPLETED' WHERE status = 'CONFIRMED';
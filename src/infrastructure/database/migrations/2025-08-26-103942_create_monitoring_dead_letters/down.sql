@@ -0,0 +1,3 @@
+-- Откат создания таблицы monitoring_dead_letters
+DROP INDEX IF EXISTS idx_monitoring_dead_letters_resolved_next_retry_at;
+DROP TABLE IF EXISTS monitoring_dead_letters;
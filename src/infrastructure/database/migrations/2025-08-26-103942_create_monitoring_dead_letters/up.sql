@@ -0,0 +1,23 @@
+-- Dead-letter таблица для ошибок обработки входящих транзакций.
+-- Депозит, упавший в process_new_incoming_transaction, сохраняется здесь
+-- с контекстом ошибки и переигрывается с экспоненциальным backoff.
+CREATE TABLE monitoring_dead_letters (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_id BIGINT NOT NULL REFERENCES wallets(id),
+    tx_hash VARCHAR(64) NOT NULL UNIQUE,
+    block_number BIGINT,
+    from_address VARCHAR(34) NOT NULL,
+    to_address VARCHAR(34) NOT NULL,
+    amount NUMERIC(20, 6) NOT NULL,
+    confirmations INTEGER NOT NULL DEFAULT 0,
+    error_message TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    resolved BOOLEAN NOT NULL DEFAULT FALSE,
+    next_retry_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для выборки записей, готовых к переигрыванию
+CREATE INDEX idx_monitoring_dead_letters_resolved_next_retry_at
+    ON monitoring_dead_letters(resolved, next_retry_at);
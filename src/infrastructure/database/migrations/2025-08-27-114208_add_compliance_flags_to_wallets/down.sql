@@ -0,0 +1,5 @@
+-- Откат комплаенс-флагов кошельков
+ALTER TABLE wallets DROP COLUMN IF EXISTS flagged_at;
+ALTER TABLE wallets DROP COLUMN IF EXISTS compliance_reviewer;
+ALTER TABLE wallets DROP COLUMN IF EXISTS compliance_notes;
+ALTER TABLE wallets DROP COLUMN IF EXISTS under_review;
@@ -0,0 +1,7 @@
+-- Комплаенс-флаги кошельков: пометка "на проверке" с заметкой и
+-- идентификатором проверяющего. Sweep'ы с помеченных кошельков не создаются
+-- и не обрабатываются до снятия флага.
+ALTER TABLE wallets ADD COLUMN under_review BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE wallets ADD COLUMN compliance_notes TEXT;
+ALTER TABLE wallets ADD COLUMN compliance_reviewer VARCHAR(64);
+ALTER TABLE wallets ADD COLUMN flagged_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+ALTER TABLE outgoing_transfers DROP COLUMN screened_at;
+ALTER TABLE outgoing_transfers DROP COLUMN risk_provider;
+ALTER TABLE outgoing_transfers DROP COLUMN risk_score;
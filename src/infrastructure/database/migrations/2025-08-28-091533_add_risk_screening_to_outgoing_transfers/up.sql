@@ -0,0 +1,4 @@
+-- Результат риск-скрининга адреса назначения на записи трансфера
+ALTER TABLE outgoing_transfers ADD COLUMN risk_score INTEGER;
+ALTER TABLE outgoing_transfers ADD COLUMN risk_provider VARCHAR(32);
+ALTER TABLE outgoing_transfers ADD COLUMN screened_at TIMESTAMPTZ;
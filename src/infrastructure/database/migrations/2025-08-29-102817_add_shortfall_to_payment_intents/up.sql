@@ -0,0 +1,2 @@
+-- Недоплата (dust), прощенная при матчинге депозита с допуском
+ALTER TABLE payment_intents ADD COLUMN shortfall NUMERIC;
@@ -0,0 +1,4 @@
+-- Откат создания таблицы trx_transfers
+DROP INDEX IF EXISTS idx_trx_transfers_related_wallet_id;
+DROP INDEX IF EXISTS idx_trx_transfers_from_address;
+DROP TABLE IF EXISTS trx_transfers;
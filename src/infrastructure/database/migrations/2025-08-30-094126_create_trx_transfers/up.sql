@@ -0,0 +1,18 @@
+-- Записи TRX отправок шлюза (активация кошельков, спонсирование газа,
+-- faucet, админские отправки). Раньше они только логировались и
+-- восстановить историю расходов мастер-кошельков было невозможно.
+CREATE TABLE trx_transfers (
+    id BIGSERIAL PRIMARY KEY,
+    from_address VARCHAR(64) NOT NULL,
+    to_address VARCHAR(64) NOT NULL,
+    amount NUMERIC(20, 6) NOT NULL,
+    purpose VARCHAR(32) NOT NULL,
+    tx_hash VARCHAR(128) NOT NULL,
+    related_wallet_id BIGINT REFERENCES wallets(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для истории мастер-кошелька
+CREATE INDEX idx_trx_transfers_from_address ON trx_transfers(from_address);
+-- Индекс для ленты активности кошелька
+CREATE INDEX idx_trx_transfers_related_wallet_id ON trx_transfers(related_wallet_id);
@@ -0,0 +1,2 @@
+-- Откат добавления archived_at
+ALTER TABLE wallets DROP COLUMN archived_at;
@@ -0,0 +1,3 @@
+-- Время архивации кошелька. Архивный кошелек остается в БД для истории,
+-- но исключается из активных операций
+ALTER TABLE wallets ADD COLUMN archived_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+-- Откат добавления колонок claim'а
+ALTER TABLE outgoing_transfers DROP COLUMN claimed_by;
+ALTER TABLE outgoing_transfers DROP COLUMN claimed_at;
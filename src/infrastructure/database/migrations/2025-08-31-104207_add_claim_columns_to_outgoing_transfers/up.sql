@@ -0,0 +1,4 @@
+-- Видимость in-flight обработки: когда и каким инстансом процессора
+-- трансфер забран в работу
+ALTER TABLE outgoing_transfers ADD COLUMN claimed_at TIMESTAMPTZ;
+ALTER TABLE outgoing_transfers ADD COLUMN claimed_by VARCHAR(64);
@@ -0,0 +1,2 @@
+-- Откат добавления amount_encoded
+ALTER TABLE payment_intents DROP COLUMN amount_encoded;
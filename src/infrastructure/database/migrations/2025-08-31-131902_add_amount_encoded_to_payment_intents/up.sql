@@ -0,0 +1,4 @@
+-- Детерминированная атрибуция депозитов по закодированной сумме:
+-- намерение получает уникальный центовый суффикс, и депозит матчится
+-- с ним только при точном совпадении суммы
+ALTER TABLE payment_intents ADD COLUMN amount_encoded BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Откат маркировки источников депозитов
+DROP INDEX idx_incoming_transactions_source_label;
+ALTER TABLE incoming_transactions DROP COLUMN source_label;
@@ -0,0 +1,8 @@
+-- Метка источника депозита: explicit-тег из конфигурации
+-- (exchange, mixer) или эвристика (internal, private_wallet).
+-- NULL для депозитов, обработанных до включения маркировки
+ALTER TABLE incoming_transactions ADD COLUMN source_label VARCHAR(32);
+
+-- Индекс для фильтрации депозитов по типу источника
+CREATE INDEX idx_incoming_transactions_source_label
+    ON incoming_transactions (wallet_id, source_label);
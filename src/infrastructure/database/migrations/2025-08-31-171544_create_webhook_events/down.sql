@@ -0,0 +1,2 @@
+-- Откат персистентного лога webhook событий
+DROP TABLE webhook_events;
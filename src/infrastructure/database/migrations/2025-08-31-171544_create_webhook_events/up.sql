@@ -0,0 +1,14 @@
+-- Персистентный лог webhook событий.
+-- Каждое отправляемое событие сохраняется до попытки доставки -
+-- мерчанты могут пересинхронизировать свои системы через bulk export
+-- ("все события между датами") с возобновляемым курсором по id
+CREATE TABLE webhook_events (
+    id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(64) NOT NULL,
+    -- Полный JSON payload события как отправлен на endpoint
+    payload TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для выборки событий за период
+CREATE INDEX idx_webhook_events_created_at ON webhook_events (created_at);
@@ -0,0 +1,3 @@
+-- Откат: удаляем колонки метаданных
+ALTER TABLE payment_intents DROP COLUMN metadata;
+ALTER TABLE outgoing_transfers DROP COLUMN metadata;
@@ -0,0 +1,6 @@
+-- Произвольные клиентские метаданные (JSON объект, лимит по размеру).
+-- Хранятся как TEXT (diesel без serde_json feature), возвращаются
+-- во всех ответах и попадают в webhook payload как есть - мерчантам
+-- не нужна отдельная таблица соответствия
+ALTER TABLE outgoing_transfers ADD COLUMN metadata TEXT;
+ALTER TABLE payment_intents ADD COLUMN metadata TEXT;
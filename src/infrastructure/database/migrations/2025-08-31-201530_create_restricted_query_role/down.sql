@@ -0,0 +1,9 @@
+-- Откат: снимаем привилегии и удаляем query-роль
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    REVOKE SELECT, INSERT, UPDATE, DELETE ON TABLES FROM gateway_query;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    REVOKE USAGE, SELECT ON SEQUENCES FROM gateway_query;
+REVOKE ALL ON ALL TABLES IN SCHEMA public FROM gateway_query;
+REVOKE ALL ON ALL SEQUENCES IN SCHEMA public FROM gateway_query;
+REVOKE USAGE ON SCHEMA public FROM gateway_query;
+DROP ROLE IF EXISTS gateway_query;
@@ -0,0 +1,29 @@
+-- Роль наименьших привилегий для query-пути шлюза.
+-- Только DML на таблицах шлюза, без DDL; лимиты на время запросов
+-- страхуют от зависших и огромных выборок. Миграции выполняются под
+-- отдельной ролью (владельцем схемы) - подключения разводятся через
+-- database.url и database.migration_url.
+-- NOLOGIN: ops выдает роль конкретному login-пользователю через GRANT.
+-- При развертывании в выделенной схеме (database.schema) замените
+-- public на имя схемы
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT FROM pg_roles WHERE rolname = 'gateway_query') THEN
+        CREATE ROLE gateway_query NOLOGIN;
+    END IF;
+END
+$$;
+
+GRANT USAGE ON SCHEMA public TO gateway_query;
+GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO gateway_query;
+GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO gateway_query;
+
+-- Будущие таблицы миграций получают те же права автоматически
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO gateway_query;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT USAGE, SELECT ON SEQUENCES TO gateway_query;
+
+-- Лимиты на запросы под query-ролью
+ALTER ROLE gateway_query SET statement_timeout = '30s';
+ALTER ROLE gateway_query SET lock_timeout = '5s';
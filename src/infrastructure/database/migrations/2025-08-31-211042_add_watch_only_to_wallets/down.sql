@@ -0,0 +1,2 @@
+-- Откат: удаляем флаг watch-only
+ALTER TABLE wallets DROP COLUMN watch_only;
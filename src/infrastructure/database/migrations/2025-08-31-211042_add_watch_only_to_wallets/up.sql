@@ -0,0 +1,5 @@
+-- Watch-only кошельки: внешние адреса без приватного ключа.
+-- Мониторинг отслеживает их депозиты наравне с обычными кошельками,
+-- но отправителями трансферов они быть не могут (нечем подписывать).
+-- private_key у таких записей - пустая строка
+ALTER TABLE wallets ADD COLUMN watch_only BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Откат: удаляем колонки комиссий
+ALTER TABLE outgoing_transfers DROP COLUMN fee_amount;
+ALTER TABLE outgoing_transfers DROP COLUMN fee_payer;
@@ -0,0 +1,9 @@
+-- Кто оплачивает комиссии трансфера и их сумма.
+-- customer: комиссии добавлены поверх order_amount (текущее поведение),
+-- merchant: комиссии поглощены мерчантом (вычтены из суммы заказа)
+ALTER TABLE outgoing_transfers
+    ADD COLUMN fee_payer VARCHAR(16) NOT NULL DEFAULT 'customer';
+
+-- Итоговая комиссия (газ + сервисная) на момент создания трансфера.
+-- NULL для трансферов, созданных до появления колонки
+ALTER TABLE outgoing_transfers ADD COLUMN fee_amount NUMERIC(20, 6);
@@ -0,0 +1,2 @@
+-- Откат: удаляем таблицу агрегатов TronGrid
+DROP TABLE trongrid_usage_daily;
@@ -0,0 +1,13 @@
+-- Дневные агрегаты использования TronGrid API.
+-- Заполняется трекером при смене дня: по каждой операции - число
+-- запросов, ошибок и суммарная латентность. История нужна для
+-- планирования квоты и разбора инцидентов с rate limit
+CREATE TABLE trongrid_usage_daily (
+    id SERIAL PRIMARY KEY,
+    day DATE NOT NULL,
+    op VARCHAR(64) NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+    errors BIGINT NOT NULL DEFAULT 0,
+    total_latency_ms BIGINT NOT NULL DEFAULT 0,
+    UNIQUE (day, op)
+);
@@ -0,0 +1,2 @@
+-- Откат outbox'а доставки webhook'ов
+DROP TABLE webhook_deliveries;
@@ -0,0 +1,22 @@
+-- Персистентный outbox доставки webhook'ов.
+-- В отличие от webhook_events (лог "что произошло") здесь отслеживается
+-- судьба каждой доставки: попытки, статус, следующий retry. Фоновый
+-- воркер планировщика дотягивает недоставленное с экспоненциальным backoff
+CREATE TABLE webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(64) NOT NULL,
+    -- Полный JSON payload события как отправляется на endpoint
+    payload TEXT NOT NULL,
+    endpoint_url VARCHAR(512) NOT NULL,
+    -- PENDING / DELIVERED / FAILED
+    status VARCHAR(16) NOT NULL DEFAULT 'PENDING',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    -- Когда воркер возьмет запись в следующий раз (NULL - не планируется)
+    next_retry_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ
+);
+
+-- Индекс для выборки due-записей воркером
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries (status, next_retry_at);
@@ -0,0 +1,2 @@
+-- Откат: удаляем колонку номера блока
+ALTER TABLE outgoing_transfers DROP COLUMN block_number;
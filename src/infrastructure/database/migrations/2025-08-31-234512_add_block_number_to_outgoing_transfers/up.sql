@@ -0,0 +1,4 @@
+-- Номер блока, в котором транзакция трансфера попала на цепочку.
+-- Заполняется трекером подтверждений при переводе в COMPLETED.
+-- NULL для трансферов, завершенных до появления трекера
+ALTER TABLE outgoing_transfers ADD COLUMN block_number BIGINT;
@@ -0,0 +1,2 @@
+-- Откат: удаляем историю запусков планировщика
+DROP TABLE scheduler_runs;
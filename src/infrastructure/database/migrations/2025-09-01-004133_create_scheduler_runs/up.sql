@@ -0,0 +1,17 @@
+-- История запусков задач планировщика.
+-- Операторы проверяют, что задачи реально выполнялись (например за
+-- выходные), без grep'а по логам. Ретеншн чистит старые записи
+CREATE TABLE scheduler_runs (
+    id BIGSERIAL PRIMARY KEY,
+    task_name VARCHAR(64) NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    items_processed BIGINT NOT NULL DEFAULT 0,
+    -- success или error
+    outcome VARCHAR(16) NOT NULL,
+    error_message TEXT
+);
+
+-- Выборки истории идут по задаче и времени
+CREATE INDEX idx_scheduler_runs_task_started ON scheduler_runs (task_name, started_at DESC);
+CREATE INDEX idx_scheduler_runs_started ON scheduler_runs (started_at);
@@ -0,0 +1,2 @@
+-- Откат: удаляем колонку вида трансфера
+ALTER TABLE outgoing_transfers DROP COLUMN transfer_kind;
@@ -0,0 +1,5 @@
+-- Вид исходящего трансфера:
+-- sweep - свип на мастер-кошелек (существующее поведение),
+-- withdrawal - вывод на произвольный адрес назначения
+ALTER TABLE outgoing_transfers
+    ADD COLUMN transfer_kind VARCHAR(16) NOT NULL DEFAULT 'sweep';
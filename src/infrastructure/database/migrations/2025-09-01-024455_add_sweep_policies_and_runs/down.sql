@@ -0,0 +1,4 @@
+-- Откат политик sweep'а и истории запусков
+DROP TABLE sweep_runs;
+ALTER TABLE wallets DROP COLUMN sweep_min_threshold;
+ALTER TABLE wallets DROP COLUMN sweep_enabled;
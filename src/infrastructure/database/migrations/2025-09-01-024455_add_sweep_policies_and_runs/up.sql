@@ -0,0 +1,22 @@
+-- Политика sweep'а на кошельке: можно выключить консолидацию для
+-- отдельного кошелька или поднять порог относительно глобального.
+-- NULL в min_threshold означает глобальный порог из конфига
+ALTER TABLE wallets ADD COLUMN sweep_enabled BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE wallets ADD COLUMN sweep_min_threshold NUMERIC;
+
+-- История запусков sweep'а: сколько кошельков просканировано,
+-- сколько фактически выметено и на какую сумму
+CREATE TABLE sweep_runs (
+    id BIGSERIAL PRIMARY KEY,
+    started_at TIMESTAMPTZ NOT NULL,
+    duration_ms BIGINT NOT NULL,
+    wallets_scanned BIGINT NOT NULL DEFAULT 0,
+    wallets_swept BIGINT NOT NULL DEFAULT 0,
+    total_amount NUMERIC NOT NULL DEFAULT 0,
+    -- success или error
+    outcome VARCHAR(16) NOT NULL,
+    error_message TEXT
+);
+
+-- Выборки истории идут от свежих запусков к старым
+CREATE INDEX idx_sweep_runs_started ON sweep_runs (started_at DESC);
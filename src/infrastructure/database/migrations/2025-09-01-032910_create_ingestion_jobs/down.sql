@@ -0,0 +1,3 @@
+-- Откат таблиц массовой загрузки трансферов
+DROP TABLE ingestion_rows;
+DROP TABLE ingestion_jobs;
@@ -0,0 +1,34 @@
+-- Массовая загрузка трансферов (NDJSON ingestion).
+-- Мерчант стримит тысячи строк за раз; валидация идет построчно,
+-- принятые строки становятся queued и конвертируются в трансферы
+-- фоновым воркером - HTTP запрос не ждет создания каждого трансфера
+CREATE TABLE ingestion_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- queued (строки в очереди) или completed (все строки обработаны)
+    status VARCHAR(16) NOT NULL DEFAULT 'queued',
+    rows_total BIGINT NOT NULL DEFAULT 0,
+    rows_accepted BIGINT NOT NULL DEFAULT 0,
+    rows_rejected BIGINT NOT NULL DEFAULT 0
+);
+
+-- Судьба каждой строки загрузки
+CREATE TABLE ingestion_rows (
+    id BIGSERIAL PRIMARY KEY,
+    job_id BIGINT NOT NULL REFERENCES ingestion_jobs(id) ON DELETE CASCADE,
+    -- Номер строки в исходном NDJSON (с единицы)
+    row_number BIGINT NOT NULL,
+    -- Исходная строка как прислал мерчант (воркер перечитывает ее)
+    payload TEXT NOT NULL,
+    -- queued / rejected / created / failed
+    status VARCHAR(16) NOT NULL,
+    -- Созданный трансфер (для status = created)
+    transfer_id BIGINT,
+    error_message TEXT,
+    processed_at TIMESTAMPTZ
+);
+
+-- Отчет по job'у идет в порядке строк
+CREATE INDEX idx_ingestion_rows_job ON ingestion_rows (job_id, row_number);
+-- Воркер выбирает queued-строки по порядку поступления
+CREATE INDEX idx_ingestion_rows_queued ON ingestion_rows (id) WHERE status = 'queued';
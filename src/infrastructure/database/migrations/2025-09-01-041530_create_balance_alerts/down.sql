@@ -0,0 +1,2 @@
+-- Откат пороговых подписок на балансы
+DROP TABLE balance_alerts;
@@ -0,0 +1,27 @@
+-- Пороговые подписки на балансы: клиент регистрирует алерт
+-- (например "USDT кошелька >= 500" или "TRX мастера < 1000"),
+-- планировщик периодически сверяет пороги с read model балансов
+-- и шлет webhook/уведомление при пересечении.
+-- triggered - текущее состояние гистерезиса: сработавший алерт
+-- не стреляет повторно, пока значение не откатится за порог
+-- на величину hysteresis (защита от дребезга вокруг порога)
+CREATE TABLE balance_alerts (
+    id BIGSERIAL PRIMARY KEY,
+    -- NULL = алерт на мастер-кошелек
+    wallet_id BIGINT REFERENCES wallets(id) ON DELETE CASCADE,
+    -- USDT или TRX
+    asset VARCHAR(8) NOT NULL,
+    -- above (значение >= порога) или below (значение <= порога)
+    direction VARCHAR(8) NOT NULL,
+    threshold NUMERIC NOT NULL,
+    hysteresis NUMERIC NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    triggered BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Последнее сверенное значение (для отладки и API)
+    last_value NUMERIC,
+    last_triggered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Планировщик выбирает только включенные алерты
+CREATE INDEX idx_balance_alerts_enabled ON balance_alerts (id) WHERE enabled;
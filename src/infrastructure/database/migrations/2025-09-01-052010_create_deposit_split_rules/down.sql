@@ -0,0 +1,3 @@
+-- Откат маршрутизации депозитов
+DROP TABLE deposit_split_legs;
+DROP TABLE deposit_split_rules;
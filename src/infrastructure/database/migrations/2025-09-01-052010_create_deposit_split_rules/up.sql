@@ -0,0 +1,36 @@
+-- Декларативная таблица маршрутизации депозитов: набор правил
+-- (ног) на кошелек описывает, как раскладывать каждый подтвержденный
+-- депозит (например 95% на мастер-кошелек и 5% партнеру).
+-- Проценты всех ног кошелька в сумме дают ровно 100
+CREATE TABLE deposit_split_rules (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_id BIGINT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+    -- Порядок ноги в наборе правил (остаток округления уходит последней)
+    position INTEGER NOT NULL,
+    -- 'master' (мастер-кошелек из пула) или конкретный TRON адрес
+    destination VARCHAR(64) NOT NULL,
+    percent NUMERIC NOT NULL,
+    -- Человекочитаемая метка ноги (partner, treasury и т.п.)
+    label VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (wallet_id, position)
+);
+
+-- Учетные записи по ногам: каждая строка связывает депозит
+-- с созданным по правилу трансфером. Наличие ног по tx_hash -
+-- признак уже обработанного депозита (идемпотентность)
+CREATE TABLE deposit_split_legs (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_id BIGINT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+    deposit_tx_hash VARCHAR(128) NOT NULL,
+    destination VARCHAR(64) NOT NULL,
+    percent NUMERIC NOT NULL,
+    amount NUMERIC NOT NULL,
+    -- Трансфер, созданный по этой ноге (NULL у ног с нулевой суммой)
+    transfer_id BIGINT REFERENCES outgoing_transfers(id) ON DELETE SET NULL,
+    label VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Проверка идемпотентности и выборка учета по депозиту
+CREATE INDEX idx_deposit_split_legs_tx_hash ON deposit_split_legs (deposit_tx_hash);
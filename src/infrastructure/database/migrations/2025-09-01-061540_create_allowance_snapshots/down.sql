@@ -0,0 +1 @@
+DROP TABLE allowance_snapshots;
@@ -0,0 +1,19 @@
+-- Снимки TRC-20 разрешений (allowance) внешних кошельков шлюзу.
+-- Каждая проверка пишет снимок - история служит аудитом pull-платежей
+-- и позволяет заметить неожиданное уменьшение разрешения
+CREATE TABLE allowance_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    -- Внешний кошелек, выдавший разрешение
+    owner_address VARCHAR(64) NOT NULL,
+    -- Кому выдано разрешение (мастер-кошелек шлюза)
+    spender_address VARCHAR(64) NOT NULL,
+    -- Контракт токена
+    token_contract VARCHAR(64) NOT NULL,
+    -- Текущее разрешение в единицах токена
+    allowance NUMERIC NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- История снимков читается по owner'у, новые первыми
+CREATE INDEX idx_allowance_snapshots_owner
+    ON allowance_snapshots (owner_address, captured_at DESC);
@@ -0,0 +1,27 @@
+-- Инвойсы (payment requests): "ожидаю 125 USDT на кошелек X за 30 минут,
+-- reference order-42". Депозиты накапливаются в paid_amount - инвойс
+-- поддерживает частичную оплату и переплату; истечение обрабатывает
+-- планировщик
+CREATE TABLE invoices (
+    id BIGSERIAL PRIMARY KEY,
+    wallet_id BIGINT NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+    -- Ссылка интегратора (номер заказа)
+    reference VARCHAR(128) NOT NULL,
+    -- Ожидаемая сумма в USDT
+    expected_amount NUMERIC NOT NULL,
+    -- Накопленная оплата (сумма заматченных депозитов)
+    paid_amount NUMERIC NOT NULL DEFAULT 0,
+    -- OPEN, PARTIAL, PAID, OVERPAID или EXPIRED
+    status VARCHAR(16) NOT NULL DEFAULT 'OPEN',
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    paid_at TIMESTAMPTZ,
+    -- Один открытый reference на кошелек
+    CONSTRAINT uq_invoices_wallet_reference UNIQUE (wallet_id, reference)
+);
+
+-- Матчинг депозитов ищет открытые инвойсы кошелька
+CREATE INDEX idx_invoices_wallet_status ON invoices (wallet_id, status);
+
+-- Задача истечения сканирует по сроку
+CREATE INDEX idx_invoices_expires_at ON invoices (status, expires_at);
@@ -0,0 +1,14 @@
+-- Дополнительные webhook endpoint'ы: операторы регистрируют несколько
+-- URL и подписывают каждый на подмножество типов событий. Primary
+-- endpoint из конфигурации продолжает получать все события
+CREATE TABLE webhook_endpoints (
+    id BIGSERIAL PRIMARY KEY,
+    url VARCHAR(512) NOT NULL UNIQUE,
+    -- Подписка: типы событий через запятую, пустая строка = все
+    event_types TEXT NOT NULL DEFAULT '',
+    -- Секрет HMAC подписи payload'ов (ротируется через API)
+    secret_key VARCHAR(128),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
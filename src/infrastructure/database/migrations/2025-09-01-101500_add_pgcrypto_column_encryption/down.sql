@@ -0,0 +1,5 @@
+ALTER TABLE webhook_endpoints DROP COLUMN secret_key_enc;
+
+ALTER TABLE wallets DROP COLUMN private_key_enc;
+
+-- Расширение pgcrypto не удаляем: им могут пользоваться другие схемы
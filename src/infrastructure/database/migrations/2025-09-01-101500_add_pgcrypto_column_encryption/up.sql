@@ -0,0 +1,9 @@
+-- Шифрование чувствительных колонок на стороне БД (pgcrypto).
+-- Альтернатива прикладному шифрованию/KMS: в режиме pgcrypto приватные
+-- ключи кошельков и секреты webhook endpoint'ов хранятся в *_enc
+-- (pgp_sym_encrypt), а plaintext-колонки остаются пустыми
+CREATE EXTENSION IF NOT EXISTS pgcrypto;
+
+ALTER TABLE wallets ADD COLUMN private_key_enc BYTEA;
+
+ALTER TABLE webhook_endpoints ADD COLUMN secret_key_enc BYTEA;
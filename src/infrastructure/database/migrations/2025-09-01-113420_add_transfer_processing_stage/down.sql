@@ -0,0 +1 @@
+ALTER TABLE outgoing_transfers DROP COLUMN processing_stage;
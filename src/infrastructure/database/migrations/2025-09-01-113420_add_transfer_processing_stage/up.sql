@@ -0,0 +1,8 @@
+-- Под-этапы обработки трансфера (resume points).
+-- process_transfer выполняет заправку газа, создание транзакции,
+-- подписание и broadcast; краш посередине раньше не оставлял следов.
+-- Теперь каждый завершенный шаг фиксируется, и после рестарта обработка
+-- продолжается с него - без повторной траты газа и re-broadcast
+ALTER TABLE outgoing_transfers ADD COLUMN processing_stage VARCHAR(32);
+
+-- Стадии: GAS_SPONSORED, TX_CREATED, SIGNED, BROADCAST (NULL - не начато)
@@ -0,0 +1,8 @@
+-- Снимаем аннотации источников времени
+COMMENT ON COLUMN incoming_transactions.detected_at IS NULL;
+COMMENT ON COLUMN incoming_transactions.confirmed_at IS NULL;
+COMMENT ON COLUMN outgoing_transfers.created_at IS NULL;
+COMMENT ON COLUMN outgoing_transfers.claimed_at IS NULL;
+COMMENT ON COLUMN outgoing_transfers.completed_at IS NULL;
+COMMENT ON COLUMN invoices.expires_at IS NULL;
+COMMENT ON COLUMN invoices.paid_at IS NULL;
@@ -0,0 +1,28 @@
+-- Аннотация источников времени (аудит clock skew).
+-- Исторически колонки заполнялись из трех источников: Utc::now() шлюза,
+-- block_timestamp ноды и now() Postgres, что давало аномалии в отчетах
+-- (completed_at раньше created_at при расхождении часов).
+-- Код стандартизован: события цепочки - block_timestamp (UTC),
+-- события шлюза - Clock сервиса (UTC). Пересчитать старые строки из
+-- цепочки офлайн нельзя - вместо backfill'а фиксируем источник
+-- комментариями, чтобы отчеты трактовали колонки однозначно.
+
+-- Депозиты: время из блокчейна
+COMMENT ON COLUMN incoming_transactions.detected_at IS
+    'UTC, block_timestamp ноды TRON; до 2025-09 - часы шлюза на момент обнаружения';
+COMMENT ON COLUMN incoming_transactions.confirmed_at IS
+    'UTC, часы шлюза на момент подтверждения';
+
+-- Исходящие трансферы: время шлюза (Clock сервиса)
+COMMENT ON COLUMN outgoing_transfers.created_at IS
+    'UTC, часы шлюза (Clock сервиса)';
+COMMENT ON COLUMN outgoing_transfers.claimed_at IS
+    'UTC, часы шлюза (Clock сервиса)';
+COMMENT ON COLUMN outgoing_transfers.completed_at IS
+    'UTC, часы шлюза (Clock сервиса); до 2025-09 - now() Postgres';
+
+-- Инвойсы: дедлайны считаются часами шлюза
+COMMENT ON COLUMN invoices.expires_at IS
+    'UTC, часы шлюза (Clock сервиса) + TTL инвойса';
+COMMENT ON COLUMN invoices.paid_at IS
+    'UTC, часы шлюза на момент применения депозита';
@@ -0,0 +1 @@
+ALTER TABLE outgoing_transfers DROP COLUMN token_symbol;
@@ -0,0 +1,6 @@
+-- Символ TRC-20 токена исходящего трансфера.
+-- Раньше пайплайн умел только USDT, и мультитокенный handler отдавал
+-- заглушку вместо реального трансфера. Теперь токен сохраняется в строке
+-- трансфера, и обработка выбирает контракт по нему.
+-- Все существующие трансферы - USDT
+ALTER TABLE outgoing_transfers ADD COLUMN token_symbol VARCHAR(16) NOT NULL DEFAULT 'USDT';
@@ -0,0 +1,3 @@
+ALTER TABLE outgoing_transfers DROP COLUMN fee_rate_captured_at;
+ALTER TABLE outgoing_transfers DROP COLUMN fee_rate_source;
+ALTER TABLE outgoing_transfers DROP COLUMN fee_trx_usdt_rate;
@@ -0,0 +1,8 @@
+-- Снимок курса TRX/USDT на момент расчета комиссий трансфера.
+-- Курс живого фида меняется, и без снимка аудит не мог воспроизвести
+-- расчет fee_amount задним числом. Теперь курс, его источник (имя
+-- оракула или config) и момент снятия сохраняются вместе с трансфером.
+-- Для исторических строк снимка нет - колонки NULL
+ALTER TABLE outgoing_transfers ADD COLUMN fee_trx_usdt_rate NUMERIC;
+ALTER TABLE outgoing_transfers ADD COLUMN fee_rate_source VARCHAR(32);
+ALTER TABLE outgoing_transfers ADD COLUMN fee_rate_captured_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+ALTER TABLE incoming_transactions DROP COLUMN contract_address;
+ALTER TABLE incoming_transactions DROP COLUMN token_symbol;
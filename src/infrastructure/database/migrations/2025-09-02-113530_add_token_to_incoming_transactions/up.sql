@@ -0,0 +1,7 @@
+-- Токен входящей транзакции для мультитокенного учета.
+-- Депозиты писались без привязки к токену - сумма неявно считалась
+-- USDT. Теперь мониторинг сохраняет символ токена и адрес контракта
+-- (из token_info TronGrid), и учет по токенам становится возможным.
+-- Все существующие депозиты - USDT; контракт для них неизвестен
+ALTER TABLE incoming_transactions ADD COLUMN token_symbol VARCHAR(16) NOT NULL DEFAULT 'USDT';
+ALTER TABLE incoming_transactions ADD COLUMN contract_address VARCHAR(64);
@@ -1,22 +1,70 @@
-pub mod schema;
+pub mod column_encryption;
 pub mod models;
+pub mod schema;
 
+pub use column_encryption::{ColumnEncryption, ColumnEncryptionMode};
 pub use models::*;
 
-use diesel_async::{AsyncPgConnection, pooled_connection::AsyncDieselConnectionManager};
-use diesel_async::pooled_connection::bb8::Pool;
 use anyhow::Result;
+use diesel_async::pooled_connection::bb8::Pool;
+use diesel_async::{
+    pooled_connection::AsyncDieselConnectionManager, AsyncConnection, AsyncPgConnection,
+    RunQueryDsl,
+};
 
 pub type DbPool = Pool<AsyncPgConnection>;
 pub type DbConnection = AsyncPgConnection;
 
-/// Создание пула соединений с базой данных
-pub async fn create_db_pool(database_url: &str) -> Result<DbPool> {
+/// Создание пула соединений с базой данных.
+///
+/// При заданной `schema` каждому соединению пула выставляется search_path -
+/// все таблицы шлюза (включая миграции) живут в выделенной схеме Postgres,
+/// и несколько инстансов могут делить один сервер БД.
+///
+/// При заданном `migration_url` DDL (создание схемы) выполняется через
+/// отдельное соединение под миграционной ролью - сам пул работает
+/// под ограниченной query-ролью без прав DDL
+pub async fn create_db_pool(
+    database_url: &str,
+    schema: Option<&str>,
+    migration_url: Option<&str>,
+) -> Result<DbPool> {
+    let database_url = match schema {
+        Some(schema) if !schema.is_empty() => {
+            // search_path передается через параметры соединения (%3D - это '='),
+            // public оставляем fallback'ом для расширений
+            let separator = if database_url.contains('?') { '&' } else { '?' };
+            format!(
+                "{}{}options=-csearch_path%3D{},public",
+                database_url, separator, schema
+            )
+        }
+        _ => database_url.to_string(),
+    };
+
     let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url);
-    let pool = Pool::builder()
-        .max_size(10)
-        .build(config)
-        .await?;
-    
+    let pool = Pool::builder().max_size(10).build(config).await?;
+
+    // Создаем схему заранее, чтобы миграции применились в нее, а не в public.
+    // Имя схемы проверено в Settings::validate() как безопасный идентификатор.
+    // Под ограниченной query-ролью DDL запрещен - используем отдельное
+    // соединение миграционной роли, если оно сконфигурировано
+    if let Some(schema) = schema {
+        if !schema.is_empty() {
+            let ddl = format!("CREATE SCHEMA IF NOT EXISTS {}", schema);
+
+            match migration_url {
+                Some(migration_url) if !migration_url.is_empty() => {
+                    let mut conn = AsyncPgConnection::establish(migration_url).await?;
+                    diesel::sql_query(ddl).execute(&mut conn).await?;
+                }
+                _ => {
+                    let mut conn = pool.get().await?;
+                    diesel::sql_query(ddl).execute(&mut conn).await?;
+                }
+            }
+        }
+    }
+
     Ok(pool)
 }
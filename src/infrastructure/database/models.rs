@@ -5,7 +5,13 @@ use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::infrastructure::database::schema::{incoming_transactions, outgoing_transfers, wallets};
+use crate::infrastructure::database::schema::{
+    allowance_snapshots, balance_alerts, deposit_split_legs, deposit_split_rules,
+    incoming_transactions, ingestion_jobs, ingestion_rows, invoices, monitoring_dead_letters,
+    outgoing_transfers, payment_intents, scheduler_runs, sweep_runs, tokens, trongrid_usage_daily,
+    trx_transfers, wallet_api_tokens, wallet_balances, wallets, webhook_deliveries,
+    webhook_endpoints, webhook_events,
+};
 
 /// Модель кошелька для diesel
 #[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +23,18 @@ pub struct WalletModel {
     pub private_key: String,
     pub owner_id: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub under_review: bool,
+    pub compliance_notes: Option<String>,
+    pub compliance_reviewer: Option<String>,
+    pub flagged_at: Option<DateTime<Utc>>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub watch_only: bool,
+    pub sweep_enabled: bool,
+    pub sweep_min_threshold: Option<BigDecimal>,
+    /// Приватный ключ, зашифрованный pgcrypto (режим database encryption);
+    /// plaintext-колонка private_key у таких записей - пустая строка
+    #[serde(skip_serializing)]
+    pub private_key_enc: Option<Vec<u8>>,
 }
 
 /// Модель для создания нового кошелька
@@ -27,6 +45,116 @@ pub struct NewWallet {
     pub hex_address: String,
     pub private_key: String,
     pub owner_id: Option<String>,
+    pub watch_only: bool,
+    pub private_key_enc: Option<Vec<u8>>,
+}
+
+/// Модель снимка TRC-20 разрешения (allowance) для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = allowance_snapshots)]
+pub struct AllowanceSnapshotModel {
+    pub id: i64,
+    /// Внешний кошелек, выдавший разрешение
+    pub owner_address: String,
+    /// Кому выдано разрешение (мастер-кошелек шлюза)
+    pub spender_address: String,
+    pub token_contract: String,
+    pub allowance: BigDecimal,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Модель для записи нового снимка разрешения
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = allowance_snapshots)]
+pub struct NewAllowanceSnapshot {
+    pub owner_address: String,
+    pub spender_address: String,
+    pub token_contract: String,
+    pub allowance: BigDecimal,
+}
+
+/// Модель пороговой подписки на баланс для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = balance_alerts)]
+pub struct BalanceAlertModel {
+    pub id: i64,
+    /// None - алерт на мастер-кошелек
+    pub wallet_id: Option<i64>,
+    pub asset: String,
+    pub direction: String,
+    pub threshold: BigDecimal,
+    pub hysteresis: BigDecimal,
+    pub enabled: bool,
+    /// Текущее состояние гистерезиса (алерт сработал и не перевзведен)
+    pub triggered: bool,
+    pub last_value: Option<BigDecimal>,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Модель для создания пороговой подписки
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = balance_alerts)]
+pub struct NewBalanceAlert {
+    pub wallet_id: Option<i64>,
+    pub asset: String,
+    pub direction: String,
+    pub threshold: BigDecimal,
+    pub hysteresis: BigDecimal,
+}
+
+/// Модель правила (ноги) маршрутизации депозитов для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = deposit_split_rules)]
+pub struct DepositSplitRuleModel {
+    pub id: i64,
+    pub wallet_id: i64,
+    /// Порядок ноги в наборе (остаток округления уходит последней)
+    pub position: i32,
+    /// 'master' или конкретный TRON адрес
+    pub destination: String,
+    pub percent: BigDecimal,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Модель для создания правила маршрутизации
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = deposit_split_rules)]
+pub struct NewDepositSplitRule {
+    pub wallet_id: i64,
+    pub position: i32,
+    pub destination: String,
+    pub percent: BigDecimal,
+    pub label: Option<String>,
+}
+
+/// Модель учетной записи по ноге разложенного депозита для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = deposit_split_legs)]
+pub struct DepositSplitLegModel {
+    pub id: i64,
+    pub wallet_id: i64,
+    pub deposit_tx_hash: String,
+    pub destination: String,
+    pub percent: BigDecimal,
+    pub amount: BigDecimal,
+    pub transfer_id: Option<i64>,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Модель для создания учетной записи по ноге
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = deposit_split_legs)]
+pub struct NewDepositSplitLeg {
+    pub wallet_id: i64,
+    pub deposit_tx_hash: String,
+    pub destination: String,
+    pub percent: BigDecimal,
+    pub amount: BigDecimal,
+    pub transfer_id: Option<i64>,
+    pub label: Option<String>,
 }
 
 /// Модель входящей транзакции для diesel
@@ -44,6 +172,11 @@ pub struct IncomingTransactionModel {
     pub error_message: Option<String>,
     pub detected_at: DateTime<Utc>,
     pub confirmed_at: Option<DateTime<Utc>>,
+    pub source_label: Option<String>,
+    /// Символ токена депозита (по умолчанию USDT)
+    pub token_symbol: String,
+    /// Адрес TRC-20 контракта токена (NULL - для исторических строк)
+    pub contract_address: Option<String>,
 }
 
 /// Модель для создания новой входящей транзакции
@@ -58,6 +191,393 @@ pub struct NewIncomingTransaction {
     pub amount: BigDecimal,
     pub status: String,
     pub error_message: Option<String>,
+    pub source_label: Option<String>,
+    pub token_symbol: String,
+    pub contract_address: Option<String>,
+}
+
+/// Модель job'а массовой загрузки трансферов для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = ingestion_jobs)]
+pub struct IngestionJobModel {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub status: String,
+    pub rows_total: i64,
+    pub rows_accepted: i64,
+    pub rows_rejected: i64,
+}
+
+/// Модель строки массовой загрузки для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = ingestion_rows)]
+pub struct IngestionRowModel {
+    pub id: i64,
+    pub job_id: i64,
+    pub row_number: i64,
+    pub payload: String,
+    pub status: String,
+    pub transfer_id: Option<i64>,
+    pub error_message: Option<String>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+/// Модель для записи новой строки загрузки
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = ingestion_rows)]
+pub struct NewIngestionRow {
+    pub job_id: i64,
+    pub row_number: i64,
+    pub payload: String,
+    pub status: String,
+    pub error_message: Option<String>,
+}
+
+/// Модель инвойса (payment request) для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = invoices)]
+pub struct InvoiceModel {
+    pub id: i64,
+    pub wallet_id: i64,
+    /// Ссылка интегратора (номер заказа)
+    pub reference: String,
+    pub expected_amount: BigDecimal,
+    /// Накопленная оплата (сумма заматченных депозитов)
+    pub paid_amount: BigDecimal,
+    /// OPEN, PARTIAL, PAID, OVERPAID или EXPIRED
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub paid_at: Option<DateTime<Utc>>,
+}
+
+/// Модель для создания нового инвойса
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = invoices)]
+pub struct NewInvoice {
+    pub wallet_id: i64,
+    pub reference: String,
+    pub expected_amount: BigDecimal,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Модель dead-letter записи мониторинга для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = monitoring_dead_letters)]
+pub struct MonitoringDeadLetterModel {
+    pub id: i64,
+    pub wallet_id: i64,
+    pub tx_hash: String,
+    pub block_number: Option<i64>,
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: BigDecimal,
+    pub confirmations: i32,
+    pub error_message: String,
+    pub attempts: i32,
+    pub resolved: bool,
+    pub next_retry_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: DateTime<Utc>,
+}
+
+/// Модель для создания новой dead-letter записи
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = monitoring_dead_letters)]
+pub struct NewMonitoringDeadLetter {
+    pub wallet_id: i64,
+    pub tx_hash: String,
+    pub block_number: Option<i64>,
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: BigDecimal,
+    pub confirmations: i32,
+    pub error_message: String,
+}
+
+/// Модель платежного намерения для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = payment_intents)]
+pub struct PaymentIntentModel {
+    pub id: i64,
+    pub wallet_id: i64,
+    pub expected_amount: BigDecimal,
+    pub reference_id: Option<String>,
+    pub refund_address: Option<String>,
+    pub status: String,
+    pub matched_tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub shortfall: Option<BigDecimal>,
+    pub amount_encoded: bool,
+    pub metadata: Option<String>,
+}
+
+/// Модель для создания нового платежного намерения
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = payment_intents)]
+pub struct NewPaymentIntent {
+    pub wallet_id: i64,
+    pub expected_amount: BigDecimal,
+    pub reference_id: Option<String>,
+    pub refund_address: Option<String>,
+    pub status: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub amount_encoded: bool,
+    pub metadata: Option<String>,
+}
+
+/// Модель TRC-20 токена для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = tokens)]
+pub struct TokenModel {
+    pub id: i64,
+    pub symbol: String,
+    pub name: String,
+    pub contract_address: String,
+    pub decimals: i16,
+    pub is_stable: bool,
+    pub min_transfer_amount: BigDecimal,
+    pub max_transfer_amount: Option<BigDecimal>,
+    pub enabled: bool,
+    pub icon_url: Option<String>,
+    pub coingecko_id: Option<String>,
+    pub version: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Модель для создания нового токена
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = tokens)]
+pub struct NewToken {
+    pub symbol: String,
+    pub name: String,
+    pub contract_address: String,
+    pub decimals: i16,
+    pub is_stable: bool,
+    pub min_transfer_amount: BigDecimal,
+    pub max_transfer_amount: Option<BigDecimal>,
+    pub enabled: bool,
+    pub icon_url: Option<String>,
+    pub coingecko_id: Option<String>,
+}
+
+/// Модель дневного агрегата использования TronGrid для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = trongrid_usage_daily)]
+pub struct TronGridUsageDailyModel {
+    pub id: i32,
+    pub day: chrono::NaiveDate,
+    pub op: String,
+    pub requests: i64,
+    pub errors: i64,
+    pub total_latency_ms: i64,
+}
+
+/// Модель для записи дневного агрегата использования TronGrid
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = trongrid_usage_daily)]
+pub struct NewTronGridUsageDaily {
+    pub day: chrono::NaiveDate,
+    pub op: String,
+    pub requests: i64,
+    pub errors: i64,
+    pub total_latency_ms: i64,
+}
+
+/// Модель записи TRX отправки для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = trx_transfers)]
+pub struct TrxTransferModel {
+    pub id: i64,
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: BigDecimal,
+    pub purpose: String,
+    pub tx_hash: String,
+    pub related_wallet_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Модель для записи новой TRX отправки
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = trx_transfers)]
+pub struct NewTrxTransfer {
+    pub from_address: String,
+    pub to_address: String,
+    pub amount: BigDecimal,
+    pub purpose: String,
+    pub tx_hash: String,
+    pub related_wallet_id: Option<i64>,
+}
+
+/// Модель wallet-scoped API токена для diesel
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = wallet_api_tokens)]
+pub struct WalletApiTokenModel {
+    pub id: i64,
+    pub wallet_id: i64,
+    pub token_hash: String,
+    pub label: Option<String>,
+    pub revoked: bool,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Модель для выпуска нового wallet-scoped API токена
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = wallet_api_tokens)]
+pub struct NewWalletApiToken {
+    pub wallet_id: i64,
+    pub token_hash: String,
+    pub label: Option<String>,
+}
+
+/// Модель проекции баланса кошелька для diesel
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = wallet_balances)]
+pub struct WalletBalanceModel {
+    pub wallet_id: i64,
+    pub usdt_balance: BigDecimal,
+    pub last_reconciled_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Модель для создания новой записи баланса
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = wallet_balances)]
+pub struct NewWalletBalance {
+    pub wallet_id: i64,
+    pub usdt_balance: BigDecimal,
+}
+
+/// Модель записи персистентного лога webhook событий для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = webhook_events)]
+pub struct WebhookEventModel {
+    pub id: i64,
+    pub event_type: String,
+    pub payload: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Модель для записи нового webhook события
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = webhook_events)]
+pub struct NewWebhookEvent {
+    pub event_type: String,
+    pub payload: String,
+}
+
+/// Модель записи истории запусков планировщика для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = scheduler_runs)]
+pub struct SchedulerRunModel {
+    pub id: i64,
+    pub task_name: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub items_processed: i64,
+    pub outcome: String,
+    pub error_message: Option<String>,
+}
+
+/// Модель для записи нового запуска планировщика
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = scheduler_runs)]
+pub struct NewSchedulerRun {
+    pub task_name: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub items_processed: i64,
+    pub outcome: String,
+    pub error_message: Option<String>,
+}
+
+/// Модель записи истории запусков sweep'а для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = sweep_runs)]
+pub struct SweepRunModel {
+    pub id: i64,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub wallets_scanned: i64,
+    pub wallets_swept: i64,
+    pub total_amount: BigDecimal,
+    pub outcome: String,
+    pub error_message: Option<String>,
+}
+
+/// Модель для записи нового запуска sweep'а
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = sweep_runs)]
+pub struct NewSweepRun {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub wallets_scanned: i64,
+    pub wallets_swept: i64,
+    pub total_amount: BigDecimal,
+    pub outcome: String,
+    pub error_message: Option<String>,
+}
+
+/// Модель записи outbox'а доставки webhook'ов для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDeliveryModel {
+    pub id: i64,
+    pub event_type: String,
+    pub payload: String,
+    pub endpoint_url: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_retry_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Модель для записи новой доставки в outbox
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub event_type: String,
+    pub payload: String,
+    pub endpoint_url: String,
+    pub status: String,
+    pub next_retry_at: Option<DateTime<Utc>>,
+}
+
+/// Модель дополнительного webhook endpoint'а для diesel
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = webhook_endpoints)]
+pub struct WebhookEndpointModel {
+    pub id: i64,
+    pub url: String,
+    /// Подписка: типы событий через запятую, пустая строка = все
+    pub event_types: String,
+    /// Секрет HMAC не отдается наружу - только признак его наличия
+    #[serde(skip_serializing)]
+    pub secret_key: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Секрет, зашифрованный pgcrypto (режим database encryption)
+    #[serde(skip_serializing)]
+    pub secret_key_enc: Option<Vec<u8>>,
+}
+
+/// Модель для регистрации нового webhook endpoint'а
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = webhook_endpoints)]
+pub struct NewWebhookEndpoint {
+    pub url: String,
+    pub event_types: String,
+    pub secret_key: Option<String>,
+    pub enabled: bool,
+    pub secret_key_enc: Option<Vec<u8>>,
 }
 
 /// Модель исходящего трансфера для diesel
@@ -74,6 +594,30 @@ pub struct OutgoingTransferModel {
     pub error_message: Option<String>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub destination_tag: Option<String>,
+    pub risk_score: Option<i32>,
+    pub risk_provider: Option<String>,
+    pub screened_at: Option<DateTime<Utc>>,
+    pub claimed_at: Option<DateTime<Utc>>,
+    pub claimed_by: Option<String>,
+    pub metadata: Option<String>,
+    pub fee_payer: String,
+    pub fee_amount: Option<BigDecimal>,
+    /// Блок, в котором транзакция попала на цепочку (от трекера подтверждений)
+    pub block_number: Option<i64>,
+    /// Вид трансфера: sweep или withdrawal
+    pub transfer_kind: String,
+    /// Под-этап обработки (GAS_SPONSORED/TX_CREATED/SIGNED/BROADCAST) -
+    /// resume point после рестарта, NULL - обработка не начиналась
+    pub processing_stage: Option<String>,
+    /// Символ TRC-20 токена трансфера (по умолчанию USDT)
+    pub token_symbol: String,
+    /// Курс TRX/USDT на момент расчета комиссий (NULL - снимка нет)
+    pub fee_trx_usdt_rate: Option<BigDecimal>,
+    /// Источник курса: имя оракула живого фида или `config`
+    pub fee_rate_source: Option<String>,
+    /// Момент снятия снимка курса
+    pub fee_rate_captured_at: Option<DateTime<Utc>>,
 }
 
 /// Модель для создания нового исходящего трансфера
@@ -85,4 +629,16 @@ pub struct NewOutgoingTransfer {
     pub amount: BigDecimal,
     pub status: String,
     pub reference_id: Option<String>,
+    pub destination_tag: Option<String>,
+    pub risk_score: Option<i32>,
+    pub risk_provider: Option<String>,
+    pub screened_at: Option<DateTime<Utc>>,
+    pub metadata: Option<String>,
+    pub fee_payer: String,
+    pub fee_amount: Option<BigDecimal>,
+    pub transfer_kind: String,
+    pub token_symbol: String,
+    pub fee_trx_usdt_rate: Option<BigDecimal>,
+    pub fee_rate_source: Option<String>,
+    pub fee_rate_captured_at: Option<DateTime<Utc>>,
 }
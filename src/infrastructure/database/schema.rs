@@ -1,5 +1,68 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    allowance_snapshots (id) {
+        id -> Int8,
+        #[max_length = 64]
+        owner_address -> Varchar,
+        #[max_length = 64]
+        spender_address -> Varchar,
+        #[max_length = 64]
+        token_contract -> Varchar,
+        allowance -> Numeric,
+        captured_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    balance_alerts (id) {
+        id -> Int8,
+        wallet_id -> Nullable<Int8>,
+        #[max_length = 8]
+        asset -> Varchar,
+        #[max_length = 8]
+        direction -> Varchar,
+        threshold -> Numeric,
+        hysteresis -> Numeric,
+        enabled -> Bool,
+        triggered -> Bool,
+        last_value -> Nullable<Numeric>,
+        last_triggered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    deposit_split_legs (id) {
+        id -> Int8,
+        wallet_id -> Int8,
+        #[max_length = 128]
+        deposit_tx_hash -> Varchar,
+        #[max_length = 64]
+        destination -> Varchar,
+        percent -> Numeric,
+        amount -> Numeric,
+        transfer_id -> Nullable<Int8>,
+        #[max_length = 64]
+        label -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    deposit_split_rules (id) {
+        id -> Int8,
+        wallet_id -> Int8,
+        position -> Int4,
+        #[max_length = 64]
+        destination -> Varchar,
+        percent -> Numeric,
+        #[max_length = 64]
+        label -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     incoming_transactions (id) {
         id -> Int8,
@@ -17,6 +80,76 @@ diesel::table! {
         error_message -> Nullable<Text>,
         detected_at -> Timestamptz,
         confirmed_at -> Nullable<Timestamptz>,
+        #[max_length = 32]
+        source_label -> Nullable<Varchar>,
+        #[max_length = 16]
+        token_symbol -> Varchar,
+        #[max_length = 64]
+        contract_address -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    ingestion_jobs (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        #[max_length = 16]
+        status -> Varchar,
+        rows_total -> Int8,
+        rows_accepted -> Int8,
+        rows_rejected -> Int8,
+    }
+}
+
+diesel::table! {
+    ingestion_rows (id) {
+        id -> Int8,
+        job_id -> Int8,
+        row_number -> Int8,
+        payload -> Text,
+        #[max_length = 16]
+        status -> Varchar,
+        transfer_id -> Nullable<Int8>,
+        error_message -> Nullable<Text>,
+        processed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    invoices (id) {
+        id -> Int8,
+        wallet_id -> Int8,
+        #[max_length = 128]
+        reference -> Varchar,
+        expected_amount -> Numeric,
+        paid_amount -> Numeric,
+        #[max_length = 16]
+        status -> Varchar,
+        expires_at -> Timestamptz,
+        created_at -> Timestamptz,
+        paid_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    monitoring_dead_letters (id) {
+        id -> Int8,
+        wallet_id -> Int8,
+        #[max_length = 64]
+        tx_hash -> Varchar,
+        block_number -> Nullable<Int8>,
+        #[max_length = 34]
+        from_address -> Varchar,
+        #[max_length = 34]
+        to_address -> Varchar,
+        amount -> Numeric,
+        confirmations -> Int4,
+        error_message -> Text,
+        attempts -> Int4,
+        resolved -> Bool,
+        next_retry_at -> Timestamptz,
+        created_at -> Timestamptz,
+        last_attempt_at -> Timestamptz,
     }
 }
 
@@ -36,6 +169,156 @@ diesel::table! {
         error_message -> Nullable<Text>,
         created_at -> Timestamptz,
         completed_at -> Nullable<Timestamptz>,
+        #[max_length = 64]
+        destination_tag -> Nullable<Varchar>,
+        risk_score -> Nullable<Int4>,
+        #[max_length = 32]
+        risk_provider -> Nullable<Varchar>,
+        screened_at -> Nullable<Timestamptz>,
+        claimed_at -> Nullable<Timestamptz>,
+        #[max_length = 64]
+        claimed_by -> Nullable<Varchar>,
+        metadata -> Nullable<Text>,
+        #[max_length = 16]
+        fee_payer -> Varchar,
+        fee_amount -> Nullable<Numeric>,
+        block_number -> Nullable<Int8>,
+        #[max_length = 16]
+        transfer_kind -> Varchar,
+        #[max_length = 32]
+        processing_stage -> Nullable<Varchar>,
+        #[max_length = 16]
+        token_symbol -> Varchar,
+        fee_trx_usdt_rate -> Nullable<Numeric>,
+        #[max_length = 32]
+        fee_rate_source -> Nullable<Varchar>,
+        fee_rate_captured_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    payment_intents (id) {
+        id -> Int8,
+        wallet_id -> Int8,
+        expected_amount -> Numeric,
+        #[max_length = 128]
+        reference_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        refund_address -> Nullable<Varchar>,
+        #[max_length = 16]
+        status -> Varchar,
+        #[max_length = 128]
+        matched_tx_hash -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        expires_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+        shortfall -> Nullable<Numeric>,
+        amount_encoded -> Bool,
+        metadata -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    scheduler_runs (id) {
+        id -> Int8,
+        #[max_length = 64]
+        task_name -> Varchar,
+        started_at -> Timestamptz,
+        duration_ms -> Int8,
+        items_processed -> Int8,
+        #[max_length = 16]
+        outcome -> Varchar,
+        error_message -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    sweep_runs (id) {
+        id -> Int8,
+        started_at -> Timestamptz,
+        duration_ms -> Int8,
+        wallets_scanned -> Int8,
+        wallets_swept -> Int8,
+        total_amount -> Numeric,
+        #[max_length = 16]
+        outcome -> Varchar,
+        error_message -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    tokens (id) {
+        id -> Int8,
+        #[max_length = 16]
+        symbol -> Varchar,
+        #[max_length = 64]
+        name -> Varchar,
+        #[max_length = 64]
+        contract_address -> Varchar,
+        decimals -> Int2,
+        is_stable -> Bool,
+        min_transfer_amount -> Numeric,
+        max_transfer_amount -> Nullable<Numeric>,
+        enabled -> Bool,
+        #[max_length = 255]
+        icon_url -> Nullable<Varchar>,
+        #[max_length = 64]
+        coingecko_id -> Nullable<Varchar>,
+        version -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    trongrid_usage_daily (id) {
+        id -> Int4,
+        day -> Date,
+        #[max_length = 64]
+        op -> Varchar,
+        requests -> Int8,
+        errors -> Int8,
+        total_latency_ms -> Int8,
+    }
+}
+
+diesel::table! {
+    trx_transfers (id) {
+        id -> Int8,
+        #[max_length = 64]
+        from_address -> Varchar,
+        #[max_length = 64]
+        to_address -> Varchar,
+        amount -> Numeric,
+        #[max_length = 32]
+        purpose -> Varchar,
+        #[max_length = 128]
+        tx_hash -> Varchar,
+        related_wallet_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    wallet_api_tokens (id) {
+        id -> Int8,
+        wallet_id -> Int8,
+        #[max_length = 64]
+        token_hash -> Varchar,
+        #[max_length = 64]
+        label -> Nullable<Varchar>,
+        revoked -> Bool,
+        created_at -> Timestamptz,
+        last_used_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    wallet_balances (wallet_id) {
+        wallet_id -> Int8,
+        usdt_balance -> Numeric,
+        last_reconciled_at -> Nullable<Timestamptz>,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -51,14 +334,97 @@ diesel::table! {
         #[max_length = 255]
         owner_id -> Nullable<Varchar>,
         created_at -> Timestamptz,
+        under_review -> Bool,
+        compliance_notes -> Nullable<Text>,
+        #[max_length = 64]
+        compliance_reviewer -> Nullable<Varchar>,
+        flagged_at -> Nullable<Timestamptz>,
+        archived_at -> Nullable<Timestamptz>,
+        watch_only -> Bool,
+        sweep_enabled -> Bool,
+        sweep_min_threshold -> Nullable<Numeric>,
+        private_key_enc -> Nullable<Bytea>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int8,
+        #[max_length = 64]
+        event_type -> Varchar,
+        payload -> Text,
+        #[max_length = 512]
+        endpoint_url -> Varchar,
+        #[max_length = 16]
+        status -> Varchar,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_retry_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        delivered_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    webhook_endpoints (id) {
+        id -> Int8,
+        #[max_length = 512]
+        url -> Varchar,
+        event_types -> Text,
+        #[max_length = 128]
+        secret_key -> Nullable<Varchar>,
+        enabled -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        secret_key_enc -> Nullable<Bytea>,
+    }
+}
+
+diesel::table! {
+    webhook_events (id) {
+        id -> Int8,
+        #[max_length = 64]
+        event_type -> Varchar,
+        payload -> Text,
+        created_at -> Timestamptz,
     }
 }
 
+diesel::joinable!(balance_alerts -> wallets (wallet_id));
+diesel::joinable!(deposit_split_legs -> outgoing_transfers (transfer_id));
+diesel::joinable!(deposit_split_legs -> wallets (wallet_id));
+diesel::joinable!(deposit_split_rules -> wallets (wallet_id));
 diesel::joinable!(incoming_transactions -> wallets (wallet_id));
+diesel::joinable!(ingestion_rows -> ingestion_jobs (job_id));
+diesel::joinable!(invoices -> wallets (wallet_id));
+diesel::joinable!(monitoring_dead_letters -> wallets (wallet_id));
 diesel::joinable!(outgoing_transfers -> wallets (from_wallet_id));
+diesel::joinable!(payment_intents -> wallets (wallet_id));
+diesel::joinable!(trx_transfers -> wallets (related_wallet_id));
+diesel::joinable!(wallet_api_tokens -> wallets (wallet_id));
+diesel::joinable!(wallet_balances -> wallets (wallet_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    allowance_snapshots,
+    balance_alerts,
+    deposit_split_legs,
+    deposit_split_rules,
     incoming_transactions,
+    ingestion_jobs,
+    ingestion_rows,
+    invoices,
+    monitoring_dead_letters,
     outgoing_transfers,
+    payment_intents,
+    scheduler_runs,
+    sweep_runs,
+    tokens,
+    trongrid_usage_daily,
+    trx_transfers,
+    wallet_api_tokens,
+    wallet_balances,
     wallets,
+    webhook_deliveries,
+    webhook_endpoints,
+    webhook_events,
 );
@@ -0,0 +1,145 @@
+//! # Режим деградации при failover Postgres
+//!
+//! Во время failover БД временно переходит в read-only. Шлюз должен
+//! продолжать отвечать на читающие запросы (проекции балансов, списки,
+//! статусы), а пишущие - отклонять с 503 и Retry-After вместо невнятных
+//! 500. Монитор детектирует read-only ошибки на пишущих путях, включает
+//! режим деградации и выключает его после первой успешной записи.
+//! Очередь отложенных записей в БД невозможна (outbox в той же БД),
+//! поэтому клиентам честно предлагается повторить запрос позже.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// Признаки read-only/failover состояния Postgres в тексте ошибки
+/// (SQLSTATE 25006 "read-only transaction", 57P03 "in recovery mode")
+fn is_read_only_error(message: &str) -> bool {
+    message.contains("read-only transaction")
+        || message.contains("in recovery mode")
+        || message.contains("25006")
+}
+
+/// Детали активного режима деградации
+struct DegradationDetails {
+    since: DateTime<Utc>,
+    reason: String,
+}
+
+/// Снимок состояния деградации для health endpoint'а
+#[derive(Debug, Clone, Serialize)]
+pub struct DegradationSnapshot {
+    pub degraded: bool,
+    pub since: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+/// Монитор режима деградации, разделяемый между handler'ами
+#[derive(Clone)]
+pub struct DegradationMonitor {
+    degraded: Arc<AtomicBool>,
+    details: Arc<Mutex<Option<DegradationDetails>>>,
+}
+
+impl DegradationMonitor {
+    /// Создает монитор в нормальном (не деградированном) состоянии
+    pub fn new() -> Self {
+        Self {
+            degraded: Arc::new(AtomicBool::new(false)),
+            details: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Анализирует ошибку пишущей операции. При признаках read-only БД
+    /// включает режим деградации и возвращает true - handler должен
+    /// ответить 503 вместо 500
+    pub fn record_write_error(&self, message: &str) -> bool {
+        if !is_read_only_error(message) {
+            return false;
+        }
+
+        if !self.degraded.swap(true, Ordering::SeqCst) {
+            warn!("🚨 БД в read-only (failover?): переход в режим деградации");
+            *self.details.lock().unwrap() = Some(DegradationDetails {
+                since: Utc::now(),
+                reason: message.to_string(),
+            });
+        }
+
+        true
+    }
+
+    /// Фиксирует успешную запись - БД снова пишется, выходим из деградации
+    pub fn record_write_success(&self) {
+        if self.degraded.swap(false, Ordering::SeqCst) {
+            info!("✅ Запись в БД восстановлена: выход из режима деградации");
+            *self.details.lock().unwrap() = None;
+        }
+    }
+
+    /// Активен ли режим деградации
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.load(Ordering::SeqCst)
+    }
+
+    /// Снимок состояния для health endpoint'а
+    pub fn snapshot(&self) -> DegradationSnapshot {
+        let details = self.details.lock().unwrap();
+
+        DegradationSnapshot {
+            degraded: self.is_degraded(),
+            since: details.as_ref().map(|d| d.since),
+            reason: details.as_ref().map(|d| d.reason.clone()),
+        }
+    }
+}
+
+impl Default for DegradationMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_error_detection() {
+        assert!(is_read_only_error(
+            "cannot execute INSERT in a read-only transaction"
+        ));
+        assert!(is_read_only_error(
+            "the database system is in recovery mode"
+        ));
+        assert!(!is_read_only_error(
+            "duplicate key value violates unique constraint"
+        ));
+        assert!(!is_read_only_error("connection refused"));
+    }
+
+    #[test]
+    fn test_monitor_enters_and_exits_degraded_mode() {
+        let monitor = DegradationMonitor::new();
+        assert!(!monitor.is_degraded());
+
+        // Обычная ошибка не включает деградацию
+        assert!(!monitor.record_write_error("connection refused"));
+        assert!(!monitor.is_degraded());
+
+        // Read-only ошибка включает
+        assert!(monitor.record_write_error("cannot execute UPDATE in a read-only transaction"));
+        assert!(monitor.is_degraded());
+
+        let snapshot = monitor.snapshot();
+        assert!(snapshot.degraded);
+        assert!(snapshot.since.is_some());
+
+        // Успешная запись выключает
+        monitor.record_write_success();
+        assert!(!monitor.is_degraded());
+        assert!(monitor.snapshot().since.is_none());
+    }
+}
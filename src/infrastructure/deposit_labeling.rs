@@ -0,0 +1,113 @@
+//! # Маркировка источников депозитов
+//!
+//! Для комплаенса и аналитики полезно знать, откуда пришел депозит:
+//! с горячего кошелька биржи, из миксера, с нашего же кошелька или
+//! с приватного кошелька клиента. Известные адреса задаются в
+//! конфигурации, остальные классифицируются эвристиками.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::DepositLabelingConfig;
+
+/// Метка для депозитов с собственных адресов шлюза (sweep, faucet)
+pub const SOURCE_INTERNAL: &str = "internal";
+
+/// Метка по умолчанию для неизвестных адресов
+pub const SOURCE_PRIVATE_WALLET: &str = "private_wallet";
+
+/// Классификатор источников депозитов по from_address
+pub struct DepositSourceLabeler {
+    /// Явные метки известных адресов из конфигурации
+    tags: HashMap<String, String>,
+    /// Собственные адреса шлюза (мастер-кошельки)
+    internal_addresses: HashSet<String>,
+}
+
+impl DepositSourceLabeler {
+    /// Создает классификатор из конфигурации
+    pub fn from_config(config: &DepositLabelingConfig) -> Self {
+        let tags = config
+            .address_tags
+            .iter()
+            .map(|tag| (tag.address.clone(), tag.label.clone()))
+            .collect();
+
+        Self {
+            tags,
+            internal_addresses: HashSet::new(),
+        }
+    }
+
+    /// Помечает собственные адреса шлюза как "internal"
+    pub fn with_internal_addresses(mut self, addresses: Vec<String>) -> Self {
+        self.internal_addresses = addresses.into_iter().collect();
+        self
+    }
+
+    /// Классифицирует источник депозита.
+    /// Явная метка из конфигурации имеет приоритет над эвристиками
+    pub fn label_for(&self, from_address: &str) -> String {
+        if let Some(label) = self.tags.get(from_address) {
+            return label.clone();
+        }
+
+        if self.internal_addresses.contains(from_address) {
+            return SOURCE_INTERNAL.to_string();
+        }
+
+        SOURCE_PRIVATE_WALLET.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AddressTagConfig;
+
+    fn labeler() -> DepositSourceLabeler {
+        DepositSourceLabeler::from_config(&DepositLabelingConfig {
+            address_tags: vec![
+                AddressTagConfig {
+                    address: "TExchangeHotWallet111111111111111".to_string(),
+                    label: "exchange".to_string(),
+                },
+                AddressTagConfig {
+                    address: "TMixer111111111111111111111111111".to_string(),
+                    label: "mixer".to_string(),
+                },
+            ],
+        })
+        .with_internal_addresses(vec!["TMasterWallet11111111111111111111".to_string()])
+    }
+
+    #[test]
+    fn test_configured_tag_wins() {
+        let labeler = labeler();
+        assert_eq!(
+            labeler.label_for("TExchangeHotWallet111111111111111"),
+            "exchange"
+        );
+        assert_eq!(
+            labeler.label_for("TMixer111111111111111111111111111"),
+            "mixer"
+        );
+    }
+
+    #[test]
+    fn test_internal_address_heuristic() {
+        let labeler = labeler();
+        assert_eq!(
+            labeler.label_for("TMasterWallet11111111111111111111"),
+            SOURCE_INTERNAL
+        );
+    }
+
+    #[test]
+    fn test_unknown_address_is_private_wallet() {
+        let labeler = labeler();
+        assert_eq!(
+            labeler.label_for("TUnknownWallet1111111111111111111"),
+            SOURCE_PRIVATE_WALLET
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! # Конвертация Decimal <-> proto
+//!
+//! Хелперы между `rust_decimal::Decimal` и строгим proto-сообщением
+//! `common.v1.Decimal` (units + nanos, масштаб 1e-9). Валидация на
+//! ингестии - кривые значения отклоняются до попадания в сервисы
+
+use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+
+use super::generated::common;
+
+/// Масштаб дробной части: nanos = доли единицы * 1e9
+const NANOS_SCALE: u32 = 9;
+
+/// Границы допустимого nanos: строго меньше одной единицы
+const NANOS_MAX_ABS: i32 = 999_999_999;
+
+/// Конвертирует Decimal в proto-сообщение.
+/// Значение округляется до 9 знаков (суммы шлюза имеют максимум 6)
+pub fn to_proto(value: Decimal) -> common::Decimal {
+    let rounded = value.round_dp(NANOS_SCALE);
+    let units = rounded.trunc().to_i64().unwrap_or(0);
+    let nanos = (rounded.fract() * Decimal::from(10_i64.pow(NANOS_SCALE)))
+        .to_i32()
+        .unwrap_or(0);
+
+    common::Decimal { units, nanos }
+}
+
+/// Восстанавливает Decimal из proto-сообщения с проверкой инвариантов:
+/// nanos в диапазоне ±999999999, знаки units и nanos совпадают
+pub fn from_proto(value: &common::Decimal) -> Result<Decimal, String> {
+    // unsigned_abs: value.nanos.abs() паникует на i32::MIN в debug
+    // и заворачивается в release, пропуская кривое значение
+    if value.nanos.unsigned_abs() > NANOS_MAX_ABS as u32 {
+        return Err(format!(
+            "nanos вне диапазона ±{}: {}",
+            NANOS_MAX_ABS, value.nanos
+        ));
+    }
+
+    if value.units != 0
+        && value.nanos != 0
+        && value.units.signum() != i64::from(value.nanos.signum())
+    {
+        return Err(format!(
+            "знаки units и nanos не совпадают: units={}, nanos={}",
+            value.units, value.nanos
+        ));
+    }
+
+    let fraction = Decimal::new(value.nanos.into(), NANOS_SCALE);
+    Ok(Decimal::from(value.units) + fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let original = Decimal::new(100_500_000, 6); // 100.5
+        let proto = to_proto(original);
+        assert_eq!(proto.units, 100);
+        assert_eq!(proto.nanos, 500_000_000);
+        assert_eq!(from_proto(&proto).unwrap(), original);
+    }
+
+    #[test]
+    fn test_negative_roundtrip() {
+        let original = Decimal::new(-25, 1); // -2.5
+        let proto = to_proto(original);
+        assert_eq!(proto.units, -2);
+        assert_eq!(proto.nanos, -500_000_000);
+        assert_eq!(from_proto(&proto).unwrap(), original);
+    }
+
+    #[test]
+    fn test_from_proto_rejects_invalid() {
+        // nanos больше единицы
+        assert!(from_proto(&common::Decimal {
+            units: 1,
+            nanos: 1_000_000_000
+        })
+        .is_err());
+
+        // Несовпадающие знаки
+        assert!(from_proto(&common::Decimal {
+            units: 1,
+            nanos: -1
+        })
+        .is_err());
+
+        // i32::MIN: abs() здесь не определен, значение должно отклоняться,
+        // а не приниматься как -2.147483648
+        assert!(from_proto(&common::Decimal {
+            units: 0,
+            nanos: i32::MIN
+        })
+        .is_err());
+    }
+}
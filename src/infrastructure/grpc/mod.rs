@@ -2,24 +2,46 @@
 //!
 //! Высокопроизводительный gRPC API для TRON Gateway
 
+pub mod decimal;
 pub mod server;
 pub mod services;
 
-// Включаем сгенерированный код
+// Включаем сгенерированный код. Структура модулей повторяет пакеты
+// proto, чтобы кросс-пакетные ссылки (common.v1.Decimal) резолвились
 pub mod generated {
-    pub mod common {
-        tonic::include_proto!("tron_gateway.common.v1");
-    }
+    pub mod tron_gateway {
+        pub mod common {
+            pub mod v1 {
+                tonic::include_proto!("tron_gateway.common.v1");
+            }
+        }
 
-    pub mod wallet {
-        tonic::include_proto!("tron_gateway.wallet.v1");
-    }
+        pub mod wallet {
+            pub mod v1 {
+                tonic::include_proto!("tron_gateway.wallet.v1");
+            }
+        }
 
-    pub mod transfer {
-        tonic::include_proto!("tron_gateway.transfer.v1");
+        pub mod transfer {
+            pub mod v1 {
+                tonic::include_proto!("tron_gateway.transfer.v1");
+            }
+        }
+
+        pub mod token {
+            pub mod v1 {
+                tonic::include_proto!("tron_gateway.token.v1");
+            }
+        }
     }
+
+    // Короткие алиасы для кода сервисов
+    pub use tron_gateway::common::v1 as common;
+    pub use tron_gateway::token::v1 as token;
+    pub use tron_gateway::transfer::v1 as transfer;
+    pub use tron_gateway::wallet::v1 as wallet;
 }
 
 // Реэкспорт для удобства
-pub use server::GrpcServer;
-pub use services::{GrpcTransferService, GrpcWalletService};
+pub use server::{ApiKeyInterceptor, GrpcServer};
+pub use services::{GrpcTokenService, GrpcTransferService, GrpcWalletService};
@@ -6,14 +6,107 @@ use std::sync::Arc;
 use tonic::transport::Server;
 use tracing::info;
 
+use tonic::{Request, Status};
+
 use crate::application::state::AppState;
 use crate::config::GrpcConfig;
+use crate::infrastructure::middleware::{ApiKeyRegistry, RateLimiter};
 
 use super::generated::{
+    token::token_service_server::TokenServiceServer,
     transfer::transfer_service_server::TransferServiceServer,
     wallet::wallet_service_server::WalletServiceServer,
 };
-use super::services::{GrpcTransferService, GrpcWalletService};
+use super::services::{GrpcTokenService, GrpcTransferService, GrpcWalletService};
+
+/// Interceptor аутентификации по API ключу (метаданные `x-api-key`)
+///
+/// Проверяет только подлинность ключа; область доступа кладется
+/// в extensions запроса, мутирующие RPC проверяют ее сами
+#[derive(Clone)]
+pub struct ApiKeyInterceptor {
+    registry: ApiKeyRegistry,
+    rate_limiter: RateLimiter,
+}
+
+impl ApiKeyInterceptor {
+    pub fn new(registry: ApiKeyRegistry, rate_limiter: RateLimiter) -> Self {
+        Self {
+            registry,
+            rate_limiter,
+        }
+    }
+
+    /// Статус RESOURCE_EXHAUSTED с метаданными троттлинга - gRPC аналог
+    /// заголовков `Retry-After` и `X-RateLimit-*` у HTTP 429
+    fn throttled_status(decision: &crate::infrastructure::middleware::RateLimitDecision) -> Status {
+        let retry_after = decision.reset_seconds.max(1);
+        let mut status = Status::resource_exhausted(format!(
+            "Слишком много запросов, повторите через {} секунд",
+            retry_after
+        ));
+
+        let metadata = status.metadata_mut();
+        let entries = [
+            ("retry-after", retry_after.to_string()),
+            ("x-ratelimit-limit", decision.limit.to_string()),
+            ("x-ratelimit-remaining", decision.remaining.to_string()),
+            ("x-ratelimit-reset", decision.reset_seconds.to_string()),
+        ];
+        for (name, value) in entries {
+            if let Ok(value) = value.parse() {
+                metadata.insert(name, value);
+            }
+        }
+
+        status
+    }
+}
+
+impl tonic::service::Interceptor for ApiKeyInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if !self.registry.is_enabled() {
+            return Ok(request);
+        }
+
+        let provided_key = request
+            .metadata()
+            .get("x-api-key")
+            .and_then(|v| v.to_str().ok())
+            .map(|k| k.to_string());
+
+        let provided_key = provided_key
+            .ok_or_else(|| Status::unauthenticated("Отсутствуют метаданные x-api-key"))?;
+
+        // Тот же rate limiter, что и в HTTP middleware. Interceptor
+        // синхронный, поэтому с Redis-хранилищем проверка пропускается
+        // (fail open) - лимит тогда применяется только на HTTP
+        if self.rate_limiter.is_enabled() {
+            let (subject, max_requests) = self
+                .rate_limiter
+                .resolve_subject(Some(&provided_key), "grpc");
+            if let Some(decision) = self
+                .rate_limiter
+                .check_rate_limit_sync(&subject, max_requests)
+            {
+                if !decision.allowed {
+                    return Err(Self::throttled_status(&decision));
+                }
+            }
+        }
+
+        match self.registry.authorize(&provided_key) {
+            Some(authorized) => {
+                request.extensions_mut().insert(authorized);
+                Ok(request)
+            }
+            None => {
+                tracing::warn!("🚫 gRPC: неизвестный API ключ");
+                Err(Status::unauthenticated("Неизвестный API ключ"))
+            }
+        }
+    }
+}
 
 /// gRPC сервер
 pub struct GrpcServer {
@@ -39,13 +132,28 @@ impl GrpcServer {
         // Создаем сервисы
         let wallet_service = GrpcWalletService::new(self.app_state.clone());
         let transfer_service = GrpcTransferService::new(self.app_state.clone());
+        let token_service = GrpcTokenService::new(self.app_state.clone());
+        let interceptor = ApiKeyInterceptor::new(
+            self.app_state.api_keys.clone(),
+            self.app_state.rate_limiter.clone(),
+        );
 
         info!("🚀 gRPC сервер запускается на {}", addr);
 
         // Запускаем сервер с нашими сервисами
         Server::builder()
-            .add_service(WalletServiceServer::new(wallet_service))
-            .add_service(TransferServiceServer::new(transfer_service))
+            .add_service(WalletServiceServer::with_interceptor(
+                wallet_service,
+                interceptor.clone(),
+            ))
+            .add_service(TransferServiceServer::with_interceptor(
+                transfer_service,
+                interceptor.clone(),
+            ))
+            .add_service(TokenServiceServer::with_interceptor(
+                token_service,
+                interceptor,
+            ))
             .serve(addr)
             .await?;
 
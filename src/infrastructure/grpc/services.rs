@@ -2,14 +2,74 @@
 //!
 //! Реализация gRPC сервисов для TRON Gateway
 
+use std::pin::Pin;
 use std::sync::Arc;
+
+use futures_util::Stream;
+use tokio::sync::broadcast::error::RecvError;
 use tonic::{Request, Response, Status};
 
 use crate::application::dto;
+use crate::application::services::{TransferEventKind, TransferStatusChange};
 use crate::application::state::AppState;
+use crate::domain::DomainError;
+use crate::infrastructure::middleware::AuthorizedApiKey;
 
+use super::decimal;
+use super::generated::token;
 use super::generated::{transfer::*, wallet::*};
 
+/// Проверяет, что API ключ из interceptor'а разрешает мутирующие RPC.
+/// Отсутствие ключа в extensions означает выключенную аутентификацию.
+/// Нарушение возвращается значением - сами RPC решают, когда отклонить запрос
+fn transfer_scope_violation<T>(request: &Request<T>) -> Option<Status> {
+    let key = request.extensions().get::<AuthorizedApiKey>()?;
+    if key.scope.allows_writes() {
+        return None;
+    }
+
+    tracing::warn!(
+        "🚫 gRPC: ключ {} (read) отклонен на мутирующем RPC",
+        key.name
+    );
+    Some(Status::permission_denied(
+        "API ключ со scope read разрешает только чтение",
+    ))
+}
+
+/// Транслирует доменную ошибку в tonic::Status с корректным кодом.
+/// Стабильный машиночитаемый error_code уходит в metadata x-error-code -
+/// тот же код HTTP слой отдает в поле code тела ответа
+fn status_from_domain(err: &DomainError) -> Status {
+    let mut status = match err {
+        DomainError::WalletNotFound { .. }
+        | DomainError::WalletNotFoundByAddress { .. }
+        | DomainError::TransferNotFound { .. } => Status::not_found(err.to_string()),
+        DomainError::TransactionAlreadyExists { .. } => Status::already_exists(err.to_string()),
+        DomainError::InvalidTronAddress { .. } | DomainError::InvalidAmount { .. } => {
+            Status::invalid_argument(err.to_string())
+        }
+        DomainError::InsufficientBalance { .. } => Status::failed_precondition(err.to_string()),
+        DomainError::CryptoError { .. }
+        | DomainError::ConfigurationError { .. }
+        | DomainError::InvariantViolation { .. } => Status::internal(err.to_string()),
+    };
+    status.metadata_mut().insert(
+        "x-error-code",
+        tonic::metadata::MetadataValue::from_static(err.error_code()),
+    );
+    status
+}
+
+/// То же для anyhow-цепочек: доменная ошибка внутри дает точный код,
+/// остальное - internal с fallback-сообщением
+fn domain_status(err: &anyhow::Error, fallback: &str) -> Status {
+    match err.downcast_ref::<DomainError>() {
+        Some(domain_err) => status_from_domain(domain_err),
+        None => Status::internal(fallback.to_string()),
+    }
+}
+
 /// gRPC сервис для кошельков
 pub struct GrpcWalletService {
     app_state: Arc<AppState>,
@@ -28,6 +88,9 @@ impl wallet_service_server::WalletService for GrpcWalletService {
         &self,
         request: Request<CreateWalletRequest>,
     ) -> Result<Response<WalletResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
         let req = request.into_inner();
 
         match self
@@ -42,13 +105,13 @@ impl wallet_service_server::WalletService for GrpcWalletService {
                     address: wallet.address,
                     owner_id: wallet.owner_id,
                     created_at: wallet.created_at.to_rfc3339(),
-                    balance: wallet.balance.map(|b| b.to_string()),
+                    balance: wallet.balance.map(decimal::to_proto),
                 };
                 Ok(Response::new(response))
             }
             Err(err) => {
                 tracing::error!("gRPC: Ошибка создания кошелька: {}", err);
-                Err(Status::internal("Failed to create wallet"))
+                Err(status_from_domain(&err))
             }
         }
     }
@@ -72,7 +135,7 @@ impl wallet_service_server::WalletService for GrpcWalletService {
                     address: wallet.address,
                     owner_id: wallet.owner_id,
                     created_at: wallet.created_at.to_rfc3339(),
-                    balance: wallet.balance.map(|b| b.to_string()),
+                    balance: wallet.balance.map(decimal::to_proto),
                 };
                 Ok(Response::new(response))
             }
@@ -84,6 +147,59 @@ impl wallet_service_server::WalletService for GrpcWalletService {
         }
     }
 
+    /// Список кошельков с фильтрами и пагинацией
+    async fn list_wallets(
+        &self,
+        request: Request<ListWalletsRequest>,
+    ) -> Result<Response<ListWalletsResponse>, Status> {
+        let req = request.into_inner();
+
+        let parse_ts = |value: Option<&str>, field: &str| {
+            value
+                .map(|raw| {
+                    chrono::DateTime::parse_from_rfc3339(raw)
+                        .map(|ts| ts.with_timezone(&chrono::Utc))
+                        .map_err(|_| format!("Невалидный RFC 3339 в {}", field))
+                })
+                .transpose()
+        };
+
+        let filter = crate::application::services::WalletListFilter {
+            owner_id: req.owner_id,
+            created_from: parse_ts(req.created_from.as_deref(), "created_from")
+                .map_err(Status::invalid_argument)?,
+            created_to: parse_ts(req.created_to.as_deref(), "created_to")
+                .map_err(Status::invalid_argument)?,
+            address_prefix: req.address_prefix,
+            limit: (req.limit > 0).then_some(req.limit as i64),
+            offset: (req.offset > 0).then_some(req.offset as i64),
+        };
+
+        match self.app_state.wallet_service.list_wallets(filter).await {
+            Ok((wallets, total_count)) => {
+                let wallets = wallets
+                    .into_iter()
+                    .map(|wallet| WalletResponse {
+                        id: wallet.id,
+                        address: wallet.address,
+                        owner_id: wallet.owner_id,
+                        created_at: wallet.created_at.to_rfc3339(),
+                        balance: wallet.balance.map(decimal::to_proto),
+                    })
+                    .collect();
+
+                Ok(Response::new(ListWalletsResponse {
+                    wallets,
+                    total_count,
+                }))
+            }
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка списка кошельков: {}", err);
+                Err(status_from_domain(&err))
+            }
+        }
+    }
+
     /// Получение баланса кошелька
     async fn get_wallet_balance(
         &self,
@@ -100,8 +216,8 @@ impl wallet_service_server::WalletService for GrpcWalletService {
             Ok((usdt_balance, trx_balance)) => {
                 let response = WalletBalanceResponse {
                     wallet_id: req.wallet_id,
-                    usdt_balance: usdt_balance.to_string(),
-                    trx_balance: trx_balance.to_string(),
+                    usdt_balance: Some(decimal::to_proto(usdt_balance)),
+                    trx_balance: Some(decimal::to_proto(trx_balance)),
                 };
                 Ok(Response::new(response))
             }
@@ -117,6 +233,9 @@ impl wallet_service_server::WalletService for GrpcWalletService {
         &self,
         request: Request<ActivateWalletRequest>,
     ) -> Result<Response<ActivateWalletResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
         let req = request.into_inner();
 
         match self
@@ -168,7 +287,7 @@ impl wallet_service_server::WalletService for GrpcWalletService {
                         id: t.id,
                         tx_hash: t.tx_hash.unwrap_or_default(),
                         status: format!("{:?}", t.status),
-                        amount: t.amount.to_string(),
+                        amount: Some(decimal::to_proto(t.amount)),
                         created_at: t.created_at.to_rfc3339(),
                     })
                     .collect();
@@ -209,13 +328,16 @@ impl transfer_service_server::TransferService for GrpcTransferService {
         let req = request.into_inner();
 
         // Конвертируем из gRPC в наш DTO
+        let order_amount = req
+            .order_amount
+            .as_ref()
+            .ok_or_else(|| Status::invalid_argument("order_amount is required"))?;
         let transfer_request = dto::TransferRequest {
             from_wallet_id: req.from_wallet_id,
-            order_amount: req
-                .order_amount
-                .parse()
-                .map_err(|_| Status::invalid_argument("Invalid order amount"))?,
+            order_amount: decimal::from_proto(order_amount)
+                .map_err(|e| Status::invalid_argument(format!("Invalid order_amount: {}", e)))?,
             reference_id: req.reference_id,
+            fee_payer: None, // gRPC контракт пока не содержит fee_payer
         };
 
         match self
@@ -226,14 +348,24 @@ impl transfer_service_server::TransferService for GrpcTransferService {
         {
             Ok(preview) => {
                 let response = TransferPreviewResponse {
-                    order_amount: preview.order_amount.to_string(),
-                    commission: preview.commission.to_string(),
-                    gas_cost_in_usdt: preview.gas_cost_in_usdt.to_string(),
-                    percentage_commission: preview.percentage_commission.to_string(),
-                    total_amount: preview.total_amount.to_string(),
-                    master_wallet_receives: preview.master_wallet_receives.to_string(),
+                    order_amount: Some(decimal::to_proto(preview.order_amount)),
+                    commission: Some(decimal::to_proto(preview.commission)),
+                    gas_cost_in_usdt: Some(decimal::to_proto(preview.gas_cost_in_usdt)),
+                    percentage_commission: Some(decimal::to_proto(preview.percentage_commission)),
+                    total_amount: Some(decimal::to_proto(preview.total_amount)),
+                    master_wallet_receives: Some(decimal::to_proto(preview.master_wallet_receives)),
                     breakdown: preview.breakdown,
-                    trx_to_usdt_rate: preview.trx_to_usdt_rate.to_string(),
+                    breakdown_items: preview
+                        .breakdown_items
+                        .into_iter()
+                        .map(|item| BreakdownItem {
+                            label: item.label,
+                            amount: Some(decimal::to_proto(item.amount)),
+                            currency: item.currency,
+                            kind: item.kind,
+                        })
+                        .collect(),
+                    trx_to_usdt_rate: Some(decimal::to_proto(preview.trx_to_usdt_rate)),
                     from_wallet_id: preview.from_wallet_id,
                     reference_id: preview.reference_id,
                 };
@@ -251,16 +383,24 @@ impl transfer_service_server::TransferService for GrpcTransferService {
         &self,
         request: Request<CreateTransferRequest>,
     ) -> Result<Response<TransferResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
         let req = request.into_inner();
 
         // Конвертируем из gRPC в наш DTO
+        let order_amount = req
+            .order_amount
+            .as_ref()
+            .ok_or_else(|| Status::invalid_argument("order_amount is required"))?;
         let transfer_request = dto::CreateTransferRequest {
             from_wallet_id: req.from_wallet_id,
-            order_amount: req
-                .order_amount
-                .parse()
-                .map_err(|_| Status::invalid_argument("Invalid order amount"))?,
+            order_amount: decimal::from_proto(order_amount)
+                .map_err(|e| Status::invalid_argument(format!("Invalid order_amount: {}", e)))?,
             reference_id: req.reference_id,
+            destination_tag: None, // gRPC контракт пока не содержит destination_tag
+            metadata: None,        // gRPC контракт пока не содержит metadata
+            fee_payer: None,       // gRPC контракт пока не содержит fee_payer
             preview_only: req.preview_only,
         };
 
@@ -275,7 +415,7 @@ impl transfer_service_server::TransferService for GrpcTransferService {
                     id: transfer.id,
                     from_wallet_id: transfer.from_wallet_id,
                     to_address: transfer.to_address,
-                    amount: transfer.amount.to_string(),
+                    amount: Some(decimal::to_proto(transfer.amount)),
                     status: format!("{:?}", transfer.status),
                     tx_hash: transfer.tx_hash,
                     reference_id: transfer.reference_id,
@@ -287,7 +427,61 @@ impl transfer_service_server::TransferService for GrpcTransferService {
             }
             Err(err) => {
                 tracing::error!("gRPC: Ошибка создания трансфера: {}", err);
-                Err(Status::internal("Failed to create transfer"))
+                Err(domain_status(&err, "Failed to create transfer"))
+            }
+        }
+    }
+
+    /// Создание вывода USDT на произвольный адрес
+    async fn create_withdrawal(
+        &self,
+        request: Request<CreateWithdrawalRequest>,
+    ) -> Result<Response<TransferResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
+        let req = request.into_inner();
+
+        let amount = req
+            .amount
+            .as_ref()
+            .ok_or_else(|| Status::invalid_argument("amount is required"))?;
+        let withdrawal_request = dto::CreateWithdrawalRequest {
+            from_wallet_id: req.from_wallet_id,
+            to_address: req.to_address,
+            amount: decimal::from_proto(amount)
+                .map_err(|e| Status::invalid_argument(format!("Invalid amount: {}", e)))?,
+            reference_id: req.reference_id,
+            destination_tag: None, // gRPC контракт пока не содержит destination_tag
+            metadata: None,        // gRPC контракт пока не содержит metadata
+            fee_payer: None,       // gRPC контракт пока не содержит fee_payer
+            token_symbol: None,    // вывод USDT; токены - через TokenService
+        };
+
+        match self
+            .app_state
+            .transfer_service
+            .create_withdrawal(withdrawal_request)
+            .await
+        {
+            Ok(transfer) => {
+                let response = TransferResponse {
+                    id: transfer.id,
+                    from_wallet_id: transfer.from_wallet_id,
+                    to_address: transfer.to_address,
+                    amount: Some(decimal::to_proto(transfer.amount)),
+                    status: format!("{:?}", transfer.status),
+                    tx_hash: transfer.tx_hash,
+                    reference_id: transfer.reference_id,
+                    error_message: transfer.error_message,
+                    created_at: transfer.created_at.to_rfc3339(),
+                    completed_at: transfer.completed_at.map(|dt| dt.to_rfc3339()),
+                };
+                Ok(Response::new(response))
+            }
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка создания вывода: {}", err);
+                Err(domain_status(&err, "Failed to create withdrawal"))
             }
         }
     }
@@ -310,7 +504,7 @@ impl transfer_service_server::TransferService for GrpcTransferService {
                     id: transfer.id,
                     from_wallet_id: transfer.from_wallet_id,
                     to_address: transfer.to_address,
-                    amount: transfer.amount.to_string(),
+                    amount: Some(decimal::to_proto(transfer.amount)),
                     status: format!("{:?}", transfer.status),
                     tx_hash: transfer.tx_hash,
                     reference_id: transfer.reference_id,
@@ -346,7 +540,7 @@ impl transfer_service_server::TransferService for GrpcTransferService {
                     id: transfer.id,
                     from_wallet_id: transfer.from_wallet_id,
                     to_address: transfer.to_address,
-                    amount: transfer.amount.to_string(),
+                    amount: Some(decimal::to_proto(transfer.amount)),
                     status: format!("{:?}", transfer.status),
                     tx_hash: transfer.tx_hash,
                     reference_id: transfer.reference_id,
@@ -384,7 +578,7 @@ impl transfer_service_server::TransferService for GrpcTransferService {
                         id: transfer.id,
                         from_wallet_id: transfer.from_wallet_id,
                         to_address: transfer.to_address,
-                        amount: transfer.amount.to_string(),
+                        amount: Some(decimal::to_proto(transfer.amount)),
                         status: format!("{:?}", transfer.status),
                         tx_hash: transfer.tx_hash,
                         reference_id: transfer.reference_id,
@@ -411,8 +605,12 @@ impl transfer_service_server::TransferService for GrpcTransferService {
     /// Обработка pending трансферов
     async fn process_pending_transfers(
         &self,
-        _request: Request<ProcessPendingTransfersRequest>,
+        request: Request<ProcessPendingTransfersRequest>,
     ) -> Result<Response<ProcessPendingTransfersResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
+
         match self
             .app_state
             .transfer_service
@@ -432,4 +630,343 @@ impl transfer_service_server::TransferService for GrpcTransferService {
             }
         }
     }
+
+    type WatchTransferStream = EventStream;
+
+    /// Стрим переходов статуса одного трансфера
+    async fn watch_transfer(
+        &self,
+        request: Request<WatchTransferRequest>,
+    ) -> Result<Response<Self::WatchTransferStream>, Status> {
+        let transfer_id = request.into_inner().transfer_id;
+
+        // Подписка до чтения снимка: переход между снимком и подпиской
+        // не потеряется
+        let mut events = self.app_state.transfer_events.subscribe();
+
+        let transfer = match self
+            .app_state
+            .transfer_service
+            .get_transfer(transfer_id)
+            .await
+        {
+            Ok(Some(transfer)) => transfer,
+            Ok(None) => return Err(Status::not_found("Transfer not found")),
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка получения трансфера: {}", err);
+                return Err(Status::internal("Failed to get transfer"));
+            }
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+
+        tokio::spawn(async move {
+            // Первое событие - текущий статус из БД
+            let terminal = transfer.status.is_terminal();
+            let snapshot = TransferStatusEvent {
+                kind: TransferEventKind::Outgoing.as_str().to_string(),
+                record_id: transfer.id,
+                wallet_id: transfer.from_wallet_id,
+                status: transfer.status.as_db_str().to_string(),
+                tx_hash: transfer.tx_hash,
+                occurred_at: transfer.created_at.to_rfc3339(),
+            };
+            if tx.send(Ok(snapshot)).await.is_err() || terminal {
+                return;
+            }
+
+            loop {
+                let change = match events.recv().await {
+                    Ok(change) => change,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            "⚠️  WatchTransfer({}) отстал на {} событий",
+                            transfer_id,
+                            skipped
+                        );
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+
+                if change.kind != TransferEventKind::Outgoing || change.record_id != transfer_id {
+                    continue;
+                }
+
+                let terminal = change.status.is_terminal();
+                if tx.send(Ok(status_event(change))).await.is_err() || terminal {
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(channel_stream(rx)))
+    }
+
+    type WatchWalletEventsStream = EventStream;
+
+    /// Стрим событий кошелька: исходящие трансферы и входящие депозиты
+    async fn watch_wallet_events(
+        &self,
+        request: Request<WatchWalletEventsRequest>,
+    ) -> Result<Response<Self::WatchWalletEventsStream>, Status> {
+        let wallet_id = request.into_inner().wallet_id;
+
+        let mut events = self.app_state.transfer_events.subscribe();
+
+        match self.app_state.wallet_service.get_wallet(wallet_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Err(Status::not_found("Wallet not found")),
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка получения кошелька: {}", err);
+                return Err(Status::internal("Failed to get wallet"));
+            }
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+
+        tokio::spawn(async move {
+            loop {
+                let change = match events.recv().await {
+                    Ok(change) => change,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            "⚠️  WatchWalletEvents({}) отстал на {} событий",
+                            wallet_id,
+                            skipped
+                        );
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+
+                if change.wallet_id != wallet_id {
+                    continue;
+                }
+
+                // Стрим кошелька живет до отмены клиентом
+                if tx.send(Ok(status_event(change))).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(channel_stream(rx)))
+    }
+}
+
+/// Тип стриминговых ответов WatchTransfer/WatchWalletEvents
+type EventStream = Pin<Box<dyn Stream<Item = Result<TransferStatusEvent, Status>> + Send>>;
+
+/// Конвертирует событие шины в proto сообщение
+fn status_event(change: TransferStatusChange) -> TransferStatusEvent {
+    TransferStatusEvent {
+        kind: change.kind.as_str().to_string(),
+        record_id: change.record_id,
+        wallet_id: change.wallet_id,
+        status: change.status.as_db_str().to_string(),
+        tx_hash: change.tx_hash,
+        occurred_at: change.occurred_at.to_rfc3339(),
+    }
+}
+
+/// Оборачивает mpsc приемник в Stream для tonic
+fn channel_stream(
+    receiver: tokio::sync::mpsc::Receiver<Result<TransferStatusEvent, Status>>,
+) -> EventStream {
+    Box::pin(futures_util::stream::unfold(
+        receiver,
+        |mut rx| async move { rx.recv().await.map(|item| (item, rx)) },
+    ))
+}
+
+/// gRPC сервис мультитокенных операций (TRC-20 помимо USDT)
+pub struct GrpcTokenService {
+    app_state: Arc<AppState>,
+}
+
+impl GrpcTokenService {
+    pub fn new(app_state: Arc<AppState>) -> Self {
+        Self { app_state }
+    }
+}
+
+/// Конвертирует доменное описание токена в proto сообщение
+fn token_info_proto(info: &crate::domain::tokens::TokenInfo) -> token::TokenInfo {
+    token::TokenInfo {
+        symbol: info.symbol.clone(),
+        name: info.name.clone(),
+        contract_address: info.contract_address.clone(),
+        decimals: u32::from(info.decimals),
+        is_stable: info.is_stable,
+        min_transfer_amount: Some(decimal::to_proto(info.min_transfer_amount)),
+        max_transfer_amount: info.max_transfer_amount.map(decimal::to_proto),
+        enabled: info.enabled,
+        icon_url: info.icon_url.clone(),
+    }
+}
+
+#[tonic::async_trait]
+impl token::token_service_server::TokenService for GrpcTokenService {
+    /// Список поддерживаемых токенов
+    async fn list_supported_tokens(
+        &self,
+        _request: Request<token::ListSupportedTokensRequest>,
+    ) -> Result<Response<token::ListSupportedTokensResponse>, Status> {
+        let tokens = self.app_state.trc20_service.get_supported_tokens().await;
+
+        let response = token::ListSupportedTokensResponse {
+            total_count: tokens.len() as i32,
+            tokens: tokens.iter().map(token_info_proto).collect(),
+        };
+        Ok(Response::new(response))
+    }
+
+    /// Балансы всех включенных токенов для адреса
+    async fn get_multi_token_balance(
+        &self,
+        request: Request<token::GetMultiTokenBalanceRequest>,
+    ) -> Result<Response<token::MultiTokenBalanceResponse>, Status> {
+        let req = request.into_inner();
+
+        match self
+            .app_state
+            .trc20_service
+            .get_multi_token_balance(&req.wallet_address)
+            .await
+        {
+            Ok(multi_balance) => {
+                let balances = multi_balance
+                    .balances
+                    .into_values()
+                    .map(|balance| token::TokenBalance {
+                        token_symbol: balance.token_symbol,
+                        balance: Some(decimal::to_proto(balance.balance)),
+                        balance_wei: balance.balance_wei,
+                        last_updated: balance.last_updated.to_rfc3339(),
+                    })
+                    .collect();
+
+                let response = token::MultiTokenBalanceResponse {
+                    wallet_address: multi_balance.wallet_address,
+                    balances,
+                    total_usd_value: multi_balance.total_usd_value.map(decimal::to_proto),
+                };
+                Ok(Response::new(response))
+            }
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка получения мультитокенных балансов: {}", err);
+                Err(domain_status(&err, "Failed to get multi-token balance"))
+            }
+        }
+    }
+
+    /// Создание трансфера произвольного поддерживаемого токена
+    async fn create_token_transfer(
+        &self,
+        request: Request<token::CreateTokenTransferRequest>,
+    ) -> Result<Response<token::TokenTransferResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
+        let req = request.into_inner();
+
+        let amount = req
+            .amount
+            .as_ref()
+            .ok_or_else(|| Status::invalid_argument("Не указана сумма (amount)"))?;
+        let amount = decimal::from_proto(amount).map_err(Status::invalid_argument)?;
+
+        // Назначение: из запроса или настроенное sweep-назначение токена
+        let to_address = match req.to_address.clone().or_else(|| {
+            self.app_state
+                .trc20_service
+                .sweep_destination_for(&req.token_symbol)
+                .map(|s| s.to_string())
+        }) {
+            Some(address) => address,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "Не указан to_address, и для токена {} не настроено sweep-назначение",
+                    req.token_symbol
+                )));
+            }
+        };
+
+        // Вывод токена идет обычным пайплайном обработки pending трансферов
+        let withdrawal_request = dto::CreateWithdrawalRequest {
+            from_wallet_id: req.from_wallet_id,
+            to_address,
+            amount,
+            reference_id: req.reference_id,
+            destination_tag: None,
+            metadata: None,
+            fee_payer: None,
+            token_symbol: Some(req.token_symbol.clone()),
+        };
+
+        match self
+            .app_state
+            .transfer_service
+            .create_withdrawal(withdrawal_request)
+            .await
+        {
+            Ok(transfer) => {
+                let response = token::TokenTransferResponse {
+                    token_symbol: transfer.token_symbol,
+                    from_wallet_id: transfer.from_wallet_id,
+                    to_address: transfer.to_address,
+                    amount: Some(decimal::to_proto(transfer.amount)),
+                    status: format!("{:?}", transfer.status),
+                    tx_id: transfer.tx_hash,
+                    reference_id: transfer.reference_id,
+                    created_at: transfer.created_at.to_rfc3339(),
+                };
+                Ok(Response::new(response))
+            }
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка создания токенового трансфера: {}", err);
+                Err(domain_status(&err, "Failed to create token transfer"))
+            }
+        }
+    }
+
+    /// Включение/отключение токена
+    async fn set_token_enabled(
+        &self,
+        request: Request<token::SetTokenEnabledRequest>,
+    ) -> Result<Response<token::SetTokenEnabledResponse>, Status> {
+        if let Some(status) = transfer_scope_violation(&request) {
+            return Err(status);
+        }
+        let req = request.into_inner();
+
+        match self
+            .app_state
+            .trc20_service
+            .set_token_enabled(&req.token_symbol, req.enabled)
+            .await
+        {
+            Ok(_) => {
+                let response = token::SetTokenEnabledResponse {
+                    token_symbol: req.token_symbol.clone(),
+                    enabled: req.enabled,
+                    message: format!(
+                        "Токен {} {}",
+                        req.token_symbol,
+                        if req.enabled {
+                            "включен"
+                        } else {
+                            "отключен"
+                        }
+                    ),
+                };
+                Ok(Response::new(response))
+            }
+            Err(err) => {
+                tracing::error!("gRPC: Ошибка изменения статуса токена: {}", err);
+                Err(Status::not_found(format!("Ошибка: {}", err)))
+            }
+        }
+    }
 }
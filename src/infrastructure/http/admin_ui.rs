@@ -0,0 +1,55 @@
+//! # Встроенный админ UI
+//!
+//! Минимальный админский интерфейс для небольших операторов без
+//! отдельного фронтенда: кошельки, трансферы в обработке, баланс
+//! мастер-кошелька и лог webhook событий поверх существующих JSON
+//! endpoint'ов. Статика вшита в бинарник и отдается под /admin
+//! только при настроенном ключе `server.admin_api_key`
+
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// Страница админ UI, вшитая в бинарник
+const ADMIN_INDEX_HTML: &str = include_str!("admin_ui/index.html");
+
+/// Query параметры страницы админ UI
+#[derive(Debug, serde::Deserialize)]
+pub struct AdminUiQuery {
+    /// Ключ доступа (альтернатива заголовку для открытия из браузера)
+    pub key: Option<String>,
+}
+
+/// Проверяет ключ из заголовка `X-Admin-Key` или query-параметра `key`
+fn is_authorized(expected: &str, req: &HttpRequest, query: &AdminUiQuery) -> bool {
+    let header_key = req
+        .headers()
+        .get("X-Admin-Key")
+        .and_then(|value| value.to_str().ok());
+
+    header_key == Some(expected) || query.key.as_deref() == Some(expected)
+}
+
+/// GET /admin - встроенная админ-страница.
+/// Без настроенного ключа UI выключен и отвечает 404
+pub async fn serve_admin_ui(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<AdminUiQuery>,
+) -> Result<HttpResponse> {
+    let Some(expected) = app_state.admin_api_key.as_deref() else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    if !is_authorized(expected, &req, &query) {
+        return Ok(HttpResponse::Unauthorized().json(json!({
+            "error": "Невалидный ключ админ UI",
+            "hint": "Передайте ключ в заголовке X-Admin-Key или параметре ?key="
+        })));
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(ADMIN_INDEX_HTML))
+}
@@ -0,0 +1,150 @@
+//! # Унифицированные ошибки HTTP API
+//!
+//! `ApiError` приводит ответы об ошибках к единому структурированному
+//! виду: корректный HTTP статус по варианту `DomainError` (404 для
+//! не найденного кошелька, 422 для невалидной суммы, 409 для дублей)
+//! и стабильный машиночитаемый код из `DomainError::error_code` -
+//! тот же код gRPC слой кладет в metadata x-error-code
+
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use serde_json::json;
+
+use crate::domain::DomainError;
+
+/// Структурированная ошибка HTTP API
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    /// Стабильный машиночитаемый код (wallet_not_found, invalid_amount...)
+    code: &'static str,
+    /// Человекочитаемое описание операции, которая не удалась
+    message: String,
+    /// Детали конкретной ошибки
+    details: Option<String>,
+}
+
+impl ApiError {
+    /// Ошибка из доменного варианта: статус и код по таблице маппинга
+    pub fn from_domain(err: &DomainError, message: impl Into<String>) -> Self {
+        let status = match err {
+            DomainError::WalletNotFound { .. }
+            | DomainError::WalletNotFoundByAddress { .. }
+            | DomainError::TransferNotFound { .. } => StatusCode::NOT_FOUND,
+            DomainError::TransactionAlreadyExists { .. } => StatusCode::CONFLICT,
+            DomainError::InvalidTronAddress { .. }
+            | DomainError::InvalidAmount { .. }
+            | DomainError::InsufficientBalance { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            DomainError::CryptoError { .. }
+            | DomainError::ConfigurationError { .. }
+            | DomainError::InvariantViolation { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status,
+            code: err.error_code(),
+            message: message.into(),
+            details: Some(err.to_string()),
+        }
+    }
+
+    /// Ошибка из anyhow: доменная ошибка внутри цепочки дает точный
+    /// статус и код, остальное - 500 internal_error
+    pub fn from_anyhow(err: &anyhow::Error, message: impl Into<String>) -> Self {
+        match err.downcast_ref::<DomainError>() {
+            Some(domain_err) => Self::from_domain(domain_err, message),
+            None => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "internal_error",
+                message: message.into(),
+                details: Some(err.to_string()),
+            },
+        }
+    }
+
+    /// 404 для ресурсов, отсутствие которых видно без доменной ошибки
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Стабильный код ошибки
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Готовый HTTP ответ (для handlers, отвечающих Ok(HttpResponse))
+    pub fn to_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status).json(json!({
+            "error": self.message,
+            "code": self.code,
+            "details": self.details,
+        }))
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.details {
+            Some(details) => write!(f, "{}: {}", self.message, details),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        self.to_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_errors_map_to_correct_statuses() {
+        let not_found = ApiError::from_domain(&DomainError::WalletNotFound { id: 1 }, "ошибка");
+        assert_eq!(not_found.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(not_found.code(), "wallet_not_found");
+
+        let invalid = ApiError::from_domain(
+            &DomainError::InvalidAmount {
+                amount: rust_decimal::Decimal::ZERO,
+            },
+            "ошибка",
+        );
+        assert_eq!(invalid.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(invalid.code(), "invalid_amount");
+
+        let duplicate = ApiError::from_domain(
+            &DomainError::TransactionAlreadyExists {
+                hash: "abc".to_string(),
+            },
+            "ошибка",
+        );
+        assert_eq!(duplicate.status_code(), StatusCode::CONFLICT);
+        assert_eq!(duplicate.code(), "transaction_already_exists");
+    }
+
+    #[test]
+    fn test_anyhow_unwraps_domain_error_or_falls_back() {
+        let domain: anyhow::Error = DomainError::TransferNotFound { id: 7 }.into();
+        let mapped = ApiError::from_anyhow(&domain, "ошибка");
+        assert_eq!(mapped.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(mapped.code(), "transfer_not_found");
+
+        let opaque = anyhow::anyhow!("сеть недоступна");
+        let fallback = ApiError::from_anyhow(&opaque, "ошибка");
+        assert_eq!(fallback.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(fallback.code(), "internal_error");
+    }
+}
@@ -0,0 +1,96 @@
+//! # Обработчики пороговых подписок на балансы
+//!
+//! HTTP handlers для регистрации и управления balance-алертами
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::services::CreateBalanceAlert;
+use crate::application::state::AppState;
+
+/// Тело запроса регистрации пороговой подписки
+#[derive(Debug, Deserialize)]
+pub struct CreateBalanceAlertRequest {
+    /// Кошелек подписки; отсутствие - алерт на мастер-кошелек
+    pub wallet_id: Option<i64>,
+    /// USDT или TRX
+    pub asset: String,
+    /// above (значение >= порога) или below (значение <= порога)
+    pub direction: String,
+    pub threshold: rust_decimal::Decimal,
+    /// Мертвая зона перевзвода против дребезга (по умолчанию 0)
+    pub hysteresis: Option<rust_decimal::Decimal>,
+}
+
+/// POST /api/alerts/balance - регистрация пороговой подписки
+pub async fn create_balance_alert(
+    app_state: web::Data<AppState>,
+    body: web::Json<CreateBalanceAlertRequest>,
+) -> Result<HttpResponse> {
+    let request = body.into_inner();
+
+    match app_state
+        .balance_alert_service
+        .create_alert(CreateBalanceAlert {
+            wallet_id: request.wallet_id,
+            asset: request.asset,
+            direction: request.direction,
+            threshold: request.threshold,
+            hysteresis: request.hysteresis,
+        })
+        .await
+    {
+        Ok(alert) => Ok(HttpResponse::Created().json(alert)),
+        Err(err) => {
+            tracing::error!("Ошибка регистрации алерта баланса: {}", err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось зарегистрировать алерт",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/alerts/balance - список пороговых подписок
+pub async fn list_balance_alerts(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    match app_state.balance_alert_service.list_alerts().await {
+        Ok(alerts) => Ok(HttpResponse::Ok().json(json!({
+            "count": alerts.len(),
+            "alerts": alerts
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка получения алертов баланса: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить список алертов",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// DELETE /api/alerts/balance/{alert_id} - удаление подписки
+pub async fn delete_balance_alert(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let alert_id = path.into_inner();
+
+    match app_state.balance_alert_service.delete_alert(alert_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(json!({
+            "alert_id": alert_id,
+            "deleted": true
+        }))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(json!({
+            "error": "Алерт не найден",
+            "alert_id": alert_id
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка удаления алерта {}: {}", alert_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось удалить алерт",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
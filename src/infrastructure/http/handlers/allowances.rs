@@ -0,0 +1,120 @@
+//! # Обработчики TRC-20 разрешений (pull-платежи)
+//!
+//! Список разрешений, выданных шлюзу внешними кошельками,
+//! on-chain проверка с записью снимка и подготовка транзакций отзыва
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// GET /api/allowances - последний снимок разрешения каждого владельца
+pub async fn list_allowances(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    match app_state.allowance_service.list_allowances().await {
+        Ok(allowances) => Ok(HttpResponse::Ok().json(json!({
+            "count": allowances.len(),
+            "allowances": allowances
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка списка разрешений: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить список разрешений",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// POST /api/allowances/{owner_address}/check - on-chain проверка
+/// разрешения с записью снимка; reduced=true сигналит о снижении
+pub async fn check_allowance(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let owner_address = path.into_inner();
+
+    match app_state
+        .allowance_service
+        .check_allowance(&owner_address)
+        .await
+    {
+        Ok(check) => Ok(HttpResponse::Ok().json(json!({
+            "allowance": check
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка проверки разрешения {}: {}", owner_address, err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось проверить разрешение",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Query параметры истории снимков разрешения
+#[derive(Debug, Deserialize)]
+pub struct AllowanceHistoryQuery {
+    /// Размер выборки (по умолчанию 50, максимум 500)
+    pub limit: Option<i64>,
+}
+
+/// GET /api/allowances/{owner_address}/history - история снимков
+/// разрешения владельца для аудита (новые первыми)
+pub async fn get_allowance_history(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<AllowanceHistoryQuery>,
+) -> Result<HttpResponse> {
+    let owner_address = path.into_inner();
+
+    match app_state
+        .allowance_service
+        .allowance_history(&owner_address, query.limit.unwrap_or(50))
+        .await
+    {
+        Ok(snapshots) => Ok(HttpResponse::Ok().json(json!({
+            "owner_address": owner_address,
+            "count": snapshots.len(),
+            "snapshots": snapshots
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка истории разрешений {}: {}", owner_address, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить историю разрешений",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// POST /api/allowances/{owner_address}/revoke - неподписанная
+/// approve(spender, 0) транзакция отзыва для подписи владельцем
+pub async fn build_allowance_revocation(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let owner_address = path.into_inner();
+
+    match app_state
+        .allowance_service
+        .build_revocation(&owner_address)
+        .await
+    {
+        Ok(transaction) => Ok(HttpResponse::Ok().json(json!({
+            "owner_address": owner_address,
+            "unsigned_transaction": transaction
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка подготовки отзыва разрешения {}: {}",
+                owner_address,
+                err
+            );
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось подготовить транзакцию отзыва",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
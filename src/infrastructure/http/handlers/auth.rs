@@ -0,0 +1,63 @@
+//! # Обработчик интроспекции API ключа
+//!
+//! Клиент узнает про предъявленный ключ: имя, область доступа и текущее
+//! потребление rate limit квоты - без ожидания первого 429
+
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// GET /api/auth/introspect - данные о предъявленном API ключе
+pub async fn introspect_api_key(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    let provided_key = req.headers().get("x-api-key").and_then(|h| h.to_str().ok());
+
+    let provided_key = match provided_key {
+        Some(key) => key,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(json!({
+                "error": "Unauthorized",
+                "message": "Отсутствует заголовок X-Api-Key"
+            })));
+        }
+    };
+
+    let authorized = match data.api_keys.authorize(provided_key) {
+        Some(authorized) => authorized,
+        None => {
+            return Ok(HttpResponse::Unauthorized().json(json!({
+                "error": "Unauthorized",
+                "message": "Неизвестный API ключ"
+            })));
+        }
+    };
+
+    // Потребление квоты смотрим без списания - introspection
+    // не должен сам тратить лимит
+    let rate_limit = if data.rate_limiter.is_enabled() {
+        let (subject, max_requests) = data
+            .rate_limiter
+            .resolve_subject(Some(provided_key), "introspect");
+        let usage = data
+            .rate_limiter
+            .current_usage(&subject, max_requests)
+            .await;
+        json!({
+            "enabled": true,
+            "limit": usage.limit,
+            "remaining": usage.remaining,
+            "reset_seconds": usage.reset_seconds,
+        })
+    } else {
+        json!({ "enabled": false })
+    };
+
+    Ok(HttpResponse::Ok().json(json!({
+        "name": authorized.name,
+        "scope": authorized.scope.as_str(),
+        "rate_limit": rate_limit,
+    })))
+}
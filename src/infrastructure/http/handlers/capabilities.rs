@@ -0,0 +1,26 @@
+//! # Обработчик возможностей шлюза
+//!
+//! Machine-readable описание включенных фич для feature-detection
+//! клиентских SDK вместо хардкода предположений под каждый деплой
+
+use actix_web::{web, HttpResponse, Result};
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// GET /api/capabilities - описание возможностей шлюза
+pub async fn get_capabilities(data: web::Data<AppState>) -> Result<HttpResponse> {
+    // Список токенов динамический (admin API может добавлять новые),
+    // поэтому собираем его на каждый запрос
+    let tokens = data.trc20_service.get_supported_tokens().await;
+    let token_symbols: Vec<&str> = tokens
+        .iter()
+        .filter(|t| t.enabled)
+        .map(|t| t.symbol.as_str())
+        .collect();
+
+    Ok(HttpResponse::Ok().json(json!({
+        "capabilities": data.capabilities.as_ref(),
+        "tokens": token_symbols,
+    })))
+}
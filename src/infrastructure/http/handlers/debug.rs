@@ -18,14 +18,68 @@ pub async fn root_handler() -> Result<HttpResponse> {
 }
 
 /// Health check эндпоинт
-pub async fn health_check() -> Result<HttpResponse> {
+pub async fn health_check(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    let degradation = app_state.degradation.snapshot();
+
     Ok(HttpResponse::Ok().json(json!({
-        "status": "healthy",
+        "status": if degradation.degraded { "degraded" } else { "healthy" },
         "version": VERSION,
-        "timestamp": chrono::Utc::now().to_rfc3339()
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "instance": *app_state.instance_identity,
+        "degradation": degradation
     })))
 }
 
+/// Получение ресурсов аккаунта (энергия, bandwidth, заморозки, делегирования)
+pub async fn get_account_resources(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let address = path.into_inner();
+
+    if let Err(err) = crate::domain::TronValidator::validate_address(&address) {
+        return Ok(HttpResponse::BadRequest().json(json!({
+            "error": "Невалидный TRON адрес",
+            "details": err.to_string()
+        })));
+    }
+
+    match app_state
+        .transfer_service
+        .tron_client
+        .get_account_resources(&address)
+        .await
+    {
+        Ok(resources) => Ok(HttpResponse::Ok().json(resources)),
+        Err(err) => {
+            tracing::error!("Ошибка получения ресурсов аккаунта {}: {}", address, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to get account resources",
+                "address": address,
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Переигрывание dead-letter записей мониторинга вручную
+pub async fn replay_monitoring_dead_letters(
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    match app_state.monitoring_service.replay_dead_letters().await {
+        Ok(replayed) => Ok(HttpResponse::Ok().json(json!({
+            "replayed": replayed,
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка переигрывания dead-letter записей: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to replay dead letters",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
 /// Получение баланса мастер-кошелька
 pub async fn get_master_wallet_balance(app_state: web::Data<AppState>) -> Result<HttpResponse> {
     // Получаем адрес мастер-кошелька из конфига через TransferService
@@ -53,3 +107,159 @@ pub async fn get_master_wallet_balance(app_state: web::Data<AppState>) -> Result
         }
     }
 }
+
+/// История TRX отправок с мастер-кошельков пула
+pub async fn get_master_wallet_history(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    let addresses = app_state.transfer_service.master_wallet_pool.addresses();
+
+    let mut history = Vec::with_capacity(addresses.len());
+
+    for address in addresses {
+        match app_state
+            .trx_transfer_service
+            .get_transfers_from(&address, 100)
+            .await
+        {
+            Ok(transfers) => history.push(json!({
+                "address": address,
+                "trx_transfers": transfers
+            })),
+            Err(err) => {
+                tracing::error!(
+                    "Ошибка получения истории TRX отправок для {}: {}",
+                    address,
+                    err
+                );
+                return Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get master wallet history",
+                    "details": err.to_string()
+                })));
+            }
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(json!({ "master_wallets": history })))
+}
+
+/// GET /api/debug/trongrid-usage - использование TronGrid API.
+/// Дневные агрегаты по операциям, процент квоты и последние запросы
+/// из кольцевого буфера - для разбора, куда уходит квота
+pub async fn get_trongrid_usage(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    let snapshot = app_state
+        .transfer_service
+        .tron_client
+        .usage_tracker()
+        .snapshot();
+
+    Ok(HttpResponse::Ok().json(snapshot))
+}
+
+/// Параметры запроса истории запусков планировщика
+#[derive(Debug, serde::Deserialize)]
+pub struct SchedulerRunsQuery {
+    /// Фильтр по имени задачи (например confirmation_tracking)
+    pub task: Option<String>,
+    /// Сколько последних запусков вернуть (по умолчанию 100, максимум 500)
+    pub limit: Option<i64>,
+}
+
+/// GET /api/debug/scheduler/runs - история запусков задач планировщика.
+/// Операторы проверяют, что задачи реально выполнялись, без grep'а по логам
+pub async fn get_scheduler_runs(
+    app_state: web::Data<AppState>,
+    query: web::Query<SchedulerRunsQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(100);
+
+    match app_state
+        .scheduler_run_log
+        .list_runs(query.task.as_deref(), limit)
+        .await
+    {
+        Ok(runs) => Ok(HttpResponse::Ok().json(json!({
+            "count": runs.len(),
+            "runs": runs
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка чтения истории запусков планировщика: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to list scheduler runs",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Query параметры истории запусков sweep'а
+#[derive(Debug, serde::Deserialize)]
+pub struct SweepRunsQuery {
+    /// Сколько последних запусков вернуть (по умолчанию 100, максимум 500)
+    pub limit: Option<i64>,
+}
+
+/// GET /api/debug/sweep/runs - история запусков консолидации средств
+pub async fn get_sweep_runs(
+    app_state: web::Data<AppState>,
+    query: web::Query<SweepRunsQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(100);
+
+    match app_state.sweep_service.list_runs(limit).await {
+        Ok(runs) => Ok(HttpResponse::Ok().json(json!({
+            "count": runs.len(),
+            "runs": runs
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка чтения истории запусков sweep'а: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to list sweep runs",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Параметры запроса статистики мониторинга
+#[derive(Debug, serde::Deserialize)]
+pub struct MonitoringStatsQuery {
+    /// Временное окно: 1h, 24h или 7d (по умолчанию - за все время)
+    pub window: Option<String>,
+}
+
+/// GET /api/stats/monitoring - сводка по входящим транзакциям
+pub async fn get_monitoring_statistics(
+    data: web::Data<AppState>,
+    query: web::Query<MonitoringStatsQuery>,
+) -> Result<HttpResponse> {
+    let since = match query.window.as_deref() {
+        None => None,
+        Some(window) => match crate::application::services::parse_stats_window(window) {
+            Some(duration) => Some(chrono::Utc::now() - duration),
+            None => {
+                return Ok(HttpResponse::BadRequest().json(json!({
+                    "error": "invalid_window",
+                    "message": "Поддерживаемые окна: 1h, 24h, 7d"
+                })))
+            }
+        },
+    };
+
+    match data
+        .monitoring_service
+        .get_monitoring_stats_since(since)
+        .await
+    {
+        Ok(stats) => Ok(HttpResponse::Ok().json(json!({
+            "window": query.window.as_deref().unwrap_or("all"),
+            "stats": stats,
+            "batch_tuning": data.monitoring_service.batch_tuning(),
+        }))),
+        Err(e) => {
+            tracing::error!("Ошибка получения статистики мониторинга: {}", e);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить статистику мониторинга",
+                "details": e.to_string()
+            })))
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! # Обработчик faucet для sandbox окружений
+//!
+//! Выдача тестовых TRX/USDT на кошелек из тестового резерва (только Shasta)
+
+use actix_web::{web, HttpResponse, Result};
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// POST /api/faucet/{address} - выдать тестовые средства на адрес
+pub async fn fund_wallet_from_faucet(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let address = path.into_inner();
+
+    match data.faucet_service.fund_wallet(&address).await {
+        Ok(funding) => Ok(HttpResponse::Ok().json(funding)),
+        Err(e) => {
+            tracing::error!("❌ Ошибка faucet для {}: {}", address, e);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Faucet request failed",
+                "message": e.to_string()
+            })))
+        }
+    }
+}
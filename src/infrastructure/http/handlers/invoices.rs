@@ -0,0 +1,91 @@
+//! # Обработчики инвойсов (payment requests)
+//!
+//! Создание платежного запроса, просмотр и список инвойсов кошелька.
+//! Депозиты засчитываются автоматически хуком пост-обработки
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::services::{CreateInvoice, InvoiceStatus};
+use crate::application::state::AppState;
+
+/// POST /api/invoices - создает платежный запрос
+pub async fn create_invoice(
+    app_state: web::Data<AppState>,
+    request: web::Json<CreateInvoice>,
+) -> Result<HttpResponse> {
+    match app_state
+        .invoice_service
+        .create_invoice(request.into_inner())
+        .await
+    {
+        Ok(invoice) => Ok(HttpResponse::Created().json(json!({
+            "invoice": invoice
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка создания инвойса: {}", err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось создать инвойс",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/invoices/{invoice_id} - инвойс по ID
+pub async fn get_invoice(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let invoice_id = path.into_inner();
+
+    match app_state.invoice_service.get_invoice(invoice_id).await {
+        Ok(Some(invoice)) => Ok(HttpResponse::Ok().json(json!({
+            "invoice": invoice
+        }))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+            "error": format!("Инвойс {} не найден", invoice_id)
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка получения инвойса {}: {}", invoice_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить инвойс",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Query параметры списка инвойсов
+#[derive(Debug, Deserialize)]
+pub struct InvoiceListQuery {
+    pub wallet_id: i64,
+    /// Фильтр по статусу (open, partial, paid, overpaid, expired)
+    pub status: Option<InvoiceStatus>,
+}
+
+/// GET /api/invoices?wallet_id=N&status=open - инвойсы кошелька
+pub async fn list_invoices(
+    app_state: web::Data<AppState>,
+    query: web::Query<InvoiceListQuery>,
+) -> Result<HttpResponse> {
+    match app_state
+        .invoice_service
+        .list_invoices(query.wallet_id, query.status)
+        .await
+    {
+        Ok(invoices) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": query.wallet_id,
+            "count": invoices.len(),
+            "invoices": invoices
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка списка инвойсов: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить список инвойсов",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
@@ -1,17 +1,37 @@
 //! # HTTP обработчики
-//! 
+//!
 //! Модули обработчиков HTTP запросов:
 //! - `wallet` - операции с кошельками
 //! - `transfer` - операции с переводами
 //! - `debug` - отладочные endpoint'ы
 
+pub mod alerts;
+pub mod allowances;
+pub mod auth;
+pub mod capabilities;
 pub mod debug;
+pub mod faucet;
+pub mod invoices;
+pub mod payment_intent;
+pub mod recovery;
+pub mod split;
 pub mod token_handlers;
 pub mod transfer;
 pub mod wallet;
+pub mod webhook;
 
 // Реэкспорт всех handlers для удобства
+pub use alerts::*;
+pub use allowances::*;
+pub use auth::*;
+pub use capabilities::*;
 pub use debug::*;
+pub use faucet::*;
+pub use invoices::*;
+pub use payment_intent::*;
+pub use recovery::*;
+pub use split::*;
 pub use token_handlers::*;
 pub use transfer::*;
 pub use wallet::*;
+pub use webhook::*;
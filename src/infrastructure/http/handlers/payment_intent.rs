@@ -0,0 +1,129 @@
+//! # Обработчики платежных намерений
+//!
+//! HTTP handlers для создания и получения payment intents
+
+use actix_web::{web, HttpResponse, Result};
+use serde_json::json;
+
+use crate::application::{dto::*, state::AppState};
+
+use super::transfer::{parse_wait_timeout, WaitQuery, WAIT_POLL_INTERVAL};
+
+/// Создание нового платежного намерения
+pub async fn create_payment_intent(
+    app_state: web::Data<AppState>,
+    request: web::Json<CreatePaymentIntentRequest>,
+) -> Result<HttpResponse> {
+    match app_state
+        .payment_intent_service
+        .create_intent(request.into_inner())
+        .await
+    {
+        Ok(intent) => {
+            app_state.degradation.record_write_success();
+            Ok(HttpResponse::Ok().json(intent))
+        }
+        Err(err) => {
+            tracing::error!("Ошибка создания платежного намерения: {}", err);
+
+            if app_state.degradation.record_write_error(&err.to_string()) {
+                return Ok(super::transfer::degraded_write_response(&err));
+            }
+
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось создать платежное намерение",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Получение платежного намерения по ID
+pub async fn get_payment_intent(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let intent_id = path.into_inner();
+
+    match app_state.payment_intent_service.get_intent(intent_id).await {
+        Ok(Some(intent)) => Ok(HttpResponse::Ok().json(intent)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+            "error": "Payment intent not found",
+            "intent_id": intent_id
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка получения платежного намерения {}: {}",
+                intent_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to get payment intent",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/payment-intents/{id}/wait - long-poll статуса намерения.
+/// Держит запрос, пока депозит не будет сматчен (или намерение не
+/// истечет) либо пока не выйдет таймаут
+pub async fn wait_for_payment_intent_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    query: web::Query<WaitQuery>,
+) -> Result<HttpResponse> {
+    let intent_id = path.into_inner();
+    let timeout = parse_wait_timeout(query.timeout.as_deref());
+
+    let initial = match app_state.payment_intent_service.get_intent(intent_id).await {
+        Ok(Some(intent)) => intent,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "error": "Платежное намерение не найдено",
+                "intent_id": intent_id
+            })))
+        }
+        Err(err) => {
+            tracing::error!("Ошибка получения намерения {}: {}", intent_id, err);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить платежное намерение",
+                "details": err.to_string()
+            })));
+        }
+    };
+
+    let deadline = std::time::Instant::now() + timeout;
+    let mut current = initial;
+
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+
+        match app_state.payment_intent_service.get_intent(intent_id).await {
+            Ok(Some(intent)) => {
+                let changed = intent.status != current.status;
+                current = intent;
+                if changed {
+                    return Ok(HttpResponse::Ok().json(json!({
+                        "changed": true,
+                        "intent": current
+                    })));
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                tracing::warn!(
+                    "⚠️ Ошибка опроса намерения {} в long-poll: {}",
+                    intent_id,
+                    err
+                );
+            }
+        }
+    }
+
+    // Таймаут истек без смены статуса
+    Ok(HttpResponse::Ok().json(json!({
+        "changed": false,
+        "intent": current
+    })))
+}
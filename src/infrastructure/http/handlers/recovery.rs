@@ -0,0 +1,78 @@
+//! # Обработчики восстановления осиротевших трансферов
+//!
+//! Admin-аналог recovery CLI: скан on-chain истории и явная привязка
+//! найденного хэша к трансферу без tx_hash
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// Query параметры скана осиротевших трансферов
+#[derive(Debug, Deserialize)]
+pub struct OrphanScanQuery {
+    /// Окно поиска в часах (по умолчанию 24)
+    pub window_hours: Option<i64>,
+}
+
+/// Тело запроса привязки on-chain транзакции
+#[derive(Debug, Deserialize)]
+pub struct LinkOrphanRequest {
+    /// Хэш on-chain транзакции, выбранный оператором из кандидатов
+    pub tx_hash: String,
+}
+
+/// GET /api/recovery/orphans - трансферы без tx_hash с on-chain кандидатами
+pub async fn scan_orphaned_transfers(
+    app_state: web::Data<AppState>,
+    query: web::Query<OrphanScanQuery>,
+) -> Result<HttpResponse> {
+    let window_hours = query.window_hours.unwrap_or(24);
+
+    match app_state
+        .recovery_service
+        .scan_orphaned_transfers(window_hours)
+        .await
+    {
+        Ok(reports) => Ok(HttpResponse::Ok().json(json!({
+            "window_hours": window_hours,
+            "count": reports.len(),
+            "orphans": reports
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка скана осиротевших трансферов: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось просканировать осиротевшие трансферы",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// POST /api/recovery/orphans/{transfer_id}/link - привязка хэша к трансферу
+pub async fn link_orphaned_transfer(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    body: web::Json<LinkOrphanRequest>,
+) -> Result<HttpResponse> {
+    let transfer_id = path.into_inner();
+
+    match app_state
+        .recovery_service
+        .link_transfer(transfer_id, &body.tx_hash)
+        .await
+    {
+        Ok(transfer) => Ok(HttpResponse::Ok().json(json!({
+            "linked": true,
+            "transfer": transfer
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка привязки трансфера {}: {}", transfer_id, err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось привязать транзакцию к трансферу",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! # Обработчики правил разложения депозитов
+//!
+//! HTTP handlers для управления таблицей маршрутизации депозитов
+//! и просмотра учета по ногам
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::services::SplitLegSpec;
+use crate::application::state::AppState;
+
+/// Тело запроса замены набора правил кошелька
+#[derive(Debug, Deserialize)]
+pub struct SetSplitRulesRequest {
+    /// Ноги разложения; проценты в сумме дают ровно 100
+    pub legs: Vec<SplitLegSpec>,
+}
+
+/// PUT /api/wallets/{wallet_id}/split-rules - замена набора правил
+pub async fn set_split_rules(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    body: web::Json<SetSplitRulesRequest>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state
+        .deposit_split_service
+        .set_rules(wallet_id, body.into_inner().legs)
+        .await
+    {
+        Ok(rules) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "count": rules.len(),
+            "rules": rules
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка установки правил разложения для кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось установить правила разложения",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/wallets/{wallet_id}/split-rules - текущий набор правил
+pub async fn get_split_rules(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state.deposit_split_service.get_rules(wallet_id).await {
+        Ok(rules) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "count": rules.len(),
+            "rules": rules
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка получения правил разложения кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить правила разложения",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// DELETE /api/wallets/{wallet_id}/split-rules - отключение разложения
+pub async fn delete_split_rules(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state.deposit_split_service.clear_rules(wallet_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "deleted": true
+        }))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(json!({
+            "error": "У кошелька нет правил разложения",
+            "wallet_id": wallet_id
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка удаления правил разложения кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось удалить правила разложения",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/transactions/{tx_hash}/split-legs - учет по ногам депозита
+pub async fn get_split_legs(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let tx_hash = path.into_inner();
+
+    match app_state.deposit_split_service.get_legs(&tx_hash).await {
+        Ok(legs) => Ok(HttpResponse::Ok().json(json!({
+            "deposit_tx_hash": tx_hash,
+            "count": legs.len(),
+            "legs": legs
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка получения ног депозита {}: {}", tx_hash, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить учет по ногам",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
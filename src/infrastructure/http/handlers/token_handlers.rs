@@ -6,6 +6,8 @@ use actix_web::{web, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::application::dto::CreateWithdrawalRequest;
+use crate::application::services::FeePayer;
 use crate::application::state::AppState;
 use crate::domain::tokens::TokenInfo;
 
@@ -72,7 +74,8 @@ impl From<&TokenInfo> for TokenInfoResponse {
 #[derive(Deserialize)]
 pub struct MultiTokenTransferRequest {
     pub from_wallet_id: i64,
-    pub to_address: String,
+    /// Если не указан, используется настроенное sweep-назначение токена
+    pub to_address: Option<String>,
     pub token_symbol: String,
     pub amount: String, // Decimal as string
     pub reference_id: Option<String>,
@@ -171,17 +174,36 @@ pub async fn get_supported_tokens(data: web::Data<AppState>) -> Result<HttpRespo
     Ok(HttpResponse::Ok().json(response))
 }
 
-/// Создает трансфер любого поддерживаемого токена
+/// Создает трансфер любого поддерживаемого токена.
+/// Вывод идет обычным пайплайном обработки pending трансферов
 pub async fn create_multi_token_transfer(
     data: web::Data<AppState>,
     request: web::Json<MultiTokenTransferRequest>,
 ) -> Result<HttpResponse> {
+    // Назначение: из запроса или настроенное sweep-назначение токена
+    let to_address = match request.to_address.clone().or_else(|| {
+        data.trc20_service
+            .sweep_destination_for(&request.token_symbol)
+            .map(|s| s.to_string())
+    }) {
+        Some(address) => address,
+        None => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "missing_to_address",
+                "message": format!(
+                    "Не указан to_address, и для токена {} не настроено sweep-назначение",
+                    request.token_symbol
+                )
+            })));
+        }
+    };
+
     tracing::info!(
         "Создание мультитокенного трансфера: {} {} от кошелька {} на {}",
         request.amount,
         request.token_symbol,
         request.from_wallet_id,
-        request.to_address
+        to_address
     );
 
     // Парсим сумму
@@ -195,38 +217,80 @@ pub async fn create_multi_token_transfer(
         }
     };
 
-    // Получаем информацию о кошельке
-    // Здесь нужно интегрироваться с существующим WalletService
-    // Для примера - заглушка
+    // Кошелек отправителя - адрес нужен для расчета комиссий
+    let wallet = match data.wallet_service.get_wallet(request.from_wallet_id).await {
+        Ok(Some(wallet)) => wallet,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "wallet_not_found",
+                "message": format!("Кошелек с ID {} не найден", request.from_wallet_id)
+            })));
+        }
+        Err(e) => {
+            tracing::error!(
+                "Ошибка получения кошелька {}: {}",
+                request.from_wallet_id,
+                e
+            );
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "internal_error",
+                "message": format!("Ошибка получения кошелька: {}", e)
+            })));
+        }
+    };
 
-    match data
-        .trc20_service
-        .create_token_transaction(
-            "mock_from_address", // В реальности получаем из БД по wallet_id
-            &request.to_address,
-            &request.token_symbol,
+    // Оценка комиссий для ответа (создание вывода пересчитает их
+    // по тем же правилам и сохранит в fee_amount)
+    let mut fee_service = data.transfer_service.fee_service.clone();
+    let (gas_fee, _percentage_commission, service_commission, total_amount) = match fee_service
+        .calculate_total_amount(
             amount,
+            &wallet.address,
+            FeePayer::default(),
+            wallet.owner_id.as_deref(),
         )
         .await
     {
-        Ok(_tx_result) => {
-            // Создаем запись в БД (интеграция с TransferService)
+        Ok(fees) => fees,
+        Err(e) => {
+            tracing::error!("Ошибка расчета комиссий: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "fee_calculation_failed",
+                "message": format!("Ошибка расчета комиссий: {}", e)
+            })));
+        }
+    };
+
+    // Вывод токена идет обычным пайплайном: строка в outgoing_transfers,
+    // заправка газа, подписание, broadcast и трекер подтверждений
+    let withdrawal = CreateWithdrawalRequest {
+        from_wallet_id: request.from_wallet_id,
+        to_address,
+        amount,
+        reference_id: request.reference_id.clone(),
+        destination_tag: None,
+        metadata: None,
+        fee_payer: None,
+        token_symbol: Some(request.token_symbol.clone()),
+    };
 
+    match data.transfer_service.create_withdrawal(withdrawal).await {
+        Ok(transfer) => {
             let response = MultiTokenTransferResponse {
-                transfer_id: 12345, // Mock ID
-                token_symbol: request.token_symbol.clone(),
-                from_wallet_id: request.from_wallet_id,
-                to_address: request.to_address.clone(),
-                amount: request.amount.clone(),
-                status: "PENDING".to_string(),
+                transfer_id: transfer.id,
+                token_symbol: transfer.token_symbol.clone(),
+                from_wallet_id: transfer.from_wallet_id,
+                to_address: transfer.to_address.clone(),
+                amount: transfer.amount.to_string(),
+                status: transfer.status.as_db_str().to_string(),
                 estimated_fees: FeeBreakdown {
-                    gas_fee_usdt: "1.5".to_string(),
-                    service_commission: "0.5".to_string(),
-                    total_fees: "2.0".to_string(),
-                    total_amount_to_deduct: (amount + rust_decimal::Decimal::new(2, 0)).to_string(),
+                    gas_fee_usdt: gas_fee.to_string(),
+                    service_commission: service_commission.to_string(),
+                    total_fees: (gas_fee + service_commission).to_string(),
+                    total_amount_to_deduct: total_amount.to_string(),
                 },
-                reference_id: request.reference_id.clone(),
-                created_at: chrono::Utc::now().to_rfc3339(),
+                reference_id: transfer.reference_id.clone(),
+                created_at: transfer.created_at.to_rfc3339(),
             };
 
             Ok(HttpResponse::Ok().json(response))
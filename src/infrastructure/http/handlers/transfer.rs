@@ -6,6 +6,7 @@ use actix_web::{web, HttpResponse, Result};
 use serde_json::json;
 
 use crate::application::{dto::*, state::AppState};
+use crate::infrastructure::http::error::ApiError;
 
 /// Превью трансфера с расчетом комиссий
 pub async fn preview_transfer(
@@ -20,10 +21,7 @@ pub async fn preview_transfer(
         Ok(preview) => Ok(HttpResponse::Ok().json(preview)),
         Err(err) => {
             tracing::error!("Ошибка создания превью трансфера: {}", err);
-            Ok(HttpResponse::InternalServerError().json(json!({
-                "error": "Не удалось создать превью трансфера",
-                "details": err.to_string()
-            })))
+            Ok(ApiError::from_anyhow(&err, "Не удалось создать превью трансфера").to_response())
         }
     }
 }
@@ -36,17 +34,57 @@ pub async fn create_transfer(
     let request = body.into_inner();
 
     match app_state.transfer_service.create_transfer(request).await {
-        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Ok(response) => {
+            app_state.degradation.record_write_success();
+            Ok(HttpResponse::Ok().json(response))
+        }
         Err(err) => {
             tracing::error!("Ошибка создания трансфера: {}", err);
-            Ok(HttpResponse::InternalServerError().json(json!({
-                "error": "Не удалось создать трансфер",
-                "details": err.to_string()
-            })))
+
+            if app_state.degradation.record_write_error(&err.to_string()) {
+                return Ok(degraded_write_response(&err));
+            }
+
+            Ok(ApiError::from_anyhow(&err, "Не удалось создать трансфер").to_response())
         }
     }
 }
 
+/// Создание вывода USDT на произвольный адрес
+pub async fn create_withdrawal(
+    app_state: web::Data<AppState>,
+    body: web::Json<CreateWithdrawalRequest>,
+) -> Result<HttpResponse> {
+    let request = body.into_inner();
+
+    match app_state.transfer_service.create_withdrawal(request).await {
+        Ok(response) => {
+            app_state.degradation.record_write_success();
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(err) => {
+            tracing::error!("Ошибка создания вывода: {}", err);
+
+            if app_state.degradation.record_write_error(&err.to_string()) {
+                return Ok(degraded_write_response(&err));
+            }
+
+            Ok(ApiError::from_anyhow(&err, "Не удалось создать вывод").to_response())
+        }
+    }
+}
+
+/// Ответ 503 для пишущих запросов в режиме деградации (read-only БД)
+pub(super) fn degraded_write_response(err: &impl std::fmt::Display) -> HttpResponse {
+    HttpResponse::ServiceUnavailable()
+        .insert_header(("Retry-After", "30"))
+        .json(json!({
+            "error": "Шлюз в режиме деградации: БД доступна только на чтение",
+            "degraded": true,
+            "details": err.to_string()
+        }))
+}
+
 /// Получение трансфера по ID
 pub async fn get_transfer(
     app_state: web::Data<AppState>,
@@ -187,3 +225,362 @@ pub async fn get_transaction(
         }
     }
 }
+
+/// Параметры long-poll запросов статуса
+#[derive(Debug, serde::Deserialize)]
+pub struct WaitQuery {
+    /// Таймаут ожидания, например "30s" или просто секунды
+    pub timeout: Option<String>,
+}
+
+/// Максимальное время удержания long-poll запроса
+const MAX_WAIT_SECONDS: u64 = 60;
+
+/// Интервал опроса статуса внутри long-poll запроса
+pub(super) const WAIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Разбирает таймаут long-poll запроса ("30s" или "30"), по умолчанию 30,
+/// не больше MAX_WAIT_SECONDS
+pub(super) fn parse_wait_timeout(timeout: Option<&str>) -> std::time::Duration {
+    let seconds = timeout
+        .map(|value| value.trim_end_matches('s'))
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(30);
+
+    std::time::Duration::from_secs(seconds.min(MAX_WAIT_SECONDS))
+}
+
+/// GET /api/transfers/{id}/wait - long-poll статуса трансфера.
+/// Держит запрос, пока статус не изменится или не истечет таймаут -
+/// для клиентов без WebSockets и webhooks
+pub async fn wait_for_transfer_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    query: web::Query<WaitQuery>,
+) -> Result<HttpResponse> {
+    let transfer_id = path.into_inner();
+    let timeout = parse_wait_timeout(query.timeout.as_deref());
+
+    let initial = match app_state.transfer_service.get_transfer(transfer_id).await {
+        Ok(Some(transfer)) => transfer,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "error": "Трансфер не найден",
+                "transfer_id": transfer_id
+            })))
+        }
+        Err(err) => {
+            tracing::error!("Ошибка получения трансфера {}: {}", transfer_id, err);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to get transfer",
+                "details": err.to_string()
+            })));
+        }
+    };
+
+    let deadline = std::time::Instant::now() + timeout;
+    let mut current = initial;
+
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+
+        match app_state.transfer_service.get_transfer(transfer_id).await {
+            Ok(Some(transfer)) => {
+                let changed = transfer.status != current.status;
+                current = transfer;
+                if changed {
+                    return Ok(HttpResponse::Ok().json(json!({
+                        "changed": true,
+                        "transfer": current
+                    })));
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                tracing::warn!(
+                    "⚠️ Ошибка опроса трансфера {} в long-poll: {}",
+                    transfer_id,
+                    err
+                );
+            }
+        }
+    }
+
+    // Таймаут истек без смены статуса
+    Ok(HttpResponse::Ok().json(json!({
+        "changed": false,
+        "transfer": current
+    })))
+}
+
+/// GET /api/transfers/in-flight - трансферы в обработке прямо сейчас
+/// (PROCESSING), с информацией кто и когда их забрал
+pub async fn get_in_flight_transfers(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    match app_state.transfer_service.get_in_flight_transfers().await {
+        Ok(transfers) => Ok(HttpResponse::Ok().json(json!({
+            "count": transfers.len(),
+            "transfers": transfers
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка получения in-flight трансферов: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to get in-flight transfers",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/stats/processing - статистика пайплайна обработки трансферов
+pub async fn get_processing_stats(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    match app_state.transfer_service.get_processing_stats().await {
+        Ok(stats) => Ok(HttpResponse::Ok().json(stats)),
+        Err(err) => {
+            tracing::error!("Ошибка получения статистики обработки: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить статистику обработки",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Параметры постраничного отчета по строкам ingestion job'а
+#[derive(Debug, serde::Deserialize)]
+pub struct IngestionRowsQuery {
+    /// id строки, после которой продолжить (из предыдущей страницы)
+    pub cursor: Option<i64>,
+    /// Размер страницы (по умолчанию 100, максимум 1000)
+    pub limit: Option<i64>,
+}
+
+/// POST /api/transfers/ingest - массовая загрузка трансферов NDJSON стримом.
+/// Тело читается по частям: каждая строка - JSON как в create_transfer.
+/// Строки валидируются на лету, принятые уходят в очередь фонового
+/// воркера; ответ 202 с id job'а приходит не дожидаясь создания трансферов
+pub async fn ingest_transfers(
+    app_state: web::Data<AppState>,
+    mut payload: web::Payload,
+) -> Result<HttpResponse> {
+    use crate::application::services::{
+        TransferIngestionService, INGESTION_MAX_LINE_BYTES, INGESTION_MAX_ROWS,
+    };
+    use crate::infrastructure::database::models::NewIngestionRow;
+    use futures_util::StreamExt;
+
+    /// Сколько строк копить перед записью пачки в БД
+    const BATCH_SIZE: usize = 500;
+
+    let service = &app_state.ingestion_service;
+
+    let job_id = match service.create_job().await {
+        Ok(job_id) => job_id,
+        Err(err) => {
+            tracing::error!("Ошибка создания ingestion job'а: {}", err);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось создать job массовой загрузки",
+                "details": err.to_string()
+            })));
+        }
+    };
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut row_number = 0i64;
+    let mut accepted = 0i64;
+    let mut rejected = 0i64;
+    let mut batch: Vec<NewIngestionRow> = Vec::new();
+    let mut stream_done = false;
+
+    while !stream_done {
+        match payload.next().await {
+            Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+            Some(Err(err)) => {
+                tracing::warn!("⚠️  Ingestion job {}: обрыв стрима: {}", job_id, err);
+                return Ok(HttpResponse::BadRequest().json(json!({
+                    "error": "Ошибка чтения NDJSON стрима",
+                    "job_id": job_id,
+                    "details": err.to_string()
+                })));
+            }
+            None => stream_done = true,
+        }
+
+        // Обрабатываем накопленные полные строки; после конца стрима
+        // хвост без завершающего \n - тоже строка
+        loop {
+            let line_end = buffer.iter().position(|b| *b == b'\n');
+            let line_bytes = match line_end {
+                Some(pos) => {
+                    let mut line: Vec<u8> = buffer.drain(..=pos).collect();
+                    line.pop(); // срезаем \n
+                    line
+                }
+                None if stream_done && !buffer.is_empty() => std::mem::take(&mut buffer),
+                None => break,
+            };
+
+            let line = String::from_utf8_lossy(&line_bytes);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            row_number += 1;
+            if row_number > INGESTION_MAX_ROWS {
+                return Ok(HttpResponse::PayloadTooLarge().json(json!({
+                    "error": format!("Больше {} строк в одном job'е", INGESTION_MAX_ROWS),
+                    "job_id": job_id
+                })));
+            }
+
+            let (status, error_message) = match TransferIngestionService::validate_line(line) {
+                Ok(_) => {
+                    accepted += 1;
+                    ("queued", None)
+                }
+                Err(e) => {
+                    rejected += 1;
+                    ("rejected", Some(e))
+                }
+            };
+
+            batch.push(NewIngestionRow {
+                job_id,
+                row_number,
+                payload: line.to_string(),
+                status: status.to_string(),
+                error_message,
+            });
+
+            if batch.len() >= BATCH_SIZE {
+                if let Err(err) = service.append_rows(std::mem::take(&mut batch)).await {
+                    tracing::error!("Ошибка записи строк ingestion job'а {}: {}", job_id, err);
+                    return Ok(HttpResponse::InternalServerError().json(json!({
+                        "error": "Не удалось сохранить строки загрузки",
+                        "job_id": job_id,
+                        "details": err.to_string()
+                    })));
+                }
+            }
+        }
+
+        // Защита от строки без перевода: буфер не должен расти бесконечно
+        if !stream_done && buffer.len() > INGESTION_MAX_LINE_BYTES {
+            return Ok(HttpResponse::PayloadTooLarge().json(json!({
+                "error": format!("Строка длиннее {} байт", INGESTION_MAX_LINE_BYTES),
+                "job_id": job_id,
+                "row_number": row_number + 1
+            })));
+        }
+    }
+
+    let finalize = async {
+        service.append_rows(batch).await?;
+        service
+            .finalize_job(job_id, row_number, accepted, rejected)
+            .await
+    };
+
+    if let Err(err) = finalize.await {
+        tracing::error!("Ошибка завершения ingestion job'а {}: {}", job_id, err);
+        return Ok(HttpResponse::InternalServerError().json(json!({
+            "error": "Не удалось завершить job массовой загрузки",
+            "job_id": job_id,
+            "details": err.to_string()
+        })));
+    }
+
+    Ok(HttpResponse::Accepted().json(json!({
+        "job_id": job_id,
+        "status": if accepted == 0 { "completed" } else { "queued" },
+        "rows_total": row_number,
+        "rows_accepted": accepted,
+        "rows_rejected": rejected,
+        "status_url": format!("/api/transfers/ingest/{}", job_id)
+    })))
+}
+
+/// GET /api/transfers/ingest/{job_id} - прогресс job'а массовой загрузки
+pub async fn get_ingestion_job(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let job_id = path.into_inner();
+
+    match app_state.ingestion_service.job_status(job_id).await {
+        Ok(Some(status)) => Ok(HttpResponse::Ok().json(status)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+            "error": "Job массовой загрузки не найден",
+            "job_id": job_id
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка получения ingestion job'а {}: {}", job_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить статус job'а",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/transfers/ingest/{job_id}/rows - построчные исходы загрузки
+/// с курсорной пагинацией (cursor = id последней строки страницы)
+pub async fn get_ingestion_rows(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    query: web::Query<IngestionRowsQuery>,
+) -> Result<HttpResponse> {
+    let job_id = path.into_inner();
+    let limit = query.limit.unwrap_or(100);
+
+    match app_state
+        .ingestion_service
+        .list_rows(job_id, query.cursor, limit)
+        .await
+    {
+        Ok(rows) => {
+            let next_cursor = rows.last().map(|row| row.id);
+            let rows: Vec<_> = rows
+                .into_iter()
+                .map(|row| {
+                    json!({
+                        "id": row.id,
+                        "row_number": row.row_number,
+                        "status": row.status,
+                        "transfer_id": row.transfer_id,
+                        "error_message": row.error_message,
+                        "processed_at": row.processed_at
+                    })
+                })
+                .collect();
+
+            Ok(HttpResponse::Ok().json(json!({
+                "job_id": job_id,
+                "count": rows.len(),
+                "rows": rows,
+                "next_cursor": next_cursor
+            })))
+        }
+        Err(err) => {
+            tracing::error!("Ошибка получения строк ingestion job'а {}: {}", job_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить строки job'а",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// PUT /api/debug/processing/tuning - изменение настроек пайплайна в рантайме
+pub async fn update_processing_tuning(
+    app_state: web::Data<AppState>,
+    body: web::Json<UpdateProcessingTuningRequest>,
+) -> Result<HttpResponse> {
+    let request = body.into_inner();
+
+    let tuning = app_state
+        .transfer_service
+        .update_processing_tuning(request.max_batch_size, request.parallelism);
+
+    Ok(HttpResponse::Ok().json(tuning))
+}
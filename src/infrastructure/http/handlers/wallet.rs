@@ -3,9 +3,22 @@
 //! HTTP handlers для операций с кошельками
 
 use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
 use serde_json::json;
 
+use crate::application::services::{BalanceSource, QrFormat};
 use crate::application::{dto::*, state::AppState};
+use crate::infrastructure::http::error::ApiError;
+
+/// Query параметры для выбора источника баланса
+#[derive(Debug, Deserialize)]
+pub struct BalanceQuery {
+    /// `chain` - живой запрос к TronGrid, иначе локальная проекция
+    pub source: Option<String>,
+    /// Момент в прошлом (RFC 3339) - баланс восстанавливается
+    /// из записанных транзакций (time-travel для финансовой сверки)
+    pub at: Option<chrono::DateTime<chrono::Utc>>,
+}
 
 /// Создание нового кошелька
 pub async fn create_wallet(
@@ -17,13 +30,97 @@ pub async fn create_wallet(
         .create_wallet(request.owner_id.clone())
         .await
     {
-        Ok(wallet) => Ok(HttpResponse::Ok().json(wallet)),
+        Ok(wallet) => {
+            app_state.degradation.record_write_success();
+            Ok(HttpResponse::Ok().json(wallet))
+        }
         Err(err) => {
             tracing::error!("Ошибка создания кошелька: {}", err);
-            Ok(HttpResponse::InternalServerError().json(json!({
-                "error": "Не удалось создать кошелек",
-                "details": err.to_string()
-            })))
+
+            if app_state.degradation.record_write_error(&err.to_string()) {
+                return Ok(super::transfer::degraded_write_response(&err));
+            }
+
+            Ok(ApiError::from_domain(&err, "Не удалось создать кошелек").to_response())
+        }
+    }
+}
+
+/// Регистрация watch-only адреса: депозиты отслеживаются,
+/// трансферы с адреса невозможны (приватного ключа нет)
+pub async fn register_watch_only_wallet(
+    app_state: web::Data<AppState>,
+    request: web::Json<RegisterWatchOnlyWalletRequest>,
+) -> Result<HttpResponse> {
+    match app_state
+        .wallet_service
+        .register_watch_only_wallet(request.address.clone(), request.owner_id.clone())
+        .await
+    {
+        Ok(wallet) => {
+            app_state.degradation.record_write_success();
+            Ok(HttpResponse::Ok().json(wallet))
+        }
+        Err(err) => {
+            tracing::error!(
+                "Ошибка регистрации watch-only адреса {}: {}",
+                request.address,
+                err
+            );
+
+            if app_state.degradation.record_write_error(&err.to_string()) {
+                return Ok(super::transfer::degraded_write_response(&err));
+            }
+
+            Ok(
+                ApiError::from_domain(&err, "Не удалось зарегистрировать watch-only адрес")
+                    .to_response(),
+            )
+        }
+    }
+}
+
+/// Query параметры списка кошельков
+#[derive(Debug, Deserialize)]
+pub struct WalletListQuery {
+    /// Фильтр по владельцу
+    pub owner_id: Option<String>,
+    /// Созданные не раньше (RFC 3339)
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Созданные не позже (RFC 3339)
+    pub created_to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Префикс base58 адреса
+    pub address_prefix: Option<String>,
+    /// Размер страницы (по умолчанию 50, максимум 200)
+    pub limit: Option<i64>,
+    /// Смещение страницы
+    pub offset: Option<i64>,
+}
+
+/// GET /api/wallets - список кошельков с фильтрами и пагинацией
+pub async fn list_wallets(
+    app_state: web::Data<AppState>,
+    query: web::Query<WalletListQuery>,
+) -> Result<HttpResponse> {
+    let query = query.into_inner();
+    let filter = crate::application::services::WalletListFilter {
+        owner_id: query.owner_id,
+        created_from: query.created_from,
+        created_to: query.created_to,
+        address_prefix: query.address_prefix,
+        limit: query.limit,
+        offset: query.offset,
+    };
+
+    match app_state.wallet_service.list_wallets(filter).await {
+        Ok((wallets, total_count)) => Ok(HttpResponse::Ok().json(json!({
+            "count": wallets.len(),
+            "total_count": total_count,
+            "wallets": wallets
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка списка кошельков: {}", err);
+            Ok(ApiError::from_domain(&err, "Не удалось получить список кошельков").to_response())
         }
     }
 }
@@ -43,8 +140,80 @@ pub async fn get_wallet(
         }))),
         Err(err) => {
             tracing::error!("Ошибка получения кошелька {}: {}", wallet_id, err);
+            Ok(ApiError::from_domain(&err, "Не удалось получить кошелек").to_response())
+        }
+    }
+}
+
+/// Query параметры QR кода платежного URI
+#[derive(Debug, Deserialize)]
+pub struct WalletQrQuery {
+    /// Формат изображения: `svg` (по умолчанию) или `png`
+    pub format: Option<String>,
+    /// Сумма платежа - попадает в URI вместе с контрактом USDT
+    pub amount: Option<rust_decimal::Decimal>,
+    /// Размер изображения в px (ограничен конфигом `[qr]`)
+    pub size: Option<u32>,
+}
+
+/// GET /api/wallets/{id}/qr - QR код платежного URI адреса кошелька,
+/// чтобы фронтендам мерчантов не требовалась своя генерация QR
+pub async fn get_wallet_qr(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    query: web::Query<WalletQrQuery>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+    let query = query.into_inner();
+
+    let format = match query.format.as_deref() {
+        None => QrFormat::Svg,
+        Some(raw) => match QrFormat::from_query_str(raw) {
+            Some(format) => format,
+            None => {
+                return Ok(HttpResponse::BadRequest().json(json!({
+                    "error": "Неизвестный формат QR кода",
+                    "details": format!("Поддерживаются svg и png, получено: {}", raw)
+                })))
+            }
+        },
+    };
+
+    if let Some(amount) = query.amount {
+        if amount <= rust_decimal::Decimal::ZERO {
+            return Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Сумма в QR коде должна быть положительной"
+            })));
+        }
+    }
+
+    let wallet = match app_state.wallet_service.get_wallet(wallet_id).await {
+        Ok(Some(wallet)) => wallet,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(json!({
+                "error": "Кошелек не найден",
+                "wallet_id": wallet_id
+            })))
+        }
+        Err(err) => {
+            tracing::error!("Ошибка получения кошелька {}: {}", wallet_id, err);
+            return Ok(ApiError::from_domain(&err, "Не удалось получить кошелек").to_response());
+        }
+    };
+
+    match app_state
+        .qr_service
+        .render(&wallet.address, format, query.amount, query.size)
+    {
+        Ok(bytes) => Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            // Адрес кошелька неизменен - изображение можно кэшировать долго
+            .insert_header(("Cache-Control", "public, max-age=86400"))
+            .body(bytes.as_ref().clone())),
+        Err(err) => {
+            tracing::error!("Ошибка рендеринга QR кода кошелька {}: {}", wallet_id, err);
             Ok(HttpResponse::InternalServerError().json(json!({
-                "error": "Не удалось получить кошелек",
+                "error": "Не удалось отрендерить QR код",
                 "details": err.to_string()
             })))
         }
@@ -52,20 +221,79 @@ pub async fn get_wallet(
 }
 
 /// Получение баланса кошелька
+/// По умолчанию отдает локальную проекцию, `?source=chain` - живой запрос к сети
 pub async fn get_wallet_balance(
     app_state: web::Data<AppState>,
     path: web::Path<i64>,
+    query: web::Query<BalanceQuery>,
 ) -> Result<HttpResponse> {
     let wallet_id = path.into_inner();
+    let source = BalanceSource::from_query(query.source.as_deref());
+
+    // Time-travel: баланс на момент в прошлом из записанных транзакций
+    if let Some(at) = query.at {
+        if at > chrono::Utc::now() {
+            return Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Момент `at` должен быть в прошлом"
+            })));
+        }
+
+        return match app_state.balance_service.get_balance_at(wallet_id, at).await {
+            Ok(balance) => Ok(HttpResponse::Ok().json(json!({
+                "balance": balance,
+                "source": "ledger",
+                "note": "pending_* - записи, которые на момент `at` еще не были подтверждены/завершены и в баланс не входят"
+            }))),
+            Err(err) => {
+                tracing::error!(
+                    "Ошибка восстановления баланса кошелька {} на {}: {}",
+                    wallet_id,
+                    at,
+                    err
+                );
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Не удалось восстановить баланс",
+                    "details": err.to_string()
+                })))
+            }
+        };
+    }
+
+    if source == BalanceSource::Chain {
+        // Escape hatch: живой запрос к TronGrid
+        return match app_state.wallet_service.get_wallet_balance(wallet_id).await {
+            Ok((usdt_balance, trx_balance)) => Ok(HttpResponse::Ok().json(json!({
+                "wallet_id": wallet_id,
+                "usdt_balance": usdt_balance.to_string(),
+                "trx_balance": trx_balance.to_string(),
+                "source": "chain"
+            }))),
+            Err(err) => {
+                tracing::error!("Ошибка получения баланса кошелька {}: {}", wallet_id, err);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Не удалось получить баланс",
+                    "details": err.to_string()
+                })))
+            }
+        };
+    }
 
-    match app_state.wallet_service.get_wallet_balance(wallet_id).await {
-        Ok((usdt_balance, trx_balance)) => Ok(HttpResponse::Ok().json(json!({
+    match app_state
+        .balance_service
+        .get_projected_balance(wallet_id)
+        .await
+    {
+        Ok(usdt_balance) => Ok(HttpResponse::Ok().json(json!({
             "wallet_id": wallet_id,
             "usdt_balance": usdt_balance.to_string(),
-            "trx_balance": trx_balance.to_string()
+            "source": "projected"
         }))),
         Err(err) => {
-            tracing::error!("Ошибка получения баланса кошелька {}: {}", wallet_id, err);
+            tracing::error!(
+                "Ошибка получения проекции баланса кошелька {}: {}",
+                wallet_id,
+                err
+            );
             Ok(HttpResponse::InternalServerError().json(json!({
                 "error": "Не удалось получить баланс",
                 "details": err.to_string()
@@ -102,6 +330,312 @@ pub async fn get_wallet_transactions(
     }
 }
 
+/// Принудительное сканирование кошелька вне графика мониторинга.
+/// Полезно при показе инвойса - депозит подхватывается сразу,
+/// а не когда до кошелька дойдет очередь по его тиру активности
+pub async fn monitor_wallet_now(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state
+        .monitoring_service
+        .scan_wallet_now(wallet_id)
+        .await
+    {
+        Ok(new_transactions) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "new_transactions": new_transactions
+        }))),
+        Err(err)
+            if err.downcast_ref::<diesel::result::Error>()
+                == Some(&diesel::result::Error::NotFound) =>
+        {
+            Ok(HttpResponse::NotFound().json(json!({
+                "error": "Кошелек не найден",
+                "wallet_id": wallet_id
+            })))
+        }
+        Err(err) => {
+            tracing::error!(
+                "Ошибка принудительного сканирования кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось просканировать кошелек",
+                "wallet_id": wallet_id,
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Query параметры для фильтрации депозитов по типу источника
+#[derive(Debug, Deserialize)]
+pub struct DepositListQuery {
+    /// Метка источника (exchange, mixer, internal, private_wallet)
+    pub source: Option<String>,
+}
+
+/// Получение депозитов кошелька с фильтрацией по типу источника
+pub async fn get_wallet_deposits(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    query: web::Query<DepositListQuery>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state
+        .monitoring_service
+        .get_wallet_deposits(wallet_id, query.source.as_deref())
+        .await
+    {
+        Ok(deposits) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "count": deposits.len(),
+            "deposits": deposits
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка получения депозитов кошелька {}: {}", wallet_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить депозиты",
+                "wallet_id": wallet_id,
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Лента активности кошелька: исходящие USDT трансферы и TRX отправки
+/// (активация, спонсирование газа, faucet)
+pub async fn get_wallet_activity(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    let transfers = match app_state
+        .transfer_service
+        .get_wallet_transfers(wallet_id)
+        .await
+    {
+        Ok(transfers) => transfers,
+        Err(err) => {
+            tracing::error!(
+                "Ошибка получения трансферов кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to get wallet activity",
+                "wallet_id": wallet_id,
+                "details": err.to_string()
+            })));
+        }
+    };
+
+    match app_state
+        .trx_transfer_service
+        .get_transfers_for_wallet(wallet_id)
+        .await
+    {
+        Ok(trx_transfers) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "transfers": transfers,
+            "trx_transfers": trx_transfers
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка получения TRX отправок кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to get wallet activity",
+                "wallet_id": wallet_id,
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Выпуск wallet-scoped API токена для кошелька
+pub async fn issue_wallet_token(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    request: web::Json<IssueWalletTokenRequest>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state
+        .wallet_token_service
+        .issue_token(wallet_id, request.label.clone())
+        .await
+    {
+        Ok(token) => Ok(HttpResponse::Ok().json(token)),
+        Err(err) => {
+            tracing::error!("Ошибка выпуска токена для кошелька {}: {}", wallet_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось выпустить токен",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Отзыв wallet-scoped API токена
+pub async fn revoke_wallet_token(
+    app_state: web::Data<AppState>,
+    path: web::Path<(i64, i64)>,
+) -> Result<HttpResponse> {
+    let (wallet_id, token_id) = path.into_inner();
+
+    match app_state
+        .wallet_token_service
+        .revoke_token(wallet_id, token_id)
+        .await
+    {
+        Ok(true) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "token_id": token_id,
+            "revoked": true
+        }))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(json!({
+            "error": "Токен не найден",
+            "wallet_id": wallet_id,
+            "token_id": token_id
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка отзыва токена {} кошелька {}: {}",
+                token_id,
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось отозвать токен",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Изменение комплаенс-статуса кошелька (admin API)
+pub async fn update_wallet_compliance(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    request: web::Json<UpdateComplianceRequest>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state
+        .wallet_service
+        .set_compliance_status(wallet_id, request.into_inner())
+        .await
+    {
+        Ok(status) => Ok(HttpResponse::Ok().json(status)),
+        Err(crate::domain::DomainError::WalletNotFound { id }) => Ok(HttpResponse::NotFound()
+            .json(json!({
+                "error": "Кошелек не найден",
+                "wallet_id": id
+            }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка изменения комплаенс-статуса кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось изменить комплаенс-статус",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Передача владения кошельком другому owner_id (admin API)
+pub async fn transfer_wallet_ownership(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    request: web::Json<TransferWalletOwnershipRequest>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state
+        .wallet_service
+        .transfer_ownership(wallet_id, request.into_inner().new_owner_id)
+        .await
+    {
+        Ok(wallet) => Ok(HttpResponse::Ok().json(wallet)),
+        Err(crate::domain::DomainError::WalletNotFound { id }) => Ok(HttpResponse::NotFound()
+            .json(json!({
+                "error": "Кошелек не найден",
+                "wallet_id": id
+            }))),
+        Err(err) => {
+            tracing::error!("Ошибка смены владельца кошелька {}: {}", wallet_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось сменить владельца кошелька",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Архивация кошелька (admin API)
+pub async fn archive_wallet(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+
+    match app_state.wallet_service.archive_wallet(wallet_id).await {
+        Ok(wallet) => Ok(HttpResponse::Ok().json(wallet)),
+        Err(crate::domain::DomainError::WalletNotFound { id }) => Ok(HttpResponse::NotFound()
+            .json(json!({
+                "error": "Кошелек не найден",
+                "wallet_id": id
+            }))),
+        Err(err) => {
+            tracing::error!("Ошибка архивации кошелька {}: {}", wallet_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось архивировать кошелек",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Ротация wallet-scoped API токена кошелька
+pub async fn rotate_wallet_token(
+    app_state: web::Data<AppState>,
+    path: web::Path<(i64, i64)>,
+) -> Result<HttpResponse> {
+    let (wallet_id, token_id) = path.into_inner();
+
+    match app_state
+        .wallet_token_service
+        .rotate_token(wallet_id, token_id)
+        .await
+    {
+        Ok(token) => Ok(HttpResponse::Ok().json(token)),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка ротации токена {} кошелька {}: {}",
+                token_id,
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось ротировать токен",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
 /// Активация кошелька отправкой TRX
 pub async fn activate_wallet(
     app_state: web::Data<AppState>,
@@ -134,3 +668,50 @@ pub async fn activate_wallet(
         }
     }
 }
+
+/// Тело запроса изменения политики sweep'а кошелька
+#[derive(Debug, Deserialize)]
+pub struct UpdateSweepPolicyRequest {
+    /// Участвует ли кошелек в периодической консолидации
+    pub sweep_enabled: bool,
+    /// Порог кошелька в USDT (None - глобальный порог из конфига)
+    pub min_threshold: Option<rust_decimal::Decimal>,
+}
+
+/// PUT /api/wallets/{wallet_id}/sweep-policy - политика консолидации
+/// кошелька: выключение sweep'а или переопределение порога
+pub async fn update_wallet_sweep_policy(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    request: web::Json<UpdateSweepPolicyRequest>,
+) -> Result<HttpResponse> {
+    let wallet_id = path.into_inner();
+    let request = request.into_inner();
+
+    match app_state
+        .sweep_service
+        .set_wallet_policy(wallet_id, request.sweep_enabled, request.min_threshold)
+        .await
+    {
+        Ok(Some(_)) => Ok(HttpResponse::Ok().json(json!({
+            "wallet_id": wallet_id,
+            "sweep_enabled": request.sweep_enabled,
+            "min_threshold": request.min_threshold,
+        }))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+            "error": "Кошелек не найден",
+            "wallet_id": wallet_id
+        }))),
+        Err(err) => {
+            tracing::error!(
+                "Ошибка изменения политики sweep'а кошелька {}: {}",
+                wallet_id,
+                err
+            );
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось изменить политику sweep'а",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
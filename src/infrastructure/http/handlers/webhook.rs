@@ -0,0 +1,386 @@
+//! # Обработчики webhook событий и доставок
+//!
+//! Bulk export персистентного лога событий для reconciliation мерчантов
+//! и инспекция/повторная отправка записей outbox'а доставок
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::state::AppState;
+
+/// Query параметры экспорта webhook событий
+#[derive(Debug, Deserialize)]
+pub struct WebhookExportQuery {
+    /// Начало периода (RFC 3339)
+    pub from: Option<DateTime<Utc>>,
+    /// Конец периода (RFC 3339)
+    pub to: Option<DateTime<Utc>>,
+    /// Курсор возобновления: id последнего обработанного события
+    pub cursor: Option<i64>,
+    /// Размер страницы (ограничен EXPORT_MAX_PAGE_SIZE)
+    pub limit: Option<i64>,
+    /// Формат выдачи (поддерживается только ndjson)
+    pub format: Option<String>,
+}
+
+/// GET /api/webhooks/events/export - экспорт событий за период.
+///
+/// Отдает newline-delimited JSON; заголовок `X-Next-Cursor` содержит
+/// курсор для следующей страницы, пока экспорт не исчерпан
+pub async fn export_webhook_events(
+    app_state: web::Data<AppState>,
+    query: web::Query<WebhookExportQuery>,
+) -> Result<HttpResponse> {
+    if let Some(format) = query.format.as_deref() {
+        if format != "ndjson" {
+            return Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Неподдерживаемый формат экспорта",
+                "supported_formats": ["ndjson"]
+            })));
+        }
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(crate::application::services::EXPORT_MAX_PAGE_SIZE);
+
+    let events = match app_state
+        .webhook_event_service
+        .export_events(query.from, query.to, query.cursor, limit)
+        .await
+    {
+        Ok(events) => events,
+        Err(err) => {
+            tracing::error!("Ошибка экспорта webhook событий: {}", err);
+            return Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось экспортировать события",
+                "details": err.to_string()
+            })));
+        }
+    };
+
+    let next_cursor = events.last().map(|event| event.id);
+    let exhausted =
+        (events.len() as i64) < limit.clamp(1, crate::application::services::EXPORT_MAX_PAGE_SIZE);
+
+    let mut body = String::new();
+    for event in events {
+        // payload хранится как JSON-строка - встраиваем без повторной сериализации
+        let payload: serde_json::Value =
+            serde_json::from_str(&event.payload).unwrap_or(serde_json::Value::Null);
+
+        body.push_str(
+            &json!({
+                "id": event.id,
+                "event_type": event.event_type,
+                "created_at": event.created_at,
+                "payload": payload
+            })
+            .to_string(),
+        );
+        body.push('\n');
+    }
+
+    let mut response = HttpResponse::Ok();
+    response.content_type("application/x-ndjson");
+
+    if let Some(cursor) = next_cursor {
+        if !exhausted {
+            response.insert_header(("X-Next-Cursor", cursor.to_string()));
+        }
+    }
+
+    Ok(response.body(body))
+}
+
+/// Query параметры списка доставок
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveriesQuery {
+    /// Фильтр по статусу (PENDING, DELIVERED, FAILED)
+    pub status: Option<String>,
+    /// Размер выборки (по умолчанию 50, максимум 500)
+    pub limit: Option<i64>,
+}
+
+/// GET /api/webhooks/deliveries - записи outbox'а доставок (новые первыми)
+pub async fn get_webhook_deliveries(
+    app_state: web::Data<AppState>,
+    query: web::Query<WebhookDeliveriesQuery>,
+) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    match webhook_service
+        .list_deliveries(query.status.as_deref(), query.limit.unwrap_or(50))
+        .await
+    {
+        Ok(deliveries) => Ok(HttpResponse::Ok().json(json!({
+            "count": deliveries.len(),
+            "deliveries": deliveries
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка выборки webhook доставок: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить записи доставок",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// POST /api/webhooks/deliveries/{delivery_id}/redeliver - принудительная
+/// повторная отправка записи outbox'а (включая терминальные FAILED)
+pub async fn redeliver_webhook_delivery(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    let delivery_id = path.into_inner();
+
+    match webhook_service.redeliver(delivery_id).await {
+        Ok(delivery) => Ok(HttpResponse::Ok().json(json!({
+            "delivery": delivery
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка повторной доставки {}: {}", delivery_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось повторить доставку",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// POST /api/webhooks/replay - запуск реплея анонимизированного среза
+/// событий в staging endpoint мерчанта. Отвечает 202 со статусом job'а
+pub async fn start_webhook_replay(
+    app_state: web::Data<AppState>,
+    request: web::Json<crate::application::services::ReplayRequest>,
+) -> Result<HttpResponse> {
+    match app_state
+        .webhook_replay_service
+        .start_replay(request.into_inner())
+        .await
+    {
+        Ok(status) => Ok(HttpResponse::Accepted().json(json!({
+            "job": status
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка запуска реплея webhook событий: {}", err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось запустить реплей",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/webhooks/replay/{job_id} - статус job'а реплея
+pub async fn get_webhook_replay_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<u64>,
+) -> Result<HttpResponse> {
+    let job_id = path.into_inner();
+
+    match app_state.webhook_replay_service.job_status(job_id) {
+        Some(status) => Ok(HttpResponse::Ok().json(json!({
+            "job": status
+        }))),
+        None => Ok(HttpResponse::NotFound().json(json!({
+            "error": "Job реплея не найден",
+            "job_id": job_id
+        }))),
+    }
+}
+
+/// GET /.well-known/jwks.json - публичные ключи JWS подписи webhook'ов.
+///
+/// Мерчант резолвит ключ по kid из заголовка JWS и проверяет подпись
+/// стандартной JWT библиотекой; при ротации старые ключи остаются
+/// в документе. Без настроенной JWS подписи endpoint отвечает 404
+pub async fn get_jwks(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    let Some(signer) = &app_state.jws_signer else {
+        return Ok(HttpResponse::NotFound().json(json!({
+            "error": "JWS подпись webhook'ов не настроена"
+        })));
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("Cache-Control", "public, max-age=300"))
+        .json(signer.jwks_document()))
+}
+
+/// Тело регистрации webhook endpoint'а
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookEndpointRequest {
+    pub url: String,
+    /// Подписка: список типов событий, пустой = все события
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Секрет HMAC подписи (опционально)
+    pub secret_key: Option<String>,
+}
+
+/// POST /api/webhooks/endpoints - регистрирует дополнительный endpoint
+pub async fn create_webhook_endpoint(
+    app_state: web::Data<AppState>,
+    request: web::Json<CreateWebhookEndpointRequest>,
+) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    match webhook_service
+        .register_endpoint(
+            &request.url,
+            &request.event_types,
+            request.secret_key.clone(),
+        )
+        .await
+    {
+        Ok(endpoint) => Ok(HttpResponse::Created().json(json!({
+            "endpoint": endpoint
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка регистрации webhook endpoint'а: {}", err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось зарегистрировать endpoint",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// GET /api/webhooks/endpoints - список зарегистрированных endpoint'ов
+pub async fn list_webhook_endpoints(app_state: web::Data<AppState>) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    match webhook_service.list_endpoints().await {
+        Ok(endpoints) => Ok(HttpResponse::Ok().json(json!({
+            "count": endpoints.len(),
+            "endpoints": endpoints
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка списка webhook endpoint'ов: {}", err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось получить список endpoint'ов",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// Тело обновления webhook endpoint'а (частичное)
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookEndpointRequest {
+    pub event_types: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+}
+
+/// PUT /api/webhooks/endpoints/{endpoint_id} - обновляет подписку
+/// и/или включенность endpoint'а
+pub async fn update_webhook_endpoint(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+    request: web::Json<UpdateWebhookEndpointRequest>,
+) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    let endpoint_id = path.into_inner();
+
+    match webhook_service
+        .update_endpoint(endpoint_id, request.event_types.as_deref(), request.enabled)
+        .await
+    {
+        Ok(endpoint) => Ok(HttpResponse::Ok().json(json!({
+            "endpoint": endpoint
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка обновления endpoint'а {}: {}", endpoint_id, err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось обновить endpoint",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// DELETE /api/webhooks/endpoints/{endpoint_id} - удаляет endpoint
+pub async fn delete_webhook_endpoint(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    let endpoint_id = path.into_inner();
+
+    match webhook_service.delete_endpoint(endpoint_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(json!({
+            "deleted": true,
+            "endpoint_id": endpoint_id
+        }))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(json!({
+            "error": format!("Endpoint {} не найден", endpoint_id)
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка удаления endpoint'а {}: {}", endpoint_id, err);
+            Ok(HttpResponse::InternalServerError().json(json!({
+                "error": "Не удалось удалить endpoint",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
+
+/// POST /api/webhooks/endpoints/{endpoint_id}/rotate-secret - ротация
+/// секрета HMAC. Новый секрет возвращается в ответе ровно один раз
+pub async fn rotate_webhook_endpoint_secret(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> Result<HttpResponse> {
+    let Some(webhook_service) = &app_state.webhook_service else {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "error": "Webhook сервис не настроен (webhooks.url не задан)"
+        })));
+    };
+
+    let endpoint_id = path.into_inner();
+
+    match webhook_service.rotate_endpoint_secret(endpoint_id).await {
+        Ok(secret_key) => Ok(HttpResponse::Ok().json(json!({
+            "endpoint_id": endpoint_id,
+            "secret_key": secret_key
+        }))),
+        Err(err) => {
+            tracing::error!("Ошибка ротации секрета endpoint'а {}: {}", endpoint_id, err);
+            Ok(HttpResponse::BadRequest().json(json!({
+                "error": "Не удалось ротировать секрет",
+                "details": err.to_string()
+            })))
+        }
+    }
+}
@@ -1,11 +1,15 @@
 //! # HTTP инфраструктура
 //!
 //! HTTP сервер и маршрутизация:
+//! - `admin_ui` - встроенный админ UI
 //! - `handlers` - обработчики HTTP запросов
 //! - `routes` - конфигурация маршрутов
 
+pub mod admin_ui;
+pub mod error;
 pub mod handlers;
 pub mod routes;
 
 // Реэкспорт для удобства
+pub use error::ApiError;
 pub use routes::configure_routes;
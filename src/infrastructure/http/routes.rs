@@ -1,67 +1,251 @@
 use actix_web::web;
 
 use super::handlers::*;
+use crate::infrastructure::middleware::DeprecationHeaders;
 
 /// Конфигурация всех HTTP маршрутов
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     // Простой корневой маршрут для проверки
     cfg.route("/", web::get().to(root_handler));
 
+    // Встроенный админ UI (активен только при настроенном admin_api_key)
+    cfg.route("/admin", web::get().to(super::admin_ui::serve_admin_ui));
+
+    // JWKS документ для проверки JWS подписи webhook'ов (RFC 7517)
+    cfg.route("/.well-known/jwks.json", web::get().to(get_jwks));
+
+    // Актуальная версия API
+    cfg.service(web::scope("/api/v1").configure(api_routes));
+
+    // Совместимость: старые неверсионированные пути продолжают работать,
+    // но помечаются заголовками Deprecation/Sunset - клиенты должны
+    // мигрировать на /api/v1
     cfg.service(
         web::scope("/api")
-            .service(
-                // Маршруты для кошельков
-                web::scope("/wallets")
-                    .route("", web::post().to(create_wallet))
-                    .route("/{wallet_id}", web::get().to(get_wallet))
-                    .route("/{wallet_id}/balance", web::get().to(get_wallet_balance))
-                    .route(
-                        "/{wallet_id}/transactions",
-                        web::get().to(get_wallet_transactions),
-                    )
-                    .route(
-                        "/activate/{wallet_address}",
-                        web::post().to(activate_wallet),
-                    ),
-            )
-            .service(
-                // Маршруты для трансферов
-                web::scope("/transfers")
-                    .route("/preview", web::post().to(preview_transfer))
-                    .route("", web::post().to(create_transfer))
-                    .route("/{transfer_id}", web::get().to(get_transfer))
-                    .route(
-                        "/by-reference/{reference_id}",
-                        web::get().to(get_transfer_by_reference),
-                    )
-                    .route("/wallet/{wallet_id}", web::get().to(get_wallet_transfers))
-                    .route(
-                        "/process-pending",
-                        web::post().to(process_pending_transfers),
-                    ),
-            )
-            .service(
-                // Маршруты для транзакций
-                web::scope("/transactions").route("/{tx_hash}", web::get().to(get_transaction)),
-            )
-            .service(
-                // 🪙 Мультитокенные маршруты (новые!)
-                web::scope("/tokens")
-                    .route("", web::get().to(get_supported_tokens))
-                    .route("/balance", web::get().to(get_multi_token_balance))
-                    .route("/transfer", web::post().to(create_multi_token_transfer))
-                    .route("/{token_symbol}/toggle", web::post().to(toggle_token_status))
-                    .route("/cache/stats", web::get().to(get_cache_stats_and_cleanup))
-                    .route("/cache/invalidate/{wallet_address}", web::delete().to(invalidate_wallet_cache)),
-            )
-            .service(
-                // Отладочные маршруты
-                web::scope("/debug")
-                    .route(
-                        "/master-wallet/balance",
-                        web::get().to(get_master_wallet_balance),
-                    )
-                    .route("/system/health", web::get().to(health_check)),
-            ),
+            .wrap(DeprecationHeaders)
+            .configure(api_routes),
     );
 }
+
+/// Маршруты API (общие для /api/v1 и deprecated-алиаса /api)
+fn api_routes(cfg: &mut web::ServiceConfig) {
+    cfg
+        // Описание возможностей шлюза для клиентских SDK
+        .route("/capabilities", web::get().to(get_capabilities))
+        // Интроспекция предъявленного API ключа (scope, остаток квоты)
+        .route("/auth/introspect", web::get().to(introspect_api_key))
+        .service(
+            // Маршруты для кошельков
+            web::scope("/wallets")
+                .route("", web::get().to(list_wallets))
+                .route("", web::post().to(create_wallet))
+                .route("/watch", web::post().to(register_watch_only_wallet))
+                .route("/{wallet_id}", web::get().to(get_wallet))
+                .route("/{wallet_id}/balance", web::get().to(get_wallet_balance))
+                .route("/{wallet_id}/qr", web::get().to(get_wallet_qr))
+                .route(
+                    "/{wallet_id}/transactions",
+                    web::get().to(get_wallet_transactions),
+                )
+                .route("/{wallet_id}/deposits", web::get().to(get_wallet_deposits))
+                .route("/{wallet_id}/activity", web::get().to(get_wallet_activity))
+                .route(
+                    "/{wallet_id}/monitor-now",
+                    web::post().to(monitor_wallet_now),
+                )
+                .route("/{wallet_id}/tokens", web::post().to(issue_wallet_token))
+                .route(
+                    "/{wallet_id}/tokens/{token_id}",
+                    web::delete().to(revoke_wallet_token),
+                )
+                .route(
+                    "/{wallet_id}/tokens/{token_id}/rotate",
+                    web::post().to(rotate_wallet_token),
+                )
+                .route(
+                    "/{wallet_id}/owner",
+                    web::put().to(transfer_wallet_ownership),
+                )
+                .route("/{wallet_id}/archive", web::post().to(archive_wallet))
+                .route(
+                    "/{wallet_id}/compliance",
+                    web::put().to(update_wallet_compliance),
+                )
+                .route(
+                    "/{wallet_id}/sweep-policy",
+                    web::put().to(update_wallet_sweep_policy),
+                )
+                .route("/{wallet_id}/split-rules", web::put().to(set_split_rules))
+                .route("/{wallet_id}/split-rules", web::get().to(get_split_rules))
+                .route(
+                    "/{wallet_id}/split-rules",
+                    web::delete().to(delete_split_rules),
+                )
+                .route(
+                    "/activate/{wallet_address}",
+                    web::post().to(activate_wallet),
+                ),
+        )
+        .service(
+            // Маршруты для трансферов
+            web::scope("/transfers")
+                .route("/preview", web::post().to(preview_transfer))
+                .route("/in-flight", web::get().to(get_in_flight_transfers))
+                .route("/ingest", web::post().to(ingest_transfers))
+                .route("/ingest/{job_id}", web::get().to(get_ingestion_job))
+                .route("/ingest/{job_id}/rows", web::get().to(get_ingestion_rows))
+                .route("", web::post().to(create_transfer))
+                .route("/{transfer_id}", web::get().to(get_transfer))
+                .route(
+                    "/{transfer_id}/wait",
+                    web::get().to(wait_for_transfer_status),
+                )
+                .route(
+                    "/by-reference/{reference_id}",
+                    web::get().to(get_transfer_by_reference),
+                )
+                .route("/wallet/{wallet_id}", web::get().to(get_wallet_transfers))
+                .route(
+                    "/process-pending",
+                    web::post().to(process_pending_transfers),
+                ),
+        )
+        .service(
+            // Выводы USDT на произвольные адреса
+            web::scope("/withdrawals").route("", web::post().to(create_withdrawal)),
+        )
+        .service(
+            // Пороговые подписки на балансы
+            web::scope("/alerts")
+                .route("/balance", web::post().to(create_balance_alert))
+                .route("/balance", web::get().to(list_balance_alerts))
+                .route(
+                    "/balance/{alert_id}",
+                    web::delete().to(delete_balance_alert),
+                ),
+        )
+        .service(
+            // Маршруты для платежных намерений
+            web::scope("/payment-intents")
+                .route("", web::post().to(create_payment_intent))
+                .route("/{intent_id}", web::get().to(get_payment_intent))
+                .route(
+                    "/{intent_id}/wait",
+                    web::get().to(wait_for_payment_intent_status),
+                ),
+        )
+        .service(
+            // Маршруты для транзакций
+            web::scope("/transactions")
+                .route("/{tx_hash}", web::get().to(get_transaction))
+                .route("/{tx_hash}/split-legs", web::get().to(get_split_legs)),
+        )
+        .service(
+            // 🪙 Мультитокенные маршруты (новые!)
+            web::scope("/tokens")
+                .route("", web::get().to(get_supported_tokens))
+                .route("/balance", web::get().to(get_multi_token_balance))
+                .route("/transfer", web::post().to(create_multi_token_transfer))
+                .route(
+                    "/{token_symbol}/toggle",
+                    web::post().to(toggle_token_status),
+                )
+                .route("/cache/stats", web::get().to(get_cache_stats_and_cleanup))
+                .route(
+                    "/cache/invalidate/{wallet_address}",
+                    web::delete().to(invalidate_wallet_cache),
+                ),
+        )
+        .service(
+            // Статистика работы шлюза
+            web::scope("/stats")
+                .route("/processing", web::get().to(get_processing_stats))
+                .route("/monitoring", web::get().to(get_monitoring_statistics)),
+        )
+        .service(
+            // Bulk export webhook событий для reconciliation мерчантов
+            web::scope("/webhooks")
+                .route("/events/export", web::get().to(export_webhook_events))
+                .route("/deliveries", web::get().to(get_webhook_deliveries))
+                .route(
+                    "/deliveries/{delivery_id}/redeliver",
+                    web::post().to(redeliver_webhook_delivery),
+                )
+                .route("/replay", web::post().to(start_webhook_replay))
+                .route("/replay/{job_id}", web::get().to(get_webhook_replay_status))
+                .route("/endpoints", web::post().to(create_webhook_endpoint))
+                .route("/endpoints", web::get().to(list_webhook_endpoints))
+                .route(
+                    "/endpoints/{endpoint_id}",
+                    web::put().to(update_webhook_endpoint),
+                )
+                .route(
+                    "/endpoints/{endpoint_id}",
+                    web::delete().to(delete_webhook_endpoint),
+                )
+                .route(
+                    "/endpoints/{endpoint_id}/rotate-secret",
+                    web::post().to(rotate_webhook_endpoint_secret),
+                ),
+        )
+        .service(
+            // Инвойсы: платежные запросы с истечением
+            web::scope("/invoices")
+                .route("", web::post().to(create_invoice))
+                .route("", web::get().to(list_invoices))
+                .route("/{invoice_id}", web::get().to(get_invoice)),
+        )
+        .service(
+            // TRC-20 разрешения для approve/transferFrom флоу
+            web::scope("/allowances")
+                .route("", web::get().to(list_allowances))
+                .route("/{owner_address}/check", web::post().to(check_allowance))
+                .route(
+                    "/{owner_address}/history",
+                    web::get().to(get_allowance_history),
+                )
+                .route(
+                    "/{owner_address}/revoke",
+                    web::post().to(build_allowance_revocation),
+                ),
+        )
+        .service(
+            // Восстановление осиротевших трансферов (broadcast прошел,
+            // но хэш потерян до записи в БД)
+            web::scope("/recovery")
+                .route("/orphans", web::get().to(scan_orphaned_transfers))
+                .route(
+                    "/orphans/{transfer_id}/link",
+                    web::post().to(link_orphaned_transfer),
+                ),
+        )
+        .service(
+            // 🚰 Faucet тестовых средств (только sandbox)
+            web::scope("/faucet").route("/{address}", web::post().to(fund_wallet_from_faucet)),
+        )
+        .service(
+            // Отладочные маршруты
+            web::scope("/debug")
+                .route(
+                    "/master-wallet/balance",
+                    web::get().to(get_master_wallet_balance),
+                )
+                .route(
+                    "/master-wallet/history",
+                    web::get().to(get_master_wallet_history),
+                )
+                .route("/resources/{address}", web::get().to(get_account_resources))
+                .route(
+                    "/monitoring/replay-dead-letters",
+                    web::post().to(replay_monitoring_dead_letters),
+                )
+                .route(
+                    "/processing/tuning",
+                    web::put().to(update_processing_tuning),
+                )
+                .route("/trongrid-usage", web::get().to(get_trongrid_usage))
+                .route("/scheduler/runs", web::get().to(get_scheduler_runs))
+                .route("/sweep/runs", web::get().to(get_sweep_runs))
+                .route("/system/health", web::get().to(health_check)),
+        );
+}
@@ -0,0 +1,101 @@
+//! # Идентичность инстанса шлюза
+//!
+//! В мульти-инстансном и мульти-региональном деплое каждое действие
+//! (claim трансфера, аудит-событие, метка в логах) должно быть
+//! атрибутируемо конкретному узлу. Идентификатор и регион задаются
+//! в конфигурации; без явного id генерируется случайный на время
+//! жизни процесса.
+
+use serde::Serialize;
+
+use crate::config::InstanceConfig;
+
+/// Идентичность инстанса: кто выполнил действие и в каком регионе
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceIdentity {
+    /// Уникальный идентификатор инстанса (например "gateway-eu-1")
+    pub id: String,
+    /// Регион деплоя (например "eu-west"), если задан
+    pub region: Option<String>,
+}
+
+impl InstanceIdentity {
+    /// Создает идентичность из конфигурации.
+    /// Без явного id генерируется случайный с префиксом "gw-"
+    pub fn from_config(config: &InstanceConfig) -> Self {
+        let id = config.id.clone().unwrap_or_else(Self::generate_instance_id);
+
+        Self {
+            id,
+            region: config.region.clone(),
+        }
+    }
+
+    /// Метка инстанса для claimed_by, логов и меток метрик:
+    /// "id@region" или просто "id" без региона
+    pub fn label(&self) -> String {
+        match &self.region {
+            Some(region) => format!("{}@{}", self.id, region),
+            None => self.id.clone(),
+        }
+    }
+
+    /// Относится ли инстанс к указанному региону.
+    /// Инстанс без региона обслуживает работу любого региона
+    pub fn serves_region(&self, region: Option<&str>) -> bool {
+        match (&self.region, region) {
+            (Some(own), Some(required)) => own == required,
+            _ => true,
+        }
+    }
+
+    /// Генерирует случайный идентификатор инстанса
+    fn generate_instance_id() -> String {
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        format!("gw-{}", &suffix[..8])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_includes_region() {
+        let identity = InstanceIdentity {
+            id: "gateway-eu-1".to_string(),
+            region: Some("eu-west".to_string()),
+        };
+        assert_eq!(identity.label(), "gateway-eu-1@eu-west");
+
+        let identity = InstanceIdentity {
+            id: "gateway-1".to_string(),
+            region: None,
+        };
+        assert_eq!(identity.label(), "gateway-1");
+    }
+
+    #[test]
+    fn test_generated_id_when_not_configured() {
+        let identity = InstanceIdentity::from_config(&InstanceConfig::default());
+        assert!(identity.id.starts_with("gw-"));
+        assert_eq!(identity.id.len(), 11);
+    }
+
+    #[test]
+    fn test_serves_region_affinity() {
+        let regional = InstanceIdentity {
+            id: "gateway-eu-1".to_string(),
+            region: Some("eu-west".to_string()),
+        };
+        assert!(regional.serves_region(Some("eu-west")));
+        assert!(!regional.serves_region(Some("us-east")));
+        assert!(regional.serves_region(None));
+
+        let global = InstanceIdentity {
+            id: "gateway-1".to_string(),
+            region: None,
+        };
+        assert!(global.serves_region(Some("eu-west")));
+    }
+}
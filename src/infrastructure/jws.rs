@@ -0,0 +1,208 @@
+//! # JWS подпись webhook payload'ов
+//!
+//! Альтернатива HMAC: payload подписывается как compact JWS (ES256),
+//! мерчант проверяет подпись стандартной JWT библиотекой по публичным
+//! ключам из JWKS документа (`/.well-known/jwks.json`). Ротация ключей
+//! не требует синхронного обновления у мерчанта: старые публичные ключи
+//! остаются в JWKS через `retired_public_keys_hex`, пока подписанные
+//! ими доставки еще могут проверяться
+
+use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::config::WebhookJwsConfig;
+
+/// Подписант webhook payload'ов в формате compact JWS (ES256)
+pub struct JwsSigner {
+    signing_key: SigningKey,
+    /// Идентификатор активного ключа (kid в заголовке JWS и JWKS)
+    key_id: String,
+    /// JWK прошлых поколений ключей - публикуются в JWKS для ротации
+    retired_jwks: Vec<Value>,
+}
+
+impl JwsSigner {
+    /// Создает подписанта из конфигурации. None - JWS подпись выключена
+    pub fn from_config(config: &WebhookJwsConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let key_hex = config
+            .signing_key_hex
+            .as_deref()
+            .ok_or_else(|| anyhow!("JWS подпись включена, но signing_key_hex не задан"))?;
+
+        let key_bytes = hex::decode(key_hex).context("Невалидный hex приватного JWS ключа")?;
+        let signing_key = SigningKey::from_slice(&key_bytes)
+            .map_err(|e| anyhow!("Невалидный приватный JWS ключ P-256: {}", e))?;
+
+        let key_id = match &config.key_id {
+            Some(key_id) => key_id.clone(),
+            None => key_thumbprint(signing_key.verifying_key()),
+        };
+
+        let retired_jwks = config
+            .retired_public_keys_hex
+            .iter()
+            .map(|public_hex| {
+                let point_bytes =
+                    hex::decode(public_hex).context("Невалидный hex публичного JWS ключа")?;
+                let verifying_key = VerifyingKey::from_sec1_bytes(&point_bytes)
+                    .map_err(|e| anyhow!("Невалидный публичный JWS ключ P-256: {}", e))?;
+                Ok(public_jwk(&verifying_key, &key_thumbprint(&verifying_key)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(Self {
+            signing_key,
+            key_id,
+            retired_jwks,
+        }))
+    }
+
+    /// Идентификатор активного ключа
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Подписывает payload как compact JWS: `base64url(header).base64url(payload).base64url(signature)`
+    pub fn sign(&self, payload: &str) -> String {
+        let header = json!({
+            "alg": "ES256",
+            "typ": "JOSE",
+            "kid": self.key_id,
+        });
+
+        let header_b64 = URL_SAFE_NO_PAD.encode(header.to_string());
+        let payload_b64 = URL_SAFE_NO_PAD.encode(payload);
+        let signing_input = format!("{}.{}", header_b64, payload_b64);
+
+        // ES256 подпись - сырые r||s (64 байта), не DER
+        let signature: Signature = self.signing_key.sign(signing_input.as_bytes());
+        let signature_b64 = URL_SAFE_NO_PAD.encode(signature.to_bytes());
+
+        format!("{}.{}", signing_input, signature_b64)
+    }
+
+    /// JWKS документ с активным и выведенными из ротации ключами
+    pub fn jwks_document(&self) -> Value {
+        let mut keys = vec![public_jwk(self.signing_key.verifying_key(), &self.key_id)];
+        keys.extend(self.retired_jwks.iter().cloned());
+
+        json!({ "keys": keys })
+    }
+}
+
+/// Публичный JWK (RFC 7517) для ключа P-256
+fn public_jwk(verifying_key: &VerifyingKey, key_id: &str) -> Value {
+    let point = verifying_key.to_encoded_point(false);
+    let x = URL_SAFE_NO_PAD.encode(point.x().expect("несжатая точка содержит x"));
+    let y = URL_SAFE_NO_PAD.encode(point.y().expect("несжатая точка содержит y"));
+
+    json!({
+        "kty": "EC",
+        "crv": "P-256",
+        "x": x,
+        "y": y,
+        "use": "sig",
+        "alg": "ES256",
+        "kid": key_id,
+    })
+}
+
+/// Thumbprint ключа по RFC 7638 (kid по умолчанию).
+/// Члены canonical JWK идут в лексикографическом порядке
+fn key_thumbprint(verifying_key: &VerifyingKey) -> String {
+    let point = verifying_key.to_encoded_point(false);
+    let x = URL_SAFE_NO_PAD.encode(point.x().expect("несжатая точка содержит x"));
+    let y = URL_SAFE_NO_PAD.encode(point.y().expect("несжатая точка содержит y"));
+
+    let canonical = format!(r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#, x, y);
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Verifier;
+
+    fn test_config() -> WebhookJwsConfig {
+        WebhookJwsConfig {
+            enabled: true,
+            signing_key_hex: Some(hex::encode([7u8; 32])),
+            key_id: None,
+            retired_public_keys_hex: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_disabled_config_yields_no_signer() {
+        let signer = JwsSigner::from_config(&WebhookJwsConfig::default()).unwrap();
+        assert!(signer.is_none());
+    }
+
+    #[test]
+    fn test_signature_verifies_with_jwks_key() {
+        let signer = JwsSigner::from_config(&test_config()).unwrap().unwrap();
+
+        let payload = r#"{"event_type":"transfer_completed","id":42}"#;
+        let jws = signer.sign(payload);
+
+        let parts: Vec<&str> = jws.split('.').collect();
+        assert_eq!(parts.len(), 3, "compact JWS состоит из трех сегментов");
+
+        // Заголовок содержит алгоритм и kid активного ключа
+        let header: Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(parts[0]).unwrap()).unwrap();
+        assert_eq!(header["alg"], "ES256");
+        assert_eq!(header["kid"], signer.key_id());
+
+        // Payload восстанавливается без искажений
+        let decoded_payload = URL_SAFE_NO_PAD.decode(parts[1]).unwrap();
+        assert_eq!(decoded_payload, payload.as_bytes());
+
+        // Подпись проверяется публичным ключом из JWKS
+        let jwks = signer.jwks_document();
+        let x = URL_SAFE_NO_PAD
+            .decode(jwks["keys"][0]["x"].as_str().unwrap())
+            .unwrap();
+        let y = URL_SAFE_NO_PAD
+            .decode(jwks["keys"][0]["y"].as_str().unwrap())
+            .unwrap();
+
+        let mut point_bytes = vec![0x04u8];
+        point_bytes.extend_from_slice(&x);
+        point_bytes.extend_from_slice(&y);
+        let verifying_key = VerifyingKey::from_sec1_bytes(&point_bytes).unwrap();
+
+        let signing_input = format!("{}.{}", parts[0], parts[1]);
+        let signature = Signature::from_slice(&URL_SAFE_NO_PAD.decode(parts[2]).unwrap()).unwrap();
+        assert!(verifying_key
+            .verify(signing_input.as_bytes(), &signature)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_retired_keys_stay_in_jwks() {
+        let retired_key = SigningKey::from_slice(&[9u8; 32]).unwrap();
+        let retired_point = retired_key.verifying_key().to_encoded_point(false);
+
+        let mut config = test_config();
+        config.retired_public_keys_hex = vec![hex::encode(retired_point.as_bytes())];
+
+        let signer = JwsSigner::from_config(&config).unwrap().unwrap();
+        let jwks = signer.jwks_document();
+
+        let keys = jwks["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 2);
+        // Активный ключ идет первым, выведенный - следом
+        assert_eq!(keys[0]["kid"], signer.key_id());
+        assert_eq!(keys[1]["kid"], key_thumbprint(retired_key.verifying_key()));
+    }
+}
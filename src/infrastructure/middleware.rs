@@ -16,42 +16,281 @@ use std::{
 };
 use tracing::{info, warn};
 
-/// Rate limiter на основе IP адресов
+/// Персональная квота API ключа (имя - для логов и Redis-ключей,
+/// чтобы значение ключа не попадало в хранилище)
+#[derive(Debug, Clone)]
+struct PerKeyQuota {
+    name: String,
+    max_requests: u32,
+}
+
+/// Хранилище счетчиков rate limiter'а
+#[derive(Clone)]
+enum RateLimitStore {
+    /// Локальные счетчики инстанса (сбрасываются при рестарте)
+    Memory(Arc<Mutex<HashMap<String, Vec<Instant>>>>),
+    /// Общие счетчики в Redis - лимиты делятся между инстансами шлюза
+    Redis(redis::aio::ConnectionManager),
+}
+
+/// Rate limiter по API ключу с fallback на IP
+///
+/// Лимит по одному IP за load balancer'ом склеивал всех клиентов в общий
+/// bucket, поэтому аутентифицированные запросы считаются по ключу
+/// `X-Api-Key` с персональной квотой из конфигурации. Анонимные запросы
+/// по-прежнему ограничиваются по IP
 #[derive(Clone)]
 pub struct RateLimiter {
+    enabled: bool,
     max_requests: u32,
     window_duration: Duration,
-    requests: Arc<Mutex<HashMap<String, Vec<Instant>>>>,
+    /// Значение API ключа -> персональная квота
+    per_key_quotas: Arc<HashMap<String, PerKeyQuota>>,
+    store: RateLimitStore,
 }
 
 impl RateLimiter {
     pub fn new(max_requests: u32, window_seconds: u64) -> Self {
         Self {
+            enabled: true,
             max_requests,
             window_duration: Duration::from_secs(window_seconds),
-            requests: Arc::new(Mutex::new(HashMap::new())),
+            per_key_quotas: Arc::new(HashMap::new()),
+            store: RateLimitStore::Memory(Arc::new(Mutex::new(HashMap::new()))),
+        }
+    }
+
+    /// Создает limiter из конфигурации: общий лимит на окно в минуту,
+    /// персональные квоты - из списка API ключей
+    pub fn from_config(
+        config: &crate::config::RateLimitConfig,
+        auth: &crate::config::AuthConfig,
+    ) -> Self {
+        let per_key_quotas = auth
+            .api_keys
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key.clone(),
+                    PerKeyQuota {
+                        name: entry.name.clone(),
+                        max_requests: entry
+                            .rate_limit_per_minute
+                            .unwrap_or(config.requests_per_minute),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            enabled: config.enabled,
+            max_requests: config.requests_per_minute,
+            window_duration: Duration::from_secs(60),
+            per_key_quotas: Arc::new(per_key_quotas),
+            store: RateLimitStore::Memory(Arc::new(Mutex::new(HashMap::new()))),
+        }
+    }
+
+    /// Переключает хранилище счетчиков на Redis (общие лимиты
+    /// для нескольких инстансов за load balancer'ом)
+    pub async fn with_redis(mut self, redis_url: &str) -> anyhow::Result<Self> {
+        let client = redis::Client::open(redis_url)?;
+        let manager = redis::aio::ConnectionManager::new(client).await?;
+        self.store = RateLimitStore::Redis(manager);
+        Ok(self)
+    }
+
+    /// Включен ли rate limiting
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Определяет субъект лимита и его квоту: известный API ключ -
+    /// персональный bucket по имени ключа, иначе - bucket по IP
+    pub fn resolve_subject(&self, api_key: Option<&str>, ip: &str) -> (String, u32) {
+        match api_key.and_then(|key| self.per_key_quotas.get(key)) {
+            Some(quota) => (format!("key:{}", quota.name), quota.max_requests),
+            None => (format!("ip:{}", ip), self.max_requests),
         }
     }
 
-    pub fn check_rate_limit(&self, ip: &str) -> bool {
-        let mut requests = self.requests.lock().unwrap();
+    /// Проверяет лимит субъекта, учитывая текущий запрос
+    pub async fn check_rate_limit(&self, subject: &str, max_requests: u32) -> RateLimitDecision {
+        match &self.store {
+            RateLimitStore::Memory(requests) => {
+                self.check_memory(requests, subject, max_requests, true)
+            }
+            RateLimitStore::Redis(manager) => {
+                self.check_redis(manager.clone(), subject, max_requests, true)
+                    .await
+            }
+        }
+    }
+
+    /// Текущее потребление квоты субъекта без учета нового запроса
+    /// (introspection endpoint)
+    pub async fn current_usage(&self, subject: &str, max_requests: u32) -> RateLimitDecision {
+        match &self.store {
+            RateLimitStore::Memory(requests) => {
+                self.check_memory(requests, subject, max_requests, false)
+            }
+            RateLimitStore::Redis(manager) => {
+                self.check_redis(manager.clone(), subject, max_requests, false)
+                    .await
+            }
+        }
+    }
+
+    /// Синхронная проверка лимита для gRPC interceptor'а (tonic
+    /// interceptor не async). In-memory счетчики проверяются на месте;
+    /// с Redis-хранилищем лимит для gRPC не применяется (fail open) -
+    /// None означает "решение принять нельзя, пропускаем"
+    pub fn check_rate_limit_sync(
+        &self,
+        subject: &str,
+        max_requests: u32,
+    ) -> Option<RateLimitDecision> {
+        match &self.store {
+            RateLimitStore::Memory(requests) => {
+                Some(self.check_memory(requests, subject, max_requests, true))
+            }
+            RateLimitStore::Redis(_) => None,
+        }
+    }
+
+    /// Sliding-window счетчик в памяти инстанса.
+    /// `consume: false` - только подсмотреть потребление, не учитывая
+    /// текущий запрос
+    fn check_memory(
+        &self,
+        requests: &Mutex<HashMap<String, Vec<Instant>>>,
+        subject: &str,
+        max_requests: u32,
+        consume: bool,
+    ) -> RateLimitDecision {
+        let mut requests = requests.lock().unwrap();
         let now = Instant::now();
 
-        let ip_requests = requests.entry(ip.to_string()).or_insert_with(Vec::new);
+        let subject_requests = requests.entry(subject.to_string()).or_default();
 
         // Удаляем старые запросы
-        ip_requests.retain(|&time| now.duration_since(time) < self.window_duration);
+        subject_requests.retain(|&time| now.duration_since(time) < self.window_duration);
 
-        if ip_requests.len() >= self.max_requests as usize {
-            warn!("🚫 Rate limit превышен для IP: {}", ip);
-            false
+        let allowed = subject_requests.len() < max_requests as usize;
+        if allowed && consume {
+            subject_requests.push(now);
+        } else if !allowed && consume {
+            warn!("🚫 Rate limit превышен: {}", subject);
+        }
+
+        // Окно освобождается, когда самый старый запрос выйдет за границу
+        let reset_seconds = subject_requests
+            .first()
+            .map(|&oldest| {
+                self.window_duration
+                    .saturating_sub(now.duration_since(oldest))
+                    .as_secs()
+            })
+            .unwrap_or(0);
+
+        RateLimitDecision {
+            allowed,
+            limit: max_requests,
+            remaining: max_requests.saturating_sub(subject_requests.len() as u32),
+            reset_seconds,
+        }
+    }
+
+    /// Fixed-window счетчик в Redis: INCR + EXPIRE на первом запросе окна.
+    /// При недоступности Redis лимит не применяется (fail open) -
+    /// деградация rate limiter'а не должна ронять API
+    async fn check_redis(
+        &self,
+        mut conn: redis::aio::ConnectionManager,
+        subject: &str,
+        max_requests: u32,
+        consume: bool,
+    ) -> RateLimitDecision {
+        use redis::AsyncCommands;
+
+        let redis_key = format!("ratelimit:{}", subject);
+
+        let count: redis::RedisResult<u64> = if consume {
+            conn.incr(&redis_key, 1u64).await
         } else {
-            ip_requests.push(now);
-            true
+            conn.get::<_, Option<u64>>(&redis_key)
+                .await
+                .map(|count| count.unwrap_or(0))
+        };
+
+        match count {
+            Ok(count) => {
+                if consume && count == 1 {
+                    let expire: redis::RedisResult<()> = conn
+                        .expire(&redis_key, self.window_duration.as_secs() as i64)
+                        .await;
+                    if let Err(e) = expire {
+                        warn!("⚠️ Redis EXPIRE для rate limit не удался: {}", e);
+                    }
+                }
+
+                let allowed = if consume {
+                    count <= max_requests as u64
+                } else {
+                    count < max_requests as u64
+                };
+                if !allowed && consume {
+                    warn!("🚫 Rate limit превышен: {}", subject);
+                }
+
+                // TTL ключа = секунды до сброса окна; дергаем Redis
+                // только когда значение реально попадет в заголовки
+                let reset_seconds = if allowed && consume {
+                    self.window_duration.as_secs()
+                } else {
+                    match conn.ttl::<_, i64>(&redis_key).await {
+                        Ok(ttl) if ttl > 0 => ttl as u64,
+                        _ => 0,
+                    }
+                };
+
+                RateLimitDecision {
+                    allowed,
+                    limit: max_requests,
+                    remaining: max_requests.saturating_sub(count.min(u64::from(u32::MAX)) as u32),
+                    reset_seconds,
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ Redis недоступен, rate limit пропущен (fail open): {}",
+                    e
+                );
+                RateLimitDecision {
+                    allowed: true,
+                    limit: max_requests,
+                    remaining: max_requests,
+                    reset_seconds: 0,
+                }
+            }
         }
     }
 }
 
+/// Решение rate limiter'а по запросу: кроме вердикта - данные для
+/// заголовков `X-RateLimit-*` и `Retry-After` (или их gRPC аналогов)
+#[derive(Debug, Clone)]
+pub struct RateLimitDecision {
+    /// Пропущен ли запрос
+    pub allowed: bool,
+    /// Квота субъекта на окно
+    pub limit: u32,
+    /// Остаток квоты в текущем окне
+    pub remaining: u32,
+    /// Секунд до сброса окна
+    pub reset_seconds: u64,
+}
+
 impl<S, B> Transform<S, ServiceRequest> for RateLimiter
 where
     S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
@@ -95,21 +334,51 @@ where
         let rate_limiter = self.rate_limiter.clone();
 
         Box::pin(async move {
-            // Получаем IP адрес
+            if !rate_limiter.enabled {
+                return service.call(req).await;
+            }
+
+            // Получаем IP адрес (fallback, если запрос без API ключа)
             let ip = req
                 .connection_info()
                 .realip_remote_addr()
                 .unwrap_or("unknown")
                 .to_string();
 
-            if !rate_limiter.check_rate_limit(&ip) {
-                return Err(actix_web::error::ErrorTooManyRequests(
-                    json!({
-                        "error": "Rate limit exceeded",
-                        "message": "Слишком много запросов, попробуйте позже"
-                    })
-                    .to_string(),
-                ));
+            let api_key = req
+                .headers()
+                .get("x-api-key")
+                .and_then(|h| h.to_str().ok())
+                .map(|k| k.to_string());
+
+            let (subject, max_requests) = rate_limiter.resolve_subject(api_key.as_deref(), &ip);
+
+            let decision = rate_limiter.check_rate_limit(&subject, max_requests).await;
+            if !decision.allowed {
+                // Retry-After не бывает нулевым: клиенту всегда есть
+                // что выждать перед повтором
+                let retry_after = decision.reset_seconds.max(1);
+                let response = actix_web::HttpResponse::TooManyRequests()
+                    .insert_header(("Retry-After", retry_after.to_string()))
+                    .insert_header(("X-RateLimit-Limit", decision.limit.to_string()))
+                    .insert_header(("X-RateLimit-Remaining", decision.remaining.to_string()))
+                    .insert_header(("X-RateLimit-Reset", decision.reset_seconds.to_string()))
+                    .content_type("application/json")
+                    .body(
+                        json!({
+                            "error": "Rate limit exceeded",
+                            "message": format!(
+                                "Слишком много запросов, повторите через {} секунд",
+                                retry_after
+                            )
+                        })
+                        .to_string(),
+                    );
+                return Err(actix_web::error::InternalError::from_response(
+                    "Rate limit exceeded",
+                    response,
+                )
+                .into());
             }
 
             service.call(req).await
@@ -209,6 +478,624 @@ where
     }
 }
 
+/// Middleware авторизации по wallet-scoped API токену
+///
+/// Пропускает только GET запросы к `/api/wallets/{wallet_id}/...`,
+/// если заголовок `X-Wallet-Token` содержит действующий токен,
+/// привязанный именно к этому кошельку
+#[derive(Clone)]
+pub struct WalletTokenAuth {
+    token_service: Arc<crate::application::services::WalletTokenService>,
+}
+
+impl WalletTokenAuth {
+    pub fn new(token_service: Arc<crate::application::services::WalletTokenService>) -> Self {
+        Self { token_service }
+    }
+
+    /// Извлекает wallet_id из пути вида `/api/wallets/{wallet_id}/...`
+    fn wallet_id_from_path(path: &str) -> Option<i64> {
+        path.strip_prefix("/api/wallets/")?
+            .split('/')
+            .next()?
+            .parse()
+            .ok()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for WalletTokenAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = WalletTokenAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(WalletTokenAuthMiddleware {
+            service: Rc::new(service),
+            token_service: self.token_service.clone(),
+        })
+    }
+}
+
+pub struct WalletTokenAuthMiddleware<S> {
+    service: Rc<S>,
+    token_service: Arc<crate::application::services::WalletTokenService>,
+}
+
+impl<S, B> Service<ServiceRequest> for WalletTokenAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let token_service = self.token_service.clone();
+
+        Box::pin(async move {
+            // Wallet-scoped токены дают только read-only доступ
+            if req.method() != actix_web::http::Method::GET {
+                return Err(actix_web::error::ErrorForbidden(
+                    json!({
+                        "error": "Forbidden",
+                        "message": "Wallet-scoped токен разрешает только чтение"
+                    })
+                    .to_string(),
+                ));
+            }
+
+            let requested_wallet_id = WalletTokenAuth::wallet_id_from_path(req.path());
+
+            let token = req
+                .headers()
+                .get("x-wallet-token")
+                .and_then(|h| h.to_str().ok())
+                .map(|t| t.to_string());
+
+            let token = match token {
+                Some(token) => token,
+                None => {
+                    return Err(actix_web::error::ErrorUnauthorized(
+                        json!({
+                            "error": "Unauthorized",
+                            "message": "Отсутствует заголовок X-Wallet-Token"
+                        })
+                        .to_string(),
+                    ));
+                }
+            };
+
+            let authorized_wallet_id = token_service
+                .authorize_token(&token)
+                .await
+                .map_err(actix_web::error::ErrorInternalServerError)?;
+
+            match (authorized_wallet_id, requested_wallet_id) {
+                (Some(token_wallet), Some(path_wallet)) if token_wallet == path_wallet => {
+                    service.call(req).await
+                }
+                _ => {
+                    warn!(
+                        "🚫 Wallet-scoped токен не подходит для пути: {}",
+                        req.path()
+                    );
+                    Err(actix_web::error::ErrorForbidden(
+                        json!({
+                            "error": "Forbidden",
+                            "message": "Токен не привязан к запрошенному кошельку"
+                        })
+                        .to_string(),
+                    ))
+                }
+            }
+        })
+    }
+}
+
+/// Область доступа API ключа
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    /// Только чтение (GET запросы и read-only RPC)
+    Read,
+    /// Полный доступ, включая создание трансферов и выводов
+    Transfer,
+}
+
+impl ApiKeyScope {
+    /// Парсит scope из конфигурации (строки проверены в `Settings::validate`)
+    fn from_config_str(scope: &str) -> Self {
+        match scope {
+            "transfer" => Self::Transfer,
+            _ => Self::Read,
+        }
+    }
+
+    /// Разрешает ли scope мутирующие операции
+    pub fn allows_writes(&self) -> bool {
+        matches!(self, Self::Transfer)
+    }
+
+    /// Строковое представление scope (introspection endpoint)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Transfer => "transfer",
+        }
+    }
+}
+
+/// Авторизованный API ключ (имя для аудита + область доступа)
+#[derive(Debug, Clone)]
+pub struct AuthorizedApiKey {
+    pub name: String,
+    pub scope: ApiKeyScope,
+}
+
+/// Реестр API ключей, общий для HTTP middleware и gRPC interceptor'а.
+/// При выключенной аутентификации пропускает все запросы -
+/// совместимость с деплоями без настроенных ключей
+#[derive(Clone)]
+pub struct ApiKeyRegistry {
+    enabled: bool,
+    keys: Arc<HashMap<String, AuthorizedApiKey>>,
+}
+
+impl ApiKeyRegistry {
+    pub fn from_config(config: &crate::config::AuthConfig) -> Self {
+        let keys = config
+            .api_keys
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key.clone(),
+                    AuthorizedApiKey {
+                        name: entry.name.clone(),
+                        scope: ApiKeyScope::from_config_str(&entry.scope),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            enabled: config.enabled,
+            keys: Arc::new(keys),
+        }
+    }
+
+    /// Включена ли проверка ключей
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Ищет ключ в реестре. None - ключ неизвестен
+    pub fn authorize(&self, key: &str) -> Option<AuthorizedApiKey> {
+        self.keys.get(key).cloned()
+    }
+}
+
+/// Middleware аутентификации по API ключу (заголовок `X-Api-Key`)
+///
+/// Защищает все маршруты `/api/*`: без ключа или с неизвестным ключом - 401,
+/// read-only ключ на мутирующем запросе - 403. Корень и `/admin`
+/// (со своим ключом) остаются вне проверки
+#[derive(Clone)]
+pub struct ApiKeyAuth {
+    registry: ApiKeyRegistry,
+}
+
+impl ApiKeyAuth {
+    pub fn new(registry: ApiKeyRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiKeyAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = ApiKeyAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ApiKeyAuthMiddleware {
+            service: Rc::new(service),
+            registry: self.registry.clone(),
+        })
+    }
+}
+
+pub struct ApiKeyAuthMiddleware<S> {
+    service: Rc<S>,
+    registry: ApiKeyRegistry,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiKeyAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let registry = self.registry.clone();
+
+        Box::pin(async move {
+            // Вне проверки: аутентификация выключена или путь не из /api
+            // (корень и /admin защищены собственным ключом)
+            if !registry.is_enabled() || !req.path().starts_with("/api") {
+                return service.call(req).await;
+            }
+
+            let provided_key = req
+                .headers()
+                .get("x-api-key")
+                .and_then(|h| h.to_str().ok())
+                .map(|k| k.to_string());
+
+            let provided_key = match provided_key {
+                Some(key) => key,
+                None => {
+                    return Err(actix_web::error::ErrorUnauthorized(
+                        json!({
+                            "error": "Unauthorized",
+                            "message": "Отсутствует заголовок X-Api-Key"
+                        })
+                        .to_string(),
+                    ));
+                }
+            };
+
+            let authorized = match registry.authorize(&provided_key) {
+                Some(authorized) => authorized,
+                None => {
+                    warn!("🚫 Неизвестный API ключ для пути: {}", req.path());
+                    return Err(actix_web::error::ErrorUnauthorized(
+                        json!({
+                            "error": "Unauthorized",
+                            "message": "Неизвестный API ключ"
+                        })
+                        .to_string(),
+                    ));
+                }
+            };
+
+            if req.method() != actix_web::http::Method::GET && !authorized.scope.allows_writes() {
+                warn!(
+                    "🚫 Ключ {} (read) отклонен на мутирующем запросе: {} {}",
+                    authorized.name,
+                    req.method(),
+                    req.path()
+                );
+                return Err(actix_web::error::ErrorForbidden(
+                    json!({
+                        "error": "Forbidden",
+                        "message": "API ключ со scope read разрешает только чтение"
+                    })
+                    .to_string(),
+                ));
+            }
+
+            service.call(req).await
+        })
+    }
+}
+
+/// Load shedding для chain-зависимых endpoint'ов.
+///
+/// При открытом circuit breaker'е или нарушении latency SLO запросы,
+/// упирающиеся в TronGrid, отклоняются с 503 - воркеры остаются свободными
+/// для DB-only endpoint'ов. Восстановление проверяется пробными запросами
+#[derive(Clone)]
+pub struct LoadShedder {
+    circuit_breaker: super::CircuitBreaker,
+    latency_slo: Duration,
+    probe_interval: Duration,
+    state: Arc<Mutex<LoadShedderState>>,
+}
+
+struct LoadShedderState {
+    /// Экспоненциально сглаженная длительность chain-зависимых запросов (мс)
+    ewma_ms: f64,
+    last_probe_at: Option<Instant>,
+}
+
+impl LoadShedder {
+    pub fn new(circuit_breaker: super::CircuitBreaker) -> Self {
+        Self {
+            circuit_breaker,
+            latency_slo: Duration::from_millis(5000),
+            probe_interval: Duration::from_secs(10),
+            state: Arc::new(Mutex::new(LoadShedderState {
+                ewma_ms: 0.0,
+                last_probe_at: None,
+            })),
+        }
+    }
+
+    /// Задает порог SLO по средней длительности запроса
+    pub fn with_latency_slo(mut self, latency_slo: Duration) -> Self {
+        self.latency_slo = latency_slo;
+        self
+    }
+
+    /// Endpoint'ы, которые ходят в TronGrid (DB-only маршруты не шеддятся)
+    fn is_chain_dependent(path: &str) -> bool {
+        path.ends_with("/balance")
+            || path.starts_with("/api/tokens/balance")
+            || path.starts_with("/api/transfers/preview")
+            || path.starts_with("/api/debug/resources/")
+            || path.starts_with("/api/debug/master-wallet")
+    }
+
+    /// Решает, пропускать ли chain-зависимый запрос
+    fn should_shed(&self) -> bool {
+        if self.circuit_breaker.is_open() {
+            return true;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.ewma_ms <= self.latency_slo.as_millis() as f64 {
+            return false;
+        }
+
+        // SLO нарушен: пропускаем пробный запрос раз в probe_interval
+        let now = Instant::now();
+        let probe_due = state
+            .last_probe_at
+            .map(|at| now.duration_since(at) >= self.probe_interval)
+            .unwrap_or(true);
+
+        if probe_due {
+            state.last_probe_at = Some(now);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Учитывает длительность пропущенного запроса в скользящем среднем
+    fn record_latency(&self, elapsed: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let elapsed_ms = elapsed.as_millis() as f64;
+        state.ewma_ms = if state.ewma_ms == 0.0 {
+            elapsed_ms
+        } else {
+            state.ewma_ms * 0.8 + elapsed_ms * 0.2
+        };
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for LoadShedder
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = LoadShedderMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(LoadShedderMiddleware {
+            service: Rc::new(service),
+            shedder: self.clone(),
+        })
+    }
+}
+
+pub struct LoadShedderMiddleware<S> {
+    service: Rc<S>,
+    shedder: LoadShedder,
+}
+
+impl<S, B> Service<ServiceRequest> for LoadShedderMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let shedder = self.shedder.clone();
+
+        Box::pin(async move {
+            if !LoadShedder::is_chain_dependent(req.path()) {
+                return service.call(req).await;
+            }
+
+            if shedder.should_shed() {
+                warn!("🚫 Load shedding: отклонен запрос {}", req.path());
+                return Err(actix_web::error::ErrorServiceUnavailable(
+                    json!({
+                        "error": "Chain degraded",
+                        "message": "TronGrid деградирует, попробуйте позже"
+                    })
+                    .to_string(),
+                ));
+            }
+
+            let started = Instant::now();
+            let response = service.call(req).await;
+            shedder.record_latency(started.elapsed());
+            response
+        })
+    }
+}
+
+/// Дата отключения неверсионированных путей /api/* (HTTP-date для Sunset)
+pub const UNVERSIONED_API_SUNSET: &str = "Mon, 01 Mar 2027 00:00:00 GMT";
+
+/// Middleware deprecation-заголовков для неверсионированных путей /api/*
+///
+/// Старые пути остаются рабочим алиасом /api/v1, но каждый ответ
+/// помечается заголовками `Deprecation: true` и `Sunset` (RFC 8594),
+/// чтобы клиенты успели мигрировать до отключения алиаса
+#[derive(Clone)]
+pub struct DeprecationHeaders;
+
+impl<S, B> Transform<S, ServiceRequest> for DeprecationHeaders
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = DeprecationHeadersMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(DeprecationHeadersMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct DeprecationHeadersMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for DeprecationHeadersMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let mut response = service.call(req).await?;
+
+            response.headers_mut().insert(
+                actix_web::http::header::HeaderName::from_static("deprecation"),
+                actix_web::http::header::HeaderValue::from_static("true"),
+            );
+            response.headers_mut().insert(
+                actix_web::http::header::HeaderName::from_static("sunset"),
+                actix_web::http::header::HeaderValue::from_static(UNVERSIONED_API_SUNSET),
+            );
+
+            Ok(response)
+        })
+    }
+}
+
+/// Middleware маркировки деградированных ответов
+///
+/// Пока шлюз в режиме деградации (read-only БД во время failover),
+/// каждый ответ помечается заголовком `X-Degraded-Mode: true` -
+/// клиенты видят, что читают потенциально отстающие данные
+#[derive(Clone)]
+pub struct DegradationMarker {
+    monitor: super::DegradationMonitor,
+}
+
+impl DegradationMarker {
+    pub fn new(monitor: super::DegradationMonitor) -> Self {
+        Self { monitor }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for DegradationMarker
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = DegradationMarkerMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(DegradationMarkerMiddleware {
+            service: Rc::new(service),
+            monitor: self.monitor.clone(),
+        })
+    }
+}
+
+pub struct DegradationMarkerMiddleware<S> {
+    service: Rc<S>,
+    monitor: super::DegradationMonitor,
+}
+
+impl<S, B> Service<ServiceRequest> for DegradationMarkerMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        futures_util::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let monitor = self.monitor.clone();
+
+        Box::pin(async move {
+            let mut response = service.call(req).await?;
+
+            if monitor.is_degraded() {
+                response.headers_mut().insert(
+                    actix_web::http::header::HeaderName::from_static("x-degraded-mode"),
+                    actix_web::http::header::HeaderValue::from_static("true"),
+                );
+            }
+
+            Ok(response)
+        })
+    }
+}
+
 /// Конфигурация middleware
 #[derive(Debug, Clone)]
 pub struct MiddlewareConfig {
@@ -226,3 +1113,88 @@ impl Default for MiddlewareConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ApiKeyConfig, AuthConfig, RateLimitConfig};
+
+    fn limiter_with_key_quota(quota: u32) -> RateLimiter {
+        let config = RateLimitConfig {
+            enabled: true,
+            requests_per_minute: 10,
+            redis_url: None,
+        };
+        let auth = AuthConfig {
+            enabled: true,
+            api_keys: vec![ApiKeyConfig {
+                key: "secret-key".to_string(),
+                name: "merchant-backend".to_string(),
+                scope: "transfer".to_string(),
+                rate_limit_per_minute: Some(quota),
+            }],
+        };
+        RateLimiter::from_config(&config, &auth)
+    }
+
+    #[test]
+    fn test_resolve_subject_prefers_api_key_over_ip() {
+        let limiter = limiter_with_key_quota(2);
+
+        // Известный ключ: bucket по имени (значение ключа не утекает)
+        // и персональная квота
+        let (subject, max_requests) = limiter.resolve_subject(Some("secret-key"), "10.0.0.1");
+        assert_eq!(subject, "key:merchant-backend");
+        assert_eq!(max_requests, 2);
+
+        // Неизвестный ключ и анонимный запрос: bucket по IP с общим лимитом
+        let (subject, max_requests) = limiter.resolve_subject(Some("unknown"), "10.0.0.1");
+        assert_eq!(subject, "ip:10.0.0.1");
+        assert_eq!(max_requests, 10);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_enforces_per_key_quota() {
+        let limiter = limiter_with_key_quota(2);
+        let (subject, max_requests) = limiter.resolve_subject(Some("secret-key"), "10.0.0.1");
+
+        let first = limiter.check_rate_limit(&subject, max_requests).await;
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+
+        assert!(
+            limiter
+                .check_rate_limit(&subject, max_requests)
+                .await
+                .allowed
+        );
+
+        let rejected = limiter.check_rate_limit(&subject, max_requests).await;
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.limit, 2);
+        assert_eq!(rejected.remaining, 0);
+        assert!(rejected.reset_seconds <= 60);
+
+        // Bucket другого субъекта не затронут
+        assert!(limiter.check_rate_limit("ip:10.0.0.1", 10).await.allowed);
+    }
+
+    #[tokio::test]
+    async fn test_current_usage_does_not_consume_quota() {
+        let limiter = limiter_with_key_quota(2);
+        let (subject, max_requests) = limiter.resolve_subject(Some("secret-key"), "10.0.0.1");
+
+        assert!(
+            limiter
+                .check_rate_limit(&subject, max_requests)
+                .await
+                .allowed
+        );
+
+        // Introspection не тратит квоту: остаток не меняется
+        let usage = limiter.current_usage(&subject, max_requests).await;
+        assert_eq!(usage.remaining, 1);
+        let usage = limiter.current_usage(&subject, max_requests).await;
+        assert_eq!(usage.remaining, 1);
+    }
+}
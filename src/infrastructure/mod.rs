@@ -1,13 +1,51 @@
+pub mod audit;
+pub mod chain;
+pub mod circuit_breaker;
 pub mod database;
+pub mod degradation;
+pub mod deposit_labeling;
 pub mod grpc;
 pub mod http;
+pub mod instance;
+pub mod jws;
 pub mod middleware;
+pub mod notifications;
+pub mod price_oracle;
 pub mod retry;
+pub mod risk_screening;
+pub mod shutdown;
 pub mod tron;
 
 // Реэкспорт для обратной совместимости
-pub use middleware::{AuditLogger, MiddlewareConfig, RateLimiter};
+pub use audit::{AuditEvent, AuditShipper, AuditSink, HttpAuditSink, TracingAuditSink};
+pub use chain::{
+    Blockchain, ChainConfirmationSemantics, ChainRegistry, ChainWallet, TronBlockchain,
+    CHAIN_ID_TRON,
+};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+pub use degradation::{DegradationMonitor, DegradationSnapshot};
+pub use deposit_labeling::DepositSourceLabeler;
+pub use instance::InstanceIdentity;
+pub use jws::JwsSigner;
+pub use middleware::{
+    ApiKeyAuth, ApiKeyRegistry, ApiKeyScope, AuditLogger, AuthorizedApiKey, DegradationMarker,
+    DeprecationHeaders, LoadShedder, MiddlewareConfig, RateLimitDecision, RateLimiter,
+    WalletTokenAuth,
+};
+pub use notifications::{
+    Notification, NotificationDispatcher, NotificationSeverity, Notifier, SmtpNotifier,
+    TracingNotifier,
+};
+pub use price_oracle::{
+    oracle_from_provider, BinanceOracle, CoinGeckoOracle, PriceFeed, PriceOracle,
+};
 pub use retry::{
-    classify_http_error, classify_reqwest_error, RetryConfig, RetryableError, RetryableService,
+    classify_http_error, classify_reqwest_error, RetryClock, RetryConfig, RetryableError,
+    RetryableService, TokioClock,
+};
+pub use risk_screening::{HttpRiskScreeningProvider, RiskScreeningProvider, ScreeningResult};
+pub use shutdown::{InFlightGuard, ShutdownCoordinator};
+pub use tron::{
+    LocalSigningBackend, RemoteSigningBackend, SigningBackend, TronGridClient,
+    TronTransactionSigner, TronWalletGenerator,
 };
-pub use tron::{TronGridClient, TronTransactionSigner, TronWalletGenerator};
@@ -0,0 +1,302 @@
+//! # Email уведомления об операционных событиях
+//!
+//! SMTP-доставка критических алертов и ежедневных сводок для команд,
+//! не использующих webhook-интеграции. Каналы подключаются через трейт
+//! `Notifier` (по аналогии с `AuditSink`), серьезность фильтруется
+//! per-recipient в конфигурации.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// Серьезность уведомления (порядок важен: Info < Warning < Critical)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotificationSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl NotificationSeverity {
+    /// Строковое представление для темы письма и логов
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "INFO",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+        }
+    }
+}
+
+impl FromStr for NotificationSeverity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "critical" => Ok(Self::Critical),
+            other => Err(anyhow::anyhow!(
+                "Неизвестная серьезность уведомления: {}",
+                other
+            )),
+        }
+    }
+}
+
+/// Операционное уведомление
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub severity: NotificationSeverity,
+    pub subject: String,
+    pub body: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl Notification {
+    /// Создает новое уведомление с текущим временем
+    pub fn new(severity: NotificationSeverity, subject: &str, body: String) -> Self {
+        Self {
+            severity,
+            subject: subject.to_string(),
+            body,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// Создает уведомление из шаблона с плейсхолдерами вида `{имя}`
+    pub fn from_template(
+        severity: NotificationSeverity,
+        subject: &str,
+        template: &str,
+        values: &HashMap<&str, String>,
+    ) -> Self {
+        Self::new(severity, subject, render_template(template, values))
+    }
+}
+
+/// Подставляет значения в плейсхолдеры `{имя}` шаблона
+pub fn render_template(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut rendered = template.to_string();
+    for (key, value) in values {
+        rendered = rendered.replace(&format!("{{{}}}", key), value);
+    }
+    rendered
+}
+
+/// Подключаемый канал доставки операционных уведомлений
+#[tonic::async_trait]
+pub trait Notifier: Send + Sync {
+    /// Имя канала для логирования
+    fn name(&self) -> &str;
+
+    /// Доставляет уведомление
+    async fn notify(&self, notification: &Notification) -> Result<()>;
+}
+
+/// Получатель email с минимальной серьезностью для доставки
+#[derive(Debug, Clone)]
+struct EmailRecipient {
+    address: lettre::message::Mailbox,
+    min_severity: NotificationSeverity,
+}
+
+/// SMTP-канал на lettre: критические алерты и сводки по email
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from_address: lettre::message::Mailbox,
+    recipients: Vec<EmailRecipient>,
+}
+
+impl SmtpNotifier {
+    /// Создает SMTP-канал из конфигурации.
+    /// Ошибки в адресах и серьезностях ловим на старте
+    pub fn new(config: &crate::config::EmailConfig) -> Result<Self> {
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)
+            .map_err(|e| anyhow::anyhow!("Невалидный SMTP хост {}: {}", config.smtp_host, e))?
+            .port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        let from_address = config
+            .from_address
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Невалидный from адрес {}: {}", config.from_address, e))?;
+
+        let recipients = config
+            .recipients
+            .iter()
+            .map(|recipient| {
+                Ok(EmailRecipient {
+                    address: recipient.address.parse().map_err(|e| {
+                        anyhow::anyhow!("Невалидный email получателя {}: {}", recipient.address, e)
+                    })?,
+                    min_severity: recipient.min_severity.parse()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from_address,
+            recipients,
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Notifier for SmtpNotifier {
+    fn name(&self) -> &str {
+        "smtp"
+    }
+
+    async fn notify(&self, notification: &Notification) -> Result<()> {
+        for recipient in &self.recipients {
+            // Получатель видит только уведомления своей серьезности и выше
+            if notification.severity < recipient.min_severity {
+                continue;
+            }
+
+            let message = Message::builder()
+                .from(self.from_address.clone())
+                .to(recipient.address.clone())
+                .subject(format!(
+                    "[{}] {}",
+                    notification.severity.as_str(),
+                    notification.subject
+                ))
+                .body(notification.body.clone())?;
+
+            self.transport.send(message).await?;
+
+            tracing::debug!(
+                "📧 Уведомление '{}' отправлено на {}",
+                notification.subject,
+                recipient.address
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Канал по умолчанию - пишет уведомления в структурированный лог
+pub struct TracingNotifier;
+
+#[tonic::async_trait]
+impl Notifier for TracingNotifier {
+    fn name(&self) -> &str {
+        "tracing"
+    }
+
+    async fn notify(&self, notification: &Notification) -> Result<()> {
+        tracing::info!(
+            "📧 NOTIFY [{}] {}: {}",
+            notification.severity.as_str(),
+            notification.subject,
+            notification.body
+        );
+        Ok(())
+    }
+}
+
+/// Диспетчер доставки уведомлений в зарегистрированные каналы
+///
+/// Ошибки доставки логируются, но не прерывают основной поток обработки
+#[derive(Clone, Default)]
+pub struct NotificationDispatcher {
+    notifiers: Vec<Arc<dyn Notifier>>,
+}
+
+impl NotificationDispatcher {
+    /// Создает новый диспетчер без каналов (no-op)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Собирает диспетчер из конфигурации (пустой, если каналы не включены)
+    pub fn from_config(config: &crate::config::NotificationsConfig) -> Result<Self> {
+        let mut dispatcher = Self::new();
+        if let Some(email) = &config.email {
+            if email.enabled {
+                dispatcher = dispatcher.with_notifier(Arc::new(SmtpNotifier::new(email)?));
+            }
+        }
+        Ok(dispatcher)
+    }
+
+    /// Регистрирует канал доставки
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifiers.push(notifier);
+        self
+    }
+
+    /// Включена ли доставка (есть хотя бы один канал)
+    pub fn is_enabled(&self) -> bool {
+        !self.notifiers.is_empty()
+    }
+
+    /// Отправляет уведомление во все зарегистрированные каналы
+    pub async fn dispatch(&self, notification: Notification) {
+        for notifier in &self.notifiers {
+            if let Err(e) = notifier.notify(&notification).await {
+                tracing::warn!(
+                    "⚠️ Не удалось доставить уведомление '{}' через канал '{}': {}",
+                    notification.subject,
+                    notifier.name(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Создает и отправляет уведомление
+    pub async fn alert(&self, severity: NotificationSeverity, subject: &str, body: String) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.dispatch(Notification::new(severity, subject, body))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_ordering_and_parsing() {
+        assert!(NotificationSeverity::Info < NotificationSeverity::Warning);
+        assert!(NotificationSeverity::Warning < NotificationSeverity::Critical);
+        assert_eq!(
+            "critical".parse::<NotificationSeverity>().unwrap(),
+            NotificationSeverity::Critical
+        );
+        assert!("urgent".parse::<NotificationSeverity>().is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let mut values = HashMap::new();
+        values.insert("count", "5".to_string());
+        values.insert("total", "120.50".to_string());
+        let rendered = render_template("Завершено {count} трансферов на {total} USDT", &values);
+        assert_eq!(rendered, "Завершено 5 трансферов на 120.50 USDT");
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_disabled_without_notifiers() {
+        let dispatcher = NotificationDispatcher::new();
+        assert!(!dispatcher.is_enabled());
+
+        let dispatcher = dispatcher.with_notifier(Arc::new(TracingNotifier));
+        assert!(dispatcher.is_enabled());
+    }
+}
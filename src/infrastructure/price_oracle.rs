@@ -0,0 +1,322 @@
+//! # Живой курс TRX/USDT
+//!
+//! Статический `trx_to_usdt_rate` из конфига дрейфует от реальности -
+//! комиссия в USDT перестает покрывать фактическую стоимость газа.
+//! `PriceOracle` абстрагирует источник курса (CoinGecko, Binance),
+//! `PriceFeed` держит кэш с фоновым обновлением и лимитом протухания:
+//! протухший курс не отдается, потребитель откатывается на конфиг
+
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+/// Источник курса TRX/USDT
+#[tonic::async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Имя провайдера для логов и диагностики
+    fn name(&self) -> &'static str;
+
+    /// Текущий курс: сколько USDT стоит 1 TRX
+    async fn fetch_trx_usdt_rate(&self) -> Result<Decimal>;
+}
+
+/// Курс через публичный API CoinGecko
+pub struct CoinGeckoOracle {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl CoinGeckoOracle {
+    /// Создает оракул с публичным API CoinGecko
+    pub fn new() -> Self {
+        Self::with_base_url("https://api.coingecko.com".to_string())
+    }
+
+    /// Создает оракул с произвольным base URL (тесты, прокси)
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url,
+        }
+    }
+}
+
+impl Default for CoinGeckoOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tonic::async_trait]
+impl PriceOracle for CoinGeckoOracle {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    async fn fetch_trx_usdt_rate(&self) -> Result<Decimal> {
+        let url = format!(
+            "{}/api/v3/simple/price?ids=tron&vs_currencies=usd",
+            self.base_url
+        );
+
+        let response: serde_json::Value = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .context("Запрос курса к CoinGecko не прошел")?
+            .json()
+            .await
+            .context("Невалидный JSON от CoinGecko")?;
+
+        let rate = response["tron"]["usd"]
+            .as_f64()
+            .ok_or_else(|| anyhow!("Ответ CoinGecko не содержит tron.usd"))?;
+
+        Decimal::from_str(&rate.to_string()).context("Курс CoinGecko не конвертируется в Decimal")
+    }
+}
+
+/// Курс через публичный API Binance (тикер TRXUSDT)
+pub struct BinanceOracle {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl BinanceOracle {
+    /// Создает оракул с публичным API Binance
+    pub fn new() -> Self {
+        Self::with_base_url("https://api.binance.com".to_string())
+    }
+
+    /// Создает оракул с произвольным base URL (тесты, прокси)
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url,
+        }
+    }
+}
+
+impl Default for BinanceOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tonic::async_trait]
+impl PriceOracle for BinanceOracle {
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    async fn fetch_trx_usdt_rate(&self) -> Result<Decimal> {
+        let url = format!("{}/api/v3/ticker/price?symbol=TRXUSDT", self.base_url);
+
+        let response: serde_json::Value = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .context("Запрос курса к Binance не прошел")?
+            .json()
+            .await
+            .context("Невалидный JSON от Binance")?;
+
+        let rate = response["price"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Ответ Binance не содержит price"))?;
+
+        Decimal::from_str(rate).context("Курс Binance не конвертируется в Decimal")
+    }
+}
+
+/// Создает оракул по имени провайдера из конфига.
+/// None - провайдер "none" или неизвестен (живой курс отключен)
+pub fn oracle_from_provider(provider: &str) -> Option<std::sync::Arc<dyn PriceOracle>> {
+    match provider {
+        "coingecko" => Some(std::sync::Arc::new(CoinGeckoOracle::new())),
+        "binance" => Some(std::sync::Arc::new(BinanceOracle::new())),
+        "none" => None,
+        other => {
+            warn!(
+                "⚠️ Неизвестный провайдер курса '{}' - живой курс отключен",
+                other
+            );
+            None
+        }
+    }
+}
+
+/// Закэшированный курс с моментом получения
+#[derive(Debug, Clone, Copy)]
+struct CachedRate {
+    rate: Decimal,
+    fetched_at: Instant,
+}
+
+/// Кэшированный фид курса TRX/USDT поверх оракула.
+///
+/// Фоновое обновление держит кэш свежим; `current_rate` не отдает
+/// курс старше лимита протухания - потребитель в этом случае
+/// откатывается на конфигурированное значение
+pub struct PriceFeed {
+    oracle: std::sync::Arc<dyn PriceOracle>,
+    refresh_interval: Duration,
+    max_staleness: Duration,
+    cache: RwLock<Option<CachedRate>>,
+}
+
+impl PriceFeed {
+    /// Создает фид поверх оракула
+    pub fn new(
+        oracle: std::sync::Arc<dyn PriceOracle>,
+        refresh_interval_secs: u64,
+        max_staleness_secs: u64,
+    ) -> Self {
+        Self {
+            oracle,
+            refresh_interval: Duration::from_secs(refresh_interval_secs),
+            max_staleness: Duration::from_secs(max_staleness_secs),
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Имя оракула, поставляющего курс (для снимков курса на трансферах)
+    pub fn source_name(&self) -> &str {
+        self.oracle.name()
+    }
+
+    /// Текущий курс, если он еще не протух. None - курса нет или он
+    /// старше лимита (оракул давно недоступен)
+    pub fn current_rate(&self) -> Option<Decimal> {
+        self.cache
+            .read()
+            .unwrap()
+            .filter(|cached| cached.fetched_at.elapsed() <= self.max_staleness)
+            .map(|cached| cached.rate)
+    }
+
+    /// Запрашивает курс у оракула и обновляет кэш
+    pub async fn refresh(&self) -> Result<Decimal> {
+        let rate = self.oracle.fetch_trx_usdt_rate().await?;
+
+        if rate <= Decimal::ZERO {
+            anyhow::bail!(
+                "Оракул {} вернул неположительный курс: {}",
+                self.oracle.name(),
+                rate
+            );
+        }
+
+        *self.cache.write().unwrap() = Some(CachedRate {
+            rate,
+            fetched_at: Instant::now(),
+        });
+
+        Ok(rate)
+    }
+
+    /// Фоновый цикл обновления курса. Ошибка оракула не роняет цикл -
+    /// кэш постепенно протухает и потребители откатываются на конфиг
+    pub async fn run_refresh_loop(self: std::sync::Arc<Self>) {
+        info!(
+            "💱 Запуск обновления курса TRX/USDT через {} (интервал {} сек)",
+            self.oracle.name(),
+            self.refresh_interval.as_secs()
+        );
+
+        let mut interval = tokio::time::interval(self.refresh_interval);
+
+        loop {
+            interval.tick().await;
+
+            match self.refresh().await {
+                Ok(rate) => {
+                    info!(
+                        "💱 Курс TRX/USDT обновлен: {} ({})",
+                        rate,
+                        self.oracle.name()
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "⚠️ Не удалось обновить курс TRX/USDT через {}: {}",
+                        self.oracle.name(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Оракул с фиксированным курсом
+    struct StubOracle {
+        rate: Decimal,
+    }
+
+    #[tonic::async_trait]
+    impl PriceOracle for StubOracle {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
+
+        async fn fetch_trx_usdt_rate(&self) -> Result<Decimal> {
+            Ok(self.rate)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feed_caches_rate_after_refresh() {
+        let feed = PriceFeed::new(
+            Arc::new(StubOracle {
+                rate: Decimal::new(12, 2),
+            }),
+            60,
+            300,
+        );
+
+        // До первого обновления курса нет
+        assert_eq!(feed.current_rate(), None);
+
+        feed.refresh().await.unwrap();
+        assert_eq!(feed.current_rate(), Some(Decimal::new(12, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_feed_rejects_non_positive_rate() {
+        let feed = PriceFeed::new(
+            Arc::new(StubOracle {
+                rate: Decimal::ZERO,
+            }),
+            60,
+            300,
+        );
+
+        assert!(feed.refresh().await.is_err());
+        assert_eq!(feed.current_rate(), None);
+    }
+
+    #[test]
+    fn test_oracle_from_provider() {
+        assert_eq!(
+            oracle_from_provider("coingecko").unwrap().name(),
+            "coingecko"
+        );
+        assert_eq!(oracle_from_provider("binance").unwrap().name(), "binance");
+        assert!(oracle_from_provider("none").is_none());
+        assert!(oracle_from_provider("kraken").is_none());
+    }
+}
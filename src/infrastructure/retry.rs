@@ -3,6 +3,7 @@
 //! Обеспечивает надежность при вызовах внешних API
 
 use anyhow::Result;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{error, warn};
@@ -14,6 +15,10 @@ pub struct RetryConfig {
     pub initial_delay: Duration,
     pub max_delay: Duration,
     pub backoff_multiplier: f64,
+    /// Доля случайного разброса задержки (0.0 - без джиттера).
+    /// Размазывает повторы множества кошельков во времени,
+    /// чтобы они не били по TronGrid одновременно
+    pub jitter: f64,
 }
 
 impl Default for RetryConfig {
@@ -23,10 +28,51 @@ impl Default for RetryConfig {
             initial_delay: Duration::from_millis(500),
             max_delay: Duration::from_secs(10),
             backoff_multiplier: 2.0,
+            jitter: 0.1,
         }
     }
 }
 
+impl RetryConfig {
+    /// Следующая задержка экспоненциального backoff (без джиттера,
+    /// чтобы состояние между попытками оставалось детерминированным)
+    pub fn next_delay(&self, previous_delay: Duration) -> Duration {
+        std::cmp::min(
+            Duration::from_millis(
+                (previous_delay.as_millis() as f64 * self.backoff_multiplier) as u64,
+            ),
+            self.max_delay,
+        )
+    }
+
+    /// Размазывает задержку случайным множителем [1-jitter, 1+jitter]
+    pub fn apply_jitter(&self, delay: Duration) -> Duration {
+        if self.jitter <= 0.0 {
+            return delay;
+        }
+        let factor = 1.0 + self.jitter * (rand::random::<f64>() * 2.0 - 1.0);
+        Duration::from_millis((delay.as_millis() as f64 * factor).max(0.0) as u64)
+    }
+}
+
+/// Часы для retry-задержек: в проде - tokio::time::sleep,
+/// в тестах подменяются фейковыми без реального ожидания
+#[tonic::async_trait]
+pub trait RetryClock: Send + Sync {
+    /// Засыпает на указанную длительность
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Реальные часы на tokio
+pub struct TokioClock;
+
+#[tonic::async_trait]
+impl RetryClock for TokioClock {
+    async fn sleep(&self, duration: Duration) {
+        sleep(duration).await;
+    }
+}
+
 /// Типы ошибок для определения стратегии retry
 #[derive(Debug)]
 pub enum RetryableError {
@@ -76,6 +122,7 @@ impl std::error::Error for RetryableError {}
 pub struct RetryableService<T> {
     inner: T,
     config: RetryConfig,
+    clock: Arc<dyn RetryClock>,
 }
 
 impl<T> RetryableService<T> {
@@ -84,12 +131,23 @@ impl<T> RetryableService<T> {
         Self {
             inner,
             config: RetryConfig::default(),
+            clock: Arc::new(TokioClock),
         }
     }
 
     /// Создает новый wrapper с кастомной конфигурацией
     pub fn with_config(inner: T, config: RetryConfig) -> Self {
-        Self { inner, config }
+        Self {
+            inner,
+            config,
+            clock: Arc::new(TokioClock),
+        }
+    }
+
+    /// Подменяет часы (для тестов backoff без реального ожидания)
+    pub fn with_clock(mut self, clock: Arc<dyn RetryClock>) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Получает ссылку на внутренний сервис
@@ -132,22 +190,21 @@ impl<T> RetryableService<T> {
                         return Err(anyhow::anyhow!("{}", retry_error));
                     }
 
-                    let total_delay = delay + retry_error.additional_delay();
+                    // Джиттер применяется только к фактическому ожиданию,
+                    // состояние backoff между попытками детерминировано
+                    let total_delay = self
+                        .config
+                        .apply_jitter(delay + retry_error.additional_delay());
 
                     warn!(
                         "⚠️  {} не удалась (попытка {}/{}): {}. Повтор через {:?}",
                         operation_name, attempt, self.config.max_attempts, retry_error, total_delay
                     );
 
-                    sleep(total_delay).await;
+                    self.clock.sleep(total_delay).await;
 
                     // Exponential backoff
-                    delay = std::cmp::min(
-                        Duration::from_millis(
-                            (delay.as_millis() as f64 * self.config.backoff_multiplier) as u64,
-                        ),
-                        self.config.max_delay,
-                    );
+                    delay = self.config.next_delay(delay);
                 }
             }
         }
@@ -185,6 +242,41 @@ pub fn classify_reqwest_error(error: reqwest::Error) -> RetryableError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    /// Фейковые часы: записывают запрошенные задержки вместо ожидания
+    struct FakeClock {
+        sleeps: Mutex<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                sleeps: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn recorded(&self) -> Vec<Duration> {
+            self.sleeps.lock().unwrap().clone()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl RetryClock for FakeClock {
+        async fn sleep(&self, duration: Duration) {
+            self.sleeps.lock().unwrap().push(duration);
+        }
+    }
+
+    /// Конфиг без джиттера для детерминированных проверок backoff
+    fn deterministic_config(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            jitter: 0.0,
+            ..RetryConfig::default()
+        }
+    }
 
     #[test]
     fn test_retry_config_default() {
@@ -201,6 +293,121 @@ mod tests {
         assert!(!RetryableError::Permanent("test".to_string()).is_retryable());
     }
 
+    #[tokio::test]
+    async fn test_retry_succeeds_after_transient_failures() {
+        let clock = FakeClock::new();
+        let service =
+            RetryableService::with_config((), deterministic_config(5)).with_clock(clock.clone());
+
+        let attempts = AtomicU32::new(0);
+        let result = service
+            .retry("test_op", || async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(RetryableError::Temporary("flaky".to_string()))
+                } else {
+                    Ok(42)
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        // Задержки растут экспоненциально: 500ms, затем 1000ms
+        assert_eq!(
+            clock.recorded(),
+            vec![Duration::from_millis(500), Duration::from_millis(1000)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_permanent_error() {
+        let clock = FakeClock::new();
+        let service =
+            RetryableService::with_config((), deterministic_config(5)).with_clock(clock.clone());
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<i32> = service
+            .retry("test_op", || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<i32, _>(RetryableError::Permanent("bad request".to_string()))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert!(clock.recorded().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausts_attempts_and_respects_rate_limit_delay() {
+        let clock = FakeClock::new();
+        let service =
+            RetryableService::with_config((), deterministic_config(3)).with_clock(clock.clone());
+
+        let result: Result<i32> = service
+            .retry("test_op", || async {
+                Err::<i32, _>(RetryableError::RateLimit("429".to_string()))
+            })
+            .await;
+
+        assert!(result.is_err());
+        let sleeps = clock.recorded();
+        assert_eq!(sleeps.len(), 2);
+        // Rate limit добавляет фиксированные 5 секунд к каждой задержке
+        assert_eq!(
+            sleeps[0],
+            Duration::from_millis(500) + Duration::from_secs(5)
+        );
+        assert_eq!(
+            sleeps[1],
+            Duration::from_millis(1000) + Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_backoff_never_exceeds_max_delay() {
+        // Property-проверка: для случайных конфигов цепочка задержек
+        // монотонно растет и упирается в max_delay
+        for _ in 0..100 {
+            let config = RetryConfig {
+                max_attempts: 10,
+                initial_delay: Duration::from_millis(100 + rand::random::<u64>() % 900),
+                max_delay: Duration::from_secs(1 + rand::random::<u64>() % 10),
+                backoff_multiplier: 1.1 + rand::random::<f64>() * 2.0,
+                jitter: 0.0,
+            };
+
+            let mut delay = config.initial_delay;
+            let mut previous = delay;
+            for _ in 0..50 {
+                delay = config.next_delay(delay);
+                assert!(delay <= config.max_delay);
+                assert!(delay >= previous.min(config.max_delay));
+                previous = delay;
+            }
+        }
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        // Property-проверка: джиттер не выводит задержку за [1-j, 1+j]
+        for _ in 0..1000 {
+            let jitter = rand::random::<f64>();
+            let config = RetryConfig {
+                jitter,
+                ..RetryConfig::default()
+            };
+
+            let base = Duration::from_millis(1 + rand::random::<u64>() % 10_000);
+            let jittered = config.apply_jitter(base).as_millis() as f64;
+            let base_ms = base.as_millis() as f64;
+
+            // Плюс миллисекунда на округление вниз при конвертации
+            assert!(jittered >= base_ms * (1.0 - jitter) - 1.0);
+            assert!(jittered <= base_ms * (1.0 + jitter) + 1.0);
+        }
+    }
+
     #[test]
     fn test_http_error_classification() {
         let error_400 = classify_http_error(reqwest::StatusCode::BAD_REQUEST, "bad request");
@@ -0,0 +1,93 @@
+//! # Скрининг риска адресов
+//!
+//! Перед обработкой выводов и возвратов адрес назначения проверяется
+//! через подключаемый провайдер риск-скрининга (Chainalysis, TRM и т.п.).
+//! Трансферы с риском выше порога блокируются, результат скрининга
+//! фиксируется на записи трансфера.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Результат скрининга адреса
+#[derive(Debug, Clone)]
+pub struct ScreeningResult {
+    /// Оценка риска 0-100 (выше - хуже)
+    pub risk_score: i32,
+    /// Имя провайдера, выдавшего оценку
+    pub provider: String,
+    /// Когда выполнен скрининг
+    pub screened_at: DateTime<Utc>,
+}
+
+/// Подключаемый провайдер риск-скрининга адресов
+#[tonic::async_trait]
+pub trait RiskScreeningProvider: Send + Sync {
+    /// Имя провайдера для логирования и записи на трансфере
+    fn name(&self) -> &str;
+
+    /// Оценивает риск адреса назначения
+    async fn screen_address(&self, address: &str) -> Result<ScreeningResult>;
+}
+
+/// Ответ HTTP провайдера скрининга
+#[derive(Debug, Deserialize)]
+struct ScreeningApiResponse {
+    risk_score: i32,
+}
+
+/// HTTP-провайдер скрининга (Chainalysis/TRM-style API)
+///
+/// Отправляет `POST {endpoint_url}` с телом `{"address": "..."}` и
+/// ожидает ответ вида `{"risk_score": 42}`
+pub struct HttpRiskScreeningProvider {
+    endpoint_url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl HttpRiskScreeningProvider {
+    /// Создает провайдер для указанного endpoint
+    pub fn new(endpoint_url: String, api_key: Option<String>) -> Self {
+        Self {
+            endpoint_url,
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl RiskScreeningProvider for HttpRiskScreeningProvider {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn screen_address(&self, address: &str) -> Result<ScreeningResult> {
+        let mut request = self
+            .client
+            .post(&self.endpoint_url)
+            .json(&serde_json::json!({ "address": address }));
+
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Провайдер скрининга вернул статус {}",
+                response.status()
+            ));
+        }
+
+        let api_response: ScreeningApiResponse = response.json().await?;
+
+        Ok(ScreeningResult {
+            risk_score: api_response.risk_score,
+            provider: self.name().to_string(),
+            screened_at: Utc::now(),
+        })
+    }
+}
@@ -0,0 +1,172 @@
+//! # Координатор graceful shutdown
+//!
+//! Жесткое убийство процесса может оставить трансфер наполовину
+//! обработанным: TRX на газ уже отправлен, а USDT транзакция еще не
+//! в сети. Координатор дает процессу завершиться аккуратно: по
+//! SIGTERM/SIGINT выставляется флаг завершения (фоновые циклы перестают
+//! брать новую работу), затем процесс ждет окончания уже начатых
+//! операций с таймаутом и только после этого выходит.
+//!
+//! Операции, которые нельзя обрывать (подпись и broadcast трансфера),
+//! регистрируются через RAII-guard [`InFlightGuard`] - счетчик активных
+//! операций виден в [`ShutdownCoordinator::drain`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Интервал опроса счетчика активных операций при drain'е
+const DRAIN_POLL_INTERVAL_MS: u64 = 50;
+
+/// Координатор завершения процесса, разделяемый между сервисами.
+/// Создается один раз в AppState и раздается через builder'ы
+pub struct ShutdownCoordinator {
+    /// true после begin_shutdown: новая работа не берется
+    signal: watch::Sender<bool>,
+    /// Количество операций, которые нужно дождаться перед выходом
+    in_flight: Arc<AtomicU64>,
+}
+
+impl ShutdownCoordinator {
+    /// Создает координатор в рабочем (не завершающемся) состоянии
+    pub fn new() -> Self {
+        let (signal, _) = watch::channel(false);
+        Self {
+            signal,
+            in_flight: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Сигнализирует о начале завершения. Идемпотентен: повторный
+    /// сигнал (например SIGTERM после Ctrl+C) ничего не меняет
+    pub fn begin_shutdown(&self) {
+        if !self.signal.send_replace(true) {
+            info!("🛑 Начато graceful завершение процесса");
+        }
+    }
+
+    /// true, если завершение уже начато - новую работу брать нельзя
+    pub fn is_shutting_down(&self) -> bool {
+        *self.signal.borrow()
+    }
+
+    /// Ожидает сигнала завершения. Разрешается немедленно,
+    /// если begin_shutdown уже был вызван
+    pub async fn cancelled(&self) {
+        let mut receiver = self.signal.subscribe();
+        // wait_for возвращает Err только если Sender уничтожен -
+        // координатор живет в AppState до конца процесса
+        let _ = receiver.wait_for(|shutting_down| *shutting_down).await;
+    }
+
+    /// Регистрирует операцию, которую нужно дождаться при завершении.
+    /// Счетчик уменьшается при Drop guard'а (в том числе при панике)
+    pub fn track(&self) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    /// Текущее количество активных операций
+    pub fn in_flight_count(&self) -> u64 {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Ждет окончания всех активных операций, но не дольше timeout.
+    /// Возвращает true, если все операции завершились
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let remaining = self.in_flight_count();
+            if remaining == 0 {
+                return true;
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                warn!(
+                    "⚠️ Таймаут drain'а: {} операций не завершились за {:?}",
+                    remaining, timeout
+                );
+                return false;
+            }
+
+            tokio::time::sleep(Duration::from_millis(DRAIN_POLL_INTERVAL_MS)).await;
+        }
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RAII-guard активной операции: пока жив, drain ждет
+pub struct InFlightGuard {
+    in_flight: Arc<AtomicU64>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_and_drop_updates_counter() {
+        let coordinator = ShutdownCoordinator::new();
+        assert_eq!(coordinator.in_flight_count(), 0);
+
+        let first = coordinator.track();
+        let second = coordinator.track();
+        assert_eq!(coordinator.in_flight_count(), 2);
+
+        drop(first);
+        assert_eq!(coordinator.in_flight_count(), 1);
+        drop(second);
+        assert_eq!(coordinator.in_flight_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_resolves_after_begin_shutdown() {
+        let coordinator = Arc::new(ShutdownCoordinator::new());
+        assert!(!coordinator.is_shutting_down());
+
+        let waiter = {
+            let coordinator = coordinator.clone();
+            tokio::spawn(async move { coordinator.cancelled().await })
+        };
+
+        coordinator.begin_shutdown();
+        waiter.await.unwrap();
+        assert!(coordinator.is_shutting_down());
+
+        // Повторный сигнал идемпотентен
+        coordinator.begin_shutdown();
+        assert!(coordinator.is_shutting_down());
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_guards_and_times_out() {
+        let coordinator = ShutdownCoordinator::new();
+
+        // Без активных операций drain завершается сразу
+        assert!(coordinator.drain(Duration::from_millis(10)).await);
+
+        // С висящим guard'ом drain упирается в таймаут
+        let guard = coordinator.track();
+        assert!(!coordinator.drain(Duration::from_millis(10)).await);
+
+        drop(guard);
+        assert!(coordinator.drain(Duration::from_millis(10)).await);
+    }
+}
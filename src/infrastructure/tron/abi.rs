@@ -0,0 +1,337 @@
+//! # ABI кодирование и декодирование TRC-20
+//!
+//! Единое место для работы с ABI смарт-контрактов: реестр селекторов
+//! функций, кодирование параметров вызова и декодирование calldata и
+//! Transfer event логов (из ответа gettransactioninfobyid).
+//! Переиспользуется клиентом, мониторингом и мультитокенным сервисом
+//! вместо ручной нарезки hex строк
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+
+use crate::domain::validation::TronValidator;
+
+/// Селектор функции transfer(address,uint256)
+pub const TRANSFER_SELECTOR: &str = "a9059cbb";
+
+/// Селектор функции transferFrom(address,address,uint256)
+pub const TRANSFER_FROM_SELECTOR: &str = "23b872dd";
+
+/// Селектор функции approve(address,uint256)
+pub const APPROVE_SELECTOR: &str = "095ea7b3";
+
+/// Селектор функции balanceOf(address)
+pub const BALANCE_OF_SELECTOR: &str = "70a08231";
+
+/// Селектор функции allowance(address,address)
+pub const ALLOWANCE_SELECTOR: &str = "dd62ed3e";
+
+/// Topic события Transfer(address,address,uint256) -
+/// keccak256 сигнатуры события
+pub const TRANSFER_EVENT_TOPIC: &str =
+    "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Длина одного ABI слова в hex символах (32 байта)
+const WORD_LEN: usize = 64;
+
+/// Длина селектора функции в hex символах (4 байта)
+const SELECTOR_LEN: usize = 8;
+
+/// Реестр известных селекторов: возвращает сигнатуру функции
+pub fn function_signature(selector: &str) -> Option<&'static str> {
+    match selector.to_ascii_lowercase().as_str() {
+        TRANSFER_SELECTOR => Some("transfer(address,uint256)"),
+        TRANSFER_FROM_SELECTOR => Some("transferFrom(address,address,uint256)"),
+        APPROVE_SELECTOR => Some("approve(address,uint256)"),
+        BALANCE_OF_SELECTOR => Some("balanceOf(address)"),
+        ALLOWANCE_SELECTOR => Some("allowance(address,address)"),
+        _ => None,
+    }
+}
+
+/// Декодированный вызов transfer(address,uint256)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trc20TransferCall {
+    /// Адрес получателя (base58)
+    pub to_address: String,
+    /// Сумма в минимальных единицах токена
+    pub amount_raw: u128,
+}
+
+/// Декодированное событие Transfer(address,address,uint256)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trc20TransferEvent {
+    /// Адрес отправителя (base58)
+    pub from_address: String,
+    /// Адрес получателя (base58)
+    pub to_address: String,
+    /// Сумма в минимальных единицах токена
+    pub amount_raw: u128,
+}
+
+/// Кодирует параметры transfer(address,uint256) для triggersmartcontract.
+/// Адрес принимается в hex с префиксом 41 (как отдает address_to_hex);
+/// префикс остается в ABI слове - так TRON кодирует 21-байтовые адреса
+pub fn encode_transfer_params(to_hex: &str, amount_raw: u128) -> String {
+    let to_word = to_hex.strip_prefix("0x").unwrap_or(to_hex);
+    format!("{:0>64}{:0>64}", to_word, format!("{:x}", amount_raw))
+}
+
+/// Кодирует параметры balanceOf(address) для triggerconstantcontract.
+/// Адрес принимается в hex с префиксом 41 (как отдает address_to_hex)
+pub fn encode_balance_of_params(owner_hex: &str) -> String {
+    let owner_word = owner_hex.strip_prefix("0x").unwrap_or(owner_hex);
+    format!("{:0>64}", owner_word)
+}
+
+/// Декодирует constant_result вызова balanceOf(address) - одно uint256 слово
+pub fn decode_balance_of_result(word: &str) -> Result<u128> {
+    decode_abi_uint(word)
+}
+
+/// Кодирует параметры allowance(address,address) для triggerconstantcontract.
+/// Адреса принимаются в hex с префиксом 41 (как отдает address_to_hex)
+pub fn encode_allowance_params(owner_hex: &str, spender_hex: &str) -> String {
+    let owner_word = owner_hex.strip_prefix("0x").unwrap_or(owner_hex);
+    let spender_word = spender_hex.strip_prefix("0x").unwrap_or(spender_hex);
+    format!("{:0>64}{:0>64}", owner_word, spender_word)
+}
+
+/// Декодирует constant_result вызова allowance(address,address) - одно uint256 слово
+pub fn decode_allowance_result(word: &str) -> Result<u128> {
+    decode_abi_uint(word)
+}
+
+/// Кодирует параметры approve(address,uint256) для triggersmartcontract.
+/// Сумма 0 отзывает разрешение полностью
+pub fn encode_approve_params(spender_hex: &str, amount_raw: u128) -> String {
+    let spender_word = spender_hex.strip_prefix("0x").unwrap_or(spender_hex);
+    format!("{:0>64}{:0>64}", spender_word, format!("{:x}", amount_raw))
+}
+
+/// Кодирует полную calldata вызова transfer(address,uint256)
+pub fn encode_transfer_call(to_hex: &str, amount_raw: u128) -> String {
+    format!(
+        "{}{}",
+        TRANSFER_SELECTOR,
+        encode_transfer_params(to_hex, amount_raw)
+    )
+}
+
+/// Декодирует calldata вызова transfer(address,uint256)
+pub fn decode_transfer_call(data: &str) -> Result<Trc20TransferCall> {
+    let data = data.strip_prefix("0x").unwrap_or(data);
+
+    if data.len() < SELECTOR_LEN + 2 * WORD_LEN {
+        return Err(anyhow::anyhow!(
+            "Некорректная длина TRC20 calldata: {} символов",
+            data.len()
+        ));
+    }
+
+    let selector = &data[..SELECTOR_LEN];
+    if !selector.eq_ignore_ascii_case(TRANSFER_SELECTOR) {
+        return Err(anyhow::anyhow!(
+            "Не transfer вызов: селектор {} ({})",
+            selector,
+            function_signature(selector).unwrap_or("неизвестная функция")
+        ));
+    }
+
+    let params = &data[SELECTOR_LEN..];
+    let to_address = decode_abi_address(abi_word(params, 0)?)?;
+    let amount_raw = decode_abi_uint(abi_word(params, 1)?)?;
+
+    Ok(Trc20TransferCall {
+        to_address,
+        amount_raw,
+    })
+}
+
+/// Декодирует Transfer event из лога gettransactioninfobyid.
+/// Индексированные from/to лежат в topics[1]/topics[2], сумма - в data
+pub fn decode_transfer_log(topics: &[String], data: &str) -> Result<Trc20TransferEvent> {
+    if topics.len() < 3 {
+        return Err(anyhow::anyhow!(
+            "Недостаточно topics для Transfer события: {}",
+            topics.len()
+        ));
+    }
+
+    if !topics[0].eq_ignore_ascii_case(TRANSFER_EVENT_TOPIC) {
+        return Err(anyhow::anyhow!("Не Transfer событие: topic {}", topics[0]));
+    }
+
+    let from_address = decode_abi_address(&topics[1])?;
+    let to_address = decode_abi_address(&topics[2])?;
+    let amount_raw = decode_abi_uint(data.strip_prefix("0x").unwrap_or(data))?;
+
+    Ok(Trc20TransferEvent {
+        from_address,
+        to_address,
+        amount_raw,
+    })
+}
+
+/// Переводит сумму из минимальных единиц в Decimal по decimals токена
+pub fn scale_amount(amount_raw: u128, decimals: u32) -> Decimal {
+    Decimal::from(amount_raw) / Decimal::from(10u64.pow(decimals))
+}
+
+/// Возвращает ABI слово (32 байта) по индексу
+fn abi_word(params: &str, index: usize) -> Result<&str> {
+    let start = index * WORD_LEN;
+    let end = start + WORD_LEN;
+
+    if params.len() < end {
+        return Err(anyhow::anyhow!(
+            "ABI параметры короче ожидаемого: слово {} отсутствует",
+            index
+        ));
+    }
+
+    Ok(&params[start..end])
+}
+
+/// Декодирует ABI слово как TRON адрес: последние 20 байт слова
+/// с префиксом mainnet 41, конвертированные в base58
+fn decode_abi_address(word: &str) -> Result<String> {
+    let word = word.strip_prefix("0x").unwrap_or(word);
+
+    if word.len() < 40 {
+        return Err(anyhow::anyhow!("ABI слово короче адреса: {}", word));
+    }
+
+    let hex_address = format!("41{}", &word[word.len() - 40..]);
+    TronValidator::hex_to_base58(&hex_address)
+        .map_err(|e| anyhow::anyhow!("Декодирование адреса из ABI: {}", e))
+}
+
+/// Декодирует ABI слово как беззнаковое число
+fn decode_abi_uint(word: &str) -> Result<u128> {
+    let word = word.strip_prefix("0x").unwrap_or(word);
+    u128::from_str_radix(word, 16).map_err(|_| anyhow::anyhow!("Некорректное ABI число: {}", word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Контракт USDT в mainnet (hex и base58)
+    const USDT_HEX: &str = "41a614f803b6fd780986a42c78ec9c7f77e6ded13c";
+    const USDT_BASE58: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+    #[test]
+    fn test_function_signature_registry() {
+        assert_eq!(
+            function_signature("a9059cbb"),
+            Some("transfer(address,uint256)")
+        );
+        assert_eq!(
+            function_signature("A9059CBB"),
+            Some("transfer(address,uint256)")
+        );
+        assert_eq!(function_signature("70a08231"), Some("balanceOf(address)"));
+        assert_eq!(function_signature("deadbeef"), None);
+    }
+
+    #[test]
+    fn test_balance_of_params_roundtrip() {
+        let params = encode_balance_of_params(USDT_HEX);
+        assert_eq!(params.len(), 64);
+        assert!(params.starts_with("000000000000000000000041"));
+        assert!(params.ends_with(USDT_HEX.trim_start_matches("41")));
+
+        let raw = decode_balance_of_result(
+            "00000000000000000000000000000000000000000000000000000000075bcd15",
+        )
+        .unwrap();
+        assert_eq!(raw, 123_456_789);
+    }
+
+    #[test]
+    fn test_allowance_and_approve_params() {
+        let params = encode_allowance_params(USDT_HEX, USDT_HEX);
+        assert_eq!(params.len(), 128);
+        assert!(params[..64].ends_with(USDT_HEX));
+        assert!(params[64..].ends_with(USDT_HEX));
+
+        let raw = decode_allowance_result(
+            "00000000000000000000000000000000000000000000000000000000075bcd15",
+        )
+        .unwrap();
+        assert_eq!(raw, 123_456_789);
+
+        // approve на 0 - полный отзыв разрешения
+        let revoke = encode_approve_params(USDT_HEX, 0);
+        assert_eq!(revoke.len(), 128);
+        assert!(revoke[64..].chars().all(|c| c == '0'));
+    }
+
+    #[test]
+    fn test_encode_decode_transfer_roundtrip() {
+        // 25.5 USDT = 25_500_000 минимальных единиц
+        let calldata = encode_transfer_call(USDT_HEX, 25_500_000);
+        assert!(calldata.starts_with(TRANSFER_SELECTOR));
+        assert_eq!(calldata.len(), 8 + 64 + 64);
+
+        let call = decode_transfer_call(&calldata).unwrap();
+        assert_eq!(call.to_address, USDT_BASE58);
+        assert_eq!(call.amount_raw, 25_500_000);
+    }
+
+    #[test]
+    fn test_decode_transfer_call_mainnet_data() {
+        // Calldata реального USDT transfer из mainnet:
+        // получатель TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t, 1000000 единиц (1 USDT)
+        let data = "a9059cbb\
+                    000000000000000000000000a614f803b6fd780986a42c78ec9c7f77e6ded13c\
+                    00000000000000000000000000000000000000000000000000000000000f4240";
+
+        let call = decode_transfer_call(data).unwrap();
+        assert_eq!(call.to_address, USDT_BASE58);
+        assert_eq!(call.amount_raw, 1_000_000);
+    }
+
+    #[test]
+    fn test_decode_transfer_call_rejects_bad_data() {
+        // Слишком короткая calldata
+        assert!(decode_transfer_call("a9059cbb00").is_err());
+
+        // Чужой селектор (approve)
+        let data = format!("095ea7b3{}", "0".repeat(128));
+        assert!(decode_transfer_call(&data).is_err());
+    }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        let topics = vec![
+            TRANSFER_EVENT_TOPIC.to_string(),
+            "000000000000000000000000a614f803b6fd780986a42c78ec9c7f77e6ded13c".to_string(),
+            "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+        ];
+
+        let event = decode_transfer_log(&topics, "0f4240").unwrap();
+        assert_eq!(event.from_address, USDT_BASE58);
+        // Нулевое слово - black hole адрес TRON
+        assert_eq!(event.to_address, "T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb");
+        assert_eq!(event.amount_raw, 1_000_000);
+
+        // Не Transfer topic отклоняется
+        let wrong = vec!["ff".repeat(32), topics[1].clone(), topics[2].clone()];
+        assert!(decode_transfer_log(&wrong, "0f4240").is_err());
+    }
+
+    #[test]
+    fn test_scale_amount() {
+        assert_eq!(scale_amount(1_000_000, 6), Decimal::new(1, 0));
+        assert_eq!(scale_amount(25_500_000, 6), Decimal::new(255, 1));
+        assert_eq!(scale_amount(0, 6), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_decode_abi_uint_zero_word() {
+        assert_eq!(decode_abi_uint(&"0".repeat(64)).unwrap(), 0);
+        assert!(decode_abi_uint("zz").is_err());
+    }
+}
@@ -9,18 +9,79 @@ use reqwest::Client;
 use rust_decimal::prelude::*;
 use serde_json::Value;
 use sha2::Digest;
-use std::time::Duration;
-
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+use super::abi;
+use super::usage::TronGridUsageTracker;
 use crate::config::TronConfig;
-use crate::domain::BlockchainTransaction;
+use crate::domain::{BlockchainTransaction, TransactionReceipt};
 use crate::infrastructure::retry::{RetryConfig, RetryableService};
 
+/// TTL кэша страниц TRC20 транзакций. Короткий, чтобы мониторинг
+/// (цикл каждые 30 секунд) не перечитывал идентичные страницы
+const TRC20_PAGE_CACHE_TTL: Duration = Duration::from_secs(20);
+
+/// Кэшированная страница TRC20 транзакций адреса
+#[derive(Debug, Clone)]
+struct CachedTrc20Page {
+    transactions: Vec<BlockchainTransaction>,
+    /// ETag ответа TronGrid для условной ревалидации (If-None-Match)
+    etag: Option<String>,
+    /// Максимальный block_timestamp страницы - граница min_timestamp
+    /// для дозапроса только новых транзакций
+    last_timestamp: Option<i64>,
+    cached_at: Instant,
+}
+
+impl CachedTrc20Page {
+    fn is_expired(&self) -> bool {
+        self.cached_at.elapsed() > TRC20_PAGE_CACHE_TTL
+    }
+}
+
+/// Счетчики эффективности кэша TRC20 страниц
+#[derive(Debug, Default)]
+struct Trc20CacheCounters {
+    hits: AtomicU64,
+    revalidations: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Снимок счетчиков кэша TRC20 страниц
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Trc20CacheStats {
+    /// Страница отдана из кэша без похода в TronGrid
+    pub hits: u64,
+    /// Кэш освежен условным запросом (min_timestamp/ETag)
+    pub revalidations: u64,
+    /// Полная загрузка страницы
+    pub misses: u64,
+}
+
+/// Актуальные ценовые параметры сети TRON
+#[derive(Debug, Clone, Copy)]
+pub struct ChainParameters {
+    /// Цена единицы энергии в sun (getEnergyFee)
+    pub energy_price_sun: i64,
+    /// Цена единицы bandwidth в sun (getTransactionFee)
+    pub bandwidth_price_sun: i64,
+}
+
 /// Клиент для взаимодействия с TronGrid API
 #[derive(Clone)]
 pub struct TronGridClient {
     client: Client,
     config: TronConfig,
     retry_service: RetryableService<()>,
+    /// Кэш страниц TRC20 транзакций, ключ: "address:contract:limit"
+    trc20_page_cache: Arc<Mutex<HashMap<String, CachedTrc20Page>>>,
+    trc20_cache_counters: Arc<Trc20CacheCounters>,
+    /// Трекер использования TronGrid (кольцевой буфер + дневные агрегаты)
+    usage: Arc<TronGridUsageTracker>,
 }
 
 impl TronGridClient {
@@ -32,17 +93,229 @@ impl TronGridClient {
             initial_delay: Duration::from_millis(1000),
             max_delay: Duration::from_secs(30),
             backoff_multiplier: 2.0,
+            jitter: 0.1,
         };
 
+        let usage = Arc::new(TronGridUsageTracker::new(config.daily_request_quota));
+
         Self {
-            client: Client::new(),
+            client: config.egress.build_client(),
             config,
             retry_service: RetryableService::with_config((), retry_config),
+            trc20_page_cache: Arc::new(Mutex::new(HashMap::new())),
+            trc20_cache_counters: Arc::new(Trc20CacheCounters::default()),
+            usage,
+        }
+    }
+
+    /// Трекер использования TronGrid API (для debug endpoint'а и персистентности)
+    pub fn usage_tracker(&self) -> Arc<TronGridUsageTracker> {
+        Arc::clone(&self.usage)
+    }
+
+    /// Фиксирует завершенный запрос к TronGrid в трекере использования
+    fn record_usage(&self, op: &'static str, status: reqwest::StatusCode, started: Instant) {
+        self.usage
+            .record(op, status.as_u16(), started.elapsed().as_millis() as u64);
+    }
+
+    /// Снимок счетчиков кэша TRC20 страниц
+    pub fn trc20_cache_stats(&self) -> Trc20CacheStats {
+        Trc20CacheStats {
+            hits: self.trc20_cache_counters.hits.load(Ordering::Relaxed),
+            revalidations: self
+                .trc20_cache_counters
+                .revalidations
+                .load(Ordering::Relaxed),
+            misses: self.trc20_cache_counters.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Логирует длительность шага работы с нодой.
+    /// Превышение бюджета из конфига поднимается до warn - медленные
+    /// взаимодействия с сетью видны в логах без профайлера
+    fn observe_op_budget(&self, op: &'static str, started: Instant, budget_ms: u64) {
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        if elapsed_ms > budget_ms {
+            tracing::warn!(
+                op,
+                elapsed_ms,
+                budget_ms,
+                "⏱️ Шаг {} превысил бюджет: {} мс (бюджет {} мс)",
+                op,
+                elapsed_ms,
+                budget_ms
+            );
+        } else {
+            tracing::debug!(op, elapsed_ms, "Шаг {} занял {} мс", op, elapsed_ms);
         }
     }
 
     /// Получение баланса USDT по адресу
     pub async fn get_usdt_balance(&self, address: &str) -> Result<rust_decimal::Decimal> {
+        match self
+            .get_trc20_balance_of(address, &self.config.usdt_contract, 6)
+            .await
+        {
+            Ok(balance) => Ok(balance),
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️ balanceOf для {} не удался: {} - фоллбек на суммирование транзакций",
+                    address,
+                    e
+                );
+                self.get_usdt_balance_from_transactions(address).await
+            }
+        }
+    }
+
+    /// Точный баланс TRC-20 через balanceOf(address) на triggerconstantcontract
+    pub async fn get_trc20_balance_of(
+        &self,
+        address: &str,
+        contract_address: &str,
+        decimals: u32,
+    ) -> Result<rust_decimal::Decimal> {
+        let owner_hex = self.address_to_hex(address)?;
+        let url = format!("{}/wallet/triggerconstantcontract", self.config.base_url);
+
+        let payload = serde_json::json!({
+            "owner_address": owner_hex,
+            "contract_address": self.address_to_hex(contract_address)?,
+            "function_selector": "balanceOf(address)",
+            "parameter": abi::encode_balance_of_params(&owner_hex),
+        });
+
+        let mut request = self.client.post(&url).json(&payload);
+
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let started = Instant::now();
+        let response = request.send().await?;
+        self.record_usage("balance_of", response.status(), started);
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "TronGrid API error for balanceOf: {}",
+                response.status()
+            ));
+        }
+
+        let result: Value = response.json().await?;
+
+        let word = result
+            .get("constant_result")
+            .and_then(|r| r.as_array())
+            .and_then(|a| a.first())
+            .and_then(|w| w.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Ответ triggerconstantcontract без constant_result"))?;
+
+        let balance_raw = abi::decode_balance_of_result(word)?;
+        Ok(abi::scale_amount(balance_raw, decimals))
+    }
+
+    /// Текущее разрешение spender'а на списание TRC-20 токенов owner'а
+    /// через allowance(address,address) на triggerconstantcontract
+    pub async fn get_trc20_allowance(
+        &self,
+        owner: &str,
+        spender: &str,
+        contract_address: &str,
+        decimals: u32,
+    ) -> Result<rust_decimal::Decimal> {
+        let owner_hex = self.address_to_hex(owner)?;
+        let spender_hex = self.address_to_hex(spender)?;
+        let url = format!("{}/wallet/triggerconstantcontract", self.config.base_url);
+
+        let payload = serde_json::json!({
+            "owner_address": owner_hex,
+            "contract_address": self.address_to_hex(contract_address)?,
+            "function_selector": "allowance(address,address)",
+            "parameter": abi::encode_allowance_params(&owner_hex, &spender_hex),
+        });
+
+        let mut request = self.client.post(&url).json(&payload);
+
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let started = Instant::now();
+        let response = request.send().await?;
+        self.record_usage("allowance", response.status(), started);
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "TronGrid API error for allowance: {}",
+                response.status()
+            ));
+        }
+
+        let result: Value = response.json().await?;
+
+        let word = result
+            .get("constant_result")
+            .and_then(|r| r.as_array())
+            .and_then(|a| a.first())
+            .and_then(|w| w.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Ответ triggerconstantcontract без constant_result"))?;
+
+        let allowance_raw = abi::decode_allowance_result(word)?;
+        Ok(abi::scale_amount(allowance_raw, decimals))
+    }
+
+    /// Создает неподписанную approve(address,uint256) транзакцию на
+    /// контракте USDT. Подписывает ее владелец внешнего кошелька;
+    /// сумма 0 отзывает разрешение полностью
+    pub async fn create_trc20_approve_transaction(
+        &self,
+        owner: &str,
+        spender: &str,
+        amount_raw: u128,
+    ) -> Result<Value> {
+        let owner_hex = self.address_to_hex(owner)?;
+        let spender_hex = self.address_to_hex(spender)?;
+
+        let url = format!("{}/wallet/triggersmartcontract", self.config.base_url);
+
+        let payload = serde_json::json!({
+            "owner_address": owner_hex,
+            "contract_address": self.address_to_hex(&self.config.usdt_contract)?,
+            "function_selector": "approve(address,uint256)",
+            "parameter": abi::encode_approve_params(&spender_hex, amount_raw),
+            "fee_limit": 100_000_000, // 100 TRX
+        });
+
+        let mut request = self.client.post(&url).json(&payload);
+
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let started = Instant::now();
+        let response = request.send().await?;
+        self.record_usage("create_approve", response.status(), started);
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow::anyhow!(
+                "Ошибка создания approve транзакции: {}",
+                error_text
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Устаревший фоллбек: оценка баланса суммированием входящих переводов.
+    /// Врет для кошельков с исходящей активностью - используется только
+    /// когда balanceOf недоступен
+    async fn get_usdt_balance_from_transactions(
+        &self,
+        address: &str,
+    ) -> Result<rust_decimal::Decimal> {
         let url = format!(
             "{}/v1/accounts/{}/transactions/trc20",
             self.config.base_url, address
@@ -57,7 +330,9 @@ impl TronGridClient {
             request = request.header("TRON-PRO-API-KEY", api_key);
         }
 
+        let started = Instant::now();
         let response = request.send().await?;
+        self.record_usage("usdt_balance", response.status(), started);
 
         if !response.status().is_success() {
             tracing::warn!("TronGrid API error for USDT balance: {}", response.status());
@@ -67,7 +342,6 @@ impl TronGridClient {
         let data: serde_json::Value = response.json().await?;
 
         // Вычисляем баланс из транзакций (упрощенная логика)
-        // В реальности нужно делать запрос к /walletsolidity/gettrc20balance
         if let Some(transactions) = data.get("data").and_then(|d| d.as_array()) {
             let mut balance = rust_decimal::Decimal::ZERO;
 
@@ -104,7 +378,9 @@ impl TronGridClient {
             request = request.header("TRON-PRO-API-KEY", api_key);
         }
 
+        let started = Instant::now();
         let response = request.send().await?;
+        self.record_usage("trx_balance", response.status(), started);
 
         if !response.status().is_success() {
             tracing::warn!("TronGrid API error for TRX balance: {}", response.status());
@@ -128,26 +404,107 @@ impl TronGridClient {
         Ok(rust_decimal::Decimal::ZERO)
     }
 
-    /// Оценка энергии для TRC20 транзакции
+    /// Оценка энергии TRC20 трансфера через triggerconstantcontract:
+    /// нода исполняет вызов без фиксации и возвращает energy_used
     pub async fn estimate_energy(
         &self,
         from: &str,
         to: &str,
         amount: rust_decimal::Decimal,
     ) -> Result<u64> {
+        let hex_from = self.address_to_hex(from)?;
+        let hex_to = self.address_to_hex(to)?;
+
+        let amount_units = amount * rust_decimal::Decimal::new(10_i64.pow(6), 0);
+        let amount_u64 = amount_units
+            .to_u64()
+            .ok_or_else(|| anyhow::anyhow!("Недопустимая сумма"))?;
+
+        let url = format!("{}/wallet/triggerconstantcontract", self.config.base_url);
+
+        let payload = serde_json::json!({
+            "owner_address": hex_from,
+            "contract_address": self.address_to_hex(&self.config.usdt_contract)?,
+            "function_selector": "transfer(address,uint256)",
+            "parameter": abi::encode_transfer_params(&hex_to, amount_u64 as u128),
+        });
+
+        let mut request = self.client.post(&url).json(&payload);
+
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let started = Instant::now();
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "estimate_energy"))
+            .await?;
+        self.record_usage("estimate_energy", response.status(), started);
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow::anyhow!("Ошибка оценки энергии: {}", error_text));
+        }
+
+        let result: Value = response.json().await?;
+        let energy_used = result
+            .get("energy_used")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Нода не вернула energy_used: {}", result))?;
+
         tracing::debug!(
-            "Оценка энергии для трансфера {} USDT с {} на {}",
+            "Оценка энергии трансфера {} USDT с {} на {}: {} единиц",
             amount,
             from,
-            to
+            to,
+            energy_used
         );
+        Ok(energy_used)
+    }
+
+    /// Актуальные ценовые параметры сети из /wallet/getchainparameters.
+    /// Отсутствующие параметры заменяются историческими значениями по
+    /// умолчанию (420 sun за энергию, 1000 sun за bandwidth)
+    pub async fn get_chain_parameters(&self) -> Result<ChainParameters> {
+        let url = format!("{}/wallet/getchainparameters", self.config.base_url);
+
+        let mut request = self.client.get(&url);
+
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let started = Instant::now();
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "chain_parameters"))
+            .await?;
+        self.record_usage("chain_parameters", response.status(), started);
 
-        // Для упрощения возвращаем фиксированную оценку
-        // В реальности можно делать запрос к /wallet/triggerconstantcontract
-        let estimated_energy = 31895_u64; // Примерная энергия для TRC20 трансфера
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow::anyhow!(
+                "Ошибка получения параметров сети: {}",
+                error_text
+            ));
+        }
+
+        let result: Value = response.json().await?;
+        let lookup = |key: &str| -> Option<i64> {
+            result
+                .get("chainParameter")?
+                .as_array()?
+                .iter()
+                .find(|param| param.get("key").and_then(|k| k.as_str()) == Some(key))?
+                .get("value")?
+                .as_i64()
+        };
 
-        tracing::debug!("Оценка энергии: {} единиц", estimated_energy);
-        Ok(estimated_energy)
+        Ok(ChainParameters {
+            energy_price_sun: lookup("getEnergyFee").unwrap_or(420),
+            bandwidth_price_sun: lookup("getTransactionFee").unwrap_or(1000),
+        })
     }
 
     /// Создание TRC20 транзакции (USDT)
@@ -172,10 +529,7 @@ impl TronGridClient {
             "owner_address": hex_from,
             "contract_address": self.address_to_hex(&self.config.usdt_contract)?,
             "function_selector": "transfer(address,uint256)",
-            "parameter": format!("{:0>64}{:0>64}",
-                &hex_to[2..], // убираем 0x
-                format!("{:x}", amount_u64)
-            ),
+            "parameter": abi::encode_transfer_params(&hex_to, amount_u64 as u128),
             "fee_limit": 100_000_000, // 100 TRX
         });
 
@@ -185,7 +539,12 @@ impl TronGridClient {
             request = request.header("TRON-PRO-API-KEY", api_key);
         }
 
-        let response = request.send().await?;
+        let started = Instant::now();
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "create_trc20"))
+            .await?;
+        self.record_usage("create_trc20", response.status(), started);
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -196,6 +555,7 @@ impl TronGridClient {
         }
 
         let result: Value = response.json().await?;
+        self.observe_op_budget("create_trc20", started, self.config.op_budgets.create_ms);
 
         tracing::debug!("TRC20 транзакция создана: {:?}", result);
         Ok(result)
@@ -231,7 +591,12 @@ impl TronGridClient {
             request = request.header("TRON-PRO-API-KEY", api_key);
         }
 
-        let response = request.send().await?;
+        let started = Instant::now();
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "create_trx"))
+            .await?;
+        self.record_usage("create_trx", response.status(), started);
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -242,13 +607,28 @@ impl TronGridClient {
         }
 
         let result: Value = response.json().await?;
+        self.observe_op_budget("create_trx", started, self.config.op_budgets.create_ms);
 
         tracing::debug!("TRX транзакция создана: {:?}", result);
         Ok(result)
     }
 
-    /// Отправка подписанной транзакции
+    /// Отправка подписанной транзакции.
+    /// В dry-run режиме broadcast пропускается и возвращается txID
+    /// подписанной транзакции как симулированный успех
     pub async fn broadcast_transaction(&self, signed_transaction: &Value) -> Result<String> {
+        if crate::utils::is_dry_run() {
+            let txid = signed_transaction
+                .get("txID")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| anyhow::anyhow!("В подписанной транзакции нет txID"))?;
+            tracing::warn!(
+                "🧪 DRY-RUN: broadcast пропущен, симулируем успех. TX Hash: {}",
+                txid
+            );
+            return Ok(txid.to_string());
+        }
+
         let url = format!("{}/wallet/broadcasttransaction", self.config.base_url);
 
         let mut request = self.client.post(&url).json(signed_transaction);
@@ -257,7 +637,12 @@ impl TronGridClient {
             request = request.header("TRON-PRO-API-KEY", api_key);
         }
 
-        let response = request.send().await?;
+        let started = Instant::now();
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "broadcast"))
+            .await?;
+        self.record_usage("broadcast", response.status(), started);
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -268,6 +653,7 @@ impl TronGridClient {
         }
 
         let result: Value = response.json().await?;
+        self.observe_op_budget("broadcast", started, self.config.op_budgets.broadcast_ms);
 
         if let Some(result_bool) = result.get("result").and_then(|r| r.as_bool()) {
             if result_bool {
@@ -295,6 +681,97 @@ impl TronGridClient {
         Err(anyhow::anyhow!("Неизвестная ошибка broadcast"))
     }
 
+    /// Проверяет, истекла ли (или вот-вот истечет) неподписанная транзакция
+    ///
+    /// Узел задает `raw_data.expiration` сам (обычно +60 секунд). Если обработка
+    /// задержалась и подписать/отправить уже не успеваем, транзакцию нужно
+    /// пересоздать - иначе broadcast упадет с невнятной ошибкой TRANSACTION_EXPIRATION_ERROR
+    pub fn transaction_expires_soon(transaction: &Value) -> bool {
+        // Запас на подписание и broadcast
+        const SAFETY_MARGIN_MS: i64 = 5_000;
+
+        let expiration_ms = transaction
+            .get("raw_data")
+            .and_then(|raw| raw.get("expiration"))
+            .and_then(|e| e.as_i64());
+
+        match expiration_ms {
+            Some(expiration_ms) => {
+                Utc::now().timestamp_millis() + SAFETY_MARGIN_MS >= expiration_ms
+            }
+            // Без expiration пересоздание не поможет - пропускаем как есть
+            None => false,
+        }
+    }
+
+    /// Получение ресурсов аккаунта (энергия, bandwidth, заморозки, делегирования)
+    ///
+    /// Комбинирует ответы `/wallet/getaccountresource` и `/wallet/getaccount` -
+    /// нужно ops при разборе, почему трансферы жгут TRX вместо энергии
+    pub async fn get_account_resources(&self, address: &str) -> Result<Value> {
+        let resource_url = format!("{}/wallet/getaccountresource", self.config.base_url);
+        let account_url = format!("{}/wallet/getaccount", self.config.base_url);
+
+        let payload = serde_json::json!({
+            "address": address,
+            "visible": true
+        });
+
+        let mut resource_request = self.client.post(&resource_url).json(&payload);
+        let mut account_request = self.client.post(&account_url).json(&payload);
+
+        if let Some(api_key) = &self.config.api_key {
+            resource_request = resource_request.header("TRON-PRO-API-KEY", api_key);
+            account_request = account_request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let started = Instant::now();
+        let resource_response = resource_request.send().await?;
+        self.record_usage("account_resources", resource_response.status(), started);
+        if !resource_response.status().is_success() {
+            let error_text = resource_response.text().await?;
+            return Err(anyhow::anyhow!(
+                "Ошибка получения ресурсов аккаунта: {}",
+                error_text
+            ));
+        }
+        let resources: Value = resource_response.json().await?;
+
+        let started = Instant::now();
+        let account_response = account_request.send().await?;
+        self.record_usage("account_resources", account_response.status(), started);
+        if !account_response.status().is_success() {
+            let error_text = account_response.text().await?;
+            return Err(anyhow::anyhow!("Ошибка получения аккаунта: {}", error_text));
+        }
+        let account: Value = account_response.json().await?;
+
+        let get_u64 =
+            |value: &Value, key: &str| value.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+
+        Ok(serde_json::json!({
+            "address": address,
+            "energy": {
+                "limit": get_u64(&resources, "EnergyLimit"),
+                "used": get_u64(&resources, "EnergyUsed"),
+            },
+            "bandwidth": {
+                "free_limit": get_u64(&resources, "freeNetLimit"),
+                "free_used": get_u64(&resources, "freeNetUsed"),
+                "limit": get_u64(&resources, "NetLimit"),
+                "used": get_u64(&resources, "NetUsed"),
+            },
+            "frozen_v2": account.get("frozenV2").cloned().unwrap_or(Value::Array(vec![])),
+            "delegated": {
+                "frozen_balance_for_bandwidth": get_u64(&account, "delegated_frozenV2_balance_for_bandwidth"),
+                "acquired_frozen_balance_for_bandwidth": get_u64(&account, "acquired_delegated_frozenV2_balance_for_bandwidth"),
+                "frozen_balance_for_energy": get_u64(account.get("account_resource").unwrap_or(&Value::Null), "delegated_frozenV2_balance_for_energy"),
+                "acquired_frozen_balance_for_energy": get_u64(account.get("account_resource").unwrap_or(&Value::Null), "acquired_delegated_frozenV2_balance_for_energy"),
+            },
+            "balance_sun": get_u64(&account, "balance"),
+        }))
+    }
+
     /// Конвертация base58 адреса в hex
     fn address_to_hex(&self, address: &str) -> Result<String> {
         if address.starts_with("0x") {
@@ -315,34 +792,164 @@ impl TronGridClient {
         Ok(hex_address)
     }
 
-    /// Получает TRC20 транзакции для адреса
+    /// Получает TRC20 транзакции для адреса.
+    ///
+    /// Страницы кэшируются с коротким TTL по ключу (адрес, контракт, limit).
+    /// Протухший кэш освежается условным запросом: только транзакции новее
+    /// последнего виденного block_timestamp (плюс If-None-Match по ETag),
+    /// что заметно сокращает объем трафика к TronGrid для стабильных кошельков
     pub async fn get_trc20_transactions(
         &self,
         address: &str,
         contract_address: &str,
         limit: u32,
     ) -> Result<Vec<BlockchainTransaction>> {
+        let cache_key = format!("{}:{}:{}", address, contract_address, limit);
+
+        // Свежий кэш - отдаем без похода в сеть
+        let stale = {
+            let cache = self.trc20_page_cache.lock().unwrap();
+            match cache.get(&cache_key) {
+                Some(page) if !page.is_expired() => {
+                    self.trc20_cache_counters
+                        .hits
+                        .fetch_add(1, Ordering::Relaxed);
+                    tracing::debug!(
+                        "Кэш TRC20 страницы для {} свежий - TronGrid не запрашивается",
+                        address
+                    );
+                    return Ok(page.transactions.clone());
+                }
+                Some(page) => Some(page.clone()),
+                None => None,
+            }
+        };
+
+        let (transactions, etag) = match &stale {
+            // Протухший кэш - ревалидируем условным запросом
+            Some(page) => {
+                self.trc20_cache_counters
+                    .revalidations
+                    .fetch_add(1, Ordering::Relaxed);
+
+                let (fresh, etag, not_modified) = self
+                    .fetch_trc20_page(
+                        address,
+                        contract_address,
+                        limit,
+                        page.last_timestamp,
+                        page.etag.as_deref(),
+                    )
+                    .await?;
+
+                if not_modified || fresh.is_empty() {
+                    // Новых транзакций нет - продлеваем жизнь кэшу
+                    (
+                        page.transactions.clone(),
+                        etag.or_else(|| page.etag.clone()),
+                    )
+                } else {
+                    // Дозагруженные транзакции старше limit вытесняют хвост
+                    let mut merged = fresh;
+                    for tx in &page.transactions {
+                        if !merged.iter().any(|m| m.tx_hash == tx.tx_hash) {
+                            merged.push(tx.clone());
+                        }
+                    }
+                    merged.truncate(limit as usize);
+                    (merged, etag)
+                }
+            }
+            // Кэша нет - полная загрузка страницы
+            None => {
+                self.trc20_cache_counters
+                    .misses
+                    .fetch_add(1, Ordering::Relaxed);
+
+                let (fresh, etag, _) = self
+                    .fetch_trc20_page(address, contract_address, limit, None, None)
+                    .await?;
+                (fresh, etag)
+            }
+        };
+
+        let last_timestamp = transactions
+            .iter()
+            .filter_map(|tx| tx.block_number)
+            .max()
+            .or(stale.and_then(|page| page.last_timestamp));
+
+        let mut cache = self.trc20_page_cache.lock().unwrap();
+        cache.insert(
+            cache_key,
+            CachedTrc20Page {
+                transactions: transactions.clone(),
+                etag,
+                last_timestamp,
+                cached_at: Instant::now(),
+            },
+        );
+
+        Ok(transactions)
+    }
+
+    /// Запрашивает страницу TRC20 транзакций у TronGrid.
+    ///
+    /// `min_timestamp` и `etag` делают запрос условным: возвращаются только
+    /// транзакции новее границы, а при совпадении ETag - 304 без тела.
+    /// Возвращает (транзакции, ETag ответа, был ли ответ 304)
+    async fn fetch_trc20_page(
+        &self,
+        address: &str,
+        contract_address: &str,
+        limit: u32,
+        min_timestamp: Option<i64>,
+        etag: Option<&str>,
+    ) -> Result<(Vec<BlockchainTransaction>, Option<String>, bool)> {
         let url = format!(
             "{}/v1/accounts/{}/transactions/trc20",
             self.config.base_url, address
         );
 
-        let mut request = self.client.get(&url).query(&[
-            ("limit", limit.to_string().as_str()),
-            ("contract_address", contract_address),
-            ("only_confirmed", "true"), // Только подтвержденные
-        ]);
+        let mut query = vec![
+            ("limit", limit.to_string()),
+            ("contract_address", contract_address.to_string()),
+            ("only_confirmed", "true".to_string()), // Только подтвержденные
+        ];
+
+        if let Some(min_timestamp) = min_timestamp {
+            // Граница строго после последней виденной транзакции
+            query.push(("min_timestamp", (min_timestamp + 1).to_string()));
+        }
+
+        let mut request = self.client.get(&url).query(&query);
 
         if let Some(api_key) = &self.config.api_key {
             request = request.header("TRON-PRO-API-KEY", api_key);
         }
 
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        let started = Instant::now();
         let response = request.send().await?;
+        self.record_usage("trc20_page", response.status(), started);
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok((Vec::new(), None, true));
+        }
 
         if !response.status().is_success() {
             return Err(anyhow::anyhow!("TronGrid API error: {}", response.status()));
         }
 
+        let response_etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+
         let result: Value = response.json().await?;
         let mut transactions = Vec::new();
 
@@ -354,7 +961,7 @@ impl TronGridClient {
             }
         }
 
-        Ok(transactions)
+        Ok((transactions, response_etag, false))
     }
 
     /// Парсит TRC20 транзакцию из JSON
@@ -410,6 +1017,17 @@ impl TronGridClient {
             0u32 // Неподтвержденная
         };
 
+        // Токен трансфера из token_info TronGrid (символ и контракт)
+        let token_info = tx_data.get("token_info");
+        let token_symbol = token_info
+            .and_then(|t| t.get("symbol"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let contract_address = token_info
+            .and_then(|t| t.get("address"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
         Ok(BlockchainTransaction {
             tx_hash,
             block_number,
@@ -418,6 +1036,8 @@ impl TronGridClient {
             amount,
             timestamp,
             confirmations,
+            token_symbol,
+            contract_address,
         })
     }
 
@@ -426,6 +1046,8 @@ impl TronGridClient {
         &self,
         tx_hash: &str,
     ) -> Result<Option<BlockchainTransaction>> {
+        let started = Instant::now();
+
         // Шаг 1: Получаем информацию о транзакции (статус, блок)
         let info_url = format!("{}/wallet/gettransactioninfobyid", self.config.base_url);
         let info_body = serde_json::json!({ "value": tx_hash });
@@ -435,7 +1057,11 @@ impl TronGridClient {
             info_request = info_request.header("TRON-PRO-API-KEY", api_key);
         }
 
-        let info_response = info_request.send().await?;
+        let info_response = info_request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "poll_info"))
+            .await?;
+        self.record_usage("poll_info", info_response.status(), started);
         if !info_response.status().is_success() {
             return Ok(None);
         }
@@ -449,12 +1075,17 @@ impl TronGridClient {
         let tx_url = format!("{}/wallet/gettransactionbyid", self.config.base_url);
         let tx_body = serde_json::json!({ "value": tx_hash });
 
+        let tx_started = Instant::now();
         let mut tx_request = self.client.post(&tx_url).json(&tx_body);
         if let Some(api_key) = &self.config.api_key {
             tx_request = tx_request.header("TRON-PRO-API-KEY", api_key);
         }
 
-        let tx_response = tx_request.send().await?;
+        let tx_response = tx_request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "poll_tx"))
+            .await?;
+        self.record_usage("poll_tx", tx_response.status(), tx_started);
         if !tx_response.status().is_success() {
             return Ok(None);
         }
@@ -464,6 +1095,8 @@ impl TronGridClient {
             return Ok(None);
         }
 
+        self.observe_op_budget("poll", started, self.config.op_budgets.poll_ms);
+
         // Парсим данные транзакции
         let block_number = info_result.get("blockNumber").and_then(|v| v.as_i64());
         let block_timestamp = info_result
@@ -511,13 +1144,14 @@ impl TronGridClient {
                             .address_to_hex(&self.config.usdt_contract)
                             .unwrap_or_default(),
                     ) {
-                        // Парсим data для получения to_address и amount
+                        // Декодируем calldata для получения to_address и amount
                         if let Some(data) = parameter.get("data").and_then(|v| v.as_str()) {
-                            if let Ok((parsed_to, parsed_amount)) =
-                                self.parse_trc20_transfer_data(data)
-                            {
-                                to_address = parsed_to;
-                                amount = parsed_amount;
+                            if let Ok(call) = abi::decode_transfer_call(data) {
+                                to_address = call.to_address;
+                                amount = abi::scale_amount(
+                                    call.amount_raw,
+                                    self.config.usdt_decimals as u32,
+                                );
                             }
                         }
                     }
@@ -525,6 +1159,34 @@ impl TronGridClient {
             }
         }
 
+        // Fallback: декодируем Transfer event из логов gettransactioninfobyid -
+        // покрывает transferFrom и вызовы, не распознанные по calldata
+        if to_address.is_empty() {
+            if let Some(logs) = info_result.get("log").and_then(|l| l.as_array()) {
+                for log in logs {
+                    let topics: Vec<String> = log
+                        .get("topics")
+                        .and_then(|t| t.as_array())
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| v.as_str().map(String::from))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    let data = log.get("data").and_then(|v| v.as_str()).unwrap_or_default();
+
+                    if let Ok(event) = abi::decode_transfer_log(&topics, data) {
+                        from_address = event.from_address;
+                        to_address = event.to_address;
+                        amount =
+                            abi::scale_amount(event.amount_raw, self.config.usdt_decimals as u32);
+                        break;
+                    }
+                }
+            }
+        }
+
         Ok(Some(BlockchainTransaction {
             tx_hash: tx_hash.to_string(),
             block_number,
@@ -533,6 +1195,93 @@ impl TronGridClient {
             amount,
             timestamp,
             confirmations,
+            // gettransactioninfobyid не отдает token_info - токен
+            // здесь не определяется
+            token_symbol: None,
+            contract_address: None,
+        }))
+    }
+
+    /// Получает номер последнего блока цепочки
+    pub async fn get_latest_block_number(&self) -> Result<i64> {
+        let started = Instant::now();
+        let url = format!("{}/wallet/getnowblock", self.config.base_url);
+
+        let mut request = self.client.post(&url);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "now_block"))
+            .await?;
+        self.record_usage("now_block", response.status(), started);
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Ошибка получения последнего блока: HTTP {}",
+                response.status()
+            ));
+        }
+
+        let result: Value = response.json().await?;
+        result
+            .get("block_header")
+            .and_then(|h| h.get("raw_data"))
+            .and_then(|rd| rd.get("number"))
+            .and_then(|n| n.as_i64())
+            .ok_or_else(|| anyhow::anyhow!("Ответ getnowblock без номера блока"))
+    }
+
+    /// Получает исход транзакции на цепочке: блок и результат выполнения.
+    ///
+    /// Легче get_transaction_info - один запрос без декодирования
+    /// контрактных данных. None - транзакция еще не видна ноде
+    pub async fn get_transaction_receipt(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<TransactionReceipt>> {
+        let started = Instant::now();
+        let url = format!("{}/wallet/gettransactioninfobyid", self.config.base_url);
+        let body = serde_json::json!({ "value": tx_hash });
+
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let response = request
+            .send()
+            .instrument(tracing::info_span!("chain_op", op = "poll_receipt"))
+            .await?;
+        self.record_usage("poll_receipt", response.status(), started);
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let result: Value = response.json().await?;
+        if result.is_null() || result.get("id").is_none() {
+            return Ok(None);
+        }
+
+        let block_number = result.get("blockNumber").and_then(|v| v.as_i64());
+
+        // Неуспех помечается двумя способами: result = "FAILED" на верхнем
+        // уровне и receipt.result != SUCCESS (REVERT, OUT_OF_ENERGY)
+        let top_level_failed = result
+            .get("result")
+            .and_then(|v| v.as_str())
+            .is_some_and(|r| r.eq_ignore_ascii_case("FAILED"));
+        let receipt_failed = result
+            .get("receipt")
+            .and_then(|r| r.get("result"))
+            .and_then(|v| v.as_str())
+            .is_some_and(|r| !r.eq_ignore_ascii_case("SUCCESS"));
+
+        Ok(Some(TransactionReceipt {
+            tx_hash: tx_hash.to_string(),
+            block_number,
+            success: !top_level_failed && !receipt_failed,
         }))
     }
 
@@ -570,27 +1319,4 @@ impl TronGridClient {
 
         Ok(full_address.to_base58())
     }
-
-    /// Парсит данные TRC20 transfer из hex
-    fn parse_trc20_transfer_data(&self, data: &str) -> Result<(String, Decimal)> {
-        if data.len() < 136 {
-            // 4 bytes method + 32 bytes to + 32 bytes amount
-            return Err(anyhow::anyhow!("Invalid TRC20 data length"));
-        }
-
-        // Пропускаем method signature (первые 8 символов)
-        let data_hex = &data[8..];
-
-        // Извлекаем to_address (следующие 64 символа, последние 40 - адрес)
-        let to_hex = &data_hex[24..64]; // Пропускаем padding
-        let to_address = self.hex_to_base58_safe(&format!("41{}", to_hex));
-
-        // Извлекаем amount (следующие 64 символа)
-        let amount_hex = &data_hex[64..128];
-        let amount_wei = u128::from_str_radix(amount_hex, 16).unwrap_or(0);
-        let amount =
-            Decimal::from(amount_wei) / Decimal::from(10u64.pow(self.config.usdt_decimals as u32));
-
-        Ok((to_address, amount))
-    }
 }
@@ -116,6 +116,10 @@ impl TronTransactionSigner {
 
     /// Подписание транзакции
     pub fn sign_transaction(&self, transaction: &Value, private_key_hex: &str) -> Result<Value> {
+        let span = tracing::info_span!("chain_op", op = "sign");
+        let _enter = span.enter();
+        let started = std::time::Instant::now();
+
         tracing::debug!("Подписание транзакции: {:?}", transaction);
 
         // 1. Получаем raw_data из транзакции
@@ -155,6 +159,18 @@ impl TronTransactionSigner {
 
         tracing::debug!("Подписанная транзакция: {:?}", signed_transaction);
 
+        // Подпись - локальная операция, бюджет фиксированный: дольше 100 мс
+        // значит что-то не так (например, конкуренция за CPU)
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        if elapsed_ms > 100 {
+            tracing::warn!(
+                op = "sign",
+                elapsed_ms,
+                "⏱️ Подписание транзакции заняло {} мс",
+                elapsed_ms
+            );
+        }
+
         Ok(signed_transaction)
     }
 
@@ -182,10 +198,7 @@ impl TronTransactionSigner {
             "contract": [{
                 "parameter": {
                     "value": {
-                        "data": format!("a9059cbb{:0>64}{:0>64}",
-                            &to_hex[2..], // убираем 0x префикс
-                            format!("{:x}", amount_u64)
-                        ),
+                        "data": super::abi::encode_transfer_call(&to_hex, amount_u64 as u128),
                         "owner_address": from_hex,
                         "contract_address": contract_hex
                     },
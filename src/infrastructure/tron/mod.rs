@@ -1,14 +1,21 @@
 //! # TRON интеграция
 //!
 //! Модули для работы с TRON блокчейном:
+//! - `abi` - ABI кодирование/декодирование TRC-20
 //! - `client` - TronGrid API клиент
 //! - `crypto` - криптографические операции
+//! - `signing` - подключаемые бэкенды подписания
+//! - `usage` - учет использования TronGrid API
 
+pub mod abi;
 pub mod client;
 pub mod crypto;
+pub mod signing;
 pub mod token_service;
+pub mod usage;
 
 // Реэкспорт основных типов
-pub use client::TronGridClient;
+pub use client::{ChainParameters, Trc20CacheStats, TronGridClient};
 pub use crypto::{TronTransactionSigner, TronWalletGenerator};
-pub use token_service::{Trc20TokenService, Trc20ServiceConfig};
+pub use signing::{LocalSigningBackend, RemoteSigningBackend, SigningBackend};
+pub use token_service::{Trc20ServiceConfig, Trc20TokenService};
@@ -0,0 +1,129 @@
+//! # Подключаемые бэкенды подписания транзакций
+//!
+//! Сервисы не работают с ключевым материалом напрямую - подписание идет
+//! через `SigningBackend`. Локальный бэкенд оборачивает
+//! `TronTransactionSigner` (ключ приходит как hex), удаленный отправляет
+//! транзакцию во внешний KMS/HSM (Vault transit или signing sidecar) и
+//! получает обратно подписанную - приватный ключ шлюз не покидает KMS.
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::crypto::TronTransactionSigner;
+
+/// Подключаемый бэкенд подписания TRON транзакций
+///
+/// `key_ref` - ссылка на ключ: для локального бэкенда это hex приватного
+/// ключа, для удаленного - идентификатор ключа в KMS (сам ключ недоступен)
+#[tonic::async_trait]
+pub trait SigningBackend: Send + Sync {
+    /// Имя бэкенда для логирования
+    fn name(&self) -> &str;
+
+    /// Подписывает неподписанную транзакцию
+    async fn sign_transaction(&self, transaction: &Value, key_ref: &str) -> Result<Value>;
+}
+
+/// Локальный бэкенд: подписание внутри процесса через `TronTransactionSigner`
+pub struct LocalSigningBackend {
+    signer: TronTransactionSigner,
+}
+
+impl LocalSigningBackend {
+    /// Создает новый локальный бэкенд
+    pub fn new() -> Self {
+        Self {
+            signer: TronTransactionSigner::new(),
+        }
+    }
+}
+
+impl Default for LocalSigningBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tonic::async_trait]
+impl SigningBackend for LocalSigningBackend {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    async fn sign_transaction(&self, transaction: &Value, key_ref: &str) -> Result<Value> {
+        self.signer.sign_transaction(transaction, key_ref)
+    }
+}
+
+/// Ответ удаленного сервиса подписания
+#[derive(Debug, Deserialize)]
+struct RemoteSigningResponse {
+    signed_transaction: Value,
+}
+
+/// Удаленный бэкенд подписания (Vault transit / gRPC signing sidecar за HTTP)
+///
+/// Отправляет `POST {endpoint_url}` с телом
+/// `{"key_ref": "...", "transaction": {...}}` и ожидает ответ вида
+/// `{"signed_transaction": {...}}`. Ключевой материал остается в KMS
+pub struct RemoteSigningBackend {
+    endpoint_url: String,
+    auth_token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl RemoteSigningBackend {
+    /// Создает бэкенд для указанного endpoint
+    pub fn new(endpoint_url: String, auth_token: Option<String>) -> Self {
+        Self {
+            endpoint_url,
+            auth_token,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl SigningBackend for RemoteSigningBackend {
+    fn name(&self) -> &str {
+        "remote"
+    }
+
+    async fn sign_transaction(&self, transaction: &Value, key_ref: &str) -> Result<Value> {
+        let span = tracing::info_span!("chain_op", op = "sign_remote");
+        let _enter = span.enter();
+
+        let mut request = self
+            .client
+            .post(&self.endpoint_url)
+            .json(&serde_json::json!({
+                "key_ref": key_ref,
+                "transaction": transaction,
+            }));
+
+        if let Some(auth_token) = &self.auth_token {
+            request = request.bearer_auth(auth_token);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Сервис подписания вернул статус {}",
+                response.status()
+            ));
+        }
+
+        let api_response: RemoteSigningResponse = response.json().await?;
+
+        // Минимальная проверка: без signature broadcast гарантированно упадет
+        if api_response.signed_transaction.get("signature").is_none() {
+            return Err(anyhow::anyhow!(
+                "Сервис подписания вернул транзакцию без signature"
+            ));
+        }
+
+        Ok(api_response.signed_transaction)
+    }
+}
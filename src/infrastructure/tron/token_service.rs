@@ -3,6 +3,8 @@
 //! Сервис для работы с множественными TRC-20 токенами с кэшированием и оптимизацией
 
 use anyhow::Result;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde_json::Value;
@@ -12,9 +14,15 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+use super::abi;
 use crate::config::TronConfig;
 use crate::domain::tokens::{MultiTokenBalance, TokenInfo, TokenRegistry};
+use crate::infrastructure::database::{
+    models::{NewToken, TokenModel},
+    schema, DbPool,
+};
 use crate::infrastructure::retry::{classify_reqwest_error, RetryConfig, RetryableService};
+use crate::utils::{bigdecimal_to_decimal, decimal_to_bigdecimal};
 
 /// Кэшированный баланс
 #[derive(Debug, Clone)]
@@ -69,6 +77,11 @@ pub struct Trc20TokenService {
     token_registry: Arc<RwLock<TokenRegistry>>,
     balance_cache: Arc<RwLock<HashMap<String, CachedBalance>>>, // key: "address:token_symbol"
     retry_service: RetryableService<()>,
+    db: Option<DbPool>,
+    // Версии токенов для optimistic concurrency при write-through записи
+    token_versions: Arc<RwLock<HashMap<String, i64>>>,
+    // Переопределение sweep-назначения по символу токена (из конфига)
+    sweep_destinations: HashMap<String, String>,
 }
 
 impl Trc20TokenService {
@@ -83,18 +96,72 @@ impl Trc20TokenService {
             initial_delay: Duration::from_millis(500),
             max_delay: Duration::from_secs(10),
             backoff_multiplier: 2.0,
+            jitter: 0.1,
         };
 
         Self {
-            client: Client::new(),
+            client: tron_config.egress.build_client(),
             tron_config,
             service_config,
             token_registry: Arc::new(RwLock::new(token_registry)),
             balance_cache: Arc::new(RwLock::new(HashMap::new())),
             retry_service: RetryableService::with_config((), retry_config),
+            db: None,
+            token_versions: Arc::new(RwLock::new(HashMap::new())),
+            sweep_destinations: HashMap::new(),
         }
     }
 
+    /// Подключает БД для персистентности реестра токенов
+    pub fn with_db(mut self, db: DbPool) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Задает переопределения sweep-назначений по символу токена
+    pub fn with_sweep_destinations(mut self, sweep_destinations: HashMap<String, String>) -> Self {
+        self.sweep_destinations = sweep_destinations;
+        self
+    }
+
+    /// Возвращает настроенное sweep-назначение для токена (если есть)
+    pub fn sweep_destination_for(&self, token_symbol: &str) -> Option<&str> {
+        self.sweep_destinations
+            .get(token_symbol)
+            .map(|s| s.as_str())
+    }
+
+    /// Возвращает информацию о токене из реестра (если поддерживается)
+    pub async fn token_info(&self, token_symbol: &str) -> Option<TokenInfo> {
+        let token_registry = self.token_registry.read().await;
+        token_registry.get_token(token_symbol).cloned()
+    }
+
+    /// Загружает сохраненные токены из БД в реестр (вызывается при старте)
+    pub async fn load_tokens_from_db(&self) -> Result<()> {
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+
+        let mut conn = db.get().await?;
+        let stored_tokens: Vec<TokenModel> = schema::tokens::table
+            .select(TokenModel::as_select())
+            .load(&mut conn)
+            .await?;
+
+        let count = stored_tokens.len();
+        let mut registry = self.token_registry.write().await;
+        let mut versions = self.token_versions.write().await;
+
+        for model in stored_tokens {
+            versions.insert(model.symbol.clone(), model.version);
+            registry.add_token(Self::model_to_token_info(model));
+        }
+
+        info!("Загружено {} токенов из БД в реестр", count);
+        Ok(())
+    }
+
     /// Получает баланс конкретного токена с кэшированием
     pub async fn get_token_balance(
         &self,
@@ -231,10 +298,7 @@ impl Trc20TokenService {
             "owner_address": hex_from,
             "contract_address": self.address_to_hex(&token_info.contract_address)?,
             "function_selector": "transfer(address,uint256)",
-            "parameter": format!("{:0>64}{:0>64}",
-                &hex_to[2..], // убираем 0x
-                format!("{:x}", amount_wei)
-            ),
+            "parameter": super::abi::encode_transfer_params(&hex_to, amount_wei as u128),
             "fee_limit": 100_000_000, // 100 TRX
         });
 
@@ -341,8 +405,13 @@ impl Trc20TokenService {
         info!("Кэш балансов очищен для кошелька {}", wallet_address);
     }
 
-    /// Добавляет новый токен в реестр
+    /// Добавляет новый токен в реестр (write-through в БД если подключена)
     pub async fn add_token(&self, token: TokenInfo) -> Result<()> {
+        // Сначала пишем в БД, чтобы не потерять токен при рестарте
+        if self.db.is_some() {
+            self.persist_token(&token).await?;
+        }
+
         let mut registry = self.token_registry.write().await;
         registry.add_token(token.clone());
         info!(
@@ -352,8 +421,42 @@ impl Trc20TokenService {
         Ok(())
     }
 
-    /// Включает/отключает токен
+    /// Включает/отключает токен (write-through в БД если подключена)
     pub async fn set_token_enabled(&self, token_symbol: &str, enabled: bool) -> Result<()> {
+        // Сначала пишем в БД с проверкой версии (optimistic concurrency)
+        if let Some(db) = &self.db {
+            let current_version = {
+                let versions = self.token_versions.read().await;
+                versions.get(token_symbol).copied()
+            };
+
+            if let Some(version) = current_version {
+                let mut conn = db.get().await?;
+                let updated = diesel::update(
+                    schema::tokens::table
+                        .filter(schema::tokens::symbol.eq(token_symbol))
+                        .filter(schema::tokens::version.eq(version)),
+                )
+                .set((
+                    schema::tokens::enabled.eq(enabled),
+                    schema::tokens::version.eq(version + 1),
+                    schema::tokens::updated_at.eq(diesel::dsl::now),
+                ))
+                .execute(&mut conn)
+                .await?;
+
+                if updated == 0 {
+                    return Err(anyhow::anyhow!(
+                        "Токен {} был изменен конкурентно, повторите операцию",
+                        token_symbol
+                    ));
+                }
+
+                let mut versions = self.token_versions.write().await;
+                versions.insert(token_symbol.to_string(), version + 1);
+            }
+        }
+
         let mut registry = self.token_registry.write().await;
         registry
             .set_token_enabled(token_symbol, enabled)
@@ -371,6 +474,98 @@ impl Trc20TokenService {
         Ok(())
     }
 
+    /// Сохраняет токен в БД (insert или update с проверкой версии)
+    async fn persist_token(&self, token: &TokenInfo) -> Result<()> {
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+
+        let mut conn = db.get().await?;
+        let current_version = {
+            let versions = self.token_versions.read().await;
+            versions.get(&token.symbol).copied()
+        };
+
+        match current_version {
+            // Токен уже известен - обновляем с проверкой версии
+            Some(version) => {
+                let updated = diesel::update(
+                    schema::tokens::table
+                        .filter(schema::tokens::symbol.eq(&token.symbol))
+                        .filter(schema::tokens::version.eq(version)),
+                )
+                .set((
+                    schema::tokens::name.eq(&token.name),
+                    schema::tokens::contract_address.eq(&token.contract_address),
+                    schema::tokens::decimals.eq(token.decimals as i16),
+                    schema::tokens::is_stable.eq(token.is_stable),
+                    schema::tokens::min_transfer_amount
+                        .eq(decimal_to_bigdecimal(token.min_transfer_amount)),
+                    schema::tokens::max_transfer_amount
+                        .eq(token.max_transfer_amount.map(decimal_to_bigdecimal)),
+                    schema::tokens::enabled.eq(token.enabled),
+                    schema::tokens::icon_url.eq(&token.icon_url),
+                    schema::tokens::coingecko_id.eq(&token.coingecko_id),
+                    schema::tokens::version.eq(version + 1),
+                    schema::tokens::updated_at.eq(diesel::dsl::now),
+                ))
+                .execute(&mut conn)
+                .await?;
+
+                if updated == 0 {
+                    return Err(anyhow::anyhow!(
+                        "Токен {} был изменен конкурентно, повторите операцию",
+                        token.symbol
+                    ));
+                }
+
+                let mut versions = self.token_versions.write().await;
+                versions.insert(token.symbol.clone(), version + 1);
+            }
+            // Новый токен - вставляем
+            None => {
+                let new_token = NewToken {
+                    symbol: token.symbol.clone(),
+                    name: token.name.clone(),
+                    contract_address: token.contract_address.clone(),
+                    decimals: token.decimals as i16,
+                    is_stable: token.is_stable,
+                    min_transfer_amount: decimal_to_bigdecimal(token.min_transfer_amount),
+                    max_transfer_amount: token.max_transfer_amount.map(decimal_to_bigdecimal),
+                    enabled: token.enabled,
+                    icon_url: token.icon_url.clone(),
+                    coingecko_id: token.coingecko_id.clone(),
+                };
+
+                diesel::insert_into(schema::tokens::table)
+                    .values(&new_token)
+                    .execute(&mut conn)
+                    .await?;
+
+                let mut versions = self.token_versions.write().await;
+                versions.insert(token.symbol.clone(), 0);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Конвертирует модель БД в доменный TokenInfo
+    fn model_to_token_info(model: TokenModel) -> TokenInfo {
+        TokenInfo {
+            symbol: model.symbol,
+            name: model.name,
+            contract_address: model.contract_address,
+            decimals: model.decimals as u8,
+            is_stable: model.is_stable,
+            min_transfer_amount: bigdecimal_to_decimal(model.min_transfer_amount),
+            max_transfer_amount: model.max_transfer_amount.map(bigdecimal_to_decimal),
+            enabled: model.enabled,
+            icon_url: model.icon_url,
+            coingecko_id: model.coingecko_id,
+        }
+    }
+
     /// Получает информацию о всех токенах
     pub async fn get_supported_tokens(&self) -> Vec<TokenInfo> {
         let registry = self.token_registry.read().await;
@@ -411,7 +606,85 @@ impl Trc20TokenService {
         contract_address: &str,
         decimals: u8,
     ) -> Result<(Decimal, u64)> {
-        // Используем правильный API для получения баланса TRC-20
+        match self
+            .fetch_balance_of(wallet_address, contract_address, decimals)
+            .await
+        {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                warn!(
+                    "⚠️ balanceOf для {} не удался: {} - фоллбек на суммирование транзакций",
+                    wallet_address, e
+                );
+                self.fetch_token_balance_from_transactions(
+                    wallet_address,
+                    contract_address,
+                    decimals,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Точный баланс токена через balanceOf(address) на triggerconstantcontract
+    async fn fetch_balance_of(
+        &self,
+        wallet_address: &str,
+        contract_address: &str,
+        decimals: u8,
+    ) -> Result<(Decimal, u64)> {
+        let owner_hex = self.address_to_hex(wallet_address)?;
+        let url = format!(
+            "{}/wallet/triggerconstantcontract",
+            self.tron_config.base_url
+        );
+
+        let payload = serde_json::json!({
+            "owner_address": owner_hex,
+            "contract_address": self.address_to_hex(contract_address)?,
+            "function_selector": "balanceOf(address)",
+            "parameter": abi::encode_balance_of_params(&owner_hex),
+        });
+
+        let mut request = self.client.post(&url).json(&payload);
+
+        if let Some(api_key) = &self.tron_config.api_key {
+            request = request.header("TRON-PRO-API-KEY", api_key);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "TronGrid API error for balanceOf: {}",
+                response.status()
+            ));
+        }
+
+        let result: Value = response.json().await?;
+
+        // .get(0) вместо .first(): diesel prelude затеняет Vec::first
+        let word = result
+            .get("constant_result")
+            .and_then(|r| r.get(0))
+            .and_then(|w| w.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Ответ triggerconstantcontract без constant_result"))?;
+
+        let balance_raw = abi::decode_balance_of_result(word)?;
+        let balance = abi::scale_amount(balance_raw, decimals as u32);
+
+        Ok((balance, u64::try_from(balance_raw).unwrap_or(u64::MAX)))
+    }
+
+    /// Устаревший фоллбек: оценка баланса суммированием входящих переводов.
+    /// Врет для кошельков с исходящей активностью - используется только
+    /// когда balanceOf недоступен
+    async fn fetch_token_balance_from_transactions(
+        &self,
+        wallet_address: &str,
+        contract_address: &str,
+        decimals: u8,
+    ) -> Result<(Decimal, u64)> {
         let url = format!(
             "{}/v1/accounts/{}/transactions/trc20",
             self.tron_config.base_url, wallet_address
@@ -434,7 +707,7 @@ impl Trc20TokenService {
 
         let data: Value = response.json().await?;
 
-        // Простая логика вычисления баланса
+        // Упрощенная логика: сумма входящих переводов
         if let Some(transactions) = data.get("data").and_then(|d| d.as_array()) {
             let mut balance_wei = 0u64;
 
@@ -0,0 +1,314 @@
+//! # Учет использования TronGrid API
+//!
+//! Каждый запрос к TronGrid записывается в кольцевой буфер (endpoint,
+//! латентность, статус), по дням ведутся агрегаты с алертом при
+//! приближении к дневной квоте - блокировки по rate limit видны
+//! заранее, а не постфактум. Завершенный день сбрасывается в БД
+//! (таблица trongrid_usage_daily) для поиска по истории
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+
+use crate::infrastructure::database::{models::NewTronGridUsageDaily, schema, DbPool};
+
+/// Емкость кольцевого буфера последних запросов
+const USAGE_RING_CAPACITY: usize = 512;
+
+/// Сколько последних запросов отдавать в snapshot
+const USAGE_RECENT_LIMIT: usize = 50;
+
+/// Доля квоты, с которой начинаются предупреждения
+const QUOTA_WARN_SHARE: f64 = 0.8;
+
+/// Приближается ли дневной счетчик к квоте
+fn approaching_quota(total_requests: u64, quota: u64) -> bool {
+    quota > 0 && (total_requests as f64) >= (quota as f64) * QUOTA_WARN_SHARE
+}
+
+/// Запись о единичном запросе к TronGrid
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageSample {
+    /// Имя операции (create_trc20, broadcast, poll, ...)
+    pub op: &'static str,
+    /// HTTP статус ответа
+    pub status: u16,
+    /// Латентность запроса в миллисекундах
+    pub latency_ms: u64,
+    /// Момент запроса
+    pub at: chrono::DateTime<Utc>,
+}
+
+/// Дневной агрегат по операции
+#[derive(Debug, Clone, Default)]
+struct OpAggregate {
+    requests: u64,
+    errors: u64,
+    total_latency_ms: u64,
+}
+
+/// Агрегаты текущего дня
+struct DayAggregates {
+    day: NaiveDate,
+    per_op: HashMap<&'static str, OpAggregate>,
+}
+
+/// Срез использования TronGrid для debug endpoint'а
+#[derive(Debug, Serialize)]
+pub struct TronGridUsageSnapshot {
+    pub day: NaiveDate,
+    pub total_requests: u64,
+    pub error_requests: u64,
+    pub daily_quota: Option<u64>,
+    pub quota_used_percent: Option<f64>,
+    pub per_op: Vec<OpUsage>,
+    /// Последние запросы из кольцевого буфера (новые в конце)
+    pub recent: Vec<UsageSample>,
+}
+
+/// Использование по операции за текущий день
+#[derive(Debug, Serialize)]
+pub struct OpUsage {
+    pub op: &'static str,
+    pub requests: u64,
+    pub errors: u64,
+    pub avg_latency_ms: u64,
+}
+
+/// Трекер использования TronGrid API
+pub struct TronGridUsageTracker {
+    samples: Mutex<VecDeque<UsageSample>>,
+    day: Mutex<DayAggregates>,
+    daily_quota: Option<u64>,
+    quota_warned: AtomicBool,
+    /// Пул БД для сброса дневных агрегатов (no-op если не подключен)
+    db: Mutex<Option<DbPool>>,
+}
+
+impl TronGridUsageTracker {
+    /// Создает трекер с дневной квотой из конфига
+    pub fn new(daily_quota: Option<u64>) -> Self {
+        Self {
+            samples: Mutex::new(VecDeque::with_capacity(USAGE_RING_CAPACITY)),
+            day: Mutex::new(DayAggregates {
+                day: Utc::now().date_naive(),
+                per_op: HashMap::new(),
+            }),
+            daily_quota,
+            quota_warned: AtomicBool::new(false),
+            db: Mutex::new(None),
+        }
+    }
+
+    /// Подключает пул БД для персистентных дневных агрегатов
+    pub fn set_persistence(&self, db: DbPool) {
+        *self.db.lock().unwrap() = Some(db);
+    }
+
+    /// Записывает завершенный запрос к TronGrid
+    pub fn record(&self, op: &'static str, status: u16, latency_ms: u64) {
+        let now = Utc::now();
+
+        {
+            let mut samples = self.samples.lock().unwrap();
+            if samples.len() == USAGE_RING_CAPACITY {
+                samples.pop_front();
+            }
+            samples.push_back(UsageSample {
+                op,
+                status,
+                latency_ms,
+                at: now,
+            });
+        }
+
+        let total_requests = {
+            let mut day = self.day.lock().unwrap();
+
+            // Смена дня: завершенные агрегаты уходят в БД, счетчики обнуляются
+            if day.day != now.date_naive() {
+                let finished_day = day.day;
+                let finished = std::mem::take(&mut day.per_op);
+                day.day = now.date_naive();
+                self.quota_warned.store(false, Ordering::Relaxed);
+
+                if let Some(db) = self.db.lock().unwrap().clone() {
+                    tokio::spawn(persist_daily_aggregates(db, finished_day, finished));
+                }
+            }
+
+            let aggregate = day.per_op.entry(op).or_default();
+            aggregate.requests += 1;
+            aggregate.total_latency_ms += latency_ms;
+            if status >= 400 {
+                aggregate.errors += 1;
+            }
+
+            day.per_op.values().map(|agg| agg.requests).sum::<u64>()
+        };
+
+        // Алерт при приближении к квоте - один раз в день
+        if let Some(quota) = self.daily_quota {
+            if approaching_quota(total_requests, quota)
+                && !self.quota_warned.swap(true, Ordering::Relaxed)
+            {
+                tracing::warn!(
+                    "🚨 Использование TronGrid приближается к дневной квоте: {} из {} запросов",
+                    total_requests,
+                    quota
+                );
+            }
+        }
+    }
+
+    /// Срез текущего использования для debug endpoint'а
+    pub fn snapshot(&self) -> TronGridUsageSnapshot {
+        let day = self.day.lock().unwrap();
+
+        let mut per_op: Vec<OpUsage> = day
+            .per_op
+            .iter()
+            .map(|(op, agg)| OpUsage {
+                op,
+                requests: agg.requests,
+                errors: agg.errors,
+                avg_latency_ms: agg.total_latency_ms.checked_div(agg.requests).unwrap_or(0),
+            })
+            .collect();
+        per_op.sort_by_key(|op| std::cmp::Reverse(op.requests));
+
+        let total_requests: u64 = per_op.iter().map(|op| op.requests).sum();
+        let error_requests: u64 = per_op.iter().map(|op| op.errors).sum();
+
+        let recent: Vec<UsageSample> = {
+            let samples = self.samples.lock().unwrap();
+            samples
+                .iter()
+                .rev()
+                .take(USAGE_RECENT_LIMIT)
+                .rev()
+                .cloned()
+                .collect()
+        };
+
+        TronGridUsageSnapshot {
+            day: day.day,
+            total_requests,
+            error_requests,
+            daily_quota: self.daily_quota,
+            quota_used_percent: self.daily_quota.map(|quota| {
+                if quota == 0 {
+                    0.0
+                } else {
+                    total_requests as f64 / quota as f64 * 100.0
+                }
+            }),
+            per_op,
+            recent,
+        }
+    }
+}
+
+/// Сбрасывает агрегаты завершенного дня в trongrid_usage_daily.
+/// Ошибка записи не критична - история просто теряет день
+async fn persist_daily_aggregates(
+    db: DbPool,
+    day: NaiveDate,
+    per_op: HashMap<&'static str, OpAggregate>,
+) {
+    let rows: Vec<NewTronGridUsageDaily> = per_op
+        .into_iter()
+        .map(|(op, agg)| NewTronGridUsageDaily {
+            day,
+            op: op.to_string(),
+            requests: agg.requests as i64,
+            errors: agg.errors as i64,
+            total_latency_ms: agg.total_latency_ms as i64,
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return;
+    }
+
+    let result = async {
+        let mut conn = db.get().await?;
+        diesel::insert_into(schema::trongrid_usage_daily::table)
+            .values(&rows)
+            .on_conflict((
+                schema::trongrid_usage_daily::day,
+                schema::trongrid_usage_daily::op,
+            ))
+            .do_update()
+            .set((
+                schema::trongrid_usage_daily::requests.eq(diesel::upsert::excluded(
+                    schema::trongrid_usage_daily::requests,
+                )),
+                schema::trongrid_usage_daily::errors.eq(diesel::upsert::excluded(
+                    schema::trongrid_usage_daily::errors,
+                )),
+                schema::trongrid_usage_daily::total_latency_ms.eq(diesel::upsert::excluded(
+                    schema::trongrid_usage_daily::total_latency_ms,
+                )),
+            ))
+            .execute(&mut conn)
+            .await?;
+        anyhow::Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            "⚠️ Не удалось сохранить дневные агрегаты TronGrid за {}: {}",
+            day,
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_aggregates_per_op() {
+        let tracker = TronGridUsageTracker::new(None);
+        tracker.record("poll", 200, 100);
+        tracker.record("poll", 500, 300);
+        tracker.record("broadcast", 200, 50);
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.total_requests, 3);
+        assert_eq!(snapshot.error_requests, 1);
+        assert_eq!(snapshot.recent.len(), 3);
+
+        let poll = snapshot.per_op.iter().find(|op| op.op == "poll").unwrap();
+        assert_eq!(poll.requests, 2);
+        assert_eq!(poll.errors, 1);
+        assert_eq!(poll.avg_latency_ms, 200);
+    }
+
+    #[test]
+    fn test_ring_buffer_eviction() {
+        let tracker = TronGridUsageTracker::new(None);
+        for _ in 0..(USAGE_RING_CAPACITY + 10) {
+            tracker.record("poll", 200, 1);
+        }
+
+        let samples = tracker.samples.lock().unwrap();
+        assert_eq!(samples.len(), USAGE_RING_CAPACITY);
+    }
+
+    #[test]
+    fn test_approaching_quota() {
+        assert!(approaching_quota(80, 100));
+        assert!(approaching_quota(100, 100));
+        assert!(!approaching_quota(79, 100));
+        assert!(!approaching_quota(10, 0)); // Нулевая квота не алертит
+    }
+}
@@ -15,6 +15,10 @@ pub mod domain;
 pub mod infrastructure;
 pub mod utils;
 
+// Генераторы тестовых данных (unit тесты и фича `test-support`)
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+
 // Реэкспорт основных типов для удобства
 pub use application::state::AppState;
 pub use config::Settings;
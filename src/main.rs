@@ -1,14 +1,55 @@
 use actix_web::{middleware::Logger, web, App, HttpServer};
 use anyhow::Result;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
 use tron_gateway_rust::{
-    infrastructure::{grpc::GrpcServer, http::configure_routes},
+    infrastructure::{
+        grpc::GrpcServer, http::configure_routes, ApiKeyAuth, DegradationMarker,
+        ShutdownCoordinator,
+    },
     AppState, Settings, VERSION,
 };
 
+/// Сколько ждать окончания in-flight операций (подпись/broadcast
+/// трансферов, очередь webhook'ов) после сигнала завершения
+const SHUTDOWN_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+/// Ожидает SIGTERM (kubernetes/systemd) или SIGINT (Ctrl+C) и
+/// сигнализирует координатору о начале завершения
+async fn listen_for_shutdown_signals(shutdown: Arc<ShutdownCoordinator>) {
+    let ctrl_c = tokio::signal::ctrl_c();
+
+    #[cfg(unix)]
+    {
+        let mut sigterm =
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(sigterm) => sigterm,
+                Err(e) => {
+                    tracing::warn!("⚠️ Не удалось подписаться на SIGTERM: {}", e);
+                    let _ = ctrl_c.await;
+                    shutdown.begin_shutdown();
+                    return;
+                }
+            };
+
+        tokio::select! {
+            _ = ctrl_c => info!("🛑 Получен SIGINT"),
+            _ = sigterm.recv() => info!("🛑 Получен SIGTERM"),
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = ctrl_c.await;
+        info!("🛑 Получен сигнал завершения");
+    }
+
+    shutdown.begin_shutdown();
+}
+
 #[actix_web::main]
 async fn main() -> Result<()> {
     // Инициализация логирования
@@ -58,6 +99,12 @@ async fn main() -> Result<()> {
             App::new()
                 .app_data(web::Data::new((*app_state_http).clone()))
                 .wrap(Logger::default())
+                // Помечаем ответы заголовком X-Degraded-Mode при failover БД
+                .wrap(DegradationMarker::new(app_state_http.degradation.clone()))
+                // Аутентификация /api/* по ключу X-Api-Key (если включена)
+                .wrap(ApiKeyAuth::new(app_state_http.api_keys.clone()))
+                // Rate limiting по API ключу / IP (если включен)
+                .wrap(app_state_http.rate_limiter.clone())
                 .configure(configure_routes)
         })
         .bind(&http_bind)?
@@ -66,17 +113,34 @@ async fn main() -> Result<()> {
         .map_err(anyhow::Error::from)
     };
 
-    // Создаем gRPC сервер
+    // Создаем gRPC сервер: останавливается по сигналу завершения
+    // (tonic сам сигналы не слушает, в отличие от actix)
     let grpc_server = async {
         if settings.grpc.enabled {
             let grpc_server = GrpcServer::new(settings.grpc.clone(), app_state.clone());
-            grpc_server.serve().await?;
+            tokio::select! {
+                result = grpc_server.serve() => result?,
+                _ = app_state.shutdown.cancelled() => {
+                    info!("🛑 gRPC сервер остановлен по сигналу завершения");
+                }
+            }
         }
         Ok::<(), anyhow::Error>(())
     };
 
+    // Слушаем SIGTERM/SIGINT: actix останавливает HTTP сервер сам,
+    // координатор гасит gRPC, планировщик и фоновые циклы
+    tokio::spawn(listen_for_shutdown_signals(app_state.shutdown.clone()));
+
     // Запускаем оба сервера параллельно
     tokio::try_join!(http_server, grpc_server)?;
 
+    // Серверы остановлены - досчитываем in-flight трансферы, доставляем
+    // очередь webhook'ов и только потом отпускаем пул БД
+    app_state
+        .shutdown_gracefully(Duration::from_secs(SHUTDOWN_DRAIN_TIMEOUT_SECS))
+        .await;
+
+    info!("👋 TRON Gateway остановлен");
     Ok(())
 }
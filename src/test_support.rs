@@ -0,0 +1,212 @@
+//! # Генераторы тестовых данных
+//!
+//! Валидные base58 адреса, правдоподобные TRC-20 транзакции в формате TronGrid,
+//! скелеты подписанных транзакций и фабрики DB моделей - чтобы тесты
+//! не собирали хрупкие JSON блобы вручную.
+//!
+//! Модуль доступен в unit тестах и downstream крейтам через фичу `test-support`.
+
+use base58::ToBase58;
+use bigdecimal::BigDecimal;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::application::services::{FeePayer, TransferKind};
+use crate::domain::TransactionStatus;
+use crate::infrastructure::database::models::{
+    NewIncomingTransaction, NewOutgoingTransfer, NewWallet,
+};
+use crate::utils::conversions::decimal_to_bigdecimal;
+
+/// Детерминированный валидный TRON адрес (base58check, префикс 0x41).
+/// Один seed всегда дает один и тот же адрес
+pub fn test_address(seed: u8) -> String {
+    let mut payload = vec![0x41u8];
+    payload.extend_from_slice(&[seed; 20]);
+
+    let first_hash = Sha256::digest(&payload);
+    let second_hash = Sha256::digest(first_hash);
+    payload.extend_from_slice(&second_hash[0..4]);
+
+    payload.to_base58()
+}
+
+/// Детерминированный tx hash (64 hex символа)
+pub fn test_tx_hash(seed: u8) -> String {
+    hex::encode([seed; 32])
+}
+
+/// TRC-20 транзакция в формате элемента `data` из
+/// `/v1/accounts/{address}/transactions/trc20` TronGrid
+pub fn trc20_transfer_json(from: &str, to: &str, amount: Decimal, tx_hash: &str) -> Value {
+    // TronGrid отдает value в минимальных единицах (6 decimals для USDT)
+    let value_units = (amount * Decimal::new(1_000_000, 0)).trunc();
+
+    json!({
+        "transaction_id": tx_hash,
+        "token_info": {
+            "symbol": "USDT",
+            "address": "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t",
+            "decimals": 6,
+            "name": "Tether USD"
+        },
+        "block_timestamp": chrono::Utc::now().timestamp_millis(),
+        "from": from,
+        "to": to,
+        "type": "Transfer",
+        "value": value_units.to_string()
+    })
+}
+
+/// Страница ответа TronGrid со списком TRC-20 транзакций
+pub fn trc20_transfer_page(transactions: Vec<Value>) -> Value {
+    json!({
+        "data": transactions,
+        "success": true,
+        "meta": {
+            "at": chrono::Utc::now().timestamp_millis(),
+            "page_size": 50
+        }
+    })
+}
+
+/// Скелет неподписанной транзакции, как ее возвращает нода
+/// (`triggersmartcontract` / `createtransaction`).
+/// `expires_in_ms` задает `raw_data.expiration` относительно текущего времени
+pub fn unsigned_transaction_json(tx_hash: &str, expires_in_ms: i64) -> Value {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+
+    json!({
+        "txID": tx_hash,
+        "raw_data": {
+            "contract": [{
+                "parameter": {
+                    "value": {
+                        "owner_address": "4100000000000000000000000000000000000001",
+                        "contract_address": "41a614f803b6fd780986a42c78ec9c7f77e6ded13c"
+                    },
+                    "type_url": "type.googleapis.com/protocol.TriggerSmartContract"
+                },
+                "type": "TriggerSmartContract"
+            }],
+            "ref_block_bytes": "a1b2",
+            "ref_block_hash": "c3d4e5f6a7b8c9d0",
+            "expiration": now_ms + expires_in_ms,
+            "fee_limit": 100_000_000,
+            "timestamp": now_ms
+        },
+        "raw_data_hex": "0a02a1b2"
+    })
+}
+
+/// Скелет подписанной транзакции (неподписанная + массив `signature`)
+pub fn signed_transaction_json(tx_hash: &str) -> Value {
+    let mut transaction = unsigned_transaction_json(tx_hash, 60_000);
+    transaction["signature"] = json!([hex::encode([0u8; 64])]);
+    transaction
+}
+
+/// Фабрика модели нового кошелька
+pub fn new_wallet(seed: u8) -> NewWallet {
+    NewWallet {
+        address: test_address(seed),
+        hex_address: format!("41{}", hex::encode([seed; 20])),
+        private_key: hex::encode([seed; 32]),
+        owner_id: None,
+        watch_only: false,
+        private_key_enc: None,
+    }
+}
+
+/// Фабрика модели входящей транзакции со статусом PENDING
+pub fn new_incoming_transaction(wallet_id: i64, amount: Decimal) -> NewIncomingTransaction {
+    NewIncomingTransaction {
+        wallet_id,
+        tx_hash: test_tx_hash(wallet_id as u8),
+        block_number: Some(60_000_000),
+        from_address: test_address(0xA0),
+        to_address: test_address(0xA1),
+        amount: decimal_to_bigdecimal(amount),
+        status: TransactionStatus::Pending.as_db_str().to_string(),
+        error_message: None,
+        source_label: None,
+        token_symbol: "USDT".to_string(),
+        contract_address: None,
+    }
+}
+
+/// Фабрика модели исходящего трансфера со статусом PENDING
+pub fn new_outgoing_transfer(from_wallet_id: i64, amount: Decimal) -> NewOutgoingTransfer {
+    NewOutgoingTransfer {
+        from_wallet_id,
+        to_address: test_address(0xB0),
+        amount: decimal_to_bigdecimal(amount),
+        status: TransactionStatus::Pending.as_db_str().to_string(),
+        reference_id: None,
+        destination_tag: None,
+        risk_score: None,
+        risk_provider: None,
+        screened_at: None,
+        metadata: None,
+        fee_payer: FeePayer::default().as_db_str().to_string(),
+        fee_amount: None,
+        transfer_kind: TransferKind::default().as_db_str().to_string(),
+        token_symbol: "USDT".to_string(),
+        fee_trx_usdt_rate: None,
+        fee_rate_source: None,
+        fee_rate_captured_at: None,
+    }
+}
+
+/// Конвертация суммы в BigDecimal для прямых сравнений в тестах
+pub fn amount(value: i64, scale: u32) -> BigDecimal {
+    decimal_to_bigdecimal(Decimal::new(value, scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TronValidator;
+
+    #[test]
+    fn test_generated_address_is_valid() {
+        for seed in [0u8, 1, 42, 255] {
+            let address = test_address(seed);
+            assert!(
+                TronValidator::validate_address(&address).is_ok(),
+                "адрес с seed {} не прошел валидацию: {}",
+                seed,
+                address
+            );
+        }
+
+        // Детерминированность
+        assert_eq!(test_address(7), test_address(7));
+    }
+
+    #[test]
+    fn test_trc20_transfer_json_shape() {
+        let tx = trc20_transfer_json(
+            &test_address(1),
+            &test_address(2),
+            Decimal::new(12_500_000, 6), // 12.5 USDT
+            &test_tx_hash(3),
+        );
+
+        assert_eq!(tx["value"], "12500000");
+        assert_eq!(tx["type"], "Transfer");
+        assert_eq!(tx["transaction_id"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_unsigned_transaction_expiration() {
+        use crate::infrastructure::TronGridClient;
+
+        let fresh = unsigned_transaction_json(&test_tx_hash(1), 60_000);
+        assert!(!TronGridClient::transaction_expires_soon(&fresh));
+
+        let stale = unsigned_transaction_json(&test_tx_hash(2), 1_000);
+        assert!(TronGridClient::transaction_expires_soon(&stale));
+    }
+}
@@ -0,0 +1,81 @@
+//! # Часы сервисов
+//!
+//! Смешение `Utc::now()`, блокчейн-таймстемпов и `diesel::dsl::now`
+//! (часы Postgres) дает аномалии clock skew в отчетах. Сервисы получают
+//! время через трейт [`Clock`]: в проде это системные часы UTC, в тестах
+//! подменяются фиксированными для детерминированных проверок дедлайнов.
+//!
+//! Конвенция источников времени:
+//! - события цепочки (депозиты) - block_timestamp ноды, UTC;
+//! - события шлюза (создание, завершение, истечение) - [`Clock::now`];
+//! - `diesel::dsl::now` в новых местах не используется.
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+/// Источник текущего времени для сервисов
+pub trait Clock: Send + Sync {
+    /// Текущее время в UTC
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Системные часы (UTC) - используются в проде
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Фиксированные часы для тестов: время меняется только явными
+/// вызовами set/advance
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// Создает часы, остановленные на указанном моменте
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Переставляет часы на указанный момент
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Продвигает часы вперед на длительность
+    pub fn advance(&self, duration: chrono::Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += duration;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock_is_controllable() {
+        let start = Utc::now();
+        let clock = FixedClock::new(start);
+        assert_eq!(clock.now(), start);
+
+        clock.advance(chrono::Duration::minutes(30));
+        assert_eq!(clock.now(), start + chrono::Duration::minutes(30));
+
+        let moment = start - chrono::Duration::days(1);
+        clock.set(moment);
+        assert_eq!(clock.now(), moment);
+    }
+}
@@ -1,5 +1,5 @@
 //! # Конвертеры типов
-//! 
+//!
 //! Утилиты для конвертации между различными типами данных
 
 use bigdecimal::BigDecimal;
@@ -15,3 +15,10 @@ pub fn decimal_to_bigdecimal(decimal: Decimal) -> BigDecimal {
 pub fn bigdecimal_to_decimal(bigdecimal: BigDecimal) -> Decimal {
     Decimal::from_str(&bigdecimal.to_string()).unwrap_or_default()
 }
+
+/// Разбирает клиентские метаданные из TEXT колонки обратно в JSON.
+/// Невалидный JSON (вручную поправленная запись в БД) не роняет
+/// ответ - метаданные просто опускаются
+pub fn parse_stored_metadata(metadata: Option<String>) -> Option<serde_json::Value> {
+    metadata.and_then(|raw| serde_json::from_str(&raw).ok())
+}
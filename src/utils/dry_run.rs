@@ -0,0 +1,24 @@
+//! # Глобальный dry-run режим
+//!
+//! Репетиция на боевых данных без трат: при включенном флаге `dry_run`
+//! в конфиге все мутирующие сеть операции (broadcast транзакций, включая
+//! TRX переводы и активацию) пропускаются и заменяются симулированным
+//! успехом. Чтение цепочки работает как обычно, поэтому шлюз можно
+//! прогнать против mainnet в production-конфигурации. Симулированные
+//! результаты явно помечаются в строках БД (`metadata.simulated`)
+//! и в webhook payload'ах (поле `simulated`).
+
+use std::sync::OnceLock;
+
+static DRY_RUN: OnceLock<bool> = OnceLock::new();
+
+/// Устанавливает dry-run режим (вызывается при старте).
+/// Повторные вызовы игнорируются
+pub fn init_dry_run(enabled: bool) {
+    let _ = DRY_RUN.set(enabled);
+}
+
+/// true, если шлюз работает в dry-run режиме
+pub fn is_dry_run() -> bool {
+    DRY_RUN.get().copied().unwrap_or(false)
+}
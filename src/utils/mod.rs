@@ -1,7 +1,16 @@
 //! # Утилиты
-//! 
+//!
 //! Общие вспомогательные функции
 
+pub mod clock;
 pub mod conversions;
+pub mod dry_run;
+pub mod serialization;
 
+pub use clock::{Clock, FixedClock, SystemClock};
 pub use conversions::*;
+pub use dry_run::{init_dry_run, is_dry_run};
+pub use serialization::{
+    format_amount, init_amount_policy, serialize_amount, serialize_optional_amount,
+    AmountSerializationPolicy,
+};
@@ -0,0 +1,88 @@
+//! # Сериализация денежных полей
+//!
+//! Единая политика представления сумм в HTTP/gRPC ответах: либо сырой
+//! Decimal (поведение по умолчанию), либо строка с фиксированной точностью -
+//! клиенты со строгими парсерами избегают float-артефактов.
+
+use std::sync::OnceLock;
+
+use rust_decimal::Decimal;
+use serde::Serializer;
+
+/// Политика сериализации денежных полей
+#[derive(Debug, Clone, Copy)]
+pub struct AmountSerializationPolicy {
+    /// Сериализовать суммы строками вместо чисел
+    pub as_strings: bool,
+    /// Количество знаков после запятой в строковом представлении
+    pub precision: u32,
+}
+
+impl Default for AmountSerializationPolicy {
+    fn default() -> Self {
+        Self {
+            as_strings: false,
+            precision: 6, // USDT decimals
+        }
+    }
+}
+
+static AMOUNT_POLICY: OnceLock<AmountSerializationPolicy> = OnceLock::new();
+
+/// Устанавливает глобальную политику сериализации сумм (вызывается при старте).
+/// Повторные вызовы игнорируются
+pub fn init_amount_policy(policy: AmountSerializationPolicy) {
+    let _ = AMOUNT_POLICY.set(policy);
+}
+
+/// Текущая политика сериализации сумм
+pub fn amount_policy() -> AmountSerializationPolicy {
+    AMOUNT_POLICY.get().copied().unwrap_or_default()
+}
+
+/// Форматирует сумму строкой с фиксированной точностью из политики
+pub fn format_amount(value: Decimal) -> String {
+    let precision = amount_policy().precision;
+    format!("{:.*}", precision as usize, value.round_dp(precision))
+}
+
+/// `serialize_with` для полей `Decimal`
+pub fn serialize_amount<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if amount_policy().as_strings {
+        serializer.serialize_str(&format_amount(*value))
+    } else {
+        serde::Serialize::serialize(value, serializer)
+    }
+}
+
+/// `serialize_with` для полей `Option<Decimal>`
+pub fn serialize_optional_amount<S>(
+    value: &Option<Decimal>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(amount) if amount_policy().as_strings => {
+            serializer.serialize_some(&format_amount(*amount))
+        }
+        _ => serde::Serialize::serialize(value, serializer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount_fixed_precision() {
+        // Политика по умолчанию: 6 знаков
+        assert_eq!(format_amount(Decimal::new(125, 1)), "12.500000");
+        assert_eq!(format_amount(Decimal::new(1, 0)), "1.000000");
+        assert_eq!(format_amount(Decimal::new(1234567, 7)), "0.123457");
+    }
+}